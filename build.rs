@@ -0,0 +1,16 @@
+// Recompile when migrations change; sqlx::migrate! embeds them at build time
+fn main() {
+    println!("cargo:rerun-if-changed=migrations");
+
+    // gRPC stubs, generated with a vendored protoc so no system install is needed
+    #[cfg(feature = "grpc")]
+    {
+        println!("cargo:rerun-if-changed=proto");
+        let protoc = protoc_bin_vendored::protoc_bin_path().expect("vendored protoc");
+        std::env::set_var("PROTOC", protoc);
+        tonic_prost_build::configure()
+            .build_client(false)
+            .compile_protos(&["proto/polymarket_bot.proto"], &["proto"])
+            .expect("compile proto/polymarket_bot.proto");
+    }
+}
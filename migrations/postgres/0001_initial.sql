@@ -0,0 +1,67 @@
+-- Baseline schema, matching migrations/sqlite/0001_initial.sql
+
+CREATE TABLE IF NOT EXISTS trades (
+    id TEXT PRIMARY KEY,
+    order_id TEXT NOT NULL,
+    market_id TEXT NOT NULL,
+    side TEXT NOT NULL,
+    price DOUBLE PRECISION NOT NULL,
+    size DOUBLE PRECISION NOT NULL,
+    fee DOUBLE PRECISION NOT NULL DEFAULT 0.0,
+    timestamp TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS positions (
+    market_id TEXT NOT NULL,
+    token_id TEXT NOT NULL,
+    side TEXT NOT NULL,
+    size DOUBLE PRECISION NOT NULL,
+    avg_price DOUBLE PRECISION NOT NULL,
+    current_price DOUBLE PRECISION NOT NULL DEFAULT 0.0,
+    pnl DOUBLE PRECISION NOT NULL DEFAULT 0.0,
+    strategy TEXT NOT NULL DEFAULT '',
+    PRIMARY KEY (market_id, token_id)
+);
+
+CREATE TABLE IF NOT EXISTS orders (
+    id TEXT PRIMARY KEY,
+    market_id TEXT NOT NULL,
+    side TEXT NOT NULL,
+    token_id TEXT NOT NULL,
+    price DOUBLE PRECISION NOT NULL,
+    size DOUBLE PRECISION NOT NULL,
+    order_type TEXT NOT NULL,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS pnl_snapshots (
+    id BIGSERIAL PRIMARY KEY,
+    timestamp TEXT NOT NULL,
+    bankroll DOUBLE PRECISION NOT NULL,
+    pnl_total DOUBLE PRECISION NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS market_data (
+    token_id TEXT NOT NULL,
+    timestamp BIGINT NOT NULL,
+    price DOUBLE PRECISION NOT NULL,
+    PRIMARY KEY (token_id, timestamp)
+);
+
+CREATE TABLE IF NOT EXISTS probable_fills (
+    order_id TEXT NOT NULL,
+    trade_key TEXT NOT NULL,
+    token_id TEXT NOT NULL,
+    price DOUBLE PRECISION NOT NULL,
+    size DOUBLE PRECISION NOT NULL,
+    trade_timestamp TEXT NOT NULL,
+    detected_at TEXT NOT NULL,
+    confirmed BIGINT,
+    PRIMARY KEY (order_id, trade_key)
+);
+
+CREATE TABLE IF NOT EXISTS config (
+    key TEXT PRIMARY KEY,
+    value TEXT NOT NULL
+);
@@ -0,0 +1,6 @@
+-- Exchange-assigned order ID, so orders can be looked up and cancelled by it
+
+ALTER TABLE orders ADD COLUMN remote_id TEXT;
+ALTER TABLE orders_archive ADD COLUMN remote_id TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_orders_remote_id ON orders (remote_id);
@@ -0,0 +1,13 @@
+-- OHLC candles built from live ticks; open_time is the bucket start in unix seconds
+
+CREATE TABLE IF NOT EXISTS candles (
+    instrument TEXT NOT NULL,
+    interval_secs BIGINT NOT NULL,
+    open_time BIGINT NOT NULL,
+    open DOUBLE PRECISION NOT NULL,
+    high DOUBLE PRECISION NOT NULL,
+    low DOUBLE PRECISION NOT NULL,
+    close DOUBLE PRECISION NOT NULL,
+    ticks BIGINT NOT NULL,
+    PRIMARY KEY (instrument, interval_secs, open_time)
+);
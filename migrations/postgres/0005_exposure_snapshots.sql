@@ -0,0 +1,13 @@
+-- Periodic exposure readings for utilization-over-time charts.
+-- by_underlying is a JSON object of underlying (spot symbol or market ID) -> net exposure
+
+CREATE TABLE IF NOT EXISTS exposure_snapshots (
+    timestamp TEXT NOT NULL,
+    gross_exposure DOUBLE PRECISION NOT NULL,
+    net_exposure DOUBLE PRECISION NOT NULL,
+    open_order_notional DOUBLE PRECISION NOT NULL,
+    free_collateral DOUBLE PRECISION NOT NULL,
+    by_underlying TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_exposure_snapshots_timestamp ON exposure_snapshots (timestamp);
@@ -0,0 +1,9 @@
+-- Markets blocked from new entries after a realized loss, so the block survives restarts.
+-- One row per market; a later loss extends the existing block.
+
+CREATE TABLE IF NOT EXISTS market_cooldowns (
+    market_id TEXT PRIMARY KEY,
+    blocked_until TEXT NOT NULL,
+    realized_loss DOUBLE PRECISION NOT NULL,
+    started_at TEXT NOT NULL
+);
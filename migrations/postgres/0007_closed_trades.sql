@@ -0,0 +1,17 @@
+-- One row per position close (or partial close), with the strategy that opened it.
+-- pnl is realized PnL net of the closing fill's fee; analytics are computed from these.
+
+CREATE TABLE IF NOT EXISTS closed_trades (
+    id TEXT PRIMARY KEY,
+    strategy TEXT NOT NULL,
+    market_id TEXT NOT NULL,
+    token_id TEXT NOT NULL,
+    side TEXT NOT NULL,
+    size DOUBLE PRECISION NOT NULL,
+    entry_price DOUBLE PRECISION NOT NULL,
+    exit_price DOUBLE PRECISION NOT NULL,
+    pnl DOUBLE PRECISION NOT NULL,
+    closed_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_closed_trades_closed_at ON closed_trades (closed_at);
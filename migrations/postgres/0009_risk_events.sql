@@ -0,0 +1,12 @@
+-- Audit log of risk decisions: rejected signals, halts, kill/resume and arm/disarm
+
+CREATE TABLE IF NOT EXISTS risk_events (
+    id BIGSERIAL PRIMARY KEY,
+    timestamp TEXT NOT NULL,
+    kind TEXT NOT NULL,
+    strategy TEXT,
+    market_id TEXT,
+    detail TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_risk_events_timestamp ON risk_events (timestamp);
@@ -0,0 +1,5 @@
+-- Traded volume per bar (shares, from the public trades stream) and its VWAP;
+-- bars built from quotes alone keep zero volume and no VWAP
+
+ALTER TABLE candles ADD COLUMN volume DOUBLE PRECISION NOT NULL DEFAULT 0;
+ALTER TABLE candles ADD COLUMN vwap DOUBLE PRECISION;
@@ -0,0 +1,16 @@
+-- Outsized prints and book sweeps flagged by the flow detector
+
+CREATE TABLE IF NOT EXISTS flow_alerts (
+    id BIGSERIAL PRIMARY KEY,
+    timestamp TEXT NOT NULL,
+    market_id TEXT NOT NULL,
+    token_id TEXT NOT NULL,
+    kind TEXT NOT NULL,
+    side TEXT NOT NULL,
+    size DOUBLE PRECISION NOT NULL,
+    notional DOUBLE PRECISION NOT NULL,
+    price DOUBLE PRECISION NOT NULL,
+    levels BIGINT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_flow_alerts_timestamp ON flow_alerts (timestamp);
@@ -0,0 +1,15 @@
+-- Positions settled at market resolution, and how they were paid out
+
+CREATE TABLE IF NOT EXISTS redemptions (
+    id BIGSERIAL PRIMARY KEY,
+    settled_at TEXT NOT NULL,
+    market_id TEXT NOT NULL,
+    token_id TEXT NOT NULL,
+    size DOUBLE PRECISION NOT NULL,
+    payout DOUBLE PRECISION NOT NULL,
+    pnl DOUBLE PRECISION NOT NULL,
+    status TEXT NOT NULL,
+    tx_hash TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_redemptions_settled_at ON redemptions (settled_at);
@@ -0,0 +1,4 @@
+-- Order a cancel/replace amended, linking each replacement to the order it took over from
+
+ALTER TABLE orders ADD COLUMN replaces TEXT;
+ALTER TABLE orders_archive ADD COLUMN replaces TEXT;
@@ -0,0 +1,5 @@
+-- When a resting order stops being good: GTD orders expire on the exchange,
+-- anything else past it is cancelled by order sync
+
+ALTER TABLE orders ADD COLUMN expires_at TEXT;
+ALTER TABLE orders_archive ADD COLUMN expires_at TEXT;
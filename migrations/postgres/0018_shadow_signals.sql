@@ -0,0 +1,5 @@
+-- Shadow strategies record their signals with a simulated fill instead of trading;
+-- size is the shares ordered (or simulated), for hypothetical PnL at resolution
+
+ALTER TABLE signal_records ADD COLUMN size DOUBLE PRECISION;
+ALTER TABLE signal_records ADD COLUMN shadow BIGINT NOT NULL DEFAULT 0;
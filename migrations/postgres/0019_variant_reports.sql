@@ -0,0 +1,16 @@
+-- Periodic comparisons of strategy A/B test arms. experiment is the base strategy,
+-- live whether the arm traded (1) or ran in shadow mode (0)
+
+CREATE TABLE IF NOT EXISTS variant_reports (
+    timestamp TEXT NOT NULL,
+    experiment TEXT NOT NULL,
+    strategy TEXT NOT NULL,
+    live BIGINT NOT NULL,
+    signals BIGINT NOT NULL,
+    resolved BIGINT NOT NULL,
+    wins BIGINT NOT NULL,
+    pnl DOUBLE PRECISION NOT NULL,
+    open_notional DOUBLE PRECISION NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_variant_reports_timestamp ON variant_reports (timestamp);
@@ -0,0 +1,3 @@
+-- Event shared by the markets of a neg-risk event (Gamma's negRiskMarketID)
+
+ALTER TABLE markets ADD COLUMN neg_risk_event TEXT;
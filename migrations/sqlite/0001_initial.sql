@@ -0,0 +1,68 @@
+-- Baseline schema. IF NOT EXISTS so bot.db files created before versioned
+-- migrations adopt it in place; see SqliteStorage::adopt_unversioned.
+
+CREATE TABLE IF NOT EXISTS trades (
+    id TEXT PRIMARY KEY,
+    order_id TEXT NOT NULL,
+    market_id TEXT NOT NULL,
+    side TEXT NOT NULL,
+    price REAL NOT NULL,
+    size REAL NOT NULL,
+    fee REAL NOT NULL DEFAULT 0.0,
+    timestamp TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS positions (
+    market_id TEXT NOT NULL,
+    token_id TEXT NOT NULL,
+    side TEXT NOT NULL,
+    size REAL NOT NULL,
+    avg_price REAL NOT NULL,
+    current_price REAL NOT NULL DEFAULT 0.0,
+    pnl REAL NOT NULL DEFAULT 0.0,
+    strategy TEXT NOT NULL DEFAULT '',
+    PRIMARY KEY (market_id, token_id)
+);
+
+CREATE TABLE IF NOT EXISTS orders (
+    id TEXT PRIMARY KEY,
+    market_id TEXT NOT NULL,
+    side TEXT NOT NULL,
+    token_id TEXT NOT NULL,
+    price REAL NOT NULL,
+    size REAL NOT NULL,
+    order_type TEXT NOT NULL,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS pnl_snapshots (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    timestamp TEXT NOT NULL,
+    bankroll REAL NOT NULL,
+    pnl_total REAL NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS market_data (
+    token_id TEXT NOT NULL,
+    timestamp INTEGER NOT NULL,
+    price REAL NOT NULL,
+    PRIMARY KEY (token_id, timestamp)
+);
+
+CREATE TABLE IF NOT EXISTS probable_fills (
+    order_id TEXT NOT NULL,
+    trade_key TEXT NOT NULL,
+    token_id TEXT NOT NULL,
+    price REAL NOT NULL,
+    size REAL NOT NULL,
+    trade_timestamp TEXT NOT NULL,
+    detected_at TEXT NOT NULL,
+    confirmed INTEGER,
+    PRIMARY KEY (order_id, trade_key)
+);
+
+CREATE TABLE IF NOT EXISTS config (
+    key TEXT PRIMARY KEY,
+    value TEXT NOT NULL
+);
@@ -0,0 +1,40 @@
+-- Settled markets' rows move here so the hot tables only hold live markets
+
+CREATE TABLE IF NOT EXISTS trades_archive (
+    id TEXT NOT NULL,
+    order_id TEXT NOT NULL,
+    market_id TEXT NOT NULL,
+    side TEXT NOT NULL,
+    price REAL NOT NULL,
+    size REAL NOT NULL,
+    fee REAL NOT NULL,
+    timestamp TEXT NOT NULL,
+    archived_at TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS orders_archive (
+    id TEXT NOT NULL,
+    market_id TEXT NOT NULL,
+    side TEXT NOT NULL,
+    token_id TEXT NOT NULL,
+    price REAL NOT NULL,
+    size REAL NOT NULL,
+    order_type TEXT NOT NULL,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    archived_at TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS positions_archive (
+    market_id TEXT NOT NULL,
+    token_id TEXT NOT NULL,
+    side TEXT NOT NULL,
+    size REAL NOT NULL,
+    avg_price REAL NOT NULL,
+    current_price REAL NOT NULL,
+    pnl REAL NOT NULL,
+    strategy TEXT NOT NULL,
+    archived_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_trades_archive_market ON trades_archive (market_id);
@@ -0,0 +1,13 @@
+-- OHLC candles built from live ticks; open_time is the bucket start in unix seconds
+
+CREATE TABLE IF NOT EXISTS candles (
+    instrument TEXT NOT NULL,
+    interval_secs INTEGER NOT NULL,
+    open_time INTEGER NOT NULL,
+    open REAL NOT NULL,
+    high REAL NOT NULL,
+    low REAL NOT NULL,
+    close REAL NOT NULL,
+    ticks INTEGER NOT NULL,
+    PRIMARY KEY (instrument, interval_secs, open_time)
+);
@@ -0,0 +1,13 @@
+-- Periodic exposure readings for utilization-over-time charts.
+-- by_underlying is a JSON object of underlying (spot symbol or market ID) -> net exposure
+
+CREATE TABLE IF NOT EXISTS exposure_snapshots (
+    timestamp TEXT NOT NULL,
+    gross_exposure REAL NOT NULL,
+    net_exposure REAL NOT NULL,
+    open_order_notional REAL NOT NULL,
+    free_collateral REAL NOT NULL,
+    by_underlying TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_exposure_snapshots_timestamp ON exposure_snapshots (timestamp);
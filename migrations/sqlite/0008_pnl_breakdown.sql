@@ -0,0 +1,5 @@
+-- Split each PnL point into realized (closed trades, net of fees) and unrealized
+-- (open positions marked to market); pnl_total is their sum
+
+ALTER TABLE pnl_snapshots ADD COLUMN realized_pnl REAL NOT NULL DEFAULT 0;
+ALTER TABLE pnl_snapshots ADD COLUMN unrealized_pnl REAL NOT NULL DEFAULT 0;
@@ -0,0 +1,18 @@
+-- Submitted signals with their reported confidence; outcome (1 won, 0 lost) is
+-- filled in when the market resolves, for per-strategy calibration curves
+
+CREATE TABLE IF NOT EXISTS signal_records (
+    id TEXT PRIMARY KEY,
+    strategy TEXT NOT NULL,
+    market_id TEXT NOT NULL,
+    token_id TEXT NOT NULL,
+    side TEXT NOT NULL,
+    confidence REAL NOT NULL,
+    price REAL NOT NULL,
+    created_at TEXT NOT NULL,
+    outcome REAL,
+    resolved_at TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_signal_records_unresolved ON signal_records (resolved_at, market_id);
+CREATE INDEX IF NOT EXISTS idx_signal_records_created_at ON signal_records (created_at);
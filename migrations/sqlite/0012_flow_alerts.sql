@@ -0,0 +1,16 @@
+-- Outsized prints and book sweeps flagged by the flow detector
+
+CREATE TABLE IF NOT EXISTS flow_alerts (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    timestamp TEXT NOT NULL,
+    market_id TEXT NOT NULL,
+    token_id TEXT NOT NULL,
+    kind TEXT NOT NULL,
+    side TEXT NOT NULL,
+    size REAL NOT NULL,
+    notional REAL NOT NULL,
+    price REAL NOT NULL,
+    levels INTEGER NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_flow_alerts_timestamp ON flow_alerts (timestamp);
@@ -0,0 +1,15 @@
+-- Positions settled at market resolution, and how they were paid out
+
+CREATE TABLE IF NOT EXISTS redemptions (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    settled_at TEXT NOT NULL,
+    market_id TEXT NOT NULL,
+    token_id TEXT NOT NULL,
+    size REAL NOT NULL,
+    payout REAL NOT NULL,
+    pnl REAL NOT NULL,
+    status TEXT NOT NULL,
+    tx_hash TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_redemptions_settled_at ON redemptions (settled_at);
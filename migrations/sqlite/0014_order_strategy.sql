@@ -0,0 +1,6 @@
+-- Strategy that originated each order, so its resting orders can be cancelled together
+
+ALTER TABLE orders ADD COLUMN strategy TEXT NOT NULL DEFAULT '';
+ALTER TABLE orders_archive ADD COLUMN strategy TEXT NOT NULL DEFAULT '';
+
+CREATE INDEX IF NOT EXISTS idx_orders_strategy ON orders (strategy);
@@ -0,0 +1,23 @@
+-- Market metadata from Gamma and the CLOB, so token IDs resolve without live lookups
+
+CREATE TABLE IF NOT EXISTS markets (
+    id TEXT PRIMARY KEY,
+    question TEXT NOT NULL,
+    end_date TEXT,
+    active INTEGER NOT NULL,
+    closed INTEGER NOT NULL,
+    winner TEXT,
+    neg_risk INTEGER NOT NULL,
+    tick_size REAL,
+    min_order_size REAL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS market_tokens (
+    token_id TEXT PRIMARY KEY,
+    market_id TEXT NOT NULL,
+    outcome TEXT NOT NULL,
+    position INTEGER NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_market_tokens_market_id ON market_tokens (market_id);
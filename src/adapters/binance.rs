@@ -1,13 +1,17 @@
 use chrono::Utc;
-use eyre::Result;
 use futures_util::{SinkExt, StreamExt};
 use reqwest::Client;
 use serde::Deserialize;
-use tokio::sync::broadcast;
 use tokio_tungstenite::{connect_async, tungstenite::Message};
 use tracing::{error, info, warn};
 
-use crate::domain::MarketData;
+use crate::bus::EventBus;
+use crate::config::BinanceStream;
+use crate::domain::{BookLevel, FeedStatus, MarketData, OrderBook};
+use crate::error::FeedError;
+use crate::watchdog::Heartbeat;
+
+type Result<T, E = FeedError> = std::result::Result<T, E>;
 
 #[derive(Debug, Deserialize)]
 struct BinanceTicker {
@@ -17,9 +21,46 @@ struct BinanceTicker {
     last_price: String,
 }
 
+#[derive(Debug, Deserialize)]
+struct BinanceAggTrade {
+    #[serde(rename = "s")]
+    symbol: String,
+    #[serde(rename = "p")]
+    price: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct BinanceBookTicker {
+    #[serde(rename = "s")]
+    symbol: String,
+    #[serde(rename = "b")]
+    bid: String,
+    #[serde(rename = "a")]
+    ask: String,
+}
+
+/// Partial book depth snapshot; price and quantity pairs as strings
+#[derive(Debug, Deserialize)]
+struct BinanceDepth {
+    bids: Vec<[String; 2]>,
+    asks: Vec<[String; 2]>,
+}
+
+/// Feed name in errors and logs
+const FEED: &str = "binance";
+
+/// Level counts Binance offers on the partial depth streams
+const DEPTH_LEVELS: &[usize] = &[5, 10, 20];
+
+#[derive(Clone)]
 pub struct BinanceWsFeed {
-    tx: broadcast::Sender<MarketData>,
+    tx: EventBus,
     symbols: Vec<String>,
+    /// Which stream the spot price comes from
+    stream: BinanceStream,
+    /// Levels per side on the depth streams; 0 subscribes to prices only
+    depth_levels: usize,
+    heartbeat: Heartbeat,
 }
 
 /// Binance endpoint rotation: try .us first (US-friendly), then .com
@@ -34,8 +75,36 @@ const REST_ENDPOINTS: &[&str] = &[
 ];
 
 impl BinanceWsFeed {
-    pub fn new(tx: broadcast::Sender<MarketData>, symbols: Vec<String>) -> Self {
-        Self { tx, symbols }
+    pub fn new(tx: EventBus, symbols: Vec<String>) -> Self {
+        Self {
+            tx,
+            symbols,
+            stream: BinanceStream::Ticker,
+            depth_levels: 0,
+            heartbeat: Heartbeat::default(),
+        }
+    }
+
+    /// Beat on every message or poll, so the watchdog can tell a dead connection
+    /// from a quiet one
+    pub fn with_heartbeat(mut self, heartbeat: Heartbeat) -> Self {
+        self.heartbeat = heartbeat;
+        self
+    }
+
+    pub fn with_stream(mut self, stream: BinanceStream) -> Self {
+        self.stream = stream;
+        self
+    }
+
+    /// Also stream the top of each symbol's book, rounded up to a level count
+    /// Binance offers (5, 10 or 20)
+    pub fn with_depth(mut self, levels: usize) -> Self {
+        self.depth_levels = match levels {
+            0 => 0,
+            n => DEPTH_LEVELS.iter().copied().find(|l| *l >= n).unwrap_or(20),
+        };
+        self
     }
 
     pub async fn run(self) -> Result<()> {
@@ -45,10 +114,12 @@ impl BinanceWsFeed {
             // Try WebSocket first, fall back to REST polling
             match self.try_websocket().await {
                 Ok(()) => {
+                    self.tx.publish(FeedStatus::disconnected(FEED, "closed"));
                     backoff_ms = 1000;
                 }
                 Err(e) => {
                     warn!("All WS endpoints failed: {:?}. Falling back to REST polling.", e);
+                    self.tx.publish(FeedStatus::disconnected(FEED, e.to_string()));
                     match self.rest_poll_loop().await {
                         Ok(()) => { backoff_ms = 1000; }
                         Err(e2) => { error!("REST polling failed: {:?}", e2); }
@@ -63,11 +134,18 @@ impl BinanceWsFeed {
     }
 
     async fn try_websocket(&self) -> Result<()> {
-        let streams: Vec<String> = self
+        let mut streams: Vec<String> = self
             .symbols
             .iter()
-            .map(|s| format!("{}@ticker", s.to_lowercase()))
+            .map(|s| format!("{}@{}", s.to_lowercase(), self.stream.stream_name()))
             .collect();
+        if self.depth_levels > 0 {
+            streams.extend(
+                self.symbols
+                    .iter()
+                    .map(|s| format!("{}@depth{}@100ms", s.to_lowercase(), self.depth_levels)),
+            );
+        }
         let stream_path = streams.join("/");
 
         for endpoint in WS_ENDPOINTS {
@@ -77,9 +155,11 @@ impl BinanceWsFeed {
             match connect_async(&url).await {
                 Ok((ws_stream, _)) => {
                     info!("Connected to price WS for {:?}", self.symbols);
+                    self.tx.publish(FeedStatus::connected(FEED));
                     let (mut write, mut read) = ws_stream.split();
 
                     while let Some(msg) = read.next().await {
+                        self.heartbeat.beat();
                         match msg {
                             Ok(Message::Text(text)) => {
                                 self.handle_message(&text);
@@ -107,7 +187,7 @@ impl BinanceWsFeed {
             }
         }
 
-        Err(eyre::eyre!("All WebSocket endpoints unreachable"))
+        Err(FeedError::unavailable(FEED, "all WebSocket endpoints unreachable"))
     }
 
     /// Fallback: poll REST API every 2 seconds
@@ -120,6 +200,7 @@ impl BinanceWsFeed {
 
         loop {
             interval.tick().await;
+            self.heartbeat.beat();
 
             let mut got_price = false;
             for endpoint in REST_ENDPOINTS {
@@ -143,50 +224,126 @@ impl BinanceWsFeed {
             } else {
                 failures += 1;
                 if failures > 30 {
-                    return Err(eyre::eyre!("REST polling failed 30 consecutive times"));
+                    return Err(FeedError::unavailable(FEED, "REST polling failed 30 consecutive times"));
                 }
             }
         }
     }
 
     fn handle_rest_price(&self, text: &str) {
-        #[derive(Deserialize)]
-        struct PriceTicker {
-            symbol: String,
-            price: String,
+        if let Some(event) = parse_rest_price(text) {
+            self.tx.publish(event);
         }
+    }
 
-        if let Ok(t) = serde_json::from_str::<PriceTicker>(text) {
-            if let Ok(price) = t.price.parse::<f64>() {
-                let _ = self.tx.send(MarketData::BinanceTicker {
-                    symbol: t.symbol,
-                    price,
-                    timestamp: Utc::now(),
-                });
-            }
-        }
+    /// One-shot REST price for every symbol, used to seed state before the WS connects
+    pub async fn rest_snapshot(&self) -> Vec<MarketData> {
+        fetch_spot(&self.symbols).await
     }
 
     fn handle_message(&self, text: &str) {
         #[derive(Deserialize)]
         struct Combined {
-            data: BinanceTicker,
+            stream: String,
+            data: serde_json::Value,
         }
 
-        let ticker = if let Ok(combined) = serde_json::from_str::<Combined>(text) {
-            combined.data
-        } else if let Ok(t) = serde_json::from_str::<BinanceTicker>(text) {
-            t
-        } else {
+        let Ok(combined) = serde_json::from_str::<Combined>(text) else {
+            if let Some(event) = serde_json::from_str::<BinanceTicker>(text).ok().and_then(ticker_price) {
+                self.tx.publish(event);
+            }
             return;
         };
 
-        if let Ok(price) = ticker.last_price.parse::<f64>() {
-            let _ = self.tx.send(MarketData::BinanceTicker {
-                symbol: ticker.symbol,
-                price,
-                timestamp: Utc::now(),
-            });
+        let Some((symbol, kind)) = combined.stream.split_once('@') else {
+            return;
+        };
+        let event = match kind {
+            // Depth payloads don't name their symbol; the stream does
+            kind if kind.starts_with("depth") => parse_depth(symbol, combined.data),
+            "aggTrade" => serde_json::from_value::<BinanceAggTrade>(combined.data)
+                .ok()
+                .and_then(|t| spot_price(t.symbol, t.price.parse().ok()?)),
+            "bookTicker" => serde_json::from_value::<BinanceBookTicker>(combined.data).ok().and_then(|q| {
+                let (bid, ask): (f64, f64) = (q.bid.parse().ok()?, q.ask.parse().ok()?);
+                spot_price(q.symbol, (bid + ask) / 2.0)
+            }),
+            _ => serde_json::from_value::<BinanceTicker>(combined.data).ok().and_then(ticker_price),
+        };
+        if let Some(event) = event {
+            self.tx.publish(event);
         }
     }
 }
+
+/// One REST ticker per symbol, trying each endpoint in turn; symbols no endpoint
+/// answers for are left out
+pub async fn fetch_spot(symbols: &[String]) -> Vec<MarketData> {
+    let client = Client::new();
+    let mut events = Vec::new();
+
+    for symbol in symbols {
+        for endpoint in REST_ENDPOINTS {
+            let url = format!("{}?symbol={}", endpoint, symbol.to_uppercase());
+            let resp = client.get(&url).timeout(std::time::Duration::from_secs(5)).send().await;
+            if let Ok(resp) = resp {
+                if let Some(event) = resp.text().await.ok().as_deref().and_then(parse_rest_price) {
+                    events.push(event);
+                    break;
+                }
+            }
+        }
+    }
+
+    events
+}
+
+fn ticker_price(ticker: BinanceTicker) -> Option<MarketData> {
+    spot_price(ticker.symbol, ticker.last_price.parse().ok()?)
+}
+
+fn spot_price(symbol: String, price: f64) -> Option<MarketData> {
+    Some(MarketData::BinanceTicker {
+        symbol,
+        price,
+        timestamp: Utc::now(),
+    })
+}
+
+/// Book event from a partial depth payload; levels that don't parse are dropped
+fn parse_depth(symbol: &str, data: serde_json::Value) -> Option<MarketData> {
+    let depth = serde_json::from_value::<BinanceDepth>(data).ok()?;
+    let levels = |raw: Vec<[String; 2]>| -> Vec<BookLevel> {
+        raw.into_iter()
+            .filter_map(|[price, size]| {
+                Some(BookLevel {
+                    price: price.parse().ok()?,
+                    size: size.parse().ok()?,
+                })
+            })
+            .collect()
+    };
+    Some(MarketData::BinanceOrderBook {
+        symbol: symbol.to_uppercase(),
+        book: OrderBook {
+            bids: levels(depth.bids),
+            asks: levels(depth.asks),
+            timestamp: Utc::now(),
+        },
+    })
+}
+
+fn parse_rest_price(text: &str) -> Option<MarketData> {
+    #[derive(Deserialize)]
+    struct PriceTicker {
+        symbol: String,
+        price: String,
+    }
+
+    let t = serde_json::from_str::<PriceTicker>(text).ok()?;
+    Some(MarketData::BinanceTicker {
+        symbol: t.symbol,
+        price: t.price.parse().ok()?,
+        timestamp: Utc::now(),
+    })
+}
@@ -0,0 +1,182 @@
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use reqwest::{Client, Method};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use sha2::Sha256;
+use std::time::Duration;
+
+use crate::config::HedgeConfig;
+use crate::domain::Side;
+use crate::error::ClobError;
+
+type Result<T, E = ClobError> = std::result::Result<T, E>;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Milliseconds a signed request stays valid for
+const RECV_WINDOW_MS: u64 = 5000;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PremiumIndex {
+    mark_price: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PositionRisk {
+    position_amt: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ExchangeInfo {
+    symbols: Vec<SymbolInfo>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SymbolInfo {
+    symbol: String,
+    filters: Vec<serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct OrderResponse {
+    order_id: u64,
+}
+
+/// Quantity increments a symbol's market orders must respect
+#[derive(Debug, Clone, Copy)]
+pub struct LotSize {
+    pub step_size: f64,
+    pub min_qty: f64,
+}
+
+impl LotSize {
+    /// `quantity` rounded down to the step, or None below the minimum
+    pub fn round(&self, quantity: f64) -> Option<f64> {
+        let rounded = if self.step_size > 0.0 {
+            (quantity / self.step_size + 1e-9).floor() * self.step_size
+        } else {
+            quantity
+        };
+        (rounded >= self.min_qty && rounded > 0.0).then_some(rounded)
+    }
+
+    /// Decimal places the step allows, for formatting order quantities
+    fn decimals(&self) -> usize {
+        if self.step_size <= 0.0 || self.step_size >= 1.0 {
+            return 0;
+        }
+        (-self.step_size.log10()).round() as usize
+    }
+}
+
+/// Signed client for the Binance USDⓈ-M futures REST API, for the hedger's reads
+/// and market orders
+#[derive(Clone)]
+pub struct BinanceFuturesClient {
+    client: Client,
+    base_url: String,
+    api_key: String,
+    api_secret: String,
+}
+
+impl BinanceFuturesClient {
+    pub fn new(config: &HedgeConfig) -> Result<Self> {
+        let (Some(api_key), Some(api_secret)) = (&config.api_key, &config.api_secret) else {
+            return Err(ClobError::auth(
+                "Binance futures",
+                "BINANCE_FUTURES_API_KEY and BINANCE_FUTURES_SECRET must be set to hedge",
+            ));
+        };
+        Ok(Self {
+            client: Client::builder()
+                .timeout(Duration::from_secs(10))
+                .build()
+                .map_err(|e| ClobError::request("build HTTP client", e))?,
+            base_url: config.base_url.trim_end_matches('/').to_string(),
+            api_key: api_key.clone(),
+            api_secret: api_secret.clone(),
+        })
+    }
+
+    /// Current mark price of `symbol`
+    pub async fn mark_price(&self, symbol: &str) -> Result<f64> {
+        let index: PremiumIndex = self
+            .request(Method::GET, "/fapi/v1/premiumIndex", &[("symbol", symbol.to_string())], false)
+            .await?;
+        index
+            .mark_price
+            .parse()
+            .map_err(|e| ClobError::decode("Binance futures mark price", e))
+    }
+
+    /// Signed position in `symbol`, in contracts (positive long)
+    pub async fn position(&self, symbol: &str) -> Result<f64> {
+        let risks: Vec<PositionRisk> = self
+            .request(Method::GET, "/fapi/v2/positionRisk", &[("symbol", symbol.to_string())], true)
+            .await?;
+        Ok(risks.iter().filter_map(|r| r.position_amt.parse::<f64>().ok()).sum())
+    }
+
+    /// Step and minimum for market orders in `symbol`
+    pub async fn lot_size(&self, symbol: &str) -> Result<LotSize> {
+        let info: ExchangeInfo = self.request(Method::GET, "/fapi/v1/exchangeInfo", &[], false).await?;
+        let symbol_info = info
+            .symbols
+            .into_iter()
+            .find(|s| s.symbol == symbol)
+            .ok_or_else(|| ClobError::Invalid(format!("{} is not listed on Binance futures", symbol)))?;
+        let filter = |kind: &str| symbol_info.filters.iter().find(|f| f["filterType"] == kind);
+        let lot = filter("MARKET_LOT_SIZE")
+            .or_else(|| filter("LOT_SIZE"))
+            .ok_or_else(|| ClobError::decode("Binance futures exchange info", format!("{} has no lot size filter", symbol)))?;
+        let field = |name: &str| lot[name].as_str().and_then(|v| v.parse::<f64>().ok()).unwrap_or(0.0);
+        Ok(LotSize {
+            step_size: field("stepSize"),
+            min_qty: field("minQty"),
+        })
+    }
+
+    /// Send a market order for `quantity` contracts; returns the exchange order ID
+    pub async fn market_order(&self, symbol: &str, side: &Side, quantity: f64, lot: &LotSize) -> Result<u64> {
+        let params = [
+            ("symbol", symbol.to_string()),
+            ("side", side.to_string()),
+            ("type", "MARKET".to_string()),
+            ("quantity", format!("{:.*}", lot.decimals(), quantity)),
+        ];
+        let order: OrderResponse = self.request(Method::POST, "/fapi/v1/order", &params, true).await?;
+        Ok(order.order_id)
+    }
+
+    async fn request<T: DeserializeOwned>(
+        &self,
+        method: Method,
+        path: &str,
+        params: &[(&str, String)],
+        signed: bool,
+    ) -> Result<T> {
+        let mut query = params
+            .iter()
+            .map(|(k, v)| format!("{}={}", k, v))
+            .collect::<Vec<_>>()
+            .join("&");
+        if signed {
+            let stamp = format!("recvWindow={}&timestamp={}", RECV_WINDOW_MS, Utc::now().timestamp_millis());
+            query = if query.is_empty() { stamp } else { format!("{}&{}", query, stamp) };
+            let mut mac = HmacSha256::new_from_slice(self.api_secret.as_bytes())
+                .map_err(|_| ClobError::auth("Binance futures", "BINANCE_FUTURES_SECRET is not usable as an HMAC key"))?;
+            mac.update(query.as_bytes());
+            query = format!("{}&signature={}", query, hex::encode(mac.finalize().into_bytes()));
+        }
+
+        let request = self
+            .client
+            .request(method, format!("{}{}?{}", self.base_url, path, query))
+            .header("X-MBX-APIKEY", &self.api_key);
+        ClobError::send_json(&format!("Binance futures {}", path), request).await
+    }
+}
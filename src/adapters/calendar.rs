@@ -0,0 +1,150 @@
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use reqwest::Client;
+use serde::Deserialize;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use tracing::{info, warn};
+
+use crate::config::CalendarConfig;
+use crate::domain::{EconEvent, EconImpact};
+use crate::error::FeedError;
+
+type Result<T, E = FeedError> = std::result::Result<T, E>;
+
+const FEED: &str = "calendar";
+
+/// Releases this long past are dropped from the calendar
+const KEEP_PAST_HOURS: i64 = 6;
+
+/// Upcoming (and just-passed) releases, soonest first. Each refresh swaps in a
+/// new list, so a reader holding the old one keeps it without a copy.
+pub type EconEvents = Arc<RwLock<Arc<Vec<EconEvent>>>>;
+
+/// One row of the Forex Factory calendar export
+#[derive(Debug, Deserialize)]
+struct CalendarRow {
+    title: String,
+    country: String,
+    /// RFC 3339 with the publisher's offset
+    date: String,
+    /// "High", "Medium", "Low" or "Holiday"
+    impact: String,
+}
+
+impl CalendarRow {
+    fn into_event(self) -> Option<EconEvent> {
+        let impact = match self.impact.to_lowercase().as_str() {
+            "high" => EconImpact::High,
+            "medium" => EconImpact::Medium,
+            "low" => EconImpact::Low,
+            _ => return None,
+        };
+        Some(EconEvent {
+            name: self.title,
+            country: self.country.to_uppercase(),
+            impact,
+            scheduled_at: DateTime::parse_from_rfc3339(&self.date).ok()?.with_timezone(&Utc),
+        })
+    }
+}
+
+/// Configured `name@RFC3339` releases; malformed entries are skipped with a warning
+fn fixed_events(config: &CalendarConfig) -> Vec<EconEvent> {
+    let country = config.countries.first().cloned().unwrap_or_else(|| "USD".to_string());
+    config
+        .events
+        .iter()
+        .filter_map(|spec| {
+            let parsed = spec
+                .rsplit_once('@')
+                .and_then(|(name, at)| Some((name.trim(), DateTime::parse_from_rfc3339(at.trim()).ok()?)));
+            let Some((name, at)) = parsed else {
+                warn!("Calendar event {:?} skipped: expected name@RFC3339", spec);
+                return None;
+            };
+            Some(EconEvent {
+                name: name.to_string(),
+                country: country.clone(),
+                impact: EconImpact::High,
+                scheduled_at: at.with_timezone(&Utc),
+            })
+        })
+        .collect()
+}
+
+/// Keeps the shared calendar current: the configured releases plus, when a URL is
+/// set, whatever the calendar feed lists, filtered to the tracked currencies and
+/// impact. Strategies read it through the context; the risk manager enforces the
+/// blackout around each release.
+pub struct CalendarFeed {
+    client: Client,
+    config: CalendarConfig,
+    fixed: Vec<EconEvent>,
+    events: EconEvents,
+}
+
+impl CalendarFeed {
+    /// Seeds the calendar with the configured releases so they apply before the first fetch
+    pub fn new(config: &CalendarConfig) -> Result<Self> {
+        let fixed = fixed_events(config);
+        let feed = Self {
+            client: Client::builder()
+                .timeout(Duration::from_secs(10))
+                .build()
+                .map_err(|e| FeedError::request(FEED, e))?,
+            config: config.clone(),
+            fixed,
+            events: EconEvents::default(),
+        };
+        feed.store(Vec::new());
+        Ok(feed)
+    }
+
+    /// Shared handle on the calendar
+    pub fn events(&self) -> EconEvents {
+        self.events.clone()
+    }
+
+    pub async fn run(self) {
+        let Some(url) = self.config.url.clone() else {
+            return;
+        };
+        let interval = Duration::from_secs(self.config.poll_secs.max(60));
+        info!("Economic calendar polling {} every {:?}", url, interval);
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            match self.fetch(&url).await {
+                Ok(fetched) => {
+                    let count = self.store(fetched);
+                    info!("Economic calendar: {} tracked releases", count);
+                }
+                Err(e) => warn!("Economic calendar fetch failed: {:?}", e),
+            }
+        }
+    }
+
+    async fn fetch(&self, url: &str) -> Result<Vec<EconEvent>> {
+        let rows: Vec<CalendarRow> = FeedError::send_json(FEED, self.client.get(url)).await?;
+        Ok(rows.into_iter().filter_map(CalendarRow::into_event).collect())
+    }
+
+    /// Replace the calendar with the fixed releases plus `fetched`; returns how many are kept
+    fn store(&self, fetched: Vec<EconEvent>) -> usize {
+        let cutoff = Utc::now() - ChronoDuration::hours(KEEP_PAST_HOURS);
+        let mut events: Vec<EconEvent> = self
+            .fixed
+            .iter()
+            .cloned()
+            .chain(fetched.into_iter().filter(|e| {
+                e.impact >= self.config.min_impact && self.config.countries.contains(&e.country)
+            }))
+            .filter(|e| e.scheduled_at >= cutoff)
+            .collect();
+        events.sort_by_key(|e| e.scheduled_at);
+        events.dedup_by(|a, b| a.name == b.name && a.scheduled_at == b.scheduled_at);
+        let count = events.len();
+        *self.events.write().unwrap() = Arc::new(events);
+        count
+    }
+}
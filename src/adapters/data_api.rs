@@ -0,0 +1,74 @@
+use reqwest::Client;
+use serde::Deserialize;
+
+use crate::domain::{MarketId, TokenId};
+use crate::error::FeedError;
+
+type Result<T, E = FeedError> = std::result::Result<T, E>;
+
+const DATA_API_URL: &str = "https://data-api.polymarket.com";
+const FEED: &str = "data-api";
+
+/// Read-only client for Polymarket's public data API (trade tape, holders, activity)
+#[derive(Clone)]
+pub struct DataApiClient {
+    client: Client,
+}
+
+/// One public trade print
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PublicTrade {
+    /// CLOB token ID
+    pub asset: TokenId,
+    pub size: f64,
+    pub price: f64,
+    /// Unix seconds
+    pub timestamp: i64,
+    pub transaction_hash: Option<String>,
+    /// Taker side, "BUY" or "SELL"
+    pub side: Option<String>,
+    /// Market (condition) ID
+    pub condition_id: Option<MarketId>,
+}
+
+impl PublicTrade {
+    /// Stable identifier for dedup; a transaction can carry several fills
+    pub fn trade_key(&self) -> String {
+        format!(
+            "{}:{}:{}:{}",
+            self.transaction_hash.as_deref().unwrap_or("-"),
+            self.asset,
+            self.price,
+            self.size
+        )
+    }
+}
+
+impl DataApiClient {
+    pub fn new() -> Result<Self> {
+        let client = Client::builder()
+            .timeout(std::time::Duration::from_secs(15))
+            .build()
+            .map_err(|e| FeedError::request(FEED, e))?;
+        Ok(Self { client })
+    }
+
+    /// Most recent public trades in a market, newest first
+    pub async fn get_market_trades(&self, market_id: &str, limit: u32) -> Result<Vec<PublicTrade>> {
+        let request = self
+            .client
+            .get(format!("{}/trades", DATA_API_URL))
+            .query(&[("market", market_id), ("limit", &limit.to_string())]);
+        FeedError::send_json(FEED, request).await
+    }
+
+    /// Most recent trades by one wallet (its proxy address), newest first
+    pub async fn get_user_trades(&self, wallet: &str, limit: u32) -> Result<Vec<PublicTrade>> {
+        let request = self
+            .client
+            .get(format!("{}/trades", DATA_API_URL))
+            .query(&[("user", wallet), ("limit", &limit.to_string())]);
+        FeedError::send_json(FEED, request).await
+    }
+}
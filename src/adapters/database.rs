@@ -1,265 +1,227 @@
-use chrono::Utc;
-use eyre::Result;
-use sqlx::sqlite::{SqlitePool, SqlitePoolOptions};
-
-use crate::domain::{Order, OrderStatus, PnlSnapshot, Position, Side, Trade};
-
-#[derive(Clone)]
-pub struct Database {
-    pub pool: SqlitePool,
-}
-
-impl Database {
-    pub async fn new(db_path: &str) -> Result<Self> {
-        let url = format!("sqlite:{}?mode=rwc", db_path);
-        let pool = SqlitePoolOptions::new()
-            .max_connections(5)
-            .connect(&url)
-            .await?;
-
-        let db = Self { pool };
-        db.run_migrations().await?;
-        Ok(db)
-    }
-
-    async fn run_migrations(&self) -> Result<()> {
-        sqlx::query(
-            r#"
-            CREATE TABLE IF NOT EXISTS trades (
-                id TEXT PRIMARY KEY,
-                order_id TEXT NOT NULL,
-                market_id TEXT NOT NULL,
-                side TEXT NOT NULL,
-                price REAL NOT NULL,
-                size REAL NOT NULL,
-                fee REAL NOT NULL DEFAULT 0.0,
-                timestamp TEXT NOT NULL
-            );
-
-            CREATE TABLE IF NOT EXISTS positions (
-                market_id TEXT NOT NULL,
-                token_id TEXT NOT NULL,
-                side TEXT NOT NULL,
-                size REAL NOT NULL,
-                avg_price REAL NOT NULL,
-                current_price REAL NOT NULL DEFAULT 0.0,
-                pnl REAL NOT NULL DEFAULT 0.0,
-                PRIMARY KEY (market_id, token_id)
-            );
-
-            CREATE TABLE IF NOT EXISTS orders (
-                id TEXT PRIMARY KEY,
-                market_id TEXT NOT NULL,
-                side TEXT NOT NULL,
-                token_id TEXT NOT NULL,
-                price REAL NOT NULL,
-                size REAL NOT NULL,
-                order_type TEXT NOT NULL,
-                status TEXT NOT NULL,
-                created_at TEXT NOT NULL
-            );
-
-            CREATE TABLE IF NOT EXISTS pnl_snapshots (
-                id INTEGER PRIMARY KEY AUTOINCREMENT,
-                timestamp TEXT NOT NULL,
-                bankroll REAL NOT NULL,
-                pnl_total REAL NOT NULL
-            );
-
-            CREATE TABLE IF NOT EXISTS config (
-                key TEXT PRIMARY KEY,
-                value TEXT NOT NULL
-            );
-            "#,
-        )
-        .execute(&self.pool)
-        .await?;
-
-        Ok(())
-    }
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::ops::Deref;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use super::postgres::PostgresStorage;
+use super::sqlite::SqliteStorage;
+use super::write_behind::WriteBehind;
+use crate::config::Config;
+use crate::domain::{
+    Candle, ClosedTrade, ExposureSnapshot, FlowAlert, FlowAlertKind, Market, MarketCooldown, MarketId, MarketStructure,
+    Order, OrderId, OrderStatus, Outcome, PnlSnapshot, Position, ProbableFill, Redemption, RedemptionStatus, RiskEvent,
+    RiskEventKind, Side, SignalRecord, TokenId, Trade, VariantReport,
+};
+use crate::error::DbError;
+
+type Result<T, E = DbError> = std::result::Result<T, E>;
+
+/// Persistence operations the bot and dashboard need, independent of backend
+#[async_trait::async_trait]
+pub trait Storage: Send + Sync {
+    /// Backend name for logs and status
+    fn backend(&self) -> &'static str;
+    /// Highest applied migration version
+    async fn schema_version(&self) -> Result<i64>;
 
     // --- Trades ---
+    async fn insert_trade(&self, trade: &Trade) -> Result<()>;
+    async fn get_recent_trades(&self, limit: i64) -> Result<Vec<Trade>>;
+    async fn get_trades_since(&self, since: DateTime<Utc>) -> Result<Vec<Trade>>;
+    async fn insert_closed_trade(&self, closed: &ClosedTrade) -> Result<()>;
+    /// Closes at or after `since`, oldest first
+    async fn get_closed_trades(&self, since: DateTime<Utc>) -> Result<Vec<ClosedTrade>>;
+    /// Sum of PnL over every closed trade
+    async fn get_realized_pnl(&self) -> Result<f64>;
 
-    pub async fn insert_trade(&self, trade: &Trade) -> Result<()> {
-        let side = trade.side.to_string();
-        let ts = trade.timestamp.to_rfc3339();
-        sqlx::query(
-            "INSERT INTO trades (id, order_id, market_id, side, price, size, fee, timestamp) VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
-        )
-        .bind(&trade.id)
-        .bind(&trade.order_id)
-        .bind(&trade.market_id)
-        .bind(&side)
-        .bind(trade.price)
-        .bind(trade.size)
-        .bind(trade.fee)
-        .bind(&ts)
-        .execute(&self.pool)
-        .await?;
-        Ok(())
-    }
+    // --- Positions ---
+    async fn upsert_position(&self, pos: &Position) -> Result<()>;
+    async fn get_positions(&self) -> Result<Vec<Position>>;
+    async fn delete_position(&self, market_id: &MarketId, token_id: &TokenId) -> Result<()>;
 
-    pub async fn get_recent_trades(&self, limit: i64) -> Result<Vec<Trade>> {
-        let rows = sqlx::query_as::<_, TradeRow>(
-            "SELECT id, order_id, market_id, side, price, size, fee, timestamp FROM trades ORDER BY timestamp DESC LIMIT ?",
-        )
-        .bind(limit)
-        .fetch_all(&self.pool)
-        .await?;
+    // --- Orders ---
+    async fn insert_order(&self, order: &Order) -> Result<()>;
+    async fn update_order_status(&self, order_id: &OrderId, status: &OrderStatus) -> Result<()>;
+    async fn set_order_remote_id(&self, order_id: &OrderId, remote_id: &str) -> Result<()>;
+    async fn get_open_orders(&self) -> Result<Vec<Order>>;
 
-        Ok(rows.into_iter().map(|r| r.into()).collect())
-    }
+    // --- Probable fills (public tape heuristic) ---
+    /// Returns false if this trade was already matched to this order
+    async fn insert_probable_fill(&self, fill: &ProbableFill) -> Result<bool>;
+    /// Probable fills not yet confirmed or refuted by reconciliation
+    async fn get_unconfirmed_fills(&self) -> Result<Vec<ProbableFill>>;
 
-    // --- Positions ---
+    // --- PnL ---
+    async fn record_pnl_snapshot(&self, snapshot: &PnlSnapshot) -> Result<()>;
+    /// Snapshots in [from, to], oldest first; with `bucket_secs` only the last
+    /// snapshot of each bucket is returned
+    async fn get_pnl_history(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        bucket_secs: Option<i64>,
+    ) -> Result<Vec<PnlSnapshot>>;
+    async fn record_exposure_snapshot(&self, snapshot: &ExposureSnapshot) -> Result<()>;
+    async fn get_exposure_history(&self, since: DateTime<Utc>) -> Result<Vec<ExposureSnapshot>>;
+
+    // --- Loss cooldowns ---
+    /// Insert or replace the cooldown for its market
+    async fn set_cooldown(&self, cooldown: &MarketCooldown) -> Result<()>;
+    /// Cooldowns still in force at `now`
+    async fn get_active_cooldowns(&self, now: DateTime<Utc>) -> Result<Vec<MarketCooldown>>;
+
+    // --- Signal calibration ---
+    async fn insert_signal_record(&self, record: &SignalRecord) -> Result<()>;
+    /// Records still waiting on their market's resolution
+    async fn get_unresolved_signal_records(&self) -> Result<Vec<SignalRecord>>;
+    async fn resolve_signal_record(&self, id: &str, outcome: f64, resolved_at: DateTime<Utc>) -> Result<()>;
+    /// Resolved records for signals submitted at or after `since`
+    async fn get_resolved_signal_records(&self, since: DateTime<Utc>) -> Result<Vec<SignalRecord>>;
+
+    // --- Strategy experiments ---
+    async fn insert_variant_reports(&self, reports: &[VariantReport]) -> Result<()>;
+    /// Reports taken at or after `since`, oldest first
+    async fn get_variant_reports(&self, since: DateTime<Utc>) -> Result<Vec<VariantReport>>;
+
+    // --- Risk audit log ---
+    async fn insert_risk_event(&self, event: &RiskEvent) -> Result<()>;
+    /// Events at or after `since`, newest first
+    async fn get_risk_events(&self, since: DateTime<Utc>, limit: i64) -> Result<Vec<RiskEvent>>;
+
+    // --- Flow alerts ---
+    async fn insert_flow_alert(&self, alert: &FlowAlert) -> Result<()>;
+    /// Alerts at or after `since`, newest first
+    async fn get_flow_alerts(&self, since: DateTime<Utc>, limit: i64) -> Result<Vec<FlowAlert>>;
+
+    // --- Redemptions ---
+    async fn insert_redemption(&self, redemption: &Redemption) -> Result<()>;
+    /// Settlements at or after `since`, newest first
+    async fn get_redemptions(&self, since: DateTime<Utc>, limit: i64) -> Result<Vec<Redemption>>;
+
+    // --- Market metadata ---
+    /// Insert or refresh markets and their tokens
+    async fn upsert_markets(&self, markets: &[Market]) -> Result<()>;
+    /// Every stored market, with its tokens in Gamma's order
+    async fn get_markets(&self) -> Result<Vec<Market>>;
+    /// The market a token belongs to, if it is stored
+    async fn get_market_by_token(&self, token_id: &TokenId) -> Result<Option<Market>>;
+
+    // --- Market data history ---
+    /// Insert (unix_ts, price) points for a token, skipping ones already stored
+    async fn insert_price_history(&self, token_id: &TokenId, points: &[(i64, f64)]) -> Result<u64>;
+    /// Stored (unix_ts, price) points for a token in [from_ts, to_ts], oldest first
+    async fn get_price_history(&self, token_id: &TokenId, from_ts: i64, to_ts: i64) -> Result<Vec<(i64, f64)>>;
+    /// Most recent stored timestamp for a token, to resume backfills incrementally
+    async fn latest_price_timestamp(&self, token_id: &TokenId) -> Result<Option<i64>>;
+
+    // --- Candles ---
+    /// Insert or merge candles; an existing bar keeps its open and widens its range
+    async fn upsert_candles(&self, candles: &[Candle]) -> Result<()>;
+    /// Most recent `limit` candles, oldest first
+    async fn get_candles(&self, instrument: &str, interval_secs: i64, limit: i64) -> Result<Vec<Candle>>;
+
+    // --- Archive ---
+    /// Markets that still have rows in the hot trades/orders/positions tables
+    async fn get_market_ids(&self) -> Result<Vec<MarketId>>;
+    /// Move a settled market's trades, orders and positions into the archive tables.
+    /// Returns the number of rows moved.
+    async fn archive_market(&self, market_id: &MarketId) -> Result<u64>;
 
-    pub async fn upsert_position(&self, pos: &Position) -> Result<()> {
-        let side = pos.side.to_string();
-        sqlx::query(
-            "INSERT INTO positions (market_id, token_id, side, size, avg_price, current_price, pnl)
-             VALUES (?, ?, ?, ?, ?, ?, ?)
-             ON CONFLICT(market_id, token_id) DO UPDATE SET
-                side = excluded.side,
-                size = excluded.size,
-                avg_price = excluded.avg_price,
-                current_price = excluded.current_price,
-                pnl = excluded.pnl",
-        )
-        .bind(&pos.market_id)
-        .bind(&pos.token_id)
-        .bind(&side)
-        .bind(pos.size)
-        .bind(pos.avg_price)
-        .bind(pos.current_price)
-        .bind(pos.pnl)
-        .execute(&self.pool)
-        .await?;
-        Ok(())
-    }
+    // --- Config KV ---
+    async fn set_config(&self, key: &str, value: &str) -> Result<()>;
+    async fn get_config(&self, key: &str) -> Result<Option<String>>;
 
-    pub async fn get_positions(&self) -> Result<Vec<Position>> {
-        let rows = sqlx::query_as::<_, PositionRow>(
-            "SELECT market_id, token_id, side, size, avg_price, current_price, pnl FROM positions WHERE size > 0",
-        )
-        .fetch_all(&self.pool)
-        .await?;
-        Ok(rows.into_iter().map(|r| r.into()).collect())
-    }
+    // --- Backups ---
+    /// Write a backup into `dir` and prune all but the newest `keep`
+    async fn backup(&self, dir: &str, keep: usize) -> Result<PathBuf>;
 
-    pub async fn delete_position(&self, market_id: &str, token_id: &str) -> Result<()> {
-        sqlx::query("DELETE FROM positions WHERE market_id = ? AND token_id = ?")
-            .bind(market_id)
-            .bind(token_id)
-            .execute(&self.pool)
-            .await?;
-        Ok(())
-    }
+    // --- Batched writes ---
+    /// Apply `writes` in order in one transaction: all of them or none
+    async fn apply_writes(&self, writes: &[Write]) -> Result<()>;
 
-    // --- Orders ---
+    /// Wait for queries in flight, then close every connection
+    async fn close(&self);
+}
 
-    pub async fn insert_order(&self, order: &Order) -> Result<()> {
-        let side = order.side.to_string();
-        let status = format!("{:?}", order.status);
-        let ot = format!("{:?}", order.order_type);
-        let ts = order.created_at.to_rfc3339();
-        sqlx::query(
-            "INSERT INTO orders (id, market_id, side, token_id, price, size, order_type, status, created_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
-        )
-        .bind(&order.id)
-        .bind(&order.market_id)
-        .bind(&side)
-        .bind(&order.token_id)
-        .bind(order.price)
-        .bind(order.size)
-        .bind(&ot)
-        .bind(&status)
-        .bind(&ts)
-        .execute(&self.pool)
-        .await?;
-        Ok(())
-    }
+/// Hot tables moved by `archive_market`, with the columns their `_archive` twins share
+pub(super) const ARCHIVED_TABLES: &[(&str, &str)] = &[
+    ("trades", "id, order_id, market_id, side, price, size, fee, timestamp"),
+    ("orders", "id, market_id, side, token_id, price, size, order_type, status, created_at, remote_id, strategy, replaces, expires_at"),
+    ("positions", "market_id, token_id, side, size, avg_price, current_price, pnl, strategy"),
+];
+
+/// A write the trading path queues rather than waits on; see `Database::enqueue`
+#[derive(Debug, Clone)]
+pub enum Write {
+    Order(Order),
+    OrderStatus(OrderId, OrderStatus),
+    OrderRemoteId(OrderId, String),
+    Trade(Trade),
+    ClosedTrade(ClosedTrade),
+    Position(Position),
+    DeletePosition(MarketId, TokenId),
+    SignalRecord(SignalRecord),
+    RiskEvent(RiskEvent),
+    Cooldown(MarketCooldown),
+}
 
-    pub async fn update_order_status(&self, order_id: &str, status: &OrderStatus) -> Result<()> {
-        let s = format!("{:?}", status);
-        sqlx::query("UPDATE orders SET status = ? WHERE id = ?")
-            .bind(&s)
-            .bind(order_id)
-            .execute(&self.pool)
-            .await?;
-        Ok(())
-    }
+/// Shared handle to the configured storage backend.
+///
+/// Reads and one-off writes go straight to the store. Writes on the trading path
+/// go through `enqueue` to a write-behind queue instead, so a slow or locked
+/// database never holds up an order; anything that reads them back soon after
+/// should `flush` first. State a restart recovers from (the config KV, such as
+/// the multi-leg executions in flight) is never queued: a crash would lose it.
+#[derive(Clone)]
+pub struct Database {
+    store: Arc<dyn Storage>,
+    writes: WriteBehind,
+}
 
-    pub async fn get_open_orders(&self) -> Result<Vec<Order>> {
-        let rows = sqlx::query_as::<_, OrderRow>(
-            "SELECT id, market_id, side, token_id, price, size, order_type, status, created_at FROM orders WHERE status IN ('Pending', 'Open')",
-        )
-        .fetch_all(&self.pool)
-        .await?;
-        Ok(rows.into_iter().map(|r| r.into()).collect())
+impl Database {
+    /// Postgres when `DATABASE_URL` is set, otherwise the SQLite file at `DB_PATH`
+    pub async fn connect(config: &Config) -> Result<Self> {
+        let store: Arc<dyn Storage> = match &config.database_url {
+            Some(url) => Arc::new(PostgresStorage::new(url).await?),
+            None => Arc::new(SqliteStorage::new(&config.db_path, &config.db).await?),
+        };
+        let writes = WriteBehind::spawn(store.clone(), config.db.write_batch);
+        Ok(Self { store, writes })
     }
 
-    // --- PnL ---
-
-    pub async fn record_pnl_snapshot(&self, bankroll: f64, pnl_total: f64) -> Result<()> {
-        let ts = Utc::now().to_rfc3339();
-        sqlx::query("INSERT INTO pnl_snapshots (timestamp, bankroll, pnl_total) VALUES (?, ?, ?)")
-            .bind(&ts)
-            .bind(bankroll)
-            .bind(pnl_total)
-            .execute(&self.pool)
-            .await?;
-        Ok(())
+    /// Queue `write` and return at once; it is committed in the next batch
+    pub fn enqueue(&self, write: Write) {
+        self.writes.enqueue(write);
     }
 
-    pub async fn get_pnl_history(&self) -> Result<Vec<PnlSnapshot>> {
-        let rows = sqlx::query_as::<_, PnlRow>(
-            "SELECT timestamp, bankroll, pnl_total FROM pnl_snapshots ORDER BY timestamp ASC",
-        )
-        .fetch_all(&self.pool)
-        .await?;
-        Ok(rows
-            .into_iter()
-            .filter_map(|r| {
-                Some(PnlSnapshot {
-                    timestamp: chrono::DateTime::parse_from_rfc3339(&r.timestamp)
-                        .ok()?
-                        .with_timezone(&Utc),
-                    bankroll: r.bankroll,
-                    pnl_total: r.pnl_total,
-                })
-            })
-            .collect())
+    /// Wait until every write queued so far is committed
+    pub async fn flush(&self) {
+        self.writes.flush().await;
     }
 
-    // --- Config KV ---
-
-    pub async fn set_config(&self, key: &str, value: &str) -> Result<()> {
-        sqlx::query(
-            "INSERT INTO config (key, value) VALUES (?, ?) ON CONFLICT(key) DO UPDATE SET value = excluded.value",
-        )
-        .bind(key)
-        .bind(value)
-        .execute(&self.pool)
-        .await?;
-        Ok(())
+    /// Commit the queued writes, then close the store
+    pub async fn close(&self) {
+        self.flush().await;
+        self.store.close().await;
     }
+}
+
+impl Deref for Database {
+    type Target = dyn Storage;
 
-    pub async fn get_config(&self, key: &str) -> Result<Option<String>> {
-        let row: Option<(String,)> =
-            sqlx::query_as("SELECT value FROM config WHERE key = ?")
-                .bind(key)
-                .fetch_optional(&self.pool)
-                .await?;
-        Ok(row.map(|r| r.0))
+    fn deref(&self) -> &Self::Target {
+        self.store.as_ref()
     }
 }
 
 // --- Row types for sqlx ---
 
 #[derive(sqlx::FromRow)]
-struct TradeRow {
+pub(super) struct TradeRow {
     id: String,
-    order_id: String,
-    market_id: String,
+    order_id: OrderId,
+    market_id: MarketId,
     side: String,
     price: f64,
     size: f64,
@@ -285,14 +247,15 @@ impl From<TradeRow> for Trade {
 }
 
 #[derive(sqlx::FromRow)]
-struct PositionRow {
-    market_id: String,
-    token_id: String,
+pub(super) struct PositionRow {
+    market_id: MarketId,
+    token_id: TokenId,
     side: String,
     size: f64,
     avg_price: f64,
     current_price: f64,
     pnl: f64,
+    strategy: String,
 }
 
 impl From<PositionRow> for Position {
@@ -305,21 +268,26 @@ impl From<PositionRow> for Position {
             avg_price: r.avg_price,
             current_price: r.current_price,
             pnl: r.pnl,
+            strategy: r.strategy,
         }
     }
 }
 
 #[derive(sqlx::FromRow)]
-struct OrderRow {
-    id: String,
-    market_id: String,
+pub(super) struct OrderRow {
+    id: OrderId,
+    market_id: MarketId,
     side: String,
-    token_id: String,
+    token_id: TokenId,
     price: f64,
     size: f64,
     order_type: String,
     status: String,
     created_at: String,
+    remote_id: Option<String>,
+    strategy: String,
+    replaces: Option<OrderId>,
+    expires_at: Option<String>,
 }
 
 impl From<OrderRow> for Order {
@@ -335,6 +303,7 @@ impl From<OrderRow> for Order {
             order_type: match r.order_type.as_str() {
                 "GTD" => OrderType::GTD,
                 "FOK" => OrderType::FOK,
+                "FAK" => OrderType::FAK,
                 _ => OrderType::GTC,
             },
             status: match r.status.as_str() {
@@ -347,13 +316,415 @@ impl From<OrderRow> for Order {
             created_at: chrono::DateTime::parse_from_rfc3339(&r.created_at)
                 .map(|dt| dt.with_timezone(&Utc))
                 .unwrap_or_else(|_| Utc::now()),
+            remote_id: r.remote_id,
+            strategy: r.strategy,
+            replaces: r.replaces,
+            expires_at: r
+                .expires_at
+                .and_then(|ts| chrono::DateTime::parse_from_rfc3339(&ts).ok())
+                .map(|dt| dt.with_timezone(&Utc)),
         }
     }
 }
 
 #[derive(sqlx::FromRow)]
-struct PnlRow {
+pub(super) struct PnlRow {
     timestamp: String,
     bankroll: f64,
     pnl_total: f64,
+    realized_pnl: f64,
+    unrealized_pnl: f64,
+}
+
+impl PnlRow {
+    /// Rows with an unparseable timestamp are skipped rather than failing the query
+    pub(super) fn into_snapshot(self) -> Option<PnlSnapshot> {
+        Some(PnlSnapshot {
+            timestamp: chrono::DateTime::parse_from_rfc3339(&self.timestamp)
+                .ok()?
+                .with_timezone(&Utc),
+            bankroll: self.bankroll,
+            pnl_total: self.pnl_total,
+            realized_pnl: self.realized_pnl,
+            unrealized_pnl: self.unrealized_pnl,
+        })
+    }
+}
+
+#[derive(sqlx::FromRow)]
+pub(super) struct ExposureRow {
+    timestamp: String,
+    gross_exposure: f64,
+    net_exposure: f64,
+    open_order_notional: f64,
+    free_collateral: f64,
+    by_underlying: String,
+}
+
+impl ExposureRow {
+    /// Rows with an unparseable timestamp are skipped rather than failing the query
+    pub(super) fn into_snapshot(self) -> Option<ExposureSnapshot> {
+        Some(ExposureSnapshot {
+            timestamp: chrono::DateTime::parse_from_rfc3339(&self.timestamp)
+                .ok()?
+                .with_timezone(&Utc),
+            gross_exposure: self.gross_exposure,
+            net_exposure: self.net_exposure,
+            open_order_notional: self.open_order_notional,
+            free_collateral: self.free_collateral,
+            by_underlying: serde_json::from_str(&self.by_underlying).unwrap_or_default(),
+        })
+    }
+}
+
+#[derive(sqlx::FromRow)]
+pub(super) struct ClosedTradeRow {
+    id: String,
+    strategy: String,
+    market_id: MarketId,
+    token_id: TokenId,
+    side: String,
+    size: f64,
+    entry_price: f64,
+    exit_price: f64,
+    pnl: f64,
+    closed_at: String,
+}
+
+impl ClosedTradeRow {
+    /// Rows with an unparseable timestamp are skipped rather than failing the query
+    pub(super) fn into_closed_trade(self) -> Option<ClosedTrade> {
+        Some(ClosedTrade {
+            id: self.id,
+            strategy: self.strategy,
+            market_id: self.market_id,
+            token_id: self.token_id,
+            side: if self.side == "BUY" { Side::Buy } else { Side::Sell },
+            size: self.size,
+            entry_price: self.entry_price,
+            exit_price: self.exit_price,
+            pnl: self.pnl,
+            closed_at: chrono::DateTime::parse_from_rfc3339(&self.closed_at)
+                .ok()?
+                .with_timezone(&Utc),
+        })
+    }
+}
+
+#[derive(sqlx::FromRow)]
+pub(super) struct SignalRecordRow {
+    id: String,
+    strategy: String,
+    market_id: MarketId,
+    token_id: TokenId,
+    side: String,
+    confidence: f64,
+    price: f64,
+    created_at: String,
+    outcome: Option<f64>,
+    resolved_at: Option<String>,
+    size: Option<f64>,
+    shadow: i64,
+}
+
+impl SignalRecordRow {
+    /// Rows with an unparseable timestamp are skipped rather than failing the query
+    pub(super) fn into_record(self) -> Option<SignalRecord> {
+        let parse = |ts: &str| chrono::DateTime::parse_from_rfc3339(ts).ok().map(|t| t.with_timezone(&Utc));
+        Some(SignalRecord {
+            id: self.id,
+            strategy: self.strategy,
+            market_id: self.market_id,
+            token_id: self.token_id,
+            side: if self.side == "BUY" { Side::Buy } else { Side::Sell },
+            confidence: self.confidence,
+            price: self.price,
+            created_at: parse(&self.created_at)?,
+            outcome: self.outcome,
+            resolved_at: self.resolved_at.as_deref().and_then(parse),
+            size: self.size,
+            shadow: self.shadow != 0,
+        })
+    }
+}
+
+#[derive(sqlx::FromRow)]
+pub(super) struct VariantReportRow {
+    timestamp: String,
+    experiment: String,
+    strategy: String,
+    live: i64,
+    signals: i64,
+    resolved: i64,
+    wins: i64,
+    pnl: f64,
+    open_notional: f64,
+}
+
+impl VariantReportRow {
+    /// Rows with an unparseable timestamp are skipped rather than failing the query
+    pub(super) fn into_report(self) -> Option<VariantReport> {
+        Some(VariantReport {
+            timestamp: chrono::DateTime::parse_from_rfc3339(&self.timestamp)
+                .ok()?
+                .with_timezone(&Utc),
+            experiment: self.experiment,
+            strategy: self.strategy,
+            live: self.live != 0,
+            signals: self.signals.max(0) as usize,
+            resolved: self.resolved.max(0) as usize,
+            wins: self.wins.max(0) as usize,
+            pnl: self.pnl,
+            open_notional: self.open_notional,
+        })
+    }
+}
+
+#[derive(sqlx::FromRow)]
+pub(super) struct RiskEventRow {
+    timestamp: String,
+    kind: String,
+    strategy: Option<String>,
+    market_id: Option<MarketId>,
+    detail: String,
+}
+
+impl RiskEventRow {
+    /// Rows with an unparseable timestamp or unknown kind are skipped rather than failing the query
+    pub(super) fn into_event(self) -> Option<RiskEvent> {
+        let kind = match self.kind.as_str() {
+            "SignalRejected" => RiskEventKind::SignalRejected,
+            "DrawdownHalt" => RiskEventKind::DrawdownHalt,
+            "BankrollHalt" => RiskEventKind::BankrollHalt,
+            "TaskHalt" => RiskEventKind::TaskHalt,
+            "AuthHalt" => RiskEventKind::AuthHalt,
+            "WarmUp" => RiskEventKind::WarmUp,
+            "Cooldown" => RiskEventKind::Cooldown,
+            "MarketList" => RiskEventKind::MarketList,
+            "Dispute" => RiskEventKind::Dispute,
+            "Kill" => RiskEventKind::Kill,
+            "Resume" => RiskEventKind::Resume,
+            "Arm" => RiskEventKind::Arm,
+            "Disarm" => RiskEventKind::Disarm,
+            _ => return None,
+        };
+        Some(RiskEvent {
+            timestamp: chrono::DateTime::parse_from_rfc3339(&self.timestamp)
+                .ok()?
+                .with_timezone(&Utc),
+            kind,
+            strategy: self.strategy,
+            market_id: self.market_id,
+            detail: self.detail,
+        })
+    }
+}
+
+#[derive(sqlx::FromRow)]
+pub(super) struct FlowAlertRow {
+    timestamp: String,
+    market_id: MarketId,
+    token_id: TokenId,
+    kind: String,
+    side: String,
+    size: f64,
+    notional: f64,
+    price: f64,
+    levels: i64,
+}
+
+impl FlowAlertRow {
+    /// Rows with an unparseable timestamp or unknown kind are skipped rather than failing the query
+    pub(super) fn into_alert(self) -> Option<FlowAlert> {
+        let kind = match self.kind.as_str() {
+            "LargeTrade" => FlowAlertKind::LargeTrade,
+            "BookSweep" => FlowAlertKind::BookSweep,
+            _ => return None,
+        };
+        Some(FlowAlert {
+            market_id: self.market_id,
+            token_id: self.token_id,
+            kind,
+            side: if self.side == "BUY" { Side::Buy } else { Side::Sell },
+            size: self.size,
+            notional: self.notional,
+            price: self.price,
+            levels: self.levels.max(0) as usize,
+            timestamp: chrono::DateTime::parse_from_rfc3339(&self.timestamp)
+                .ok()?
+                .with_timezone(&Utc),
+        })
+    }
+}
+
+#[derive(sqlx::FromRow)]
+pub(super) struct RedemptionRow {
+    settled_at: String,
+    market_id: MarketId,
+    token_id: TokenId,
+    size: f64,
+    payout: f64,
+    pnl: f64,
+    status: String,
+    tx_hash: Option<String>,
+}
+
+impl RedemptionRow {
+    /// Rows with an unparseable timestamp or unknown status are skipped rather than failing the query
+    pub(super) fn into_redemption(self) -> Option<Redemption> {
+        let status = match self.status.as_str() {
+            "Redeemed" => RedemptionStatus::Redeemed,
+            "Manual" => RedemptionStatus::Manual,
+            "Lost" => RedemptionStatus::Lost,
+            _ => return None,
+        };
+        Some(Redemption {
+            market_id: self.market_id,
+            token_id: self.token_id,
+            size: self.size,
+            payout: self.payout,
+            pnl: self.pnl,
+            status,
+            tx_hash: self.tx_hash,
+            settled_at: chrono::DateTime::parse_from_rfc3339(&self.settled_at)
+                .ok()?
+                .with_timezone(&Utc),
+        })
+    }
+}
+
+#[derive(sqlx::FromRow)]
+pub(super) struct MarketRow {
+    id: MarketId,
+    question: String,
+    end_date: Option<String>,
+    active: i64,
+    closed: i64,
+    winner: Option<TokenId>,
+    neg_risk: i64,
+    neg_risk_event: Option<String>,
+    tick_size: Option<f64>,
+    min_order_size: Option<f64>,
+}
+
+#[derive(sqlx::FromRow)]
+pub(super) struct MarketTokenRow {
+    token_id: TokenId,
+    market_id: MarketId,
+    outcome: String,
+}
+
+/// Join stored markets with their tokens, which come ordered by market and
+/// position. The oracle status isn't stored; it is only ever read live.
+pub(super) fn assemble_markets(rows: Vec<MarketRow>, tokens: Vec<MarketTokenRow>) -> Vec<Market> {
+    let mut by_market: HashMap<MarketId, Vec<Outcome>> = HashMap::new();
+    for t in tokens {
+        by_market.entry(t.market_id).or_default().push(Outcome {
+            token_id: t.token_id,
+            name: t.outcome,
+        });
+    }
+    rows.into_iter()
+        .map(|r| {
+            let outcomes = by_market.remove(&r.id).unwrap_or_default();
+            Market {
+            structure: MarketStructure::new(r.neg_risk != 0, r.neg_risk_event, outcomes.len()),
+            outcomes,
+            end_date: r
+                .end_date
+                .and_then(|s| chrono::DateTime::parse_from_rfc3339(&s).ok())
+                .map(|t| t.with_timezone(&Utc)),
+            id: r.id,
+            question: r.question,
+            active: r.active != 0,
+            closed: r.closed != 0,
+            winner: r.winner,
+            uma_status: None,
+            tick_size: r.tick_size,
+            min_order_size: r.min_order_size,
+        }
+        })
+        .collect()
+}
+
+#[derive(sqlx::FromRow)]
+pub(super) struct CooldownRow {
+    market_id: MarketId,
+    blocked_until: String,
+    realized_loss: f64,
+    started_at: String,
+}
+
+impl CooldownRow {
+    /// Rows with an unparseable timestamp are skipped rather than failing the query
+    pub(super) fn into_cooldown(self) -> Option<MarketCooldown> {
+        let parse = |s: &str| chrono::DateTime::parse_from_rfc3339(s).ok().map(|t| t.with_timezone(&Utc));
+        Some(MarketCooldown {
+            market_id: self.market_id,
+            until: parse(&self.blocked_until)?,
+            realized_loss: self.realized_loss,
+            started_at: parse(&self.started_at)?,
+        })
+    }
+}
+
+#[derive(sqlx::FromRow)]
+pub(super) struct ProbableFillRow {
+    order_id: OrderId,
+    trade_key: String,
+    token_id: TokenId,
+    price: f64,
+    size: f64,
+    trade_timestamp: String,
+    detected_at: String,
+}
+
+impl From<ProbableFillRow> for ProbableFill {
+    fn from(r: ProbableFillRow) -> Self {
+        let parse = |s: &str| {
+            chrono::DateTime::parse_from_rfc3339(s)
+                .map(|dt| dt.with_timezone(&Utc))
+                .unwrap_or_else(|_| Utc::now())
+        };
+        ProbableFill {
+            order_id: r.order_id,
+            trade_key: r.trade_key,
+            token_id: r.token_id,
+            price: r.price,
+            size: r.size,
+            trade_timestamp: parse(&r.trade_timestamp),
+            detected_at: parse(&r.detected_at),
+        }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+pub(super) struct CandleRow {
+    instrument: String,
+    interval_secs: i64,
+    open_time: i64,
+    open: f64,
+    high: f64,
+    low: f64,
+    close: f64,
+    ticks: i64,
+    volume: f64,
+    vwap: Option<f64>,
+}
+
+impl From<CandleRow> for Candle {
+    fn from(r: CandleRow) -> Self {
+        Candle {
+            instrument: r.instrument,
+            interval_secs: r.interval_secs,
+            open_time: r.open_time,
+            open: r.open,
+            high: r.high,
+            low: r.low,
+            close: r.close,
+            ticks: r.ticks,
+            volume: r.volume,
+            vwap: r.vwap,
+        }
+    }
 }
@@ -0,0 +1,178 @@
+use chrono::{DateTime, Utc};
+use reqwest::Client;
+use serde::Deserialize;
+use std::collections::HashSet;
+use tracing::{info, warn};
+
+use crate::config::WatchlistConfig;
+use crate::domain::{Market, MarketId, MarketStructure, Outcome, TokenId, UmaStatus};
+use crate::error::FeedError;
+
+type Result<T, E = FeedError> = std::result::Result<T, E>;
+
+const GAMMA_URL: &str = "https://gamma-api.polymarket.com";
+const FEED: &str = "gamma";
+
+/// Read-only client for Polymarket's Gamma market metadata API
+#[derive(Clone)]
+pub struct GammaClient {
+    client: Client,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GammaMarket {
+    condition_id: MarketId,
+    question: String,
+    /// JSON-encoded array of outcome names, e.g. "[\"Yes\", \"No\"]"
+    outcomes: Option<String>,
+    /// JSON-encoded array of CLOB token IDs, parallel to `outcomes`
+    clob_token_ids: Option<String>,
+    /// JSON-encoded array of outcome prices as strings, parallel to `outcomes`;
+    /// after resolution the winner's is "1"
+    outcome_prices: Option<String>,
+    end_date: Option<String>,
+    active: Option<bool>,
+    closed: Option<bool>,
+    /// e.g. "proposed", "disputed", "resolved"; absent before any proposal
+    uma_resolution_status: Option<String>,
+    /// Part of a multi-outcome event settled through the neg-risk adapter
+    neg_risk: Option<bool>,
+    /// Shared by every market of a neg-risk event
+    #[serde(rename = "negRiskMarketID")]
+    neg_risk_market_id: Option<String>,
+    order_price_min_tick_size: Option<f64>,
+    order_min_size: Option<f64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchResponse {
+    events: Option<Vec<SearchEvent>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchEvent {
+    markets: Option<Vec<GammaMarket>>,
+}
+
+fn uma_status(status: &str) -> Option<UmaStatus> {
+    let status = status.to_lowercase();
+    if status.contains("disput") || status.contains("challeng") {
+        Some(UmaStatus::Disputed)
+    } else if status.contains("propos") {
+        Some(UmaStatus::Proposed)
+    } else if status.contains("resolved") || status.contains("settled") {
+        Some(UmaStatus::Resolved)
+    } else {
+        None
+    }
+}
+
+impl From<GammaMarket> for Market {
+    fn from(m: GammaMarket) -> Self {
+        let parse_list = |s: &Option<String>| -> Vec<String> {
+            s.as_deref()
+                .and_then(|s| serde_json::from_str(s).ok())
+                .unwrap_or_default()
+        };
+        let outcomes = parse_list(&m.outcomes);
+        let token_ids = parse_list(&m.clob_token_ids);
+        let closed = m.closed.unwrap_or(false);
+        // Closed markets can sit unresolved for a while; only a price of 1 settles it
+        let winner = parse_list(&m.outcome_prices)
+            .iter()
+            .position(|p| p.parse::<f64>().is_ok_and(|p| p >= 0.99))
+            .filter(|_| closed)
+            .and_then(|i| token_ids.get(i).map(TokenId::from));
+
+        let outcomes: Vec<Outcome> = token_ids
+            .into_iter()
+            .zip(outcomes)
+            .map(|(token_id, name)| Outcome { token_id: token_id.into(), name })
+            .collect();
+
+        Market {
+            id: m.condition_id,
+            question: m.question,
+            structure: MarketStructure::new(m.neg_risk.unwrap_or(false), m.neg_risk_market_id, outcomes.len()),
+            outcomes,
+            end_date: m
+                .end_date
+                .and_then(|d| DateTime::parse_from_rfc3339(&d).ok())
+                .map(|d| d.with_timezone(&Utc)),
+            active: m.active.unwrap_or(false) && !closed,
+            closed,
+            winner,
+            uma_status: m.uma_resolution_status.as_deref().and_then(uma_status),
+            tick_size: m.order_price_min_tick_size,
+            min_order_size: m.order_min_size,
+        }
+    }
+}
+
+impl GammaClient {
+    pub fn new() -> Result<Self> {
+        let client = Client::builder()
+            .timeout(std::time::Duration::from_secs(15))
+            .build()
+            .map_err(|e| FeedError::request(FEED, e))?;
+        Ok(Self { client })
+    }
+
+    /// Look up markets by condition ID
+    pub async fn get_markets(&self, market_ids: &[MarketId]) -> Result<Vec<Market>> {
+        if market_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let query: Vec<(&str, &str)> = market_ids
+            .iter()
+            .map(|id| ("condition_ids", id.as_str()))
+            .collect();
+
+        let request = self.client.get(format!("{}/markets", GAMMA_URL)).query(&query);
+        let markets: Vec<GammaMarket> = FeedError::send_json(FEED, request).await?;
+
+        Ok(markets.into_iter().map(Market::from).collect())
+    }
+
+    /// Free-text market search; returns only active markets
+    pub async fn search_markets(&self, query: &str) -> Result<Vec<Market>> {
+        let request = self.client.get(format!("{}/public-search", GAMMA_URL)).query(&[("q", query)]);
+        let resp: SearchResponse = FeedError::send_json(FEED, request).await?;
+
+        Ok(resp
+            .events
+            .unwrap_or_default()
+            .into_iter()
+            .flat_map(|e| e.markets.unwrap_or_default())
+            .map(Market::from)
+            .filter(|m| m.active)
+            .collect())
+    }
+
+    /// Resolve configured market IDs and search queries into a deduplicated market list.
+    /// Lookup failures are logged and skipped so one bad query doesn't block startup.
+    pub async fn resolve_watchlist(&self, watchlist: &WatchlistConfig) -> Vec<Market> {
+        let mut markets = Vec::new();
+
+        match self.get_markets(&watchlist.market_ids).await {
+            Ok(found) => markets.extend(found),
+            Err(e) => warn!("Failed to resolve watchlist market IDs: {:?}", e),
+        }
+
+        for query in &watchlist.queries {
+            match self.search_markets(query).await {
+                Ok(found) => {
+                    info!("Watchlist query {:?} matched {} markets", query, found.len());
+                    markets.extend(found);
+                }
+                Err(e) => warn!("Watchlist query {:?} failed: {:?}", query, e),
+            }
+        }
+
+        let mut seen = HashSet::new();
+        markets.retain(|m| seen.insert(m.id.clone()));
+        markets
+    }
+}
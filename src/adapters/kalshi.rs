@@ -0,0 +1,409 @@
+use base64::Engine;
+use chrono::Utc;
+use futures_util::{SinkExt, StreamExt};
+use reqwest::Client;
+use rsa::pkcs1::DecodeRsaPrivateKey;
+use rsa::pkcs8::DecodePrivateKey;
+use rsa::pss::BlindedSigningKey;
+use rsa::signature::{RandomizedSigner, SignatureEncoding};
+use rsa::RsaPrivateKey;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::sync::Arc;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::{connect_async, tungstenite::Message};
+use tracing::{error, info, warn};
+
+use crate::bus::EventBus;
+use crate::config::KalshiConfig;
+use crate::domain::{FeedStatus, MarketData, MarketId, Side, TokenId};
+use crate::error::{ClobError, FeedError};
+use crate::watchdog::Heartbeat;
+
+type Result<T, E = ClobError> = std::result::Result<T, E>;
+
+/// Feed name in errors and logs
+const FEED: &str = "kalshi";
+
+/// Market IDs of Kalshi legs carry this prefix so the order manager can route them
+pub const MARKET_PREFIX: &str = "kalshi:";
+
+/// Kalshi ticker of a `kalshi:<ticker>` market ID
+pub fn ticker_of(market_id: &str) -> Option<&str> {
+    market_id.strip_prefix(MARKET_PREFIX)
+}
+
+pub fn market_id(ticker: &str) -> MarketId {
+    MarketId::new(format!("{}{}", MARKET_PREFIX, ticker))
+}
+
+/// Token ID of one side of a Kalshi contract: `kalshi:<ticker>:yes` or `:no`
+pub fn token_id(ticker: &str, yes: bool) -> TokenId {
+    TokenId::new(format!("{}{}:{}", MARKET_PREFIX, ticker, if yes { "yes" } else { "no" }))
+}
+
+/// Ticker and side (true for YES) of a Kalshi token ID
+pub fn parse_token(token_id: &str) -> Option<(&str, bool)> {
+    let (ticker, side) = token_id.strip_prefix(MARKET_PREFIX)?.rsplit_once(':')?;
+    match side {
+        "yes" => Some((ticker, true)),
+        "no" => Some((ticker, false)),
+        _ => None,
+    }
+}
+
+/// Signs requests as Kalshi expects: RSA-PSS/SHA-256 over timestamp + method + path
+struct Signer {
+    key_id: String,
+    key: BlindedSigningKey<Sha256>,
+}
+
+impl Signer {
+    fn load(key_id: &str, path: &str) -> Result<Self> {
+        let pem = std::fs::read_to_string(path)
+            .map_err(|e| ClobError::auth("KALSHI_PRIVATE_KEY_PATH", format!("Failed to read Kalshi key {}: {}", path, e)))?;
+        let key = RsaPrivateKey::from_pkcs1_pem(&pem)
+            .or_else(|_| RsaPrivateKey::from_pkcs8_pem(&pem))
+            .map_err(|e| ClobError::auth("KALSHI_PRIVATE_KEY_PATH", format!("Invalid Kalshi private key in {}: {}", path, e)))?;
+        Ok(Self {
+            key_id: key_id.to_string(),
+            key: BlindedSigningKey::new(key),
+        })
+    }
+
+    fn headers(&self, method: &str, path: &str) -> [(&'static str, String); 3] {
+        let timestamp = Utc::now().timestamp_millis().to_string();
+        let message = format!("{}{}{}", timestamp, method, path);
+        let signature = self.key.sign_with_rng(&mut rsa::rand_core::OsRng, message.as_bytes());
+        [
+            ("KALSHI-ACCESS-KEY", self.key_id.clone()),
+            ("KALSHI-ACCESS-TIMESTAMP", timestamp),
+            (
+                "KALSHI-ACCESS-SIGNATURE",
+                base64::engine::general_purpose::STANDARD.encode(signature.to_bytes()),
+            ),
+        ]
+    }
+}
+
+#[derive(Clone)]
+pub struct KalshiClient {
+    client: Client,
+    api_url: String,
+    /// Path part of `api_url`, which signatures cover
+    api_path: String,
+    signer: Option<Arc<Signer>>,
+    fee_rate: f64,
+}
+
+#[derive(Debug, Deserialize)]
+struct MarketResponse {
+    market: KalshiMarket,
+}
+
+/// Prices are in cents
+#[derive(Debug, Deserialize)]
+struct KalshiMarket {
+    ticker: String,
+    #[serde(default)]
+    yes_bid: i64,
+    #[serde(default)]
+    yes_ask: i64,
+}
+
+#[derive(Debug, Serialize)]
+struct CreateOrderRequest<'a> {
+    ticker: &'a str,
+    client_order_id: String,
+    action: &'static str,
+    side: &'static str,
+    count: u64,
+    #[serde(rename = "type")]
+    order_type: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    yes_price: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    no_price: Option<i64>,
+    time_in_force: &'static str,
+}
+
+#[derive(Debug, Deserialize)]
+struct CreateOrderResponse {
+    order: KalshiOrder,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct KalshiOrder {
+    pub order_id: String,
+    /// "executed", "resting" or "canceled"
+    pub status: String,
+}
+
+impl KalshiClient {
+    pub fn new(config: &KalshiConfig) -> Result<Self> {
+        let client = Client::builder()
+            .timeout(std::time::Duration::from_secs(15))
+            .build()
+            .map_err(|e| ClobError::request("build HTTP client", e))?;
+        let signer = match (&config.key_id, &config.private_key_path) {
+            (Some(key_id), Some(path)) => Some(Arc::new(Signer::load(key_id, path)?)),
+            _ => None,
+        };
+        Ok(Self {
+            client,
+            api_url: config.api_url.trim_end_matches('/').to_string(),
+            api_path: url_path(&config.api_url)?,
+            signer,
+            fee_rate: config.kalshi_fee_rate,
+        })
+    }
+
+    /// Trading fee for `count` contracts at `price`: rate × C × P × (1 − P)
+    pub fn fee(&self, count: f64, price: f64) -> f64 {
+        self.fee_rate * count * price * (1.0 - price)
+    }
+
+    /// Credentials are configured, so orders and the WS feed are available
+    pub fn can_sign(&self) -> bool {
+        self.signer.is_some()
+    }
+
+    /// Current top of book as a market data event (public endpoint)
+    pub async fn get_quote(&self, ticker: &str) -> Result<MarketData> {
+        let request = self.client.get(format!("{}/markets/{}", self.api_url, ticker));
+        let resp: MarketResponse = ClobError::send_json("Kalshi market", request).await?;
+        Ok(quote(&resp.market.ticker, resp.market.yes_bid, resp.market.yes_ask))
+    }
+
+    /// Fill-or-kill limit order for `count` contracts of one side at `price` (dollars).
+    /// Kalshi refuses a second order under the same client order ID, so a resend
+    /// can't trade twice.
+    pub async fn create_order(
+        &self,
+        client_order_id: &str,
+        ticker: &str,
+        yes: bool,
+        side: &Side,
+        count: u64,
+        price: f64,
+    ) -> Result<KalshiOrder> {
+        let signer = self
+            .signer
+            .as_ref()
+            .ok_or_else(|| ClobError::auth("Kalshi order", "Kalshi credentials not configured"))?;
+        let cents = (price * 100.0).round() as i64;
+        let body = CreateOrderRequest {
+            ticker,
+            client_order_id: client_order_id.to_string(),
+            action: match side {
+                Side::Buy => "buy",
+                Side::Sell => "sell",
+            },
+            side: if yes { "yes" } else { "no" },
+            count,
+            order_type: "limit",
+            yes_price: yes.then_some(cents),
+            no_price: (!yes).then_some(cents),
+            time_in_force: "fill_or_kill",
+        };
+
+        let path = format!("{}/portfolio/orders", self.api_path);
+        let mut request = self.client.post(format!("{}/portfolio/orders", self.api_url)).json(&body);
+        for (name, value) in signer.headers("POST", &path) {
+            request = request.header(name, value);
+        }
+        let resp: CreateOrderResponse = ClobError::send_json("Kalshi order", request).await?;
+        Ok(resp.order)
+    }
+}
+
+/// Kalshi quote in cents → event in dollars; a zero ask means no offers (pay 1)
+fn quote(ticker: &str, yes_bid: i64, yes_ask: i64) -> MarketData {
+    let yes_ask = if yes_ask <= 0 { 100 } else { yes_ask };
+    MarketData::KalshiTicker {
+        ticker: ticker.to_string(),
+        yes_bid: yes_bid as f64 / 100.0,
+        yes_ask: yes_ask as f64 / 100.0,
+        timestamp: Utc::now(),
+    }
+}
+
+fn url_path(url: &str) -> Result<String> {
+    let parsed = reqwest::Url::parse(url).map_err(|e| ClobError::Invalid(format!("Invalid Kalshi URL {}: {}", url, e)))?;
+    Ok(parsed.path().trim_end_matches('/').to_string())
+}
+
+/// Kalshi top-of-book for the paired tickers: the authenticated ticker channel when
+/// credentials are set, otherwise (or when the WS is down) public REST polling
+#[derive(Clone)]
+pub struct KalshiWsFeed {
+    tx: EventBus,
+    client: KalshiClient,
+    ws_url: String,
+    tickers: Vec<String>,
+    heartbeat: Heartbeat,
+}
+
+impl KalshiWsFeed {
+    pub fn new(tx: EventBus, client: KalshiClient, ws_url: String, tickers: Vec<String>) -> Self {
+        Self {
+            tx,
+            client,
+            ws_url,
+            tickers,
+            heartbeat: Heartbeat::default(),
+        }
+    }
+
+    /// Beat on every message or poll, so the watchdog can tell a dead connection
+    /// from a quiet one
+    pub fn with_heartbeat(mut self, heartbeat: Heartbeat) -> Self {
+        self.heartbeat = heartbeat;
+        self
+    }
+
+    pub async fn run(self) -> Result<(), FeedError> {
+        let mut backoff_ms: u64 = 1000;
+
+        loop {
+            let result = if self.client.can_sign() {
+                match self.try_websocket().await {
+                    Ok(()) => {
+                        self.tx.publish(FeedStatus::disconnected(FEED, "closed"));
+                        Ok(())
+                    }
+                    Err(e) => {
+                        warn!("Kalshi WS failed: {:?}. Falling back to REST polling.", e);
+                        self.tx.publish(FeedStatus::disconnected(FEED, e.to_string()));
+                        self.rest_poll_loop().await
+                    }
+                }
+            } else {
+                self.rest_poll_loop().await
+            };
+            match result {
+                Ok(()) => backoff_ms = 1000,
+                Err(e) => {
+                    error!("Kalshi feed failed: {:?}", e);
+                    if let Some(wait) = e.retry_after() {
+                        backoff_ms = backoff_ms.max(wait.as_millis() as u64);
+                    }
+                }
+            }
+
+            warn!("Reconnecting Kalshi feed in {}ms", backoff_ms);
+            tokio::time::sleep(std::time::Duration::from_millis(backoff_ms)).await;
+            backoff_ms = (backoff_ms * 2).min(30_000);
+        }
+    }
+
+    async fn try_websocket(&self) -> Result<(), FeedError> {
+        let signer = self
+            .client
+            .signer
+            .as_ref()
+            .ok_or_else(|| FeedError::auth(FEED, "Kalshi credentials not configured"))?;
+        let mut request = self
+            .ws_url
+            .as_str()
+            .into_client_request()
+            .map_err(|e| FeedError::websocket(FEED, e))?;
+        let path = url_path(&self.ws_url).map_err(|e| FeedError::unavailable(FEED, e.to_string()))?;
+        for (name, value) in signer.headers("GET", &path) {
+            let value = value.parse().map_err(|_| FeedError::auth(FEED, format!("{} is not a valid header value", name)))?;
+            request.headers_mut().insert(name, value);
+        }
+
+        let (ws_stream, _) = connect_async(request).await.map_err(|e| FeedError::websocket(FEED, e))?;
+        info!("Connected to Kalshi WS for {:?}", self.tickers);
+        self.tx.publish(FeedStatus::connected(FEED));
+        let (mut write, mut read) = ws_stream.split();
+
+        // The ticker channel only sends changes, so seed current quotes first
+        self.publish_snapshot().await;
+        let subscribe = serde_json::json!({
+            "id": 1,
+            "cmd": "subscribe",
+            "params": { "channels": ["ticker"], "market_tickers": self.tickers },
+        });
+        write
+            .send(Message::Text(subscribe.to_string()))
+            .await
+            .map_err(|e| FeedError::websocket(FEED, e))?;
+
+        while let Some(msg) = read.next().await {
+            self.heartbeat.beat();
+            match msg {
+                Ok(Message::Text(text)) => self.handle_message(&text),
+                Ok(Message::Ping(data)) => {
+                    let _ = write.send(Message::Pong(data)).await;
+                }
+                Ok(Message::Close(_)) => {
+                    info!("Kalshi WS closed by server");
+                    break;
+                }
+                Err(e) => {
+                    error!("Kalshi WS read error: {:?}", e);
+                    break;
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn handle_message(&self, text: &str) {
+        #[derive(Deserialize)]
+        struct Envelope {
+            #[serde(rename = "type")]
+            kind: String,
+            msg: Option<Ticker>,
+        }
+        #[derive(Deserialize)]
+        struct Ticker {
+            market_ticker: String,
+            yes_bid: i64,
+            yes_ask: i64,
+        }
+
+        if let Ok(Envelope { kind, msg: Some(t) }) = serde_json::from_str::<Envelope>(text) {
+            if kind == "ticker" {
+                self.tx.publish(quote(&t.market_ticker, t.yes_bid, t.yes_ask));
+            }
+        }
+    }
+
+    async fn publish_snapshot(&self) -> bool {
+        let mut got_quote = false;
+        for ticker in &self.tickers {
+            match self.client.get_quote(ticker).await {
+                Ok(event) => {
+                    self.tx.publish(event);
+                    got_quote = true;
+                }
+                Err(e) => warn!("Kalshi quote for {} failed: {:?}", ticker, e),
+            }
+        }
+        got_quote
+    }
+
+    /// Poll REST every 2 seconds; gives up after 30 consecutive empty passes
+    async fn rest_poll_loop(&self) -> Result<(), FeedError> {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(2));
+        let mut failures = 0u32;
+        info!("Starting Kalshi REST polling for {:?}", self.tickers);
+
+        loop {
+            interval.tick().await;
+            self.heartbeat.beat();
+            if self.publish_snapshot().await {
+                failures = 0;
+            } else {
+                failures += 1;
+                if failures > 30 {
+                    return Err(FeedError::unavailable(FEED, "REST polling failed 30 consecutive times"));
+                }
+            }
+        }
+    }
+}
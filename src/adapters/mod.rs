@@ -1,4 +1,16 @@
 pub mod polymarket;
 pub mod polymarket_ws;
 pub mod binance;
+pub mod binance_futures;
+pub mod calendar;
+pub mod data_api;
 pub mod database;
+pub mod polygon;
+pub mod postgres;
+pub mod gamma;
+pub mod kalshi;
+pub mod recorder;
+pub mod sports;
+pub mod sqlite;
+pub mod venue;
+pub mod write_behind;
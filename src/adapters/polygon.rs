@@ -0,0 +1,262 @@
+use reqwest::Client;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use sha3::{Digest, Keccak256};
+use std::time::Duration;
+
+use crate::error::ClobError;
+use crate::wallet::Wallet;
+
+type Result<T, E = ClobError> = std::result::Result<T, E>;
+
+/// Wei per POL (and per ether on any EVM chain)
+const WEI_PER_POL: f64 = 1e18;
+/// Wei per gwei
+const WEI_PER_GWEI: f64 = 1e9;
+/// Polygon PoS mainnet, for EIP-155 replay protection
+const CHAIN_ID: u64 = 137;
+/// Gnosis Conditional Tokens contract Polymarket outcome tokens live in
+pub const CTF_ADDRESS: &str = "0x4D97DCd97eC945f40cF65F87097ACe5EA0476045";
+/// Polymarket's neg-risk adapter, which wraps collateral for multi-outcome events
+/// and is where their markets merge and redeem
+pub const NEG_RISK_ADAPTER_ADDRESS: &str = "0xd91E80cF2E7be2e162c6513ceD06f1dD0dA35296";
+/// Bridged USDC (USDC.e), the collateral behind Polymarket's binary markets
+const USDC_ADDRESS: &str = "0x2791Bca1f2de4661ED88A30C99A7a9449Aa84174";
+
+#[derive(Debug, Deserialize)]
+struct RpcResponse {
+    result: Option<Value>,
+    error: Option<RpcError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+/// Minimal Polygon JSON-RPC client for the reads the bot needs
+#[derive(Clone)]
+pub struct PolygonRpc {
+    client: Client,
+    url: String,
+}
+
+impl PolygonRpc {
+    pub fn new(url: &str) -> Result<Self> {
+        Ok(Self {
+            client: Client::builder()
+                .timeout(Duration::from_secs(10))
+                .build()
+                .map_err(|e| ClobError::request("build HTTP client", e))?,
+            url: url.to_string(),
+        })
+    }
+
+    /// Native (POL) balance of `address`, in POL
+    pub async fn balance(&self, address: &str) -> Result<f64> {
+        let wei = self.quantity("eth_getBalance", json!([address, "latest"])).await?;
+        Ok(wei as f64 / WEI_PER_POL)
+    }
+
+    /// Current legacy gas price, in gwei
+    pub async fn gas_price(&self) -> Result<f64> {
+        Ok(self.quantity("eth_gasPrice", json!([])).await? as f64 / WEI_PER_GWEI)
+    }
+
+    /// Sign a legacy (EIP-155) transaction calling `to` with `data` from `wallet`,
+    /// at the node's gas price, and broadcast it. Returns the transaction hash.
+    pub async fn send_transaction(&self, wallet: &Wallet, to: &str, data: &[u8], gas_limit: u64) -> Result<String> {
+        let nonce = self
+            .quantity("eth_getTransactionCount", json!([wallet.address, "pending"]))
+            .await?;
+        let gas_price = self.quantity("eth_gasPrice", json!([])).await?;
+        let to = decode_hex(to, 20)?;
+        let fields = |v: Vec<u8>, r: &[u8], s: &[u8]| {
+            rlp_list(&[
+                rlp_uint(nonce),
+                rlp_uint(gas_price),
+                rlp_uint(gas_limit as u128),
+                rlp_bytes(&to),
+                rlp_uint(0),
+                rlp_bytes(data),
+                v,
+                rlp_bytes(r),
+                rlp_bytes(s),
+            ])
+        };
+
+        let unsigned = fields(rlp_uint(CHAIN_ID as u128), &[], &[]);
+        let (signature, recovery) = wallet.sign_hash(&Keccak256::digest(&unsigned))?;
+        let v = CHAIN_ID as u128 * 2 + 35 + recovery as u128;
+        let raw = fields(rlp_uint(v), trim_zeros(&signature[..32]), trim_zeros(&signature[32..]));
+
+        let hash = self
+            .request("eth_sendRawTransaction", json!([format!("0x{}", hex::encode(raw))]))
+            .await?;
+        hash.as_str()
+            .map(str::to_string)
+            .ok_or_else(|| ClobError::decode("eth_sendRawTransaction", format!("returned {}", hash)))
+    }
+
+    /// Whether a transaction succeeded; None while it is still pending
+    pub async fn receipt_status(&self, tx_hash: &str) -> Result<Option<bool>> {
+        let receipt = self.request("eth_getTransactionReceipt", json!([tx_hash])).await?;
+        if receipt.is_null() {
+            return Ok(None);
+        }
+        Ok(Some(receipt["status"].as_str() == Some("0x1")))
+    }
+
+    /// A JSON-RPC call whose result is a hex quantity
+    async fn quantity(&self, method: &str, params: Value) -> Result<u128> {
+        let result = self.request(method, params).await?;
+        let hex = result
+            .as_str()
+            .ok_or_else(|| ClobError::decode(method, format!("returned {}", result)))?;
+        u128::from_str_radix(hex.trim_start_matches("0x"), 16)
+            .map_err(|_| ClobError::decode(method, format!("returned a bad quantity {:?}", hex)))
+    }
+
+    /// A JSON-RPC call; a null result comes back as `Value::Null`
+    async fn request(&self, method: &str, params: Value) -> Result<Value> {
+        let request = self
+            .client
+            .post(&self.url)
+            .json(&json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params }));
+        let resp: RpcResponse = ClobError::send_json(method, request).await?;
+        if let Some(e) = resp.error {
+            return Err(ClobError::Rejected {
+                context: method.to_string(),
+                reason: format!("{} ({})", e.message, e.code),
+            });
+        }
+        Ok(resp.result.unwrap_or(Value::Null))
+    }
+}
+
+/// Calldata for `redeemPositions` on the Conditional Tokens contract, paying out
+/// every one of a condition's `outcomes` against USDC.e
+pub fn redeem_positions_call(condition_id: &str, outcomes: usize) -> Result<Vec<u8>> {
+    let mut collateral = [0u8; 32];
+    collateral[12..].copy_from_slice(&decode_hex(USDC_ADDRESS, 20)?);
+
+    let mut data = Keccak256::digest(b"redeemPositions(address,bytes32,bytes32,uint256[])")[..4].to_vec();
+    data.extend_from_slice(&collateral);
+    // Top-level position: no parent collection
+    data.extend_from_slice(&[0u8; 32]);
+    data.extend_from_slice(&decode_hex(condition_id, 32)?);
+    // Offset of the index set array, past the four head words
+    data.extend_from_slice(&word(4 * 32));
+    data.extend(index_sets(outcomes)?);
+    Ok(data)
+}
+
+/// Calldata for `mergePositions` on the Conditional Tokens contract, turning
+/// `amount` (in collateral base units) complete sets of a condition's `outcomes`
+/// back into USDC.e
+pub fn merge_positions_call(condition_id: &str, outcomes: usize, amount: u64) -> Result<Vec<u8>> {
+    let mut collateral = [0u8; 32];
+    collateral[12..].copy_from_slice(&decode_hex(USDC_ADDRESS, 20)?);
+
+    let mut data = Keccak256::digest(b"mergePositions(address,bytes32,bytes32,uint256[],uint256)")[..4].to_vec();
+    data.extend_from_slice(&collateral);
+    data.extend_from_slice(&[0u8; 32]);
+    data.extend_from_slice(&decode_hex(condition_id, 32)?);
+    // Offset of the partition array, past the five head words
+    data.extend_from_slice(&word(5 * 32));
+    data.extend_from_slice(&word(amount));
+    data.extend(index_sets(outcomes)?);
+    Ok(data)
+}
+
+/// Calldata for `redeemPositions` on the neg-risk adapter, paying out a
+/// neg-risk leg's YES and NO tokens. `amounts` holds the wallet's balance of each,
+/// in collateral base units and in outcome order; the adapter pulls exactly that
+/// much, so the wallet must have approved it on the Conditional Tokens contract.
+pub fn neg_risk_redeem_call(condition_id: &str, amounts: &[u64]) -> Result<Vec<u8>> {
+    let mut data = Keccak256::digest(b"redeemPositions(bytes32,uint256[])")[..4].to_vec();
+    data.extend_from_slice(&decode_hex(condition_id, 32)?);
+    // Offset of the amount array, past the two head words
+    data.extend_from_slice(&word(2 * 32));
+    data.extend_from_slice(&word(amounts.len() as u64));
+    for amount in amounts {
+        data.extend_from_slice(&word(*amount));
+    }
+    Ok(data)
+}
+
+/// Calldata for `mergePositions` on the neg-risk adapter, turning `amount` (in
+/// collateral base units) YES+NO sets of a neg-risk leg back into USDC.e
+pub fn neg_risk_merge_call(condition_id: &str, amount: u64) -> Result<Vec<u8>> {
+    let mut data = Keccak256::digest(b"mergePositions(bytes32,uint256)")[..4].to_vec();
+    data.extend_from_slice(&decode_hex(condition_id, 32)?);
+    data.extend_from_slice(&word(amount));
+    Ok(data)
+}
+
+/// One ABI word holding `v`
+fn word(v: u64) -> [u8; 32] {
+    let mut w = [0u8; 32];
+    w[24..].copy_from_slice(&v.to_be_bytes());
+    w
+}
+
+/// A length-prefixed array of one index set per outcome: 0b001, 0b010, 0b100, ...
+fn index_sets(outcomes: usize) -> Result<Vec<u8>> {
+    if !(2..=64).contains(&outcomes) {
+        return Err(ClobError::Invalid(format!("a condition can't have {} outcomes", outcomes)));
+    }
+    let mut data = word(outcomes as u64).to_vec();
+    for i in 0..outcomes {
+        data.extend_from_slice(&word(1 << i));
+    }
+    Ok(data)
+}
+
+fn decode_hex(value: &str, len: usize) -> Result<Vec<u8>> {
+    let bytes =
+        hex::decode(value.trim_start_matches("0x")).map_err(|_| ClobError::Invalid(format!("{:?} is not hex", value)))?;
+    if bytes.len() != len {
+        return Err(ClobError::Invalid(format!("{:?} is {} bytes, expected {}", value, bytes.len(), len)));
+    }
+    Ok(bytes)
+}
+
+fn trim_zeros(bytes: &[u8]) -> &[u8] {
+    let start = bytes.iter().position(|b| *b != 0).unwrap_or(bytes.len());
+    &bytes[start..]
+}
+
+fn rlp_prefix(offset: u8, len: usize) -> Vec<u8> {
+    if len < 56 {
+        return vec![offset + len as u8];
+    }
+    let len_bytes = len.to_be_bytes();
+    let len_bytes = trim_zeros(&len_bytes);
+    let mut prefix = vec![offset + 55 + len_bytes.len() as u8];
+    prefix.extend_from_slice(len_bytes);
+    prefix
+}
+
+fn rlp_bytes(bytes: &[u8]) -> Vec<u8> {
+    if let [b] = bytes {
+        if *b < 0x80 {
+            return vec![*b];
+        }
+    }
+    let mut encoded = rlp_prefix(0x80, bytes.len());
+    encoded.extend_from_slice(bytes);
+    encoded
+}
+
+fn rlp_uint(value: u128) -> Vec<u8> {
+    rlp_bytes(trim_zeros(&value.to_be_bytes()))
+}
+
+fn rlp_list(items: &[Vec<u8>]) -> Vec<u8> {
+    let payload = items.concat();
+    let mut encoded = rlp_prefix(0xc0, payload.len());
+    encoded.extend(payload);
+    encoded
+}
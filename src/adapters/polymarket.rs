@@ -1,14 +1,22 @@
 use base64::Engine;
-use chrono::Utc;
-use eyre::{Result, WrapErr};
+use chrono::{DateTime, Utc};
 use hmac::{Hmac, Mac};
 use reqwest::Client;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
-use sha2::Sha256;
-use std::sync::Arc;
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use tracing::warn;
 
+use crate::adapters::venue::{Venue, VenueExperiment, VenueMode, VenueReport};
 use crate::config::Config;
-use crate::domain::{BookLevel, OrderBook, OrderType, Side};
+use crate::domain::{BookLevel, Market, MarketData, MarketId, Order, OrderBook, OrderId, OrderRules, OrderType, Side, TokenId};
+use crate::error::ClobError;
+use crate::wallet::Wallet;
+
+type Result<T, E = ClobError> = std::result::Result<T, E>;
 
 const BASE_URL: &str = "https://clob.polymarket.com";
 
@@ -18,12 +26,84 @@ type HmacSha256 = Hmac<Sha256>;
 pub struct PolymarketClient {
     client: Client,
     config: Arc<Config>,
+    wallet: Wallet,
+    venues: Arc<VenueExperiment>,
+    acks: Arc<Mutex<Acks>>,
+}
+
+/// Acknowledged submissions remembered for dedupe; far more than a retry window spans
+const ACK_MEMORY: usize = 1024;
+
+/// Exchange acks by client order ID, oldest dropped first
+#[derive(Default)]
+struct Acks {
+    by_client_id: HashMap<OrderId, OrderResponse>,
+    order: VecDeque<OrderId>,
+}
+
+impl Acks {
+    fn get(&self, client_order_id: &OrderId) -> Option<OrderResponse> {
+        self.by_client_id.get(client_order_id).cloned()
+    }
+
+    /// Only accepted orders are kept; a rejected one may be sent again
+    fn record(&mut self, client_order_id: &OrderId, resp: &OrderResponse) {
+        if !resp.success || self.by_client_id.contains_key(client_order_id) {
+            return;
+        }
+        self.by_client_id.insert(client_order_id.clone(), resp.clone());
+        self.order.push_back(client_order_id.clone());
+        while self.order.len() > ACK_MEMORY {
+            if let Some(oldest) = self.order.pop_front() {
+                self.by_client_id.remove(&oldest);
+            }
+        }
+    }
+}
+
+/// Most orders the CLOB takes in one batch request
+const MAX_BATCH_ORDERS: usize = 15;
+
+/// The CLOB expires GTD orders this long before their stated expiration, so it
+/// has to be added on top of the time we want them gone by
+const GTD_SECURITY_SECS: i64 = 60;
+
+/// One order as submitted, alone or as part of a batch
+#[derive(Debug, Clone)]
+pub struct OrderLeg {
+    /// Our ID for the order, stable across resubmissions of it
+    pub client_order_id: OrderId,
+    pub token_id: TokenId,
+    pub price: f64,
+    pub size: f64,
+    pub side: Side,
+    pub order_type: OrderType,
+    /// When a GTD order should be off the book; ignored for other types
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl From<&Order> for OrderLeg {
+    fn from(order: &Order) -> Self {
+        Self {
+            client_order_id: order.id.clone(),
+            token_id: order.token_id.clone(),
+            price: order.price,
+            size: order.size,
+            side: order.side.clone(),
+            order_type: order.order_type.clone(),
+            expires_at: order.expires_at,
+        }
+    }
 }
 
 #[derive(Debug, Serialize)]
 struct OrderRequest {
+    /// Derived from the client order ID. The CLOB identifies an order by the hash
+    /// of its fields, salt included, so a resend of one that already landed is
+    /// refused as a duplicate instead of trading twice.
+    salt: u64,
     #[serde(rename = "tokenID")]
-    token_id: String,
+    token_id: TokenId,
     price: f64,
     size: f64,
     side: String,
@@ -31,15 +111,59 @@ struct OrderRequest {
     order_type: String,
     #[serde(rename = "feeRateBps", skip_serializing_if = "Option::is_none")]
     fee_rate_bps: Option<u32>,
+    /// Unix seconds, GTD orders only
+    #[serde(skip_serializing_if = "Option::is_none")]
+    expiration: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct OrderResponse {
     pub success: bool,
     #[serde(rename = "orderID")]
     pub order_id: Option<String>,
     #[serde(rename = "errorMsg")]
     pub error_msg: Option<String>,
+    /// "matched", "live", "delayed" or "unmatched"
+    pub status: Option<String>,
+    /// What we gave up when matched immediately: shares for a SELL, USDC for a BUY
+    #[serde(rename = "makingAmount")]
+    pub making_amount: Option<String>,
+    /// What we received when matched immediately: USDC for a SELL, shares for a BUY
+    #[serde(rename = "takingAmount")]
+    pub taking_amount: Option<String>,
+}
+
+impl OrderResponse {
+    /// Average execution price of the immediately matched part, if any
+    pub fn fill_price(&self, side: &Side) -> Option<f64> {
+        let making: f64 = self.making_amount.as_deref()?.parse().ok()?;
+        let taking: f64 = self.taking_amount.as_deref()?.parse().ok()?;
+        let (usdc, shares) = match side {
+            Side::Sell => (taking, making),
+            Side::Buy => (making, taking),
+        };
+        (shares > 0.0).then(|| usdc / shares)
+    }
+
+    /// Shares matched on arrival, if the response reports any
+    pub fn filled_shares(&self, side: &Side) -> Option<f64> {
+        let shares = match side {
+            Side::Sell => self.making_amount.as_deref()?,
+            Side::Buy => self.taking_amount.as_deref()?,
+        };
+        shares.parse().ok()
+    }
+}
+
+/// What the CLOB made of a multi-order cancel
+#[derive(Debug, Default, Deserialize)]
+pub struct CancelResponse {
+    /// Exchange IDs of the orders it cancelled
+    #[serde(default)]
+    pub canceled: Vec<String>,
+    /// Exchange ID → why it was left alone (typically already filled or gone)
+    #[serde(default)]
+    pub not_canceled: HashMap<String, String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -64,34 +188,94 @@ struct OrderBookLevel {
     pub size: String,
 }
 
+#[derive(Debug, Deserialize)]
+struct PriceHistoryResponse {
+    pub history: Vec<PricePoint>,
+}
+
+/// One point from /prices-history: unix seconds and price
+#[derive(Debug, Clone, Deserialize)]
+pub struct PricePoint {
+    pub t: i64,
+    pub p: f64,
+}
+
+/// An order as the CLOB reports it
 #[derive(Debug, Deserialize)]
 pub struct OpenOrder {
     pub id: String,
-    #[serde(rename = "tokenID")]
-    pub token_id: String,
+    /// "LIVE", "MATCHED" or "CANCELED"
+    #[serde(default)]
+    pub status: String,
+    #[serde(rename = "asset_id")]
+    pub token_id: TokenId,
     pub price: String,
-    pub size: String,
+    pub original_size: String,
+    #[serde(default)]
+    pub size_matched: String,
     pub side: String,
 }
 
+impl OpenOrder {
+    /// Shares matched so far
+    pub fn matched(&self) -> f64 {
+        self.size_matched.parse().unwrap_or(0.0)
+    }
+
+    /// Fully matched, whatever the status string says
+    pub fn is_filled(&self) -> bool {
+        let original: f64 = self.original_size.parse().unwrap_or(0.0);
+        self.status.eq_ignore_ascii_case("matched") || (original > 0.0 && self.matched() >= original)
+    }
+}
+
+/// The fields of a CLOB market that constrain orders on it
+#[derive(Debug, Deserialize)]
+struct ClobMarket {
+    minimum_tick_size: f64,
+    minimum_order_size: f64,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenOrdersResponse {
+    data: Vec<OpenOrder>,
+}
+
 impl PolymarketClient {
     pub fn new(config: Arc<Config>) -> Result<Self> {
+        let wallet = Wallet::from_private_key(&config.private_key)?;
         let client = Client::builder()
             .pool_max_idle_per_host(5)
             .timeout(std::time::Duration::from_secs(30))
             .build()
-            .wrap_err("Failed to build HTTP client")?;
+            .map_err(|e| ClobError::request("build HTTP client", e))?;
+
+        let venues = if config.execution_venues.is_empty() {
+            vec![Venue::new("default", BASE_URL, VenueMode::Pooled)?]
+        } else {
+            config
+                .execution_venues
+                .iter()
+                .map(|spec| Venue::from_spec(spec))
+                .collect::<Result<Vec<_>>>()?
+        };
 
-        Ok(Self { client, config })
+        Ok(Self {
+            client,
+            config,
+            wallet,
+            venues: VenueExperiment::new(venues),
+            acks: Arc::default(),
+        })
     }
 
     fn sign(&self, timestamp: &str, method: &str, path: &str, body: &str) -> Result<String> {
         let message = format!("{}{}{}{}", timestamp, method, path, body);
         let secret_bytes = base64::engine::general_purpose::STANDARD
             .decode(&self.config.polymarket_secret)
-            .wrap_err("Failed to decode API secret")?;
+            .map_err(|_| ClobError::auth("POLYMARKET_SECRET", "not valid base64"))?;
         let mut mac = HmacSha256::new_from_slice(&secret_bytes)
-            .wrap_err("Invalid HMAC key")?;
+            .map_err(|_| ClobError::auth("POLYMARKET_SECRET", "not usable as an HMAC key"))?;
         mac.update(message.as_bytes());
         let result = mac.finalize();
         Ok(base64::engine::general_purpose::STANDARD.encode(result.into_bytes()))
@@ -107,7 +291,7 @@ impl PolymarketClient {
         let signature = self.sign(&timestamp, method, path, body)?;
 
         Ok(vec![
-            ("POLY-ADDRESS".into(), self.config.private_key.clone()),
+            ("POLY-ADDRESS".into(), self.wallet.address.clone()),
             ("POLY-SIGNATURE".into(), signature),
             ("POLY-TIMESTAMP".into(), timestamp),
             ("POLY-API-KEY".into(), self.config.polymarket_api_key.clone()),
@@ -118,57 +302,78 @@ impl PolymarketClient {
         ])
     }
 
-    pub async fn get_price(&self, token_id: &str) -> Result<f64> {
+    /// Checksummed address of the signing wallet
+    pub fn address(&self) -> &str {
+        &self.wallet.address
+    }
+
+    /// Signed probe confirming the API key belongs to the configured wallet.
+    /// Errors when the exchange rejects the credentials; an unreachable exchange
+    /// is only logged so startup isn't blocked by a network blip.
+    pub async fn verify_credentials(&self) -> Result<()> {
+        let path = "/auth/api-keys";
+        let headers = self.auth_headers("GET", path, "")?;
+        let url = format!("{}{}", BASE_URL, path);
+
+        let mut builder = self.client.get(&url);
+        for (k, v) in headers {
+            builder = builder.header(&k, &v);
+        }
+
+        let resp = match builder.send().await {
+            Ok(r) => r,
+            Err(e) => {
+                warn!("Credential probe could not reach the CLOB, skipping: {:?}", e);
+                return Ok(());
+            }
+        };
+        let status = resp.status();
+        let body = resp.text().await.unwrap_or_default();
+        if !status.is_success() {
+            return Err(ClobError::auth(
+                path,
+                format!(
+                    "CLOB rejected API credentials for wallet {} ({}): {} — check that POLYMARKET_API_KEY was created by this PRIVATE_KEY",
+                    self.wallet.address, status, body
+                ),
+            ));
+        }
+        if !body.contains(&self.config.polymarket_api_key) {
+            return Err(ClobError::auth(
+                path,
+                format!("POLYMARKET_API_KEY is not among the API keys of wallet {}", self.wallet.address),
+            ));
+        }
+        Ok(())
+    }
+
+    pub async fn get_price(&self, token_id: &TokenId) -> Result<f64> {
         let path = format!("/price?token_id={}", token_id);
         let url = format!("{}{}", BASE_URL, path);
 
-        let resp: PriceResponse = self
-            .client
-            .get(&url)
-            .send()
-            .await
-            .wrap_err("get_price request failed")?
-            .json()
-            .await
-            .wrap_err("get_price parse failed")?;
+        let resp: PriceResponse = ClobError::send_json("get_price", self.client.get(&url)).await?;
 
         resp.price
-            .ok_or_else(|| eyre::eyre!("No price returned"))
-            .and_then(|p| p.parse::<f64>().map_err(|e| eyre::eyre!(e)))
+            .ok_or_else(|| ClobError::decode("get_price", "no price returned"))
+            .and_then(|p| p.parse::<f64>().map_err(|e| ClobError::decode("get_price", e)))
     }
 
-    pub async fn get_midpoint(&self, token_id: &str) -> Result<f64> {
+    pub async fn get_midpoint(&self, token_id: &TokenId) -> Result<f64> {
         let path = format!("/midpoint?token_id={}", token_id);
         let url = format!("{}{}", BASE_URL, path);
 
-        let resp: MidpointResponse = self
-            .client
-            .get(&url)
-            .send()
-            .await
-            .wrap_err("get_midpoint request failed")?
-            .json()
-            .await
-            .wrap_err("get_midpoint parse failed")?;
+        let resp: MidpointResponse = ClobError::send_json("get_midpoint", self.client.get(&url)).await?;
 
         resp.mid
-            .ok_or_else(|| eyre::eyre!("No midpoint returned"))
-            .and_then(|p| p.parse::<f64>().map_err(|e| eyre::eyre!(e)))
+            .ok_or_else(|| ClobError::decode("get_midpoint", "no midpoint returned"))
+            .and_then(|p| p.parse::<f64>().map_err(|e| ClobError::decode("get_midpoint", e)))
     }
 
-    pub async fn get_orderbook(&self, token_id: &str) -> Result<OrderBook> {
+    pub async fn get_orderbook(&self, token_id: &TokenId) -> Result<OrderBook> {
         let path = format!("/book?token_id={}", token_id);
         let url = format!("{}{}", BASE_URL, path);
 
-        let resp: OrderBookResponse = self
-            .client
-            .get(&url)
-            .send()
-            .await
-            .wrap_err("get_orderbook request failed")?
-            .json()
-            .await
-            .wrap_err("get_orderbook parse failed")?;
+        let resp: OrderBookResponse = ClobError::send_json("get_orderbook", self.client.get(&url)).await?;
 
         let parse_levels = |levels: Option<Vec<OrderBookLevel>>| -> Vec<BookLevel> {
             levels
@@ -190,52 +395,94 @@ impl PolymarketClient {
         })
     }
 
-    pub async fn post_order(
+    /// Historical prices for a token between two unix timestamps,
+    /// sampled every `fidelity_mins` minutes
+    pub async fn get_price_history(
         &self,
-        token_id: &str,
-        price: f64,
-        size: f64,
-        side: Side,
-        order_type: OrderType,
-    ) -> Result<OrderResponse> {
-        let path = "/order";
-        let side_str = match side {
-            Side::Buy => "BUY",
-            Side::Sell => "SELL",
-        };
-        let ot_str = match order_type {
-            OrderType::GTC => "GTC",
-            OrderType::GTD => "GTD",
-            OrderType::FOK => "FOK",
-        };
+        token_id: &TokenId,
+        start_ts: i64,
+        end_ts: i64,
+        fidelity_mins: u32,
+    ) -> Result<Vec<PricePoint>> {
+        let path = format!(
+            "/prices-history?market={}&startTs={}&endTs={}&fidelity={}",
+            token_id, start_ts, end_ts, fidelity_mins
+        );
+        let url = format!("{}{}", BASE_URL, path);
 
-        let req = OrderRequest {
-            token_id: token_id.to_string(),
-            price,
-            size,
-            side: side_str.to_string(),
-            order_type: ot_str.to_string(),
-            fee_rate_bps: None,
-        };
+        let resp: PriceHistoryResponse = ClobError::send_json("get_price_history", self.client.get(&url)).await?;
+
+        Ok(resp.history)
+    }
+
+    /// Submit one order. Resubmitting a client order ID the exchange already
+    /// accepted returns that ack instead of posting again.
+    pub async fn post_order(&self, leg: &OrderLeg) -> Result<OrderResponse> {
+        if let Some(ack) = self.acked(leg) {
+            return Ok(ack);
+        }
+        let req = order_request(leg);
+        let resp: OrderResponse = self.post_signed("/order", encode(&req)?).await?;
+        self.acks.lock().unwrap().record(&leg.client_order_id, &resp);
+        Ok(resp)
+    }
+
+    /// Submit several orders in one authenticated request per batch of up to 15,
+    /// so they reach the book together. Responses come back in the order of `legs`;
+    /// legs already accepted under their client order ID aren't sent again.
+    pub async fn post_orders(&self, legs: &[OrderLeg]) -> Result<Vec<OrderResponse>> {
+        let mut responses: Vec<Option<OrderResponse>> = legs.iter().map(|leg| self.acked(leg)).collect();
+        let fresh: Vec<usize> = (0..legs.len()).filter(|&i| responses[i].is_none()).collect();
+        for batch in fresh.chunks(MAX_BATCH_ORDERS) {
+            let reqs: Vec<OrderRequest> = batch.iter().map(|&i| order_request(&legs[i])).collect();
+            let batch_responses: Vec<OrderResponse> = self.post_signed("/orders", encode(&reqs)?).await?;
+            if batch_responses.len() != batch.len() {
+                return Err(ClobError::decode(
+                    "post_orders",
+                    format!("sent {} orders but got {} responses", batch.len(), batch_responses.len()),
+                ));
+            }
+            let mut acks = self.acks.lock().unwrap();
+            for (&i, resp) in batch.iter().zip(batch_responses) {
+                acks.record(&legs[i].client_order_id, &resp);
+                responses[i] = Some(resp);
+            }
+        }
+        Ok(responses.into_iter().flatten().collect())
+    }
 
-        let body = serde_json::to_string(&req)?;
+    /// The exchange's earlier ack of this leg, if it already accepted it
+    fn acked(&self, leg: &OrderLeg) -> Option<OrderResponse> {
+        let ack = self.acks.lock().unwrap().get(&leg.client_order_id)?;
+        warn!(
+            "Order {} already accepted as {}; not resubmitting",
+            leg.client_order_id,
+            ack.order_id.as_deref().unwrap_or("?")
+        );
+        Some(ack)
+    }
+
+    /// POST an authenticated JSON body through the next execution venue, recording its latency
+    async fn post_signed<T: DeserializeOwned>(&self, path: &str, body: String) -> Result<T> {
         let headers = self.auth_headers("POST", path, &body)?;
-        let url = format!("{}{}", BASE_URL, path);
+        let venue = self.venues.pick();
+        let url = format!("{}{}", venue.base_url, path);
 
-        let mut builder = self.client.post(&url).body(body.clone()).header("Content-Type", "application/json");
+        let mut builder = venue.client.post(&url).body(body).header("Content-Type", "application/json");
         for (k, v) in headers {
             builder = builder.header(&k, &v);
         }
 
-        let resp: OrderResponse = builder
-            .send()
-            .await
-            .wrap_err("post_order request failed")?
-            .json()
-            .await
-            .wrap_err("post_order parse failed")?;
+        let started = Instant::now();
+        let result = ClobError::send_json(&format!("POST {} via {}", path, venue.name), builder).await;
+        self.venues.record(&venue.name, started.elapsed(), result.is_ok());
 
-        Ok(resp)
+        result
+    }
+
+    /// Ack latency per execution venue for the A/B comparison
+    pub fn venue_report(&self) -> Vec<VenueReport> {
+        self.venues.report()
     }
 
     pub async fn cancel_order(&self, order_id: &str) -> Result<bool> {
@@ -249,7 +496,7 @@ impl PolymarketClient {
             builder = builder.header(&k, &v);
         }
 
-        let status = builder.send().await.wrap_err("cancel_order failed")?.status();
+        let status = builder.send().await.map_err(|e| ClobError::request("cancel_order", e))?.status();
         Ok(status.is_success())
     }
 
@@ -264,12 +511,44 @@ impl PolymarketClient {
             builder = builder.header(&k, &v);
         }
 
-        let status = builder.send().await.wrap_err("cancel_all failed")?.status();
+        let status = builder.send().await.map_err(|e| ClobError::request("cancel_all", e))?.status();
         Ok(status.is_success())
     }
 
+    /// Cancel several orders by exchange ID in one request
+    pub async fn cancel_orders(&self, order_ids: &[String]) -> Result<CancelResponse> {
+        if order_ids.is_empty() {
+            return Ok(CancelResponse::default());
+        }
+        self.delete_signed("/orders", encode(order_ids)?).await
+    }
+
+    /// Cancel every resting order in a market, or only those on one of its tokens
+    pub async fn cancel_market_orders(&self, market_id: &MarketId, token_id: Option<&TokenId>) -> Result<CancelResponse> {
+        let body = serde_json::json!({
+            "market": market_id,
+            "asset_id": token_id.map(TokenId::as_str).unwrap_or_default(),
+        })
+        .to_string();
+        self.delete_signed("/cancel-market-orders", body).await
+    }
+
+    /// DELETE with an authenticated JSON body, failing on a non-success status
+    async fn delete_signed<T: DeserializeOwned>(&self, path: &str, body: String) -> Result<T> {
+        let headers = self.auth_headers("DELETE", path, &body)?;
+        let url = format!("{}{}", BASE_URL, path);
+
+        let mut builder = self.client.delete(&url).body(body).header("Content-Type", "application/json");
+        for (k, v) in headers {
+            builder = builder.header(&k, &v);
+        }
+
+        ClobError::send_json(&format!("DELETE {}", path), builder).await
+    }
+
+    /// Our resting orders on the CLOB (first page, up to 500)
     pub async fn get_open_orders(&self) -> Result<Vec<OpenOrder>> {
-        let path = "/orders";
+        let path = "/data/orders";
         let headers = self.auth_headers("GET", path, "")?;
         let url = format!("{}{}", BASE_URL, path);
 
@@ -278,14 +557,113 @@ impl PolymarketClient {
             builder = builder.header(&k, &v);
         }
 
-        let orders: Vec<OpenOrder> = builder
-            .send()
-            .await
-            .wrap_err("get_open_orders failed")?
+        let resp: OpenOrdersResponse = ClobError::send_json("get_open_orders", builder).await?;
+
+        Ok(resp.data)
+    }
+
+    /// One order by exchange ID, in any status; None when the CLOB doesn't know it
+    /// Current tick size and minimum order size of a market, by condition ID
+    pub async fn get_order_rules(&self, condition_id: &MarketId) -> Result<OrderRules> {
+        let url = format!("{}/markets/{}", BASE_URL, condition_id);
+        let market: ClobMarket = ClobError::send_json("get_order_rules", self.client.get(&url)).await?;
+        Ok(OrderRules {
+            tick_size: market.minimum_tick_size,
+            min_size: market.minimum_order_size,
+        })
+    }
+
+    pub async fn get_order(&self, order_id: &str) -> Result<Option<OpenOrder>> {
+        let path = format!("/data/order/{}", order_id);
+        let headers = self.auth_headers("GET", &path, "")?;
+        let url = format!("{}{}", BASE_URL, path);
+
+        let mut builder = self.client.get(&url);
+        for (k, v) in headers {
+            builder = builder.header(&k, &v);
+        }
+
+        let resp = builder.send().await.map_err(|e| ClobError::request("get_order", e))?;
+        if resp.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        // Unknown IDs come back as 200 with a null body
+        let order: Option<OpenOrder> = ClobError::check("get_order", resp)
+            .await?
             .json()
             .await
-            .wrap_err("get_open_orders parse failed")?;
+            .map_err(|e| ClobError::request("get_order", e))?;
+
+        Ok(order)
+    }
+
+    /// REST snapshot of midpoints and books for every token in `markets`,
+    /// as MarketData events ready to seed the aggregator before WS data arrives
+    pub async fn snapshot(&self, markets: &[Market]) -> Vec<MarketData> {
+        let requests = markets.iter().flat_map(|m| {
+            m.outcomes.iter().map(move |t| async move {
+                let mut events = Vec::new();
+                match self.get_midpoint(&t.token_id).await {
+                    Ok(price) => events.push(MarketData::PolymarketPrice {
+                        market_id: m.id.clone(),
+                        token_id: t.token_id.clone(),
+                        price,
+                        timestamp: Utc::now(),
+                    }),
+                    Err(e) => warn!("Bootstrap midpoint failed for {}: {:?}", t.token_id, e),
+                }
+                match self.get_orderbook(&t.token_id).await {
+                    Ok(book) => events.push(MarketData::PolymarketOrderBook {
+                        market_id: m.id.clone(),
+                        token_id: t.token_id.clone(),
+                        book,
+                    }),
+                    Err(e) => warn!("Bootstrap book failed for {}: {:?}", t.token_id, e),
+                }
+                events
+            })
+        });
+
+        futures_util::future::join_all(requests)
+            .await
+            .into_iter()
+            .flatten()
+            .collect()
+    }
+}
 
-        Ok(orders)
+fn order_request(leg: &OrderLeg) -> OrderRequest {
+    let order_type = match leg.order_type {
+        OrderType::GTC => "GTC",
+        OrderType::GTD => "GTD",
+        OrderType::FOK => "FOK",
+        OrderType::FAK => "FAK",
+    };
+    OrderRequest {
+        salt: client_salt(&leg.client_order_id),
+        token_id: leg.token_id.clone(),
+        price: leg.price,
+        size: leg.size,
+        side: leg.side.to_string(),
+        order_type: order_type.to_string(),
+        fee_rate_bps: None,
+        expiration: leg
+            .expires_at
+            .filter(|_| leg.order_type == OrderType::GTD)
+            .map(|t| (t.timestamp() + GTD_SECURITY_SECS).to_string()),
     }
 }
+
+/// JSON request body
+fn encode(body: &(impl Serialize + ?Sized)) -> Result<String> {
+    serde_json::to_string(body).map_err(|e| ClobError::Invalid(format!("Failed to encode request: {}", e)))
+}
+
+/// A stable salt for a client order ID, kept within 53 bits so it survives JSON
+/// number handling on the other end
+fn client_salt(client_order_id: &str) -> u64 {
+    let digest = Sha256::digest(client_order_id.as_bytes());
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&digest[..8]);
+    u64::from_be_bytes(bytes) & ((1 << 53) - 1)
+}
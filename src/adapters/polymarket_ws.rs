@@ -1,22 +1,32 @@
 use chrono::Utc;
-use eyre::Result;
 use futures_util::{SinkExt, StreamExt};
 use serde::Deserialize;
-use tokio::sync::broadcast;
 use tokio_tungstenite::{connect_async, tungstenite::Message};
 use tracing::{error, info, warn};
 
-use crate::domain::{BookLevel, MarketData, OrderBook};
+use crate::bus::EventBus;
+use crate::domain::{BookLevel, FeedStatus, MarketData, MarketId, OrderBook, Side, TokenId};
+use crate::error::FeedError;
+use crate::watchdog::Heartbeat;
+
+type Result<T, E = FeedError> = std::result::Result<T, E>;
 
 const WS_URL: &str = "wss://ws-subscriptions-clob.polymarket.com/ws/market";
+/// Feed name in errors and logs
+const FEED: &str = "polymarket";
 
 #[derive(Debug, Deserialize)]
 struct WsMessage {
     #[serde(rename = "type")]
     msg_type: Option<String>,
-    market: Option<String>,
-    asset_id: Option<String>,
+    /// Trade prints name their kind here rather than in `type`
+    event_type: Option<String>,
+    market: Option<MarketId>,
+    asset_id: Option<TokenId>,
     price: Option<String>,
+    /// Aggressor side and shares, on trade prints
+    side: Option<String>,
+    size: Option<String>,
     bids: Option<Vec<WsLevel>>,
     asks: Option<Vec<WsLevel>>,
 }
@@ -27,14 +37,26 @@ struct WsLevel {
     size: String,
 }
 
+#[derive(Clone)]
 pub struct PolymarketWsFeed {
-    tx: broadcast::Sender<MarketData>,
-    market_ids: Vec<String>,
+    tx: EventBus,
+    market_ids: Vec<MarketId>,
+    heartbeat: Heartbeat,
 }
 
 impl PolymarketWsFeed {
-    pub fn new(tx: broadcast::Sender<MarketData>, market_ids: Vec<String>) -> Self {
-        Self { tx, market_ids }
+    pub fn new(tx: EventBus, market_ids: Vec<MarketId>) -> Self {
+        Self {
+            tx,
+            market_ids,
+            heartbeat: Heartbeat::default(),
+        }
+    }
+
+    /// Beat on every message, so the watchdog can tell a dead connection from a quiet one
+    pub fn with_heartbeat(mut self, heartbeat: Heartbeat) -> Self {
+        self.heartbeat = heartbeat;
+        self
     }
 
     pub async fn run(self) -> Result<()> {
@@ -44,10 +66,12 @@ impl PolymarketWsFeed {
             match self.connect_and_listen().await {
                 Ok(()) => {
                     info!("Polymarket WS disconnected cleanly");
+                    self.tx.publish(FeedStatus::disconnected(FEED, "closed"));
                     backoff_ms = 1000;
                 }
                 Err(e) => {
                     error!("Polymarket WS error: {:?}", e);
+                    self.tx.publish(FeedStatus::disconnected(FEED, e.to_string()));
                 }
             }
 
@@ -58,10 +82,11 @@ impl PolymarketWsFeed {
     }
 
     async fn connect_and_listen(&self) -> Result<()> {
-        let (ws_stream, _) = connect_async(WS_URL).await?;
+        let (ws_stream, _) = connect_async(WS_URL).await.map_err(|e| FeedError::websocket(FEED, e))?;
         let (mut write, mut read) = ws_stream.split();
 
         info!("Connected to Polymarket WS");
+        self.tx.publish(FeedStatus::connected(FEED));
 
         // Subscribe to markets
         for market_id in &self.market_ids {
@@ -70,10 +95,14 @@ impl PolymarketWsFeed {
                 "market": market_id,
                 "channel": "market"
             });
-            write.send(Message::Text(sub.to_string().into())).await?;
+            write
+                .send(Message::Text(sub.to_string()))
+                .await
+                .map_err(|e| FeedError::websocket(FEED, e))?;
         }
 
         while let Some(msg) = read.next().await {
+            self.heartbeat.beat();
             match msg {
                 Ok(Message::Text(text)) => {
                     if let Err(e) = self.handle_message(&text) {
@@ -99,16 +128,16 @@ impl PolymarketWsFeed {
     }
 
     fn handle_message(&self, text: &str) -> Result<()> {
-        let msg: WsMessage = serde_json::from_str(text)?;
+        let msg: WsMessage = serde_json::from_str(text).map_err(|e| FeedError::decode(FEED, e))?;
 
         let market_id = msg.market.unwrap_or_default();
         let asset_id = msg.asset_id.unwrap_or_default();
 
-        match msg.msg_type.as_deref() {
+        match msg.msg_type.as_deref().or(msg.event_type.as_deref()) {
             Some("price") => {
                 if let Some(price_str) = msg.price {
                     if let Ok(price) = price_str.parse::<f64>() {
-                        let _ = self.tx.send(MarketData::PolymarketPrice {
+                        self.tx.publish(MarketData::PolymarketPrice {
                             market_id,
                             token_id: asset_id,
                             price,
@@ -117,6 +146,27 @@ impl PolymarketWsFeed {
                     }
                 }
             }
+            Some("last_trade_price") => {
+                let side = match msg.side.as_deref() {
+                    Some(s) if s.eq_ignore_ascii_case("buy") => Side::Buy,
+                    Some(s) if s.eq_ignore_ascii_case("sell") => Side::Sell,
+                    _ => return Ok(()),
+                };
+                let (Some(price), Some(size)) = (
+                    msg.price.and_then(|p| p.parse::<f64>().ok()),
+                    msg.size.and_then(|s| s.parse::<f64>().ok()),
+                ) else {
+                    return Ok(());
+                };
+                self.tx.publish(MarketData::PolymarketTrade {
+                    market_id,
+                    token_id: asset_id,
+                    price,
+                    size,
+                    side,
+                    timestamp: Utc::now(),
+                });
+            }
             Some("book") => {
                 let parse_levels = |levels: Option<Vec<WsLevel>>| -> Vec<BookLevel> {
                     levels
@@ -137,7 +187,7 @@ impl PolymarketWsFeed {
                     timestamp: Utc::now(),
                 };
 
-                let _ = self.tx.send(MarketData::PolymarketOrderBook {
+                self.tx.publish(MarketData::PolymarketOrderBook {
                     market_id,
                     token_id: asset_id,
                     book,
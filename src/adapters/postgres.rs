@@ -0,0 +1,868 @@
+use chrono::{DateTime, Utc};
+use sqlx::postgres::{PgExecutor, PgPool, PgPoolOptions};
+use std::path::PathBuf;
+
+use super::database::{assemble_markets, ARCHIVED_TABLES, CandleRow, ClosedTradeRow, CooldownRow, ExposureRow, FlowAlertRow, MarketRow, MarketTokenRow, OrderRow, PnlRow, PositionRow, ProbableFillRow, RedemptionRow, RiskEventRow, SignalRecordRow, Storage, TradeRow, VariantReportRow, Write};
+use crate::domain::{
+    Candle, ClosedTrade, ExposureSnapshot, FlowAlert, Market, MarketCooldown, MarketId, Order, OrderId, OrderStatus,
+    PnlSnapshot, Position, ProbableFill, Redemption, RiskEvent, SignalRecord, TokenId, Trade, VariantReport,
+};
+use crate::error::DbError;
+
+type Result<T, E = DbError> = std::result::Result<T, E>;
+
+/// Managed Postgres store; lets several dashboards read while the bot writes.
+/// Schema and column encodings mirror the SQLite backend (timestamps as RFC 3339 text).
+pub struct PostgresStorage {
+    pool: PgPool,
+}
+
+impl PostgresStorage {
+    pub async fn new(url: &str) -> Result<Self> {
+        let pool = PgPoolOptions::new()
+            .max_connections(10)
+            .connect(url)
+            .await
+            .map_err(DbError::Connect)?;
+
+        let db = Self { pool };
+        db.run_migrations().await?;
+        Ok(db)
+    }
+
+    /// Apply pending migrations from `migrations/postgres`
+    async fn run_migrations(&self) -> Result<()> {
+        sqlx::migrate!("./migrations/postgres")
+            .run(&self.pool)
+            .await?;
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl Storage for PostgresStorage {
+    fn backend(&self) -> &'static str {
+        "postgres"
+    }
+
+    async fn schema_version(&self) -> Result<i64> {
+        let (version,): (Option<i64>,) =
+            sqlx::query_as("SELECT MAX(version) FROM _sqlx_migrations WHERE success")
+                .fetch_one(&self.pool)
+                .await?;
+        Ok(version.unwrap_or(0))
+    }
+
+    // --- Trades ---
+
+    async fn insert_trade(&self, trade: &Trade) -> Result<()> {
+        insert_trade(&self.pool, trade).await
+    }
+
+    async fn get_recent_trades(&self, limit: i64) -> Result<Vec<Trade>> {
+        let rows = sqlx::query_as::<_, TradeRow>(
+            "SELECT id, order_id, market_id, side, price, size, fee, timestamp FROM trades ORDER BY timestamp DESC LIMIT $1",
+        )
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(|r| r.into()).collect())
+    }
+
+    async fn get_trades_since(&self, since: DateTime<Utc>) -> Result<Vec<Trade>> {
+        let rows = sqlx::query_as::<_, TradeRow>(
+            "SELECT id, order_id, market_id, side, price, size, fee, timestamp FROM trades WHERE timestamp >= $1 ORDER BY timestamp ASC",
+        )
+        .bind(since.to_rfc3339())
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows.into_iter().map(|r| r.into()).collect())
+    }
+
+    async fn insert_closed_trade(&self, closed: &ClosedTrade) -> Result<()> {
+        insert_closed_trade(&self.pool, closed).await
+    }
+
+    async fn get_closed_trades(&self, since: DateTime<Utc>) -> Result<Vec<ClosedTrade>> {
+        let rows = sqlx::query_as::<_, ClosedTradeRow>(
+            "SELECT id, strategy, market_id, token_id, side, size, entry_price, exit_price, pnl, closed_at
+             FROM closed_trades WHERE closed_at >= $1 ORDER BY closed_at ASC",
+        )
+        .bind(since.to_rfc3339())
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows.into_iter().filter_map(ClosedTradeRow::into_closed_trade).collect())
+    }
+
+    async fn get_realized_pnl(&self) -> Result<f64> {
+        let row: (Option<f64>,) = sqlx::query_as("SELECT SUM(pnl) FROM closed_trades")
+            .fetch_one(&self.pool)
+            .await?;
+        Ok(row.0.unwrap_or(0.0))
+    }
+
+    // --- Positions ---
+
+    async fn upsert_position(&self, pos: &Position) -> Result<()> {
+        upsert_position(&self.pool, pos).await
+    }
+
+    async fn get_positions(&self) -> Result<Vec<Position>> {
+        let rows = sqlx::query_as::<_, PositionRow>(
+            "SELECT market_id, token_id, side, size, avg_price, current_price, pnl, strategy FROM positions WHERE size > 0",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows.into_iter().map(|r| r.into()).collect())
+    }
+
+    async fn delete_position(&self, market_id: &MarketId, token_id: &TokenId) -> Result<()> {
+        delete_position(&self.pool, market_id, token_id).await
+    }
+
+    // --- Orders ---
+
+    async fn insert_order(&self, order: &Order) -> Result<()> {
+        insert_order(&self.pool, order).await
+    }
+
+    async fn update_order_status(&self, order_id: &OrderId, status: &OrderStatus) -> Result<()> {
+        update_order_status(&self.pool, order_id, status).await
+    }
+
+    async fn set_order_remote_id(&self, order_id: &OrderId, remote_id: &str) -> Result<()> {
+        set_order_remote_id(&self.pool, order_id, remote_id).await
+    }
+
+    async fn get_open_orders(&self) -> Result<Vec<Order>> {
+        let rows = sqlx::query_as::<_, OrderRow>(
+            "SELECT id, market_id, side, token_id, price, size, order_type, status, created_at, remote_id, strategy, replaces, expires_at FROM orders WHERE status IN ('Pending', 'Open')",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows.into_iter().map(|r| r.into()).collect())
+    }
+
+    // --- Probable fills (public tape heuristic) ---
+
+    async fn insert_probable_fill(&self, fill: &ProbableFill) -> Result<bool> {
+        let result = sqlx::query(
+            "INSERT INTO probable_fills (order_id, trade_key, token_id, price, size, trade_timestamp, detected_at)
+             VALUES ($1, $2, $3, $4, $5, $6, $7)
+             ON CONFLICT DO NOTHING",
+        )
+        .bind(&fill.order_id)
+        .bind(&fill.trade_key)
+        .bind(&fill.token_id)
+        .bind(fill.price)
+        .bind(fill.size)
+        .bind(fill.trade_timestamp.to_rfc3339())
+        .bind(fill.detected_at.to_rfc3339())
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn get_unconfirmed_fills(&self) -> Result<Vec<ProbableFill>> {
+        let rows = sqlx::query_as::<_, ProbableFillRow>(
+            "SELECT order_id, trade_key, token_id, price, size, trade_timestamp, detected_at FROM probable_fills WHERE confirmed IS NULL ORDER BY detected_at ASC",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows.into_iter().map(|r| r.into()).collect())
+    }
+
+    // --- PnL ---
+
+    async fn record_pnl_snapshot(&self, snapshot: &PnlSnapshot) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO pnl_snapshots (timestamp, bankroll, pnl_total, realized_pnl, unrealized_pnl)
+             VALUES ($1, $2, $3, $4, $5)",
+        )
+        .bind(snapshot.timestamp.to_rfc3339())
+        .bind(snapshot.bankroll)
+        .bind(snapshot.pnl_total)
+        .bind(snapshot.realized_pnl)
+        .bind(snapshot.unrealized_pnl)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn get_pnl_history(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        bucket_secs: Option<i64>,
+    ) -> Result<Vec<PnlSnapshot>> {
+        // Bucketing keeps the last snapshot of each interval, so a chart over months
+        // of 5-minute snapshots stays a few hundred points
+        let query = match bucket_secs {
+            Some(secs) => sqlx::query_as::<_, PnlRow>(
+                "SELECT timestamp, bankroll, pnl_total, realized_pnl, unrealized_pnl FROM (
+                    SELECT timestamp, bankroll, pnl_total, realized_pnl, unrealized_pnl, ROW_NUMBER() OVER (
+                        PARTITION BY FLOOR(EXTRACT(EPOCH FROM timestamp::timestamptz) / $3) ORDER BY timestamp DESC
+                    ) AS rn
+                    FROM pnl_snapshots WHERE timestamp >= $1 AND timestamp <= $2
+                 ) bucketed WHERE rn = 1 ORDER BY timestamp ASC",
+            )
+            .bind(from.to_rfc3339())
+            .bind(to.to_rfc3339())
+            .bind(secs.max(1)),
+            None => sqlx::query_as::<_, PnlRow>(
+                "SELECT timestamp, bankroll, pnl_total, realized_pnl, unrealized_pnl FROM pnl_snapshots
+                 WHERE timestamp >= $1 AND timestamp <= $2 ORDER BY timestamp ASC",
+            )
+            .bind(from.to_rfc3339())
+            .bind(to.to_rfc3339()),
+        };
+        let rows = query.fetch_all(&self.pool).await?;
+        Ok(rows.into_iter().filter_map(PnlRow::into_snapshot).collect())
+    }
+
+    async fn record_exposure_snapshot(&self, snapshot: &ExposureSnapshot) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO exposure_snapshots (timestamp, gross_exposure, net_exposure, open_order_notional, free_collateral, by_underlying)
+             VALUES ($1, $2, $3, $4, $5, $6)",
+        )
+        .bind(snapshot.timestamp.to_rfc3339())
+        .bind(snapshot.gross_exposure)
+        .bind(snapshot.net_exposure)
+        .bind(snapshot.open_order_notional)
+        .bind(snapshot.free_collateral)
+        .bind(serde_json::to_string(&snapshot.by_underlying)?)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn get_exposure_history(&self, since: DateTime<Utc>) -> Result<Vec<ExposureSnapshot>> {
+        let rows = sqlx::query_as::<_, ExposureRow>(
+            "SELECT timestamp, gross_exposure, net_exposure, open_order_notional, free_collateral, by_underlying
+             FROM exposure_snapshots WHERE timestamp >= $1 ORDER BY timestamp ASC",
+        )
+        .bind(since.to_rfc3339())
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows.into_iter().filter_map(ExposureRow::into_snapshot).collect())
+    }
+
+    // --- Loss cooldowns ---
+
+    async fn set_cooldown(&self, cooldown: &MarketCooldown) -> Result<()> {
+        set_cooldown(&self.pool, cooldown).await
+    }
+
+    async fn get_active_cooldowns(&self, now: DateTime<Utc>) -> Result<Vec<MarketCooldown>> {
+        let rows = sqlx::query_as::<_, CooldownRow>(
+            "SELECT market_id, blocked_until, realized_loss, started_at
+             FROM market_cooldowns WHERE blocked_until > $1",
+        )
+        .bind(now.to_rfc3339())
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows.into_iter().filter_map(CooldownRow::into_cooldown).collect())
+    }
+
+    // --- Signal calibration ---
+
+    async fn insert_signal_record(&self, record: &SignalRecord) -> Result<()> {
+        insert_signal_record(&self.pool, record).await
+    }
+
+    async fn get_unresolved_signal_records(&self) -> Result<Vec<SignalRecord>> {
+        let rows = sqlx::query_as::<_, SignalRecordRow>(
+            "SELECT id, strategy, market_id, token_id, side, confidence, price, created_at, outcome, resolved_at, size, shadow
+             FROM signal_records WHERE resolved_at IS NULL ORDER BY created_at ASC",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows.into_iter().filter_map(SignalRecordRow::into_record).collect())
+    }
+
+    async fn resolve_signal_record(&self, id: &str, outcome: f64, resolved_at: DateTime<Utc>) -> Result<()> {
+        sqlx::query("UPDATE signal_records SET outcome = $1, resolved_at = $2 WHERE id = $3")
+            .bind(outcome)
+            .bind(resolved_at.to_rfc3339())
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn get_resolved_signal_records(&self, since: DateTime<Utc>) -> Result<Vec<SignalRecord>> {
+        let rows = sqlx::query_as::<_, SignalRecordRow>(
+            "SELECT id, strategy, market_id, token_id, side, confidence, price, created_at, outcome, resolved_at, size, shadow
+             FROM signal_records WHERE outcome IS NOT NULL AND created_at >= $1 ORDER BY created_at ASC",
+        )
+        .bind(since.to_rfc3339())
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows.into_iter().filter_map(SignalRecordRow::into_record).collect())
+    }
+
+    // --- Strategy experiments ---
+
+    async fn insert_variant_reports(&self, reports: &[VariantReport]) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+        for report in reports {
+            sqlx::query(
+                "INSERT INTO variant_reports (timestamp, experiment, strategy, live, signals, resolved, wins, pnl, open_notional)
+                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)",
+            )
+            .bind(report.timestamp.to_rfc3339())
+            .bind(&report.experiment)
+            .bind(&report.strategy)
+            .bind(report.live as i64)
+            .bind(report.signals as i64)
+            .bind(report.resolved as i64)
+            .bind(report.wins as i64)
+            .bind(report.pnl)
+            .bind(report.open_notional)
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn get_variant_reports(&self, since: DateTime<Utc>) -> Result<Vec<VariantReport>> {
+        let rows = sqlx::query_as::<_, VariantReportRow>(
+            "SELECT timestamp, experiment, strategy, live, signals, resolved, wins, pnl, open_notional
+             FROM variant_reports WHERE timestamp >= $1 ORDER BY timestamp ASC, experiment, strategy",
+        )
+        .bind(since.to_rfc3339())
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows.into_iter().filter_map(VariantReportRow::into_report).collect())
+    }
+
+    // --- Risk audit log ---
+
+    async fn insert_risk_event(&self, event: &RiskEvent) -> Result<()> {
+        insert_risk_event(&self.pool, event).await
+    }
+
+    async fn get_risk_events(&self, since: DateTime<Utc>, limit: i64) -> Result<Vec<RiskEvent>> {
+        let rows = sqlx::query_as::<_, RiskEventRow>(
+            "SELECT timestamp, kind, strategy, market_id, detail FROM risk_events
+             WHERE timestamp >= $1 ORDER BY timestamp DESC LIMIT $2",
+        )
+        .bind(since.to_rfc3339())
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows.into_iter().filter_map(RiskEventRow::into_event).collect())
+    }
+
+    // --- Flow alerts ---
+
+    async fn insert_flow_alert(&self, alert: &FlowAlert) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO flow_alerts (timestamp, market_id, token_id, kind, side, size, notional, price, levels)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)",
+        )
+        .bind(alert.timestamp.to_rfc3339())
+        .bind(&alert.market_id)
+        .bind(&alert.token_id)
+        .bind(format!("{:?}", alert.kind))
+        .bind(alert.side.to_string())
+        .bind(alert.size)
+        .bind(alert.notional)
+        .bind(alert.price)
+        .bind(alert.levels as i64)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn get_flow_alerts(&self, since: DateTime<Utc>, limit: i64) -> Result<Vec<FlowAlert>> {
+        let rows = sqlx::query_as::<_, FlowAlertRow>(
+            "SELECT timestamp, market_id, token_id, kind, side, size, notional, price, levels FROM flow_alerts
+             WHERE timestamp >= $1 ORDER BY timestamp DESC LIMIT $2",
+        )
+        .bind(since.to_rfc3339())
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows.into_iter().filter_map(FlowAlertRow::into_alert).collect())
+    }
+
+    // --- Redemptions ---
+
+    async fn insert_redemption(&self, redemption: &Redemption) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO redemptions (settled_at, market_id, token_id, size, payout, pnl, status, tx_hash)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8)",
+        )
+        .bind(redemption.settled_at.to_rfc3339())
+        .bind(&redemption.market_id)
+        .bind(&redemption.token_id)
+        .bind(redemption.size)
+        .bind(redemption.payout)
+        .bind(redemption.pnl)
+        .bind(format!("{:?}", redemption.status))
+        .bind(&redemption.tx_hash)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn get_redemptions(&self, since: DateTime<Utc>, limit: i64) -> Result<Vec<Redemption>> {
+        let rows = sqlx::query_as::<_, RedemptionRow>(
+            "SELECT settled_at, market_id, token_id, size, payout, pnl, status, tx_hash FROM redemptions
+             WHERE settled_at >= $1 ORDER BY settled_at DESC LIMIT $2",
+        )
+        .bind(since.to_rfc3339())
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows.into_iter().filter_map(RedemptionRow::into_redemption).collect())
+    }
+
+    // --- Market metadata ---
+
+    async fn upsert_markets(&self, markets: &[Market]) -> Result<()> {
+        let now = Utc::now().to_rfc3339();
+        let mut tx = self.pool.begin().await?;
+        for m in markets {
+            sqlx::query(
+                "INSERT INTO markets (id, question, end_date, active, closed, winner, neg_risk, neg_risk_event, tick_size, min_order_size, updated_at)
+                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+                 ON CONFLICT (id) DO UPDATE SET
+                    question = excluded.question,
+                    end_date = excluded.end_date,
+                    active = excluded.active,
+                    closed = excluded.closed,
+                    winner = excluded.winner,
+                    neg_risk = excluded.neg_risk,
+                    neg_risk_event = excluded.neg_risk_event,
+                    tick_size = COALESCE(excluded.tick_size, markets.tick_size),
+                    min_order_size = COALESCE(excluded.min_order_size, markets.min_order_size),
+                    updated_at = excluded.updated_at",
+            )
+            .bind(&m.id)
+            .bind(&m.question)
+            .bind(m.end_date.map(|t| t.to_rfc3339()))
+            .bind(m.active as i64)
+            .bind(m.closed as i64)
+            .bind(&m.winner)
+            .bind(m.neg_risk() as i64)
+            .bind(m.structure.event_id())
+            .bind(m.tick_size)
+            .bind(m.min_order_size)
+            .bind(&now)
+            .execute(&mut *tx)
+            .await?;
+            for (position, t) in m.outcomes.iter().enumerate() {
+                sqlx::query(
+                    "INSERT INTO market_tokens (token_id, market_id, outcome, position) VALUES ($1, $2, $3, $4)
+                     ON CONFLICT (token_id) DO UPDATE SET
+                        market_id = excluded.market_id,
+                        outcome = excluded.outcome,
+                        position = excluded.position",
+                )
+                .bind(&t.token_id)
+                .bind(&m.id)
+                .bind(&t.name)
+                .bind(position as i64)
+                .execute(&mut *tx)
+                .await?;
+            }
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn get_markets(&self) -> Result<Vec<Market>> {
+        let rows = sqlx::query_as::<_, MarketRow>(
+            "SELECT id, question, end_date, active, closed, winner, neg_risk, neg_risk_event, tick_size, min_order_size FROM markets",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        let tokens = sqlx::query_as::<_, MarketTokenRow>(
+            "SELECT token_id, market_id, outcome FROM market_tokens ORDER BY market_id, position",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(assemble_markets(rows, tokens))
+    }
+
+    async fn get_market_by_token(&self, token_id: &TokenId) -> Result<Option<Market>> {
+        let market_id: Option<(String,)> =
+            sqlx::query_as("SELECT market_id FROM market_tokens WHERE token_id = $1")
+                .bind(token_id)
+                .fetch_optional(&self.pool)
+                .await?;
+        let Some((market_id,)) = market_id else {
+            return Ok(None);
+        };
+        let rows = sqlx::query_as::<_, MarketRow>(
+            "SELECT id, question, end_date, active, closed, winner, neg_risk, neg_risk_event, tick_size, min_order_size FROM markets
+             WHERE id = $1",
+        )
+        .bind(&market_id)
+        .fetch_all(&self.pool)
+        .await?;
+        let tokens = sqlx::query_as::<_, MarketTokenRow>(
+            "SELECT token_id, market_id, outcome FROM market_tokens WHERE market_id = $1 ORDER BY position",
+        )
+        .bind(&market_id)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(assemble_markets(rows, tokens).pop())
+    }
+
+    // --- Market data history ---
+
+    async fn insert_price_history(&self, token_id: &TokenId, points: &[(i64, f64)]) -> Result<u64> {
+        let mut tx = self.pool.begin().await?;
+        let mut inserted = 0;
+        for (ts, price) in points {
+            inserted += sqlx::query(
+                "INSERT INTO market_data (token_id, timestamp, price) VALUES ($1, $2, $3) ON CONFLICT DO NOTHING",
+            )
+            .bind(token_id)
+            .bind(ts)
+            .bind(price)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+        }
+        tx.commit().await?;
+        Ok(inserted)
+    }
+
+    async fn get_price_history(&self, token_id: &TokenId, from_ts: i64, to_ts: i64) -> Result<Vec<(i64, f64)>> {
+        let rows: Vec<(i64, f64)> = sqlx::query_as(
+            "SELECT timestamp, price FROM market_data WHERE token_id = $1 AND timestamp BETWEEN $2 AND $3 ORDER BY timestamp ASC",
+        )
+        .bind(token_id)
+        .bind(from_ts)
+        .bind(to_ts)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows)
+    }
+
+    async fn latest_price_timestamp(&self, token_id: &TokenId) -> Result<Option<i64>> {
+        let row: (Option<i64>,) =
+            sqlx::query_as("SELECT MAX(timestamp) FROM market_data WHERE token_id = $1")
+                .bind(token_id)
+                .fetch_one(&self.pool)
+                .await?;
+        Ok(row.0)
+    }
+
+    // --- Candles ---
+
+    async fn upsert_candles(&self, candles: &[Candle]) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+        for c in candles {
+            sqlx::query(
+                "INSERT INTO candles (instrument, interval_secs, open_time, open, high, low, close, ticks, volume, vwap)
+                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+                 ON CONFLICT (instrument, interval_secs, open_time) DO UPDATE SET
+                    high = GREATEST(candles.high, excluded.high),
+                    low = LEAST(candles.low, excluded.low),
+                    close = excluded.close,
+                    ticks = GREATEST(candles.ticks, excluded.ticks),
+                    vwap = CASE WHEN excluded.volume >= candles.volume THEN excluded.vwap ELSE candles.vwap END,
+                    volume = GREATEST(candles.volume, excluded.volume)",
+            )
+            .bind(&c.instrument)
+            .bind(c.interval_secs)
+            .bind(c.open_time)
+            .bind(c.open)
+            .bind(c.high)
+            .bind(c.low)
+            .bind(c.close)
+            .bind(c.ticks)
+            .bind(c.volume)
+            .bind(c.vwap)
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn get_candles(&self, instrument: &str, interval_secs: i64, limit: i64) -> Result<Vec<Candle>> {
+        let rows = sqlx::query_as::<_, CandleRow>(
+            "SELECT instrument, interval_secs, open_time, open, high, low, close, ticks, volume, vwap FROM candles
+             WHERE instrument = $1 AND interval_secs = $2 ORDER BY open_time DESC LIMIT $3",
+        )
+        .bind(instrument)
+        .bind(interval_secs)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows.into_iter().rev().map(|r| r.into()).collect())
+    }
+
+    // --- Archive ---
+
+    async fn get_market_ids(&self) -> Result<Vec<MarketId>> {
+        let rows: Vec<(MarketId,)> = sqlx::query_as(
+            "SELECT market_id FROM trades UNION SELECT market_id FROM orders UNION SELECT market_id FROM positions",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows.into_iter().map(|r| r.0).collect())
+    }
+
+    async fn archive_market(&self, market_id: &MarketId) -> Result<u64> {
+        let archived_at = Utc::now().to_rfc3339();
+        let mut tx = self.pool.begin().await?;
+        let mut moved = 0;
+        for (table, columns) in ARCHIVED_TABLES {
+            moved += sqlx::query(&format!(
+                "INSERT INTO {table}_archive ({columns}, archived_at) SELECT {columns}, $1 FROM {table} WHERE market_id = $2"
+            ))
+            .bind(&archived_at)
+            .bind(market_id)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+            sqlx::query(&format!("DELETE FROM {table} WHERE market_id = $1"))
+                .bind(market_id)
+                .execute(&mut *tx)
+                .await?;
+        }
+        tx.commit().await?;
+        Ok(moved)
+    }
+
+    // --- Config KV ---
+
+    async fn set_config(&self, key: &str, value: &str) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO config (key, value) VALUES ($1, $2) ON CONFLICT (key) DO UPDATE SET value = excluded.value",
+        )
+        .bind(key)
+        .bind(value)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn get_config(&self, key: &str) -> Result<Option<String>> {
+        let row: Option<(String,)> =
+            sqlx::query_as("SELECT value FROM config WHERE key = $1")
+                .bind(key)
+                .fetch_optional(&self.pool)
+                .await?;
+        Ok(row.map(|r| r.0))
+    }
+
+    // --- Backups ---
+
+    /// Postgres backups belong to the server (pg_dump, WAL archiving, provider snapshots)
+    async fn backup(&self, _dir: &str, _keep: usize) -> Result<PathBuf> {
+        Err(DbError::Backup(
+            "file backups are not supported on the Postgres backend; use pg_dump or the provider's snapshots".into(),
+        ))
+    }
+
+    // --- Batched writes ---
+
+    async fn apply_writes(&self, writes: &[Write]) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+        for write in writes {
+            match write {
+                Write::Order(order) => insert_order(&mut *tx, order).await?,
+                Write::OrderStatus(order_id, status) => update_order_status(&mut *tx, order_id, status).await?,
+                Write::OrderRemoteId(order_id, remote_id) => set_order_remote_id(&mut *tx, order_id, remote_id).await?,
+                Write::Trade(trade) => insert_trade(&mut *tx, trade).await?,
+                Write::ClosedTrade(closed) => insert_closed_trade(&mut *tx, closed).await?,
+                Write::Position(pos) => upsert_position(&mut *tx, pos).await?,
+                Write::DeletePosition(market_id, token_id) => delete_position(&mut *tx, market_id, token_id).await?,
+                Write::SignalRecord(record) => insert_signal_record(&mut *tx, record).await?,
+                Write::RiskEvent(event) => insert_risk_event(&mut *tx, event).await?,
+                Write::Cooldown(cooldown) => set_cooldown(&mut *tx, cooldown).await?,
+            }
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn close(&self) {
+        self.pool.close().await;
+    }
+}
+
+// --- Writes, on the pool or inside an `apply_writes` transaction ---
+
+async fn insert_trade<'c>(conn: impl PgExecutor<'c>, trade: &Trade) -> Result<()> {
+    let side = trade.side.to_string();
+    let ts = trade.timestamp.to_rfc3339();
+    sqlx::query(
+        "INSERT INTO trades (id, order_id, market_id, side, price, size, fee, timestamp) VALUES ($1, $2, $3, $4, $5, $6, $7, $8)",
+    )
+    .bind(&trade.id)
+    .bind(&trade.order_id)
+    .bind(&trade.market_id)
+    .bind(&side)
+    .bind(trade.price)
+    .bind(trade.size)
+    .bind(trade.fee)
+    .bind(&ts)
+    .execute(conn)
+    .await?;
+    Ok(())
+}
+
+async fn insert_closed_trade<'c>(conn: impl PgExecutor<'c>, closed: &ClosedTrade) -> Result<()> {
+    sqlx::query(
+        "INSERT INTO closed_trades (id, strategy, market_id, token_id, side, size, entry_price, exit_price, pnl, closed_at)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)",
+    )
+    .bind(&closed.id)
+    .bind(&closed.strategy)
+    .bind(&closed.market_id)
+    .bind(&closed.token_id)
+    .bind(closed.side.to_string())
+    .bind(closed.size)
+    .bind(closed.entry_price)
+    .bind(closed.exit_price)
+    .bind(closed.pnl)
+    .bind(closed.closed_at.to_rfc3339())
+    .execute(conn)
+    .await?;
+    Ok(())
+}
+
+async fn upsert_position<'c>(conn: impl PgExecutor<'c>, pos: &Position) -> Result<()> {
+    let side = pos.side.to_string();
+    sqlx::query(
+        "INSERT INTO positions (market_id, token_id, side, size, avg_price, current_price, pnl, strategy)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+         ON CONFLICT (market_id, token_id) DO UPDATE SET
+            side = excluded.side,
+            size = excluded.size,
+            avg_price = excluded.avg_price,
+            current_price = excluded.current_price,
+            pnl = excluded.pnl,
+            strategy = excluded.strategy",
+    )
+    .bind(&pos.market_id)
+    .bind(&pos.token_id)
+    .bind(&side)
+    .bind(pos.size)
+    .bind(pos.avg_price)
+    .bind(pos.current_price)
+    .bind(pos.pnl)
+    .bind(&pos.strategy)
+    .execute(conn)
+    .await?;
+    Ok(())
+}
+
+async fn delete_position<'c>(conn: impl PgExecutor<'c>, market_id: &MarketId, token_id: &TokenId) -> Result<()> {
+    sqlx::query("DELETE FROM positions WHERE market_id = $1 AND token_id = $2")
+        .bind(market_id)
+        .bind(token_id)
+        .execute(conn)
+        .await?;
+    Ok(())
+}
+
+async fn insert_order<'c>(conn: impl PgExecutor<'c>, order: &Order) -> Result<()> {
+    let side = order.side.to_string();
+    let status = format!("{:?}", order.status);
+    let ot = format!("{:?}", order.order_type);
+    let ts = order.created_at.to_rfc3339();
+    sqlx::query(
+        "INSERT INTO orders (id, market_id, side, token_id, price, size, order_type, status, created_at, remote_id, strategy, replaces, expires_at) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)",
+    )
+    .bind(&order.id)
+    .bind(&order.market_id)
+    .bind(&side)
+    .bind(&order.token_id)
+    .bind(order.price)
+    .bind(order.size)
+    .bind(&ot)
+    .bind(&status)
+    .bind(&ts)
+    .bind(&order.remote_id)
+    .bind(&order.strategy)
+    .bind(&order.replaces)
+    .bind(order.expires_at.map(|t| t.to_rfc3339()))
+    .execute(conn)
+    .await?;
+    Ok(())
+}
+
+async fn update_order_status<'c>(conn: impl PgExecutor<'c>, order_id: &OrderId, status: &OrderStatus) -> Result<()> {
+    let s = format!("{:?}", status);
+    sqlx::query("UPDATE orders SET status = $1 WHERE id = $2")
+        .bind(&s)
+        .bind(order_id)
+        .execute(conn)
+        .await?;
+    Ok(())
+}
+
+async fn set_order_remote_id<'c>(conn: impl PgExecutor<'c>, order_id: &OrderId, remote_id: &str) -> Result<()> {
+    sqlx::query("UPDATE orders SET remote_id = $1 WHERE id = $2")
+        .bind(remote_id)
+        .bind(order_id)
+        .execute(conn)
+        .await?;
+    Ok(())
+}
+
+async fn set_cooldown<'c>(conn: impl PgExecutor<'c>, cooldown: &MarketCooldown) -> Result<()> {
+    sqlx::query(
+        "INSERT INTO market_cooldowns (market_id, blocked_until, realized_loss, started_at)
+         VALUES ($1, $2, $3, $4)
+         ON CONFLICT (market_id) DO UPDATE SET
+            blocked_until = excluded.blocked_until,
+            realized_loss = excluded.realized_loss,
+            started_at = excluded.started_at",
+    )
+    .bind(&cooldown.market_id)
+    .bind(cooldown.until.to_rfc3339())
+    .bind(cooldown.realized_loss)
+    .bind(cooldown.started_at.to_rfc3339())
+    .execute(conn)
+    .await?;
+    Ok(())
+}
+
+async fn insert_signal_record<'c>(conn: impl PgExecutor<'c>, record: &SignalRecord) -> Result<()> {
+    sqlx::query(
+        "INSERT INTO signal_records (id, strategy, market_id, token_id, side, confidence, price, created_at, outcome, resolved_at, size, shadow)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)",
+    )
+    .bind(&record.id)
+    .bind(&record.strategy)
+    .bind(&record.market_id)
+    .bind(&record.token_id)
+    .bind(record.side.to_string())
+    .bind(record.confidence)
+    .bind(record.price)
+    .bind(record.created_at.to_rfc3339())
+    .bind(record.outcome)
+    .bind(record.resolved_at.map(|t| t.to_rfc3339()))
+    .bind(record.size)
+    .bind(record.shadow as i64)
+    .execute(conn)
+    .await?;
+    Ok(())
+}
+
+async fn insert_risk_event<'c>(conn: impl PgExecutor<'c>, event: &RiskEvent) -> Result<()> {
+    sqlx::query(
+        "INSERT INTO risk_events (timestamp, kind, strategy, market_id, detail) VALUES ($1, $2, $3, $4, $5)",
+    )
+    .bind(event.timestamp.to_rfc3339())
+    .bind(format!("{:?}", event.kind))
+    .bind(&event.strategy)
+    .bind(&event.market_id)
+    .bind(&event.detail)
+    .execute(conn)
+    .await?;
+    Ok(())
+}
@@ -0,0 +1,167 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tokio::fs::File;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader, BufWriter};
+use tracing::{info, warn};
+
+use crate::bus::{EventBus, EventReceiver};
+use crate::clock::ManualClock;
+use crate::domain::MarketData;
+use crate::error::FeedError;
+
+type Result<T, E = FeedError> = std::result::Result<T, E>;
+
+/// Unthrottled replay backs off once any consumer has this many instruments pending
+const MAX_QUEUED: usize = 512;
+
+/// One line of a recording: the event plus when we received it
+#[derive(Debug, Serialize, Deserialize)]
+struct RecordedEvent {
+    recv_at: DateTime<Utc>,
+    event: MarketData,
+}
+
+/// Appends MarketData events to a JSONL file, every tick of them: it reads a
+/// lossless bus subscription, so nothing conflates while the disk catches up. A
+/// recorder that falls past the bus's backlog limit loses the oldest, and says so.
+/// Flow alerts are derived from the feeds and left out; a replay regenerates them.
+pub struct MarketRecorder {
+    rx: EventReceiver<MarketData>,
+    path: String,
+}
+
+impl MarketRecorder {
+    pub fn new(rx: EventReceiver<MarketData>, path: String) -> Self {
+        Self { rx, path }
+    }
+
+    pub async fn run(mut self) -> Result<()> {
+        let file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .await
+            .map_err(|e| FeedError::recording(&self.path, e))?;
+        let mut writer = BufWriter::new(file);
+        info!("Recording market data to {}", self.path);
+        let failed = |e: std::io::Error| FeedError::recording(&self.path, e);
+
+        loop {
+            let event = self.rx.recv().await;
+            let missed = self.rx.take_missed();
+            if missed > 0 {
+                warn!("Recorder fell behind: {} market data events were dropped from {}", missed, self.path);
+            }
+            if matches!(event, MarketData::FlowAlert { .. }) {
+                continue;
+            }
+            let line = serde_json::to_string(&RecordedEvent { recv_at: Utc::now(), event }).map_err(|e| failed(e.into()))?;
+            writer.write_all(line.as_bytes()).await.map_err(failed)?;
+            writer.write_all(b"\n").await.map_err(failed)?;
+            // Flush when caught up so a crash loses at most the current burst
+            if self.rx.is_empty() {
+                writer.flush().await.map_err(failed)?;
+            }
+        }
+    }
+}
+
+/// Read a whole recording into memory as (receipt time, event), in file order.
+/// Malformed lines are skipped, as in a replay.
+pub async fn read_recording(path: &str) -> Result<Vec<(DateTime<Utc>, MarketData)>> {
+    let file = File::open(path).await.map_err(|e| FeedError::recording(path, e))?;
+    let mut lines = BufReader::new(file).lines();
+    let mut events = Vec::new();
+    while let Some(line) = lines.next_line().await.map_err(|e| FeedError::recording(path, e))? {
+        match serde_json::from_str::<RecordedEvent>(&line) {
+            Ok(recorded) => events.push((recorded.recv_at, recorded.event)),
+            Err(e) => warn!("Skipping malformed recording line: {:?}", e),
+        }
+    }
+    Ok(events)
+}
+
+/// When the first readable event of a recording was received, to start a
+/// replay's clock from
+pub async fn first_timestamp(path: &str) -> Result<Option<DateTime<Utc>>> {
+    let file = File::open(path).await.map_err(|e| FeedError::recording(path, e))?;
+    let mut lines = BufReader::new(file).lines();
+    while let Some(line) = lines.next_line().await.map_err(|e| FeedError::recording(path, e))? {
+        if let Ok(recorded) = serde_json::from_str::<RecordedEvent>(&line) {
+            return Ok(Some(recorded.recv_at));
+        }
+    }
+    Ok(None)
+}
+
+/// Republishes a recording onto the event bus, preserving the original
+/// inter-event timing divided by `speed` (0 = as fast as possible)
+pub struct ReplayFeed {
+    tx: EventBus,
+    path: String,
+    speed: f64,
+    clock: Option<Arc<ManualClock>>,
+}
+
+impl ReplayFeed {
+    pub fn new(tx: EventBus, path: String, speed: f64) -> Self {
+        Self {
+            tx,
+            path,
+            speed,
+            clock: None,
+        }
+    }
+
+    /// Move `clock` to each event's receipt time as it is published, so whatever
+    /// reads it runs on recorded time
+    pub fn with_clock(mut self, clock: Arc<ManualClock>) -> Self {
+        self.clock = Some(clock);
+        self
+    }
+
+    pub async fn run(self) -> Result<()> {
+        let failed = |e: std::io::Error| FeedError::recording(&self.path, e);
+        let file = File::open(&self.path).await.map_err(failed)?;
+        let mut lines = BufReader::new(file).lines();
+        let mut last_recv: Option<DateTime<Utc>> = None;
+        let mut count = 0u64;
+
+        info!("Replaying {} at {}x", self.path, self.speed);
+
+        while let Some(line) = lines.next_line().await.map_err(failed)? {
+            let recorded: RecordedEvent = match serde_json::from_str(&line) {
+                Ok(r) => r,
+                Err(e) => {
+                    warn!("Skipping malformed recording line: {:?}", e);
+                    continue;
+                }
+            };
+
+            if self.speed > 0.0 {
+                if let Some(prev) = last_recv {
+                    let gap_ms = (recorded.recv_at - prev).num_milliseconds().max(0) as f64 / self.speed;
+                    if gap_ms >= 1.0 {
+                        tokio::time::sleep(std::time::Duration::from_millis(gap_ms as u64)).await;
+                    }
+                }
+            } else {
+                // Unthrottled: still don't outrun consumers into conflating replayed ticks
+                while self.tx.max_pending() > MAX_QUEUED {
+                    tokio::time::sleep(std::time::Duration::from_millis(1)).await;
+                }
+            }
+            last_recv = Some(recorded.recv_at);
+            if let Some(clock) = &self.clock {
+                clock.set(recorded.recv_at);
+            }
+
+            self.tx.publish(recorded.event);
+            count += 1;
+        }
+
+        info!("Replay finished: {} events", count);
+        Ok(())
+    }
+}
@@ -0,0 +1,187 @@
+use chrono::Utc;
+use reqwest::Client;
+use serde::Deserialize;
+use std::collections::BTreeSet;
+use std::time::Duration;
+use tracing::{info, warn};
+
+use crate::bus::EventBus;
+use crate::config::SportsConfig;
+use crate::domain::{GameState, GameStatus, MarketData};
+use crate::error::FeedError;
+
+type Result<T, E = FeedError> = std::result::Result<T, E>;
+
+const FEED: &str = "sports";
+
+/// Scoreboard path and regulation clock of a league the feed understands
+pub struct League {
+    pub key: &'static str,
+    /// Path under the scoreboard API root
+    pub path: &'static str,
+    pub periods: u32,
+    pub period_secs: f64,
+}
+
+impl League {
+    /// Regulation length
+    pub fn game_secs(&self) -> f64 {
+        self.periods as f64 * self.period_secs
+    }
+}
+
+pub const LEAGUES: &[League] = &[
+    League { key: "nba", path: "basketball/nba", periods: 4, period_secs: 720.0 },
+    League { key: "wnba", path: "basketball/wnba", periods: 4, period_secs: 600.0 },
+    League { key: "ncaab", path: "basketball/mens-college-basketball", periods: 2, period_secs: 1200.0 },
+    League { key: "nfl", path: "football/nfl", periods: 4, period_secs: 900.0 },
+    League { key: "ncaaf", path: "football/college-football", periods: 4, period_secs: 900.0 },
+    League { key: "nhl", path: "hockey/nhl", periods: 3, period_secs: 1200.0 },
+];
+
+pub fn league(key: &str) -> Option<&'static League> {
+    LEAGUES.iter().find(|l| l.key.eq_ignore_ascii_case(key))
+}
+
+#[derive(Debug, Deserialize)]
+struct Scoreboard {
+    #[serde(default)]
+    events: Vec<ScoreboardEvent>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ScoreboardEvent {
+    id: String,
+    #[serde(default)]
+    competitions: Vec<Competition>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Competition {
+    competitors: Vec<Competitor>,
+    status: EventStatus,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Competitor {
+    home_away: String,
+    #[serde(default)]
+    score: String,
+    team: Team,
+}
+
+#[derive(Debug, Deserialize)]
+struct Team {
+    abbreviation: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct EventStatus {
+    /// Seconds left in the current period
+    #[serde(default)]
+    clock: f64,
+    #[serde(default)]
+    period: u32,
+    #[serde(rename = "type")]
+    kind: StatusType,
+}
+
+#[derive(Debug, Deserialize)]
+struct StatusType {
+    /// "pre", "in" or "post"
+    state: String,
+}
+
+/// Polls league scoreboards and publishes the tracked games' scores
+pub struct SportsFeed {
+    tx: EventBus,
+    client: Client,
+    api_url: String,
+    interval: Duration,
+    /// League key → game IDs to publish
+    games: Vec<(&'static League, BTreeSet<String>)>,
+}
+
+impl SportsFeed {
+    /// `games` are (league key, game ID) pairs; unknown leagues are skipped
+    pub fn new(tx: EventBus, config: &SportsConfig, games: &[(String, String)]) -> Result<Self> {
+        let mut tracked: Vec<(&'static League, BTreeSet<String>)> = Vec::new();
+        for (key, game_id) in games {
+            let Some(league) = league(key) else {
+                warn!("Sports game {} skipped: unknown league {:?}", game_id, key);
+                continue;
+            };
+            match tracked.iter_mut().find(|(l, _)| l.key == league.key) {
+                Some((_, ids)) => {
+                    ids.insert(game_id.clone());
+                }
+                None => tracked.push((league, BTreeSet::from([game_id.clone()]))),
+            }
+        }
+        Ok(Self {
+            tx,
+            client: Client::builder()
+                .timeout(Duration::from_secs(10))
+                .build()
+                .map_err(|e| FeedError::request(FEED, e))?,
+            api_url: config.api_url.trim_end_matches('/').to_string(),
+            interval: Duration::from_secs(config.poll_secs.max(1)),
+            games: tracked,
+        })
+    }
+
+    pub async fn run(self) {
+        info!("Sports feed polling {} league(s) every {:?}", self.games.len(), self.interval);
+        let mut ticker = tokio::time::interval(self.interval);
+        loop {
+            ticker.tick().await;
+            for (league, ids) in &self.games {
+                match self.scoreboard(league).await {
+                    Ok(games) => {
+                        for game in games.into_iter().filter(|g| ids.contains(&g.game_id)) {
+                            self.tx.publish(MarketData::GameScore { game });
+                        }
+                    }
+                    Err(e) => warn!("{} scoreboard poll failed: {:?}", league.key, e),
+                }
+            }
+        }
+    }
+
+    async fn scoreboard(&self, league: &League) -> Result<Vec<GameState>> {
+        let url = format!("{}/{}/scoreboard", self.api_url, league.path);
+        let board: Scoreboard = FeedError::send_json(league.key, self.client.get(&url)).await?;
+        Ok(board.events.into_iter().filter_map(|e| game_state(league, e)).collect())
+    }
+}
+
+/// None for events missing a home or away side
+fn game_state(league: &League, event: ScoreboardEvent) -> Option<GameState> {
+    let competition = event.competitions.into_iter().next()?;
+    let side = |home_away: &str| competition.competitors.iter().find(|c| c.home_away == home_away);
+    let (home, away) = (side("home")?, side("away")?);
+    let status = &competition.status;
+
+    let (status_kind, secs_remaining) = match status.kind.state.as_str() {
+        "pre" => (GameStatus::Scheduled, league.game_secs()),
+        "post" => (GameStatus::Final, 0.0),
+        _ if status.period > league.periods => (GameStatus::InProgress, status.clock),
+        _ => {
+            let later_periods = league.periods.saturating_sub(status.period.max(1)) as f64;
+            (GameStatus::InProgress, later_periods * league.period_secs + status.clock)
+        }
+    };
+
+    Some(GameState {
+        game_id: event.id,
+        league: league.key.to_string(),
+        home_team: home.team.abbreviation.clone(),
+        away_team: away.team.abbreviation.clone(),
+        home_score: home.score.parse().unwrap_or(0.0),
+        away_score: away.score.parse().unwrap_or(0.0),
+        secs_remaining,
+        status: status_kind,
+        timestamp: Utc::now(),
+    })
+}
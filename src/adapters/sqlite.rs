@@ -0,0 +1,988 @@
+use chrono::{DateTime, Utc};
+use sqlx::sqlite::{
+    SqliteConnectOptions, SqliteExecutor, SqliteJournalMode, SqlitePool, SqlitePoolOptions, SqliteSynchronous,
+};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+use tracing::{info, warn};
+
+use super::database::{assemble_markets, ARCHIVED_TABLES, CandleRow, ClosedTradeRow, CooldownRow, ExposureRow, FlowAlertRow, MarketRow, MarketTokenRow, OrderRow, PnlRow, PositionRow, ProbableFillRow, RedemptionRow, RiskEventRow, SignalRecordRow, Storage, TradeRow, VariantReportRow, Write};
+use crate::config::DbConfig;
+use crate::domain::{
+    Candle, ClosedTrade, ExposureSnapshot, FlowAlert, Market, MarketCooldown, MarketId, Order, OrderId, OrderStatus,
+    PnlSnapshot, Position, ProbableFill, Redemption, RiskEvent, SignalRecord, TokenId, Trade, VariantReport,
+};
+use crate::error::DbError;
+
+type Result<T, E = DbError> = std::result::Result<T, E>;
+
+/// Local single-file SQLite store (the default backend)
+pub struct SqliteStorage {
+    pool: SqlitePool,
+}
+
+impl SqliteStorage {
+    /// Opens in WAL mode, so the dashboard's reads and backups don't block the
+    /// writer, with `synchronous = NORMAL` (a power cut can lose the last commits
+    /// but never corrupts the file) and a busy timeout for when writers do collide
+    pub async fn new(db_path: &str, config: &DbConfig) -> Result<Self> {
+        let options = SqliteConnectOptions::from_str(&format!("sqlite:{}?mode=rwc", db_path))
+            .map_err(DbError::Connect)?
+            .journal_mode(SqliteJournalMode::Wal)
+            .synchronous(SqliteSynchronous::Normal)
+            .busy_timeout(Duration::from_millis(config.busy_timeout_ms));
+        let pool = SqlitePoolOptions::new()
+            .max_connections(5)
+            .connect_with(options)
+            .await
+            .map_err(DbError::Connect)?;
+
+        let db = Self { pool };
+        db.run_migrations().await?;
+        Ok(db)
+    }
+
+    /// Apply pending migrations from `migrations/sqlite`; versions are tracked in `_sqlx_migrations`
+    async fn run_migrations(&self) -> Result<()> {
+        self.adopt_unversioned().await?;
+        sqlx::migrate!("./migrations/sqlite")
+            .run(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Databases from before versioned migrations may predate columns that the
+    /// baseline migration's CREATE TABLE IF NOT EXISTS can't add; patch those in
+    /// so 0001 can be recorded as applied.
+    async fn adopt_unversioned(&self) -> Result<()> {
+        let tracked: Option<(String,)> =
+            sqlx::query_as("SELECT name FROM sqlite_master WHERE type = 'table' AND name = '_sqlx_migrations'")
+                .fetch_optional(&self.pool)
+                .await?;
+        let legacy: Option<(String,)> =
+            sqlx::query_as("SELECT name FROM sqlite_master WHERE type = 'table' AND name = 'positions'")
+                .fetch_optional(&self.pool)
+                .await?;
+        if tracked.is_none() && legacy.is_some() {
+            info!("Adopting unversioned database into migration tracking");
+            self.ensure_column("positions", "strategy", "TEXT NOT NULL DEFAULT ''").await?;
+        }
+        Ok(())
+    }
+
+    async fn ensure_column(&self, table: &str, column: &str, decl: &str) -> Result<()> {
+        let columns: Vec<(String,)> =
+            sqlx::query_as(&format!("SELECT name FROM pragma_table_info('{}')", table))
+                .fetch_all(&self.pool)
+                .await?;
+        if !columns.iter().any(|(name,)| name == column) {
+            sqlx::query(&format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, decl))
+                .execute(&self.pool)
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Validate a backup file and swap it in as `db_path`. The current file is kept
+    /// alongside as `<db_path>.pre-restore-<timestamp>`. Must not run while the bot is live.
+    pub async fn restore(db_path: &str, backup_path: &str) -> Result<()> {
+        if !Path::new(backup_path).is_file() {
+            return Err(DbError::Backup(format!("{} does not exist", backup_path)));
+        }
+
+        // Validate: opens as SQLite, passes integrity check, and has our core tables
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect(&format!("sqlite:{}?mode=ro", backup_path))
+            .await
+            .map_err(|e| DbError::Backup(format!("{} is not a readable SQLite database: {}", backup_path, e)))?;
+        let (integrity,): (String,) = sqlx::query_as("PRAGMA integrity_check")
+            .fetch_one(&pool)
+            .await?;
+        if integrity != "ok" {
+            return Err(DbError::Backup(format!("{} failed integrity check: {}", backup_path, integrity)));
+        }
+        for table in ["trades", "positions", "orders", "pnl_snapshots"] {
+            let found: Option<(String,)> =
+                sqlx::query_as("SELECT name FROM sqlite_master WHERE type = 'table' AND name = ?")
+                    .bind(table)
+                    .fetch_optional(&pool)
+                    .await?;
+            if found.is_none() {
+                return Err(DbError::Backup(format!("{} is missing table {}", backup_path, table)));
+            }
+        }
+        pool.close().await;
+
+        if Path::new(db_path).exists() {
+            let aside = format!("{}.pre-restore-{}", db_path, Utc::now().format("%Y%m%d-%H%M%S"));
+            std::fs::rename(db_path, &aside).map_err(|e| DbError::io(format!("Failed to move {} aside", db_path), e))?;
+            info!("Moved current database to {}", aside);
+        }
+        for suffix in ["-wal", "-shm"] {
+            let _ = std::fs::remove_file(format!("{}{}", db_path, suffix));
+        }
+        std::fs::copy(backup_path, db_path)
+            .map_err(|e| DbError::io(format!("Failed to copy {} to {}", backup_path, db_path), e))?;
+
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl Storage for SqliteStorage {
+    fn backend(&self) -> &'static str {
+        "sqlite"
+    }
+
+    async fn schema_version(&self) -> Result<i64> {
+        let (version,): (Option<i64>,) =
+            sqlx::query_as("SELECT MAX(version) FROM _sqlx_migrations WHERE success = 1")
+                .fetch_one(&self.pool)
+                .await?;
+        Ok(version.unwrap_or(0))
+    }
+
+    // --- Trades ---
+
+    async fn insert_trade(&self, trade: &Trade) -> Result<()> {
+        insert_trade(&self.pool, trade).await
+    }
+
+    async fn get_recent_trades(&self, limit: i64) -> Result<Vec<Trade>> {
+        let rows = sqlx::query_as::<_, TradeRow>(
+            "SELECT id, order_id, market_id, side, price, size, fee, timestamp FROM trades ORDER BY timestamp DESC LIMIT ?",
+        )
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(|r| r.into()).collect())
+    }
+
+    async fn get_trades_since(&self, since: DateTime<Utc>) -> Result<Vec<Trade>> {
+        let rows = sqlx::query_as::<_, TradeRow>(
+            "SELECT id, order_id, market_id, side, price, size, fee, timestamp FROM trades WHERE timestamp >= ? ORDER BY timestamp ASC",
+        )
+        .bind(since.to_rfc3339())
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows.into_iter().map(|r| r.into()).collect())
+    }
+
+    async fn insert_closed_trade(&self, closed: &ClosedTrade) -> Result<()> {
+        insert_closed_trade(&self.pool, closed).await
+    }
+
+    async fn get_closed_trades(&self, since: DateTime<Utc>) -> Result<Vec<ClosedTrade>> {
+        let rows = sqlx::query_as::<_, ClosedTradeRow>(
+            "SELECT id, strategy, market_id, token_id, side, size, entry_price, exit_price, pnl, closed_at
+             FROM closed_trades WHERE closed_at >= ? ORDER BY closed_at ASC",
+        )
+        .bind(since.to_rfc3339())
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows.into_iter().filter_map(ClosedTradeRow::into_closed_trade).collect())
+    }
+
+    async fn get_realized_pnl(&self) -> Result<f64> {
+        let row: (Option<f64>,) = sqlx::query_as("SELECT SUM(pnl) FROM closed_trades")
+            .fetch_one(&self.pool)
+            .await?;
+        Ok(row.0.unwrap_or(0.0))
+    }
+
+    // --- Positions ---
+
+    async fn upsert_position(&self, pos: &Position) -> Result<()> {
+        upsert_position(&self.pool, pos).await
+    }
+
+    async fn get_positions(&self) -> Result<Vec<Position>> {
+        let rows = sqlx::query_as::<_, PositionRow>(
+            "SELECT market_id, token_id, side, size, avg_price, current_price, pnl, strategy FROM positions WHERE size > 0",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows.into_iter().map(|r| r.into()).collect())
+    }
+
+    async fn delete_position(&self, market_id: &MarketId, token_id: &TokenId) -> Result<()> {
+        delete_position(&self.pool, market_id, token_id).await
+    }
+
+    // --- Orders ---
+
+    async fn insert_order(&self, order: &Order) -> Result<()> {
+        insert_order(&self.pool, order).await
+    }
+
+    async fn update_order_status(&self, order_id: &OrderId, status: &OrderStatus) -> Result<()> {
+        update_order_status(&self.pool, order_id, status).await
+    }
+
+    async fn set_order_remote_id(&self, order_id: &OrderId, remote_id: &str) -> Result<()> {
+        set_order_remote_id(&self.pool, order_id, remote_id).await
+    }
+
+    async fn get_open_orders(&self) -> Result<Vec<Order>> {
+        let rows = sqlx::query_as::<_, OrderRow>(
+            "SELECT id, market_id, side, token_id, price, size, order_type, status, created_at, remote_id, strategy, replaces, expires_at FROM orders WHERE status IN ('Pending', 'Open')",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows.into_iter().map(|r| r.into()).collect())
+    }
+
+    // --- Probable fills (public tape heuristic) ---
+
+    async fn insert_probable_fill(&self, fill: &ProbableFill) -> Result<bool> {
+        let result = sqlx::query(
+            "INSERT OR IGNORE INTO probable_fills (order_id, trade_key, token_id, price, size, trade_timestamp, detected_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(&fill.order_id)
+        .bind(&fill.trade_key)
+        .bind(&fill.token_id)
+        .bind(fill.price)
+        .bind(fill.size)
+        .bind(fill.trade_timestamp.to_rfc3339())
+        .bind(fill.detected_at.to_rfc3339())
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn get_unconfirmed_fills(&self) -> Result<Vec<ProbableFill>> {
+        let rows = sqlx::query_as::<_, ProbableFillRow>(
+            "SELECT order_id, trade_key, token_id, price, size, trade_timestamp, detected_at FROM probable_fills WHERE confirmed IS NULL ORDER BY detected_at ASC",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows.into_iter().map(|r| r.into()).collect())
+    }
+
+    // --- PnL ---
+
+    async fn record_pnl_snapshot(&self, snapshot: &PnlSnapshot) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO pnl_snapshots (timestamp, bankroll, pnl_total, realized_pnl, unrealized_pnl)
+             VALUES (?, ?, ?, ?, ?)",
+        )
+        .bind(snapshot.timestamp.to_rfc3339())
+        .bind(snapshot.bankroll)
+        .bind(snapshot.pnl_total)
+        .bind(snapshot.realized_pnl)
+        .bind(snapshot.unrealized_pnl)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn get_pnl_history(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        bucket_secs: Option<i64>,
+    ) -> Result<Vec<PnlSnapshot>> {
+        // Bucketing keeps the last snapshot of each interval, so a chart over months
+        // of 5-minute snapshots stays a few hundred points
+        let query = match bucket_secs {
+            Some(secs) => sqlx::query_as::<_, PnlRow>(
+                "SELECT timestamp, bankroll, pnl_total, realized_pnl, unrealized_pnl FROM (
+                    SELECT timestamp, bankroll, pnl_total, realized_pnl, unrealized_pnl, ROW_NUMBER() OVER (
+                        PARTITION BY CAST(strftime('%s', timestamp) AS INTEGER) / ? ORDER BY timestamp DESC
+                    ) AS rn
+                    FROM pnl_snapshots WHERE timestamp >= ? AND timestamp <= ?
+                 ) WHERE rn = 1 ORDER BY timestamp ASC",
+            )
+            .bind(secs.max(1))
+            .bind(from.to_rfc3339())
+            .bind(to.to_rfc3339()),
+            None => sqlx::query_as::<_, PnlRow>(
+                "SELECT timestamp, bankroll, pnl_total, realized_pnl, unrealized_pnl FROM pnl_snapshots
+                 WHERE timestamp >= ? AND timestamp <= ? ORDER BY timestamp ASC",
+            )
+            .bind(from.to_rfc3339())
+            .bind(to.to_rfc3339()),
+        };
+        let rows = query.fetch_all(&self.pool).await?;
+        Ok(rows.into_iter().filter_map(PnlRow::into_snapshot).collect())
+    }
+
+    async fn record_exposure_snapshot(&self, snapshot: &ExposureSnapshot) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO exposure_snapshots (timestamp, gross_exposure, net_exposure, open_order_notional, free_collateral, by_underlying)
+             VALUES (?, ?, ?, ?, ?, ?)",
+        )
+        .bind(snapshot.timestamp.to_rfc3339())
+        .bind(snapshot.gross_exposure)
+        .bind(snapshot.net_exposure)
+        .bind(snapshot.open_order_notional)
+        .bind(snapshot.free_collateral)
+        .bind(serde_json::to_string(&snapshot.by_underlying)?)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn get_exposure_history(&self, since: DateTime<Utc>) -> Result<Vec<ExposureSnapshot>> {
+        let rows = sqlx::query_as::<_, ExposureRow>(
+            "SELECT timestamp, gross_exposure, net_exposure, open_order_notional, free_collateral, by_underlying
+             FROM exposure_snapshots WHERE timestamp >= ? ORDER BY timestamp ASC",
+        )
+        .bind(since.to_rfc3339())
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows.into_iter().filter_map(ExposureRow::into_snapshot).collect())
+    }
+
+    // --- Loss cooldowns ---
+
+    async fn set_cooldown(&self, cooldown: &MarketCooldown) -> Result<()> {
+        set_cooldown(&self.pool, cooldown).await
+    }
+
+    async fn get_active_cooldowns(&self, now: DateTime<Utc>) -> Result<Vec<MarketCooldown>> {
+        let rows = sqlx::query_as::<_, CooldownRow>(
+            "SELECT market_id, blocked_until, realized_loss, started_at
+             FROM market_cooldowns WHERE blocked_until > ?",
+        )
+        .bind(now.to_rfc3339())
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows.into_iter().filter_map(CooldownRow::into_cooldown).collect())
+    }
+
+    // --- Signal calibration ---
+
+    async fn insert_signal_record(&self, record: &SignalRecord) -> Result<()> {
+        insert_signal_record(&self.pool, record).await
+    }
+
+    async fn get_unresolved_signal_records(&self) -> Result<Vec<SignalRecord>> {
+        let rows = sqlx::query_as::<_, SignalRecordRow>(
+            "SELECT id, strategy, market_id, token_id, side, confidence, price, created_at, outcome, resolved_at, size, shadow
+             FROM signal_records WHERE resolved_at IS NULL ORDER BY created_at ASC",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows.into_iter().filter_map(SignalRecordRow::into_record).collect())
+    }
+
+    async fn resolve_signal_record(&self, id: &str, outcome: f64, resolved_at: DateTime<Utc>) -> Result<()> {
+        sqlx::query("UPDATE signal_records SET outcome = ?, resolved_at = ? WHERE id = ?")
+            .bind(outcome)
+            .bind(resolved_at.to_rfc3339())
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn get_resolved_signal_records(&self, since: DateTime<Utc>) -> Result<Vec<SignalRecord>> {
+        let rows = sqlx::query_as::<_, SignalRecordRow>(
+            "SELECT id, strategy, market_id, token_id, side, confidence, price, created_at, outcome, resolved_at, size, shadow
+             FROM signal_records WHERE outcome IS NOT NULL AND created_at >= ? ORDER BY created_at ASC",
+        )
+        .bind(since.to_rfc3339())
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows.into_iter().filter_map(SignalRecordRow::into_record).collect())
+    }
+
+    // --- Strategy experiments ---
+
+    async fn insert_variant_reports(&self, reports: &[VariantReport]) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+        for report in reports {
+            sqlx::query(
+                "INSERT INTO variant_reports (timestamp, experiment, strategy, live, signals, resolved, wins, pnl, open_notional)
+                 VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            )
+            .bind(report.timestamp.to_rfc3339())
+            .bind(&report.experiment)
+            .bind(&report.strategy)
+            .bind(report.live as i64)
+            .bind(report.signals as i64)
+            .bind(report.resolved as i64)
+            .bind(report.wins as i64)
+            .bind(report.pnl)
+            .bind(report.open_notional)
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn get_variant_reports(&self, since: DateTime<Utc>) -> Result<Vec<VariantReport>> {
+        let rows = sqlx::query_as::<_, VariantReportRow>(
+            "SELECT timestamp, experiment, strategy, live, signals, resolved, wins, pnl, open_notional
+             FROM variant_reports WHERE timestamp >= ? ORDER BY timestamp ASC, experiment, strategy",
+        )
+        .bind(since.to_rfc3339())
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows.into_iter().filter_map(VariantReportRow::into_report).collect())
+    }
+
+    // --- Risk audit log ---
+
+    async fn insert_risk_event(&self, event: &RiskEvent) -> Result<()> {
+        insert_risk_event(&self.pool, event).await
+    }
+
+    async fn get_risk_events(&self, since: DateTime<Utc>, limit: i64) -> Result<Vec<RiskEvent>> {
+        let rows = sqlx::query_as::<_, RiskEventRow>(
+            "SELECT timestamp, kind, strategy, market_id, detail FROM risk_events
+             WHERE timestamp >= ? ORDER BY timestamp DESC LIMIT ?",
+        )
+        .bind(since.to_rfc3339())
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows.into_iter().filter_map(RiskEventRow::into_event).collect())
+    }
+
+    // --- Flow alerts ---
+
+    async fn insert_flow_alert(&self, alert: &FlowAlert) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO flow_alerts (timestamp, market_id, token_id, kind, side, size, notional, price, levels)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(alert.timestamp.to_rfc3339())
+        .bind(&alert.market_id)
+        .bind(&alert.token_id)
+        .bind(format!("{:?}", alert.kind))
+        .bind(alert.side.to_string())
+        .bind(alert.size)
+        .bind(alert.notional)
+        .bind(alert.price)
+        .bind(alert.levels as i64)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn get_flow_alerts(&self, since: DateTime<Utc>, limit: i64) -> Result<Vec<FlowAlert>> {
+        let rows = sqlx::query_as::<_, FlowAlertRow>(
+            "SELECT timestamp, market_id, token_id, kind, side, size, notional, price, levels FROM flow_alerts
+             WHERE timestamp >= ? ORDER BY timestamp DESC LIMIT ?",
+        )
+        .bind(since.to_rfc3339())
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows.into_iter().filter_map(FlowAlertRow::into_alert).collect())
+    }
+
+    // --- Redemptions ---
+
+    async fn insert_redemption(&self, redemption: &Redemption) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO redemptions (settled_at, market_id, token_id, size, payout, pnl, status, tx_hash)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(redemption.settled_at.to_rfc3339())
+        .bind(&redemption.market_id)
+        .bind(&redemption.token_id)
+        .bind(redemption.size)
+        .bind(redemption.payout)
+        .bind(redemption.pnl)
+        .bind(format!("{:?}", redemption.status))
+        .bind(&redemption.tx_hash)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn get_redemptions(&self, since: DateTime<Utc>, limit: i64) -> Result<Vec<Redemption>> {
+        let rows = sqlx::query_as::<_, RedemptionRow>(
+            "SELECT settled_at, market_id, token_id, size, payout, pnl, status, tx_hash FROM redemptions
+             WHERE settled_at >= ? ORDER BY settled_at DESC LIMIT ?",
+        )
+        .bind(since.to_rfc3339())
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows.into_iter().filter_map(RedemptionRow::into_redemption).collect())
+    }
+
+    // --- Market metadata ---
+
+    async fn upsert_markets(&self, markets: &[Market]) -> Result<()> {
+        let now = Utc::now().to_rfc3339();
+        let mut tx = self.pool.begin().await?;
+        for m in markets {
+            sqlx::query(
+                "INSERT INTO markets (id, question, end_date, active, closed, winner, neg_risk, neg_risk_event, tick_size, min_order_size, updated_at)
+                 VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                 ON CONFLICT (id) DO UPDATE SET
+                    question = excluded.question,
+                    end_date = excluded.end_date,
+                    active = excluded.active,
+                    closed = excluded.closed,
+                    winner = excluded.winner,
+                    neg_risk = excluded.neg_risk,
+                    neg_risk_event = excluded.neg_risk_event,
+                    tick_size = COALESCE(excluded.tick_size, markets.tick_size),
+                    min_order_size = COALESCE(excluded.min_order_size, markets.min_order_size),
+                    updated_at = excluded.updated_at",
+            )
+            .bind(&m.id)
+            .bind(&m.question)
+            .bind(m.end_date.map(|t| t.to_rfc3339()))
+            .bind(m.active as i64)
+            .bind(m.closed as i64)
+            .bind(&m.winner)
+            .bind(m.neg_risk() as i64)
+            .bind(m.structure.event_id())
+            .bind(m.tick_size)
+            .bind(m.min_order_size)
+            .bind(&now)
+            .execute(&mut *tx)
+            .await?;
+            for (position, t) in m.outcomes.iter().enumerate() {
+                sqlx::query(
+                    "INSERT INTO market_tokens (token_id, market_id, outcome, position) VALUES (?, ?, ?, ?)
+                     ON CONFLICT (token_id) DO UPDATE SET
+                        market_id = excluded.market_id,
+                        outcome = excluded.outcome,
+                        position = excluded.position",
+                )
+                .bind(&t.token_id)
+                .bind(&m.id)
+                .bind(&t.name)
+                .bind(position as i64)
+                .execute(&mut *tx)
+                .await?;
+            }
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn get_markets(&self) -> Result<Vec<Market>> {
+        let rows = sqlx::query_as::<_, MarketRow>(
+            "SELECT id, question, end_date, active, closed, winner, neg_risk, neg_risk_event, tick_size, min_order_size FROM markets",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        let tokens = sqlx::query_as::<_, MarketTokenRow>(
+            "SELECT token_id, market_id, outcome FROM market_tokens ORDER BY market_id, position",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(assemble_markets(rows, tokens))
+    }
+
+    async fn get_market_by_token(&self, token_id: &TokenId) -> Result<Option<Market>> {
+        let market_id: Option<(String,)> =
+            sqlx::query_as("SELECT market_id FROM market_tokens WHERE token_id = ?")
+                .bind(token_id)
+                .fetch_optional(&self.pool)
+                .await?;
+        let Some((market_id,)) = market_id else {
+            return Ok(None);
+        };
+        let rows = sqlx::query_as::<_, MarketRow>(
+            "SELECT id, question, end_date, active, closed, winner, neg_risk, neg_risk_event, tick_size, min_order_size FROM markets
+             WHERE id = ?",
+        )
+        .bind(&market_id)
+        .fetch_all(&self.pool)
+        .await?;
+        let tokens = sqlx::query_as::<_, MarketTokenRow>(
+            "SELECT token_id, market_id, outcome FROM market_tokens WHERE market_id = ? ORDER BY position",
+        )
+        .bind(&market_id)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(assemble_markets(rows, tokens).pop())
+    }
+
+    // --- Market data history ---
+
+    async fn insert_price_history(&self, token_id: &TokenId, points: &[(i64, f64)]) -> Result<u64> {
+        let mut tx = self.pool.begin().await?;
+        let mut inserted = 0;
+        for (ts, price) in points {
+            inserted += sqlx::query(
+                "INSERT OR IGNORE INTO market_data (token_id, timestamp, price) VALUES (?, ?, ?)",
+            )
+            .bind(token_id)
+            .bind(ts)
+            .bind(price)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+        }
+        tx.commit().await?;
+        Ok(inserted)
+    }
+
+    async fn get_price_history(&self, token_id: &TokenId, from_ts: i64, to_ts: i64) -> Result<Vec<(i64, f64)>> {
+        let rows: Vec<(i64, f64)> = sqlx::query_as(
+            "SELECT timestamp, price FROM market_data WHERE token_id = ? AND timestamp BETWEEN ? AND ? ORDER BY timestamp ASC",
+        )
+        .bind(token_id)
+        .bind(from_ts)
+        .bind(to_ts)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows)
+    }
+
+    async fn latest_price_timestamp(&self, token_id: &TokenId) -> Result<Option<i64>> {
+        let row: (Option<i64>,) =
+            sqlx::query_as("SELECT MAX(timestamp) FROM market_data WHERE token_id = ?")
+                .bind(token_id)
+                .fetch_one(&self.pool)
+                .await?;
+        Ok(row.0)
+    }
+
+    // --- Candles ---
+
+    async fn upsert_candles(&self, candles: &[Candle]) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+        for c in candles {
+            sqlx::query(
+                "INSERT INTO candles (instrument, interval_secs, open_time, open, high, low, close, ticks, volume, vwap)
+                 VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                 ON CONFLICT (instrument, interval_secs, open_time) DO UPDATE SET
+                    high = MAX(candles.high, excluded.high),
+                    low = MIN(candles.low, excluded.low),
+                    close = excluded.close,
+                    ticks = MAX(candles.ticks, excluded.ticks),
+                    vwap = CASE WHEN excluded.volume >= candles.volume THEN excluded.vwap ELSE candles.vwap END,
+                    volume = MAX(candles.volume, excluded.volume)",
+            )
+            .bind(&c.instrument)
+            .bind(c.interval_secs)
+            .bind(c.open_time)
+            .bind(c.open)
+            .bind(c.high)
+            .bind(c.low)
+            .bind(c.close)
+            .bind(c.ticks)
+            .bind(c.volume)
+            .bind(c.vwap)
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn get_candles(&self, instrument: &str, interval_secs: i64, limit: i64) -> Result<Vec<Candle>> {
+        let rows = sqlx::query_as::<_, CandleRow>(
+            "SELECT instrument, interval_secs, open_time, open, high, low, close, ticks, volume, vwap FROM candles
+             WHERE instrument = ? AND interval_secs = ? ORDER BY open_time DESC LIMIT ?",
+        )
+        .bind(instrument)
+        .bind(interval_secs)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows.into_iter().rev().map(|r| r.into()).collect())
+    }
+
+    // --- Archive ---
+
+    async fn get_market_ids(&self) -> Result<Vec<MarketId>> {
+        let rows: Vec<(MarketId,)> = sqlx::query_as(
+            "SELECT market_id FROM trades UNION SELECT market_id FROM orders UNION SELECT market_id FROM positions",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows.into_iter().map(|r| r.0).collect())
+    }
+
+    async fn archive_market(&self, market_id: &MarketId) -> Result<u64> {
+        let archived_at = Utc::now().to_rfc3339();
+        let mut tx = self.pool.begin().await?;
+        let mut moved = 0;
+        for (table, columns) in ARCHIVED_TABLES {
+            moved += sqlx::query(&format!(
+                "INSERT INTO {table}_archive ({columns}, archived_at) SELECT {columns}, ? FROM {table} WHERE market_id = ?"
+            ))
+            .bind(&archived_at)
+            .bind(market_id)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+            sqlx::query(&format!("DELETE FROM {table} WHERE market_id = ?"))
+                .bind(market_id)
+                .execute(&mut *tx)
+                .await?;
+        }
+        tx.commit().await?;
+        Ok(moved)
+    }
+
+    // --- Config KV ---
+
+    async fn set_config(&self, key: &str, value: &str) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO config (key, value) VALUES (?, ?) ON CONFLICT(key) DO UPDATE SET value = excluded.value",
+        )
+        .bind(key)
+        .bind(value)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn get_config(&self, key: &str) -> Result<Option<String>> {
+        let row: Option<(String,)> =
+            sqlx::query_as("SELECT value FROM config WHERE key = ?")
+                .bind(key)
+                .fetch_optional(&self.pool)
+                .await?;
+        Ok(row.map(|r| r.0))
+    }
+
+    // --- Backups ---
+
+    /// Online backup via VACUUM INTO, then prune all but the newest `keep` backups in `dir`
+    async fn backup(&self, dir: &str, keep: usize) -> Result<PathBuf> {
+        std::fs::create_dir_all(dir).map_err(|e| DbError::io(format!("Failed to create backup dir {}", dir), e))?;
+        let path = Path::new(dir).join(format!("bot-{}.db", Utc::now().format("%Y%m%d-%H%M%S")));
+        sqlx::query("VACUUM INTO ?")
+            .bind(path.to_string_lossy().to_string())
+            .execute(&self.pool)
+            .await
+            .map_err(|e| DbError::Backup(format!("VACUUM INTO failed: {}", e)))?;
+
+        let mut backups = list_backups(dir)?;
+        while backups.len() > keep {
+            let oldest = backups.remove(0);
+            if let Err(e) = std::fs::remove_file(&oldest) {
+                warn!("Failed to prune backup {}: {:?}", oldest.display(), e);
+            }
+        }
+
+        Ok(path)
+    }
+
+    // --- Batched writes ---
+
+    async fn apply_writes(&self, writes: &[Write]) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+        for write in writes {
+            match write {
+                Write::Order(order) => insert_order(&mut *tx, order).await?,
+                Write::OrderStatus(order_id, status) => update_order_status(&mut *tx, order_id, status).await?,
+                Write::OrderRemoteId(order_id, remote_id) => set_order_remote_id(&mut *tx, order_id, remote_id).await?,
+                Write::Trade(trade) => insert_trade(&mut *tx, trade).await?,
+                Write::ClosedTrade(closed) => insert_closed_trade(&mut *tx, closed).await?,
+                Write::Position(pos) => upsert_position(&mut *tx, pos).await?,
+                Write::DeletePosition(market_id, token_id) => delete_position(&mut *tx, market_id, token_id).await?,
+                Write::SignalRecord(record) => insert_signal_record(&mut *tx, record).await?,
+                Write::RiskEvent(event) => insert_risk_event(&mut *tx, event).await?,
+                Write::Cooldown(cooldown) => set_cooldown(&mut *tx, cooldown).await?,
+            }
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Closing the last connection also checkpoints the WAL into the database file
+    async fn close(&self) {
+        self.pool.close().await;
+    }
+}
+
+/// Backup files in `dir`, oldest first (names embed a sortable timestamp)
+fn list_backups(dir: &str) -> Result<Vec<PathBuf>> {
+    let mut backups: Vec<PathBuf> = std::fs::read_dir(dir)
+        .map_err(|e| DbError::io(format!("Failed to list backup dir {}", dir), e))?
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| {
+            p.file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.starts_with("bot-") && n.ends_with(".db"))
+        })
+        .collect();
+    backups.sort();
+    Ok(backups)
+}
+
+// --- Writes, on the pool or inside an `apply_writes` transaction ---
+
+async fn insert_trade<'c>(conn: impl SqliteExecutor<'c>, trade: &Trade) -> Result<()> {
+    let side = trade.side.to_string();
+    let ts = trade.timestamp.to_rfc3339();
+    sqlx::query(
+        "INSERT INTO trades (id, order_id, market_id, side, price, size, fee, timestamp) VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(&trade.id)
+    .bind(&trade.order_id)
+    .bind(&trade.market_id)
+    .bind(&side)
+    .bind(trade.price)
+    .bind(trade.size)
+    .bind(trade.fee)
+    .bind(&ts)
+    .execute(conn)
+    .await?;
+    Ok(())
+}
+
+async fn insert_closed_trade<'c>(conn: impl SqliteExecutor<'c>, closed: &ClosedTrade) -> Result<()> {
+    sqlx::query(
+        "INSERT INTO closed_trades (id, strategy, market_id, token_id, side, size, entry_price, exit_price, pnl, closed_at)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(&closed.id)
+    .bind(&closed.strategy)
+    .bind(&closed.market_id)
+    .bind(&closed.token_id)
+    .bind(closed.side.to_string())
+    .bind(closed.size)
+    .bind(closed.entry_price)
+    .bind(closed.exit_price)
+    .bind(closed.pnl)
+    .bind(closed.closed_at.to_rfc3339())
+    .execute(conn)
+    .await?;
+    Ok(())
+}
+
+async fn upsert_position<'c>(conn: impl SqliteExecutor<'c>, pos: &Position) -> Result<()> {
+    let side = pos.side.to_string();
+    sqlx::query(
+        "INSERT INTO positions (market_id, token_id, side, size, avg_price, current_price, pnl, strategy)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+         ON CONFLICT(market_id, token_id) DO UPDATE SET
+            side = excluded.side,
+            size = excluded.size,
+            avg_price = excluded.avg_price,
+            current_price = excluded.current_price,
+            pnl = excluded.pnl,
+            strategy = excluded.strategy",
+    )
+    .bind(&pos.market_id)
+    .bind(&pos.token_id)
+    .bind(&side)
+    .bind(pos.size)
+    .bind(pos.avg_price)
+    .bind(pos.current_price)
+    .bind(pos.pnl)
+    .bind(&pos.strategy)
+    .execute(conn)
+    .await?;
+    Ok(())
+}
+
+async fn delete_position<'c>(conn: impl SqliteExecutor<'c>, market_id: &MarketId, token_id: &TokenId) -> Result<()> {
+    sqlx::query("DELETE FROM positions WHERE market_id = ? AND token_id = ?")
+        .bind(market_id)
+        .bind(token_id)
+        .execute(conn)
+        .await?;
+    Ok(())
+}
+
+async fn insert_order<'c>(conn: impl SqliteExecutor<'c>, order: &Order) -> Result<()> {
+    let side = order.side.to_string();
+    let status = format!("{:?}", order.status);
+    let ot = format!("{:?}", order.order_type);
+    let ts = order.created_at.to_rfc3339();
+    sqlx::query(
+        "INSERT INTO orders (id, market_id, side, token_id, price, size, order_type, status, created_at, remote_id, strategy, replaces, expires_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(&order.id)
+    .bind(&order.market_id)
+    .bind(&side)
+    .bind(&order.token_id)
+    .bind(order.price)
+    .bind(order.size)
+    .bind(&ot)
+    .bind(&status)
+    .bind(&ts)
+    .bind(&order.remote_id)
+    .bind(&order.strategy)
+    .bind(&order.replaces)
+    .bind(order.expires_at.map(|t| t.to_rfc3339()))
+    .execute(conn)
+    .await?;
+    Ok(())
+}
+
+async fn update_order_status<'c>(conn: impl SqliteExecutor<'c>, order_id: &OrderId, status: &OrderStatus) -> Result<()> {
+    let s = format!("{:?}", status);
+    sqlx::query("UPDATE orders SET status = ? WHERE id = ?")
+        .bind(&s)
+        .bind(order_id)
+        .execute(conn)
+        .await?;
+    Ok(())
+}
+
+async fn set_order_remote_id<'c>(conn: impl SqliteExecutor<'c>, order_id: &OrderId, remote_id: &str) -> Result<()> {
+    sqlx::query("UPDATE orders SET remote_id = ? WHERE id = ?")
+        .bind(remote_id)
+        .bind(order_id)
+        .execute(conn)
+        .await?;
+    Ok(())
+}
+
+async fn set_cooldown<'c>(conn: impl SqliteExecutor<'c>, cooldown: &MarketCooldown) -> Result<()> {
+    sqlx::query(
+        "INSERT INTO market_cooldowns (market_id, blocked_until, realized_loss, started_at)
+         VALUES (?, ?, ?, ?)
+         ON CONFLICT (market_id) DO UPDATE SET
+            blocked_until = excluded.blocked_until,
+            realized_loss = excluded.realized_loss,
+            started_at = excluded.started_at",
+    )
+    .bind(&cooldown.market_id)
+    .bind(cooldown.until.to_rfc3339())
+    .bind(cooldown.realized_loss)
+    .bind(cooldown.started_at.to_rfc3339())
+    .execute(conn)
+    .await?;
+    Ok(())
+}
+
+async fn insert_signal_record<'c>(conn: impl SqliteExecutor<'c>, record: &SignalRecord) -> Result<()> {
+    sqlx::query(
+        "INSERT INTO signal_records (id, strategy, market_id, token_id, side, confidence, price, created_at, outcome, resolved_at, size, shadow)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(&record.id)
+    .bind(&record.strategy)
+    .bind(&record.market_id)
+    .bind(&record.token_id)
+    .bind(record.side.to_string())
+    .bind(record.confidence)
+    .bind(record.price)
+    .bind(record.created_at.to_rfc3339())
+    .bind(record.outcome)
+    .bind(record.resolved_at.map(|t| t.to_rfc3339()))
+    .bind(record.size)
+    .bind(record.shadow as i64)
+    .execute(conn)
+    .await?;
+    Ok(())
+}
+
+async fn insert_risk_event<'c>(conn: impl SqliteExecutor<'c>, event: &RiskEvent) -> Result<()> {
+    sqlx::query(
+        "INSERT INTO risk_events (timestamp, kind, strategy, market_id, detail) VALUES (?, ?, ?, ?, ?)",
+    )
+    .bind(event.timestamp.to_rfc3339())
+    .bind(format!("{:?}", event.kind))
+    .bind(&event.strategy)
+    .bind(&event.market_id)
+    .bind(&event.detail)
+    .execute(conn)
+    .await?;
+    Ok(())
+}
@@ -0,0 +1,151 @@
+use reqwest::Client;
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::error::ClobError;
+
+type Result<T, E = ClobError> = std::result::Result<T, E>;
+
+/// Latency samples kept per venue for percentile reporting
+const SAMPLE_WINDOW: usize = 500;
+
+/// How the HTTP connection to a venue is managed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VenueMode {
+    /// HTTP/1.1 with a keep-alive connection pool
+    Pooled,
+    /// New connection per request (no idle pool)
+    Fresh,
+    /// HTTP/2 with prior knowledge, multiplexed over one connection
+    Http2,
+}
+
+/// One execution path for order submission: an endpoint plus a connection strategy
+pub struct Venue {
+    pub name: String,
+    pub base_url: String,
+    pub mode: VenueMode,
+    pub client: Client,
+}
+
+impl Venue {
+    pub fn new(name: &str, base_url: &str, mode: VenueMode) -> Result<Self> {
+        let builder = Client::builder().timeout(Duration::from_secs(30));
+        let builder = match mode {
+            VenueMode::Pooled => builder.pool_max_idle_per_host(5),
+            VenueMode::Fresh => builder.pool_max_idle_per_host(0),
+            VenueMode::Http2 => builder.http2_prior_knowledge(),
+        };
+        let client = builder.build().map_err(|e| ClobError::request("build HTTP client", e))?;
+        Ok(Self {
+            name: name.to_string(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+            mode,
+            client,
+        })
+    }
+
+    /// Parse a `name|base_url|mode` venue spec (mode defaults to pooled)
+    pub fn from_spec(spec: &str) -> Result<Self> {
+        let parts: Vec<&str> = spec.split('|').map(str::trim).collect();
+        let (name, url) = match parts.as_slice() {
+            [name, url, ..] => (*name, *url),
+            _ => return Err(ClobError::Invalid(format!("Invalid venue spec {:?}, expected name|url|mode", spec))),
+        };
+        let mode = match parts.get(2).copied().unwrap_or("pooled") {
+            "pooled" => VenueMode::Pooled,
+            "fresh" => VenueMode::Fresh,
+            "http2" => VenueMode::Http2,
+            other => return Err(ClobError::Invalid(format!("Unknown venue mode {:?}", other))),
+        };
+        Self::new(name, url, mode)
+    }
+}
+
+#[derive(Default)]
+struct VenueSamples {
+    submitted: u64,
+    errors: u64,
+    latencies_ms: VecDeque<f64>,
+}
+
+/// Comparative ack-latency summary for one venue
+#[derive(Debug, Clone, Serialize)]
+pub struct VenueReport {
+    pub name: String,
+    pub base_url: String,
+    pub mode: VenueMode,
+    pub submitted: u64,
+    pub errors: u64,
+    pub p50_ms: Option<f64>,
+    pub p95_ms: Option<f64>,
+    pub p99_ms: Option<f64>,
+    pub mean_ms: Option<f64>,
+}
+
+/// Round-robins order submissions across venues and records ack latency per venue
+pub struct VenueExperiment {
+    venues: Vec<Venue>,
+    next: AtomicUsize,
+    samples: Mutex<HashMap<String, VenueSamples>>,
+}
+
+impl VenueExperiment {
+    pub fn new(venues: Vec<Venue>) -> Arc<Self> {
+        Arc::new(Self {
+            venues,
+            next: AtomicUsize::new(0),
+            samples: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// Next venue in rotation
+    pub fn pick(&self) -> &Venue {
+        let i = self.next.fetch_add(1, Ordering::Relaxed) % self.venues.len();
+        &self.venues[i]
+    }
+
+    pub fn record(&self, venue: &str, latency: Duration, ok: bool) {
+        let mut samples = self.samples.lock().unwrap();
+        let entry = samples.entry(venue.to_string()).or_default();
+        entry.submitted += 1;
+        if !ok {
+            entry.errors += 1;
+            return;
+        }
+        if entry.latencies_ms.len() == SAMPLE_WINDOW {
+            entry.latencies_ms.pop_front();
+        }
+        entry.latencies_ms.push_back(latency.as_secs_f64() * 1000.0);
+    }
+
+    pub fn report(&self) -> Vec<VenueReport> {
+        let samples = self.samples.lock().unwrap();
+        self.venues
+            .iter()
+            .map(|v| {
+                let s = samples.get(&v.name);
+                let mut sorted: Vec<f64> = s.map(|s| s.latencies_ms.iter().copied().collect()).unwrap_or_default();
+                sorted.sort_by(|a, b| a.total_cmp(b));
+                let pct = |q: f64| {
+                    (!sorted.is_empty()).then(|| sorted[((sorted.len() - 1) as f64 * q).round() as usize])
+                };
+                VenueReport {
+                    name: v.name.clone(),
+                    base_url: v.base_url.clone(),
+                    mode: v.mode,
+                    submitted: s.map(|s| s.submitted).unwrap_or(0),
+                    errors: s.map(|s| s.errors).unwrap_or(0),
+                    p50_ms: pct(0.50),
+                    p95_ms: pct(0.95),
+                    p99_ms: pct(0.99),
+                    mean_ms: (!sorted.is_empty()).then(|| sorted.iter().sum::<f64>() / sorted.len() as f64),
+                }
+            })
+            .collect()
+    }
+}
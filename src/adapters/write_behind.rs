@@ -0,0 +1,84 @@
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::sync::{mpsc, oneshot};
+use tracing::{error, warn};
+
+use super::database::{Storage, Write};
+use crate::metrics::METRICS;
+
+enum Queued {
+    Write(Write),
+    /// Answered once every write queued before it is committed
+    Flush(oneshot::Sender<()>),
+}
+
+/// Write-behind queue: the trading path hands writes off here and carries on,
+/// while a dedicated task commits them in batches, one transaction per batch.
+///
+/// Writes are applied in the order they were queued. A batch that fails is
+/// retried one write at a time so a single bad row can't take the rest with it;
+/// a write that still fails is logged and dropped. The queue is unbounded so
+/// enqueueing never waits on the database.
+#[derive(Clone)]
+pub struct WriteBehind {
+    tx: mpsc::UnboundedSender<Queued>,
+}
+
+impl WriteBehind {
+    /// Start the writer task for `store`, committing up to `max_batch` writes at a time
+    pub fn spawn(store: Arc<dyn Storage>, max_batch: usize) -> Self {
+        let (tx, rx) = mpsc::unbounded_channel();
+        tokio::spawn(run(store, rx, max_batch.max(1)));
+        Self { tx }
+    }
+
+    pub fn enqueue(&self, write: Write) {
+        if self.tx.send(Queued::Write(write)).is_err() {
+            error!("Database writer has stopped; write dropped");
+        }
+    }
+
+    /// Wait until everything queued so far is committed (or has failed)
+    pub async fn flush(&self) {
+        let (done, flushed) = oneshot::channel();
+        if self.tx.send(Queued::Flush(done)).is_ok() {
+            let _ = flushed.await;
+        }
+    }
+}
+
+async fn run(store: Arc<dyn Storage>, mut rx: mpsc::UnboundedReceiver<Queued>, max_batch: usize) {
+    let mut queued = Vec::with_capacity(max_batch);
+    // Whatever has piled up while the last batch committed goes in the next one
+    while rx.recv_many(&mut queued, max_batch).await > 0 {
+        let mut writes = Vec::with_capacity(queued.len());
+        let mut flushes = Vec::new();
+        for item in queued.drain(..) {
+            match item {
+                Queued::Write(write) => writes.push(write),
+                Queued::Flush(done) => flushes.push(done),
+            }
+        }
+        if !writes.is_empty() {
+            commit(store.as_ref(), &writes).await;
+        }
+        for done in flushes {
+            let _ = done.send(());
+        }
+    }
+}
+
+async fn commit(store: &dyn Storage, writes: &[Write]) {
+    let started = Instant::now();
+    let result = store.apply_writes(writes).await;
+    METRICS.db_batch.observe_ms(started.elapsed().as_secs_f64() * 1000.0);
+    let Err(e) = result else {
+        return;
+    };
+    warn!("Batch of {} database writes failed, retrying one at a time: {}", writes.len(), e);
+    for write in writes {
+        if let Err(e) = store.apply_writes(std::slice::from_ref(write)).await {
+            error!("Dropped database write {:?}: {}", write, e);
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use axum::{
+    extract::{Request, State},
+    http::{header, Method, StatusCode},
+    middleware::Next,
+    response::Response,
+};
+use std::sync::Arc;
+
+use super::AppState;
+use crate::config::ApiAuthConfig;
+
+/// Bearer-token gate for the dashboard API.
+///
+/// Mutating routes need the admin token and are refused outright when none is
+/// configured. GET routes are open unless a read token is configured, in which
+/// case either token is accepted.
+pub async fn require_token(State(state): State<Arc<AppState>>, req: Request, next: Next) -> Result<Response, StatusCode> {
+    let presented = req
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "));
+    let mutating = !(req.method() == Method::GET || req.method() == Method::HEAD);
+    check_token(&state.config.api_auth, presented, mutating)?;
+    Ok(next.run(req).await)
+}
+
+/// The token policy above, shared with the gRPC interface
+pub(super) fn check_token(auth: &ApiAuthConfig, presented: Option<&str>, mutating: bool) -> Result<(), StatusCode> {
+    let matches = |expected: &Option<String>| match (expected, presented) {
+        (Some(expected), Some(presented)) => constant_time_eq(expected.as_bytes(), presented.as_bytes()),
+        _ => false,
+    };
+
+    let allowed = if !mutating {
+        auth.read_token.is_none() || matches(&auth.read_token) || matches(&auth.admin_token)
+    } else if auth.admin_token.is_none() {
+        return Err(StatusCode::FORBIDDEN);
+    } else {
+        matches(&auth.admin_token)
+    };
+
+    if allowed {
+        Ok(())
+    } else {
+        Err(StatusCode::UNAUTHORIZED)
+    }
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
@@ -0,0 +1,621 @@
+use axum::http::StatusCode;
+use futures_util::stream::{self, Stream};
+use std::collections::HashSet;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::sync::Arc;
+use tonic::{Code, Request, Response, Status};
+use tracing::info;
+
+use super::{auth, AppState, ApiError, PlaceOrderRequest};
+use crate::bus::{EventReceiver, Topic};
+use crate::domain::{self, Execution, FlowAlertKind, GameStatus, MarketData, OrderStatus, OrderType, Side};
+use crate::engine::liquidation::LiquidationResult;
+use crate::engine::order_manager::SignalOutcome;
+use crate::engine::risk::RiskStatus;
+use crate::strategy::heartbeat::StrategyHeartbeat;
+
+pub mod pb {
+    tonic::include_proto!("polymarket_bot.v1");
+}
+
+use pb::bot_server::{Bot, BotServer};
+
+type EventStream<T> = Pin<Box<dyn Stream<Item = Result<T, Status>> + Send>>;
+
+/// gRPC counterpart of the dashboard API, plus live event streams.
+///
+/// Shares `AppState` and the bearer-token policy with the HTTP router, so both
+/// interfaces always agree on what a call does and who may make it.
+pub struct BotService {
+    state: Arc<AppState>,
+}
+
+/// Serve the gRPC interface until the process exits
+pub async fn serve(state: Arc<AppState>, port: u16) -> Result<(), tonic::transport::Error> {
+    let addr = SocketAddr::from(([0, 0, 0, 0], port));
+    info!("gRPC interface running on {}", addr);
+    tonic::transport::Server::builder()
+        .add_service(BotServer::new(BotService { state }))
+        .serve(addr)
+        .await
+}
+
+impl BotService {
+    fn authorize<T>(&self, req: &Request<T>, mutating: bool) -> Result<(), Status> {
+        let presented = req
+            .metadata()
+            .get("authorization")
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.strip_prefix("Bearer "));
+        auth::check_token(&self.state.config.api_auth, presented, mutating).map_err(|code| match code {
+            StatusCode::FORBIDDEN => Status::permission_denied("admin token not configured"),
+            _ => Status::unauthenticated("missing or invalid bearer token"),
+        })
+    }
+}
+
+fn to_status((code, message): ApiError) -> Status {
+    let code = match code {
+        StatusCode::BAD_REQUEST => Code::InvalidArgument,
+        StatusCode::NOT_FOUND => Code::NotFound,
+        StatusCode::CONFLICT => Code::FailedPrecondition,
+        StatusCode::BAD_GATEWAY | StatusCode::SERVICE_UNAVAILABLE => Code::Unavailable,
+        _ => Code::Internal,
+    };
+    Status::new(code, message)
+}
+
+fn internal(e: impl std::fmt::Display) -> Status {
+    Status::internal(e.to_string())
+}
+
+#[tonic::async_trait]
+impl Bot for BotService {
+    async fn get_status(&self, req: Request<pb::Empty>) -> Result<Response<pb::Status>, Status> {
+        self.authorize(&req, false)?;
+        let s = self.state.status().await;
+        Ok(Response::new(pb::Status {
+            bankroll: s.bankroll,
+            pnl_total: s.pnl_total,
+            active_positions: s.active_positions as u64,
+            uptime_secs: s.uptime_secs,
+            trading_active: s.trading_active,
+            armed: s.armed,
+            realized_pnl: s.realized_pnl,
+            unrealized_pnl: s.unrealized_pnl,
+        }))
+    }
+
+    async fn get_positions(&self, req: Request<pb::Empty>) -> Result<Response<pb::PositionList>, Status> {
+        self.authorize(&req, false)?;
+        let positions = self.state.positions().await.map_err(internal)?;
+        Ok(Response::new(pb::PositionList {
+            positions: positions.into_iter().map(Into::into).collect(),
+        }))
+    }
+
+    async fn get_open_orders(&self, req: Request<pb::Empty>) -> Result<Response<pb::OrderList>, Status> {
+        self.authorize(&req, false)?;
+        let orders = self.state.db.get_open_orders().await.map_err(internal)?;
+        Ok(Response::new(pb::OrderList {
+            orders: orders.into_iter().map(Into::into).collect(),
+        }))
+    }
+
+    async fn get_trades(&self, req: Request<pb::TradesRequest>) -> Result<Response<pb::TradeList>, Status> {
+        self.authorize(&req, false)?;
+        let limit = match req.into_inner().limit {
+            0 => 100,
+            n => n.min(5000) as i64,
+        };
+        let trades = self.state.db.get_recent_trades(limit).await.map_err(internal)?;
+        Ok(Response::new(pb::TradeList {
+            trades: trades.into_iter().map(Into::into).collect(),
+        }))
+    }
+
+    async fn get_pnl_history(&self, req: Request<pb::PnlHistoryRequest>) -> Result<Response<pb::PnlHistory>, Status> {
+        self.authorize(&req, false)?;
+        let req = req.into_inner();
+        let history = self
+            .state
+            .pnl_history(
+                req.from.and_then(chrono::DateTime::from_timestamp_millis),
+                req.to.and_then(chrono::DateTime::from_timestamp_millis),
+                (!req.interval.is_empty()).then_some(req.interval.as_str()),
+            )
+            .await
+            .map_err(to_status)?;
+        Ok(Response::new(pb::PnlHistory {
+            snapshots: history
+                .into_iter()
+                .map(|s| pb::PnlSnapshot {
+                    timestamp: s.timestamp.timestamp_millis(),
+                    bankroll: s.bankroll,
+                    pnl_total: s.pnl_total,
+                    realized_pnl: s.realized_pnl,
+                    unrealized_pnl: s.unrealized_pnl,
+                })
+                .collect(),
+        }))
+    }
+
+    async fn get_candles(&self, req: Request<pb::CandlesRequest>) -> Result<Response<pb::CandleList>, Status> {
+        self.authorize(&req, false)?;
+        let req = req.into_inner();
+        let interval = if req.interval.is_empty() { "1m" } else { req.interval.as_str() };
+        let limit = if req.limit == 0 { 200 } else { req.limit as i64 };
+        let (interval_secs, candles) = self
+            .state
+            .candles(&req.instrument, interval, limit)
+            .await
+            .map_err(to_status)?;
+        Ok(Response::new(pb::CandleList {
+            instrument: req.instrument,
+            interval_secs,
+            candles: candles
+                .into_iter()
+                .map(|c| pb::Candle {
+                    open_time: c.open_time,
+                    open: c.open,
+                    high: c.high,
+                    low: c.low,
+                    close: c.close,
+                    ticks: c.ticks,
+                    volume: c.volume,
+                    vwap: c.vwap,
+                })
+                .collect(),
+        }))
+    }
+
+    async fn get_risk(&self, req: Request<pb::Empty>) -> Result<Response<pb::RiskStatus>, Status> {
+        self.authorize(&req, false)?;
+        let risk = self.state.risk_status().await.map_err(internal)?;
+        Ok(Response::new(risk.into()))
+    }
+
+    async fn get_strategies(&self, req: Request<pb::Empty>) -> Result<Response<pb::StrategyList>, Status> {
+        self.authorize(&req, false)?;
+        Ok(Response::new(pb::StrategyList {
+            strategies: self.state.strategies.snapshot().into_iter().map(Into::into).collect(),
+        }))
+    }
+
+    async fn place_order(&self, req: Request<pb::PlaceOrderRequest>) -> Result<Response<pb::SignalOutcome>, Status> {
+        self.authorize(&req, true)?;
+        let req = req.into_inner();
+        let side = match req.side() {
+            pb::Side::Buy => Side::Buy,
+            pb::Side::Sell => Side::Sell,
+            pb::Side::Unspecified => return Err(Status::invalid_argument("side is required")),
+        };
+        let order_type = match req.order_type() {
+            pb::OrderType::Unspecified => None,
+            pb::OrderType::Gtc => Some(OrderType::GTC),
+            pb::OrderType::Gtd => Some(OrderType::GTD),
+            pb::OrderType::Fok => Some(OrderType::FOK),
+            pb::OrderType::Fak => Some(OrderType::FAK),
+        };
+        let outcome = self
+            .state
+            .place_order(PlaceOrderRequest {
+                market_id: (!req.market_id.is_empty()).then(|| req.market_id.into()),
+                token_id: req.token_id.into(),
+                side,
+                price: req.price,
+                size: req.size,
+                order_type,
+                good_till: req.good_till.and_then(chrono::DateTime::from_timestamp_millis),
+            })
+            .await
+            .map_err(to_status)?;
+        Ok(Response::new(outcome.into()))
+    }
+
+    async fn cancel_order(
+        &self,
+        req: Request<pb::CancelOrderRequest>,
+    ) -> Result<Response<pb::CancelOrderResponse>, Status> {
+        self.authorize(&req, true)?;
+        let order_id = domain::OrderId::from(req.into_inner().order_id);
+        let remote_id = self.state.cancel_order(&order_id).await.map_err(to_status)?;
+        Ok(Response::new(pb::CancelOrderResponse {
+            order_id: order_id.into(),
+            remote_id,
+        }))
+    }
+
+    async fn close_position(
+        &self,
+        req: Request<pb::ClosePositionRequest>,
+    ) -> Result<Response<pb::SignalOutcome>, Status> {
+        self.authorize(&req, true)?;
+        let req = req.into_inner();
+        let outcome = self
+            .state
+            .close_position(&req.market_id.into(), &req.token_id.into())
+            .await
+            .map_err(to_status)?;
+        Ok(Response::new(outcome.into()))
+    }
+
+    async fn arm(&self, req: Request<pb::Empty>) -> Result<Response<pb::ArmState>, Status> {
+        self.authorize(&req, true)?;
+        self.state.risk.arm();
+        Ok(Response::new(pb::ArmState {
+            armed: true,
+            trading_active: self.state.risk.is_active(),
+        }))
+    }
+
+    async fn disarm(&self, req: Request<pb::Empty>) -> Result<Response<pb::ArmState>, Status> {
+        self.authorize(&req, true)?;
+        self.state.risk.disarm();
+        Ok(Response::new(pb::ArmState {
+            armed: false,
+            trading_active: self.state.risk.is_active(),
+        }))
+    }
+
+    async fn kill(&self, req: Request<pb::KillRequest>) -> Result<Response<pb::KillResponse>, Status> {
+        self.authorize(&req, true)?;
+        let liquidations = self.state.kill(req.into_inner().liquidate).await;
+        Ok(Response::new(pb::KillResponse {
+            trading_active: false,
+            liquidations: liquidations.into_iter().map(Into::into).collect(),
+        }))
+    }
+
+    async fn take_pnl_snapshot(&self, req: Request<pb::Empty>) -> Result<Response<pb::PnlSnapshot>, Status> {
+        self.authorize(&req, true)?;
+        let snapshot = self.state.take_pnl_snapshot().await.map_err(to_status)?;
+        Ok(Response::new(pb::PnlSnapshot {
+            timestamp: snapshot.timestamp.timestamp_millis(),
+            bankroll: snapshot.bankroll,
+            pnl_total: snapshot.pnl_total,
+            realized_pnl: snapshot.realized_pnl,
+            unrealized_pnl: snapshot.unrealized_pnl,
+        }))
+    }
+
+    type StreamMarketDataStream = EventStream<pb::MarketDataEvent>;
+
+    async fn stream_market_data(
+        &self,
+        req: Request<pb::MarketDataRequest>,
+    ) -> Result<Response<Self::StreamMarketDataStream>, Status> {
+        self.authorize(&req, false)?;
+        let filter: HashSet<String> = req.into_inner().instruments.into_iter().collect();
+        let rx = self.state.events.subscribe::<MarketData>();
+
+        let events = stream::unfold((rx, filter), |(mut rx, filter)| async move {
+            loop {
+                let event = rx.recv().await;
+                let instrument = match &event {
+                    MarketData::PolymarketPrice { token_id, .. }
+                    | MarketData::PolymarketOrderBook { token_id, .. }
+                    | MarketData::PolymarketTrade { token_id, .. } => token_id.as_str(),
+                    MarketData::FlowAlert { alert } => &alert.token_id,
+                    MarketData::BinanceTicker { symbol, .. } | MarketData::BinanceOrderBook { symbol, .. } => symbol,
+                    MarketData::KalshiTicker { ticker, .. } => ticker,
+                    MarketData::GameScore { game } => &game.game_id,
+                    MarketData::Resolution { market_id, .. } => market_id,
+                };
+                if filter.is_empty() || filter.contains(instrument) {
+                    return Some((Ok(event.into()), (rx, filter)));
+                }
+            }
+        });
+        Ok(Response::new(Box::pin(events)))
+    }
+
+    type StreamSignalsStream = EventStream<pb::SignalEvent>;
+
+    async fn stream_signals(&self, req: Request<pb::Empty>) -> Result<Response<Self::StreamSignalsStream>, Status> {
+        self.authorize(&req, false)?;
+        let events = bus_stream(self.state.events.subscribe(), |signal: domain::Signal, missed| pb::SignalEvent {
+            timestamp: chrono::Utc::now().timestamp_millis(),
+            signal: Some(signal.into()),
+            missed,
+        });
+        Ok(Response::new(events))
+    }
+
+    type StreamOrdersStream = EventStream<pb::OrderEvent>;
+
+    async fn stream_orders(&self, req: Request<pb::Empty>) -> Result<Response<Self::StreamOrdersStream>, Status> {
+        self.authorize(&req, false)?;
+        let events = bus_stream(self.state.events.subscribe(), |order: domain::Order, missed| pb::OrderEvent {
+            timestamp: chrono::Utc::now().timestamp_millis(),
+            order: Some(order.into()),
+            missed,
+        });
+        Ok(Response::new(events))
+    }
+}
+
+/// Adapt a bus receiver, reporting events lost to lag in the next one's `missed` count
+fn bus_stream<T, E>(rx: EventReceiver<T>, wrap: fn(T, u64) -> E) -> EventStream<E>
+where
+    T: Topic + Send + 'static,
+    E: Send + 'static,
+{
+    Box::pin(stream::unfold(rx, move |mut rx| async move {
+        let item = rx.recv().await;
+        let missed = rx.take_missed();
+        Some((Ok(wrap(item, missed)), rx))
+    }))
+}
+
+// --- Domain → protobuf ---
+
+impl From<Side> for pb::Side {
+    fn from(side: Side) -> Self {
+        match side {
+            Side::Buy => pb::Side::Buy,
+            Side::Sell => pb::Side::Sell,
+        }
+    }
+}
+
+impl From<OrderType> for pb::OrderType {
+    fn from(order_type: OrderType) -> Self {
+        match order_type {
+            OrderType::GTC => pb::OrderType::Gtc,
+            OrderType::GTD => pb::OrderType::Gtd,
+            OrderType::FOK => pb::OrderType::Fok,
+            OrderType::FAK => pb::OrderType::Fak,
+        }
+    }
+}
+
+impl From<Execution> for pb::Execution {
+    fn from(execution: Execution) -> Self {
+        match execution {
+            Execution::Passive => pb::Execution::Passive,
+            Execution::Take => pb::Execution::Take,
+        }
+    }
+}
+
+impl From<OrderStatus> for pb::OrderStatus {
+    fn from(status: OrderStatus) -> Self {
+        match status {
+            OrderStatus::Pending => pb::OrderStatus::Pending,
+            OrderStatus::Open => pb::OrderStatus::Open,
+            OrderStatus::Filled => pb::OrderStatus::Filled,
+            OrderStatus::Cancelled => pb::OrderStatus::Cancelled,
+            OrderStatus::Failed => pb::OrderStatus::Failed,
+        }
+    }
+}
+
+impl From<domain::Position> for pb::Position {
+    fn from(p: domain::Position) -> Self {
+        pb::Position {
+            market_id: p.market_id.into(),
+            token_id: p.token_id.into(),
+            side: pb::Side::from(p.side).into(),
+            size: p.size,
+            avg_price: p.avg_price,
+            current_price: p.current_price,
+            pnl: p.pnl,
+            strategy: p.strategy,
+        }
+    }
+}
+
+impl From<domain::Order> for pb::Order {
+    fn from(o: domain::Order) -> Self {
+        pb::Order {
+            id: o.id.into(),
+            market_id: o.market_id.into(),
+            token_id: o.token_id.into(),
+            side: pb::Side::from(o.side).into(),
+            price: o.price,
+            size: o.size,
+            order_type: pb::OrderType::from(o.order_type).into(),
+            status: pb::OrderStatus::from(o.status).into(),
+            created_at: o.created_at.timestamp_millis(),
+            remote_id: o.remote_id,
+            strategy: o.strategy,
+            replaces: o.replaces.map(String::from),
+            expires_at: o.expires_at.map(|t| t.timestamp_millis()),
+        }
+    }
+}
+
+impl From<domain::Trade> for pb::Trade {
+    fn from(t: domain::Trade) -> Self {
+        pb::Trade {
+            id: t.id,
+            order_id: t.order_id.into(),
+            market_id: t.market_id.into(),
+            side: pb::Side::from(t.side).into(),
+            price: t.price,
+            size: t.size,
+            fee: t.fee,
+            timestamp: t.timestamp.timestamp_millis(),
+        }
+    }
+}
+
+impl From<domain::Signal> for pb::Signal {
+    fn from(s: domain::Signal) -> Self {
+        pb::Signal {
+            strategy: s.strategy,
+            market_id: s.market_id.into(),
+            token_id: s.token_id.map(String::from),
+            side: pb::Side::from(s.side).into(),
+            confidence: s.confidence,
+            price: s.price,
+            size: s.size,
+            order_type: s.order_type.map(pb::OrderType::from).unwrap_or_default().into(),
+            event_time: s.event_time.map(|t| t.timestamp_millis()),
+            execution: pb::Execution::from(s.execution).into(),
+            good_till: s.good_till.map(|t| t.timestamp_millis()),
+        }
+    }
+}
+
+impl From<SignalOutcome> for pb::SignalOutcome {
+    fn from(outcome: SignalOutcome) -> Self {
+        use pb::signal_outcome::{Failed, Outcome};
+        let outcome = match outcome {
+            SignalOutcome::Rejected { reason } => Outcome::Rejected(reason),
+            SignalOutcome::Submitted { order } => Outcome::Submitted(order.into()),
+            SignalOutcome::Failed { order, error } => Outcome::Failed(Failed {
+                order: Some(order.into()),
+                error,
+            }),
+        };
+        pb::SignalOutcome { outcome: Some(outcome) }
+    }
+}
+
+impl From<RiskStatus> for pb::RiskStatus {
+    fn from(r: RiskStatus) -> Self {
+        pb::RiskStatus {
+            trading_active: r.trading_active,
+            armed: r.armed,
+            bankroll: r.bankroll,
+            peak_bankroll: r.peak_bankroll,
+            min_bankroll: r.min_bankroll,
+            drawdown_pct: r.drawdown_pct,
+            max_drawdown_pct: r.max_drawdown_pct,
+            drawdown_utilization: r.drawdown_utilization,
+            exposure: r.exposure,
+            max_exposure: r.max_exposure,
+            exposure_utilization: r.exposure_utilization,
+            open_positions: r.open_positions as u64,
+            max_open_positions: r.max_open_positions as u64,
+            markets: r
+                .markets
+                .into_iter()
+                .map(|m| pb::MarketExposure {
+                    market_id: m.market_id.into(),
+                    exposure: m.exposure,
+                    utilization: m.utilization,
+                })
+                .collect(),
+            daily_pnl: r.daily_pnl,
+            cooldowns: r
+                .cooldowns
+                .into_iter()
+                .map(|c| pb::MarketCooldown {
+                    market_id: c.market_id.into(),
+                    until: c.until.timestamp_millis(),
+                    realized_loss: c.realized_loss,
+                    started_at: c.started_at.timestamp_millis(),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<StrategyHeartbeat> for pb::StrategyHeartbeat {
+    fn from(hb: StrategyHeartbeat) -> Self {
+        pb::StrategyHeartbeat {
+            name: hb.name,
+            instances: hb.instances as u64,
+            enabled: hb.enabled,
+            params_json: hb.params.to_string(),
+            schedule: hb.schedule,
+            scheduled: hb.scheduled,
+            evaluations: hb.evaluations,
+            signals: hb.signals,
+            last_evaluation: hb.last_evaluation.map(|t| t.timestamp_millis()),
+            last_signal: hb.last_signal.map(|t| t.timestamp_millis()),
+        }
+    }
+}
+
+impl From<LiquidationResult> for pb::Liquidation {
+    fn from(l: LiquidationResult) -> Self {
+        pb::Liquidation {
+            market_id: l.market_id.into(),
+            token_id: l.token_id.into(),
+            side: pb::Side::from(l.side).into(),
+            size: l.size,
+            limit_price: l.limit_price,
+            order_id: l.order_id,
+            status: l.status,
+            exit_price: l.exit_price,
+            error: l.error,
+        }
+    }
+}
+
+impl From<MarketData> for pb::MarketDataEvent {
+    fn from(event: MarketData) -> Self {
+        use pb::market_data_event::{Book, Event, FlowAlert, Game, KalshiTicker, Price, Resolution, SpotBook, Ticker, Trade};
+        let timestamp = event.timestamp().timestamp_millis();
+        let levels = |levels: Vec<domain::BookLevel>| {
+            levels
+                .into_iter()
+                .map(|l| pb::BookLevel { price: l.price, size: l.size })
+                .collect()
+        };
+        let event = match event {
+            MarketData::PolymarketPrice { market_id, token_id, price, .. } => {
+                Event::Price(Price {
+                    market_id: market_id.into(),
+                    token_id: token_id.into(),
+                    price,
+                })
+            }
+            MarketData::PolymarketOrderBook { market_id, token_id, book } => Event::Book(Book {
+                market_id: market_id.into(),
+                token_id: token_id.into(),
+                bids: levels(book.bids),
+                asks: levels(book.asks),
+            }),
+            MarketData::PolymarketTrade { market_id, token_id, price, size, side, .. } => Event::Trade(Trade {
+                market_id: market_id.into(),
+                token_id: token_id.into(),
+                price,
+                size,
+                side: pb::Side::from(side).into(),
+            }),
+            MarketData::BinanceTicker { symbol, price, .. } => Event::Ticker(Ticker { symbol, price }),
+            MarketData::BinanceOrderBook { symbol, book } => Event::SpotBook(SpotBook {
+                symbol,
+                bids: levels(book.bids),
+                asks: levels(book.asks),
+            }),
+            MarketData::KalshiTicker { ticker, yes_bid, yes_ask, .. } => {
+                Event::Kalshi(KalshiTicker { ticker, yes_bid, yes_ask })
+            }
+            MarketData::GameScore { game } => Event::Game(Game {
+                game_id: game.game_id,
+                league: game.league,
+                home_team: game.home_team,
+                away_team: game.away_team,
+                home_score: game.home_score,
+                away_score: game.away_score,
+                secs_remaining: game.secs_remaining,
+                in_progress: game.status == GameStatus::InProgress,
+            }),
+            MarketData::FlowAlert { alert } => Event::FlowAlert(FlowAlert {
+                market_id: alert.market_id.into(),
+                token_id: alert.token_id.into(),
+                sweep: alert.kind == FlowAlertKind::BookSweep,
+                side: pb::Side::from(alert.side).into(),
+                size: alert.size,
+                notional: alert.notional,
+                price: alert.price,
+                levels: alert.levels as u32,
+            }),
+            MarketData::Resolution { market_id, status, .. } => Event::Resolution(Resolution {
+                market_id: market_id.into(),
+                status: format!("{:?}", status).to_lowercase(),
+            }),
+        };
+        pb::MarketDataEvent {
+            timestamp,
+            event: Some(event),
+        }
+    }
+}
@@ -1,78 +1,687 @@
+mod auth;
+#[cfg(feature = "grpc")]
+pub mod grpc;
+
 use axum::{
-    extract::State,
+    extract::{Path, Query, State},
     http::StatusCode,
+    middleware,
     response::Json,
-    routing::{get, post},
+    routing::{delete, get, post, put},
     Router,
 };
-use serde::Serialize;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::sync::Arc;
 use std::time::Instant;
-use tokio::sync::RwLock;
+use tokio::sync::{mpsc, oneshot, RwLock};
 use tower_http::cors::CorsLayer;
+use tracing::error;
 
 use crate::adapters::database::Database;
 use crate::adapters::polymarket::PolymarketClient;
-use crate::engine::risk::RiskManager;
+use crate::bus::EventBus;
+use crate::config::Config;
+use crate::domain::{
+    Candle, Execution, ExposureSnapshot, FlowAlert, Market, MarketId, OrderBook, OrderId, OrderType, PnlSnapshot,
+    Position, Redemption, RiskEvent, Side, Signal, TokenId, VariantReport,
+};
+use crate::engine::analytics::{self, StrategyAnalytics};
+use crate::engine::inflight::{CancelReport, InflightOrders};
+use crate::engine::calibration::{self, CalibrationCurve};
+use crate::engine::candles::INTERVALS;
+use crate::engine::liquidation::{self, LiquidationResult};
+use crate::engine::order_manager::{ManualOrder, SignalOutcome};
+use crate::engine::participation::ParticipationTracker;
+use crate::engine::positions;
+use crate::engine::readiness::{Readiness, ReadinessStatus};
+use crate::engine::risk::{MarketList, MarketLists, RiskManager, RiskStatus};
+use crate::engine::ruin::{self, RuinReport};
+use crate::engine::experiments::{self, Experiment};
+use crate::engine::shadow::{self, ShadowReport};
+use crate::engine::snapshots::{self, PnlBreakdown, PnlSnapshotter};
+use crate::engine::wallet_health::{WalletHealth, WalletStatus};
+use crate::engine::whatif::{self, WhatIfReport};
+use crate::feeds::{BookCache, PriceCache};
+use crate::metrics::METRICS;
+use crate::strategy::heartbeat::{StrategyHeartbeat, StrategyMonitor};
+use crate::watchdog::{TaskStatus, TaskStatuses};
 
 pub struct AppState {
     pub db: Database,
+    pub orders: InflightOrders,
+    /// Manual order entry into the order manager; None where no order manager runs
+    pub manual_orders: Option<mpsc::Sender<ManualOrder>>,
+    /// Everything the process publishes: market data, signals, orders, fills,
+    /// risk decisions and feed status, for streaming clients
+    pub events: EventBus,
+    /// Latest prices and books off the feeds, read without ever blocking the
+    /// aggregator; empty where no feeds run
+    pub prices: PriceCache,
+    pub orderbooks: BookCache,
+    /// On-demand PnL snapshots; None where no snapshot task runs
+    pub snapshots: Option<PnlSnapshotter>,
+    /// Gas balance of the signing wallet; None where no wallet monitor runs
+    pub wallet: Option<WalletStatus>,
+    /// States of the tasks the watchdog supervises; None where none run
+    pub tasks: Option<TaskStatuses>,
+    /// Boot warm-up gate; None where nothing trades
+    pub readiness: Option<Readiness>,
     pub risk: RiskManager,
     pub poly_client: PolymarketClient,
     pub bankroll: Arc<RwLock<f64>>,
+    pub participation: Arc<ParticipationTracker>,
+    pub strategies: StrategyMonitor,
+    pub config: Arc<Config>,
     pub start_time: Instant,
 }
 
 pub fn router(state: Arc<AppState>) -> Router {
     Router::new()
         .route("/api/status", get(status))
+        .route("/api/tasks", get(tasks))
+        .route("/api/readiness", get(readiness))
         .route("/api/positions", get(positions))
+        .route("/api/positions/net", get(net_positions))
+        .route("/api/positions/whatif", get(whatif))
+        .route("/api/positions/{market_id}/{token_id}/close", post(close_position))
+        .route("/api/risk", get(risk))
+        .route("/api/risk/events", get(risk_events))
+        .route("/api/risk/ruin", get(ruin))
+        .route("/api/risk/markets", get(market_lists))
+        .route("/api/risk/markets/{list}/{market_id}", put(list_market).delete(unlist_market))
+        .route("/api/alerts/flow", get(flow_alerts))
+        .route("/api/redemptions", get(redemptions))
+        .route("/api/markets", get(markets))
+        .route("/api/markets/token/{token_id}", get(market_by_token))
+        .route("/api/prices", get(prices))
+        .route("/api/books/{token_id}", get(orderbook))
         .route("/api/trades", get(trades))
+        .route("/api/wallet", get(wallet))
         .route("/api/pnl", get(pnl))
-        .route("/api/orders", get(orders))
+        .route("/api/pnl/snapshot", post(take_pnl_snapshot))
+        .route("/api/candles", get(candles))
+        .route("/api/orders", get(orders).post(place_order))
+        .route("/api/orders/{id}", delete(cancel_order))
+        .route("/api/orders/market/{market_id}", delete(cancel_market_orders))
+        .route("/api/orders/strategy/{strategy}", delete(cancel_strategy_orders))
+        .route("/api/fills/probable", get(probable_fills))
         .route("/api/strategies", get(strategies))
+        .route("/api/strategies/shadow", get(shadow_report))
+        .route("/api/strategies/{name}/paused", put(pause_strategy).delete(resume_strategy))
+        .route("/api/experiments", get(experiments))
+        .route("/api/experiments/history", get(experiment_history))
+        .route("/api/config", get(config))
+        .route("/api/execution/venues", get(execution_venues))
+        .route("/api/analytics", get(analytics))
+        .route("/api/analytics/calibration", get(calibration))
+        .route("/api/analytics/participation", get(participation))
+        .route("/api/analytics/exposure", get(exposure_history))
+        .route("/api/metrics/latency", get(latency))
+        .route("/metrics", get(prometheus_metrics))
+        .route("/api/arm", post(arm))
+        .route("/api/disarm", post(disarm))
         .route("/api/kill", post(kill))
+        .route("/api/resume", post(resume))
+        .route_layer(middleware::from_fn_with_state(state.clone(), auth::require_token))
         .layer(CorsLayer::permissive())
         .with_state(state)
 }
 
+/// Start the gRPC interface alongside the router when GRPC_PORT is set
+pub fn spawn_grpc(state: &Arc<AppState>) {
+    let Some(port) = state.config.grpc_port else {
+        return;
+    };
+    #[cfg(feature = "grpc")]
+    {
+        let state = state.clone();
+        tokio::spawn(async move {
+            if let Err(e) = grpc::serve(state, port).await {
+                error!("gRPC interface failed: {:?}", e);
+            }
+        });
+    }
+    #[cfg(not(feature = "grpc"))]
+    tracing::warn!("GRPC_PORT={} ignored: built without the grpc feature", port);
+}
+
 #[derive(Serialize)]
 struct StatusResponse {
     bankroll: f64,
+    /// Realized plus unrealized
     pnl_total: f64,
+    /// From closed positions, net of fees
+    realized_pnl: f64,
+    /// Open positions marked to market
+    unrealized_pnl: f64,
     active_positions: usize,
     uptime_secs: u64,
     trading_active: bool,
+    /// Order submission enabled by the operator
+    armed: bool,
+}
+
+/// Handler error: status code plus a message for the caller
+type ApiError = (StatusCode, String);
+
+impl AppState {
+    /// Stored positions, once the fills queued so far are written
+    pub async fn positions(&self) -> crate::error::Result<Vec<Position>> {
+        self.db.flush().await;
+        Ok(self.db.get_positions().await?)
+    }
+
+    async fn status(&self) -> StatusResponse {
+        let bankroll = *self.bankroll.read().await;
+        let positions = self.positions().await.unwrap_or_default();
+        let pnl = match &self.snapshots {
+            Some(snapshots) => snapshots.breakdown().await,
+            // Without feeds in this process the cache is empty and positions are
+            // marked at their last fill
+            None => snapshots::pnl_breakdown(&self.db, &self.orderbooks).await,
+        };
+        let pnl = pnl.unwrap_or_else(|e| {
+            error!("PnL breakdown failed: {:?}", e);
+            PnlBreakdown::default()
+        });
+        let uptime = self.start_time.elapsed().as_secs();
+
+        StatusResponse {
+            bankroll,
+            pnl_total: pnl.total(),
+            realized_pnl: pnl.realized,
+            unrealized_pnl: pnl.unrealized,
+            active_positions: positions.len(),
+            uptime_secs: uptime,
+            trading_active: self.risk.is_active(),
+            armed: self.risk.is_armed(),
+        }
+    }
+
+    async fn risk_status(&self) -> crate::error::Result<RiskStatus> {
+        let bankroll = *self.bankroll.read().await;
+        let positions = self.positions().await?;
+        let day_start = Utc::now().date_naive().and_hms_opt(0, 0, 0).unwrap().and_utc();
+        let day_start_bankroll = self
+            .db
+            .get_pnl_history(day_start, Utc::now(), None)
+            .await?
+            .first()
+            .map(|s| s.bankroll);
+        Ok(self.risk.status(bankroll, &positions, day_start_bankroll).await)
+    }
+
+    async fn candles(&self, instrument: &str, interval: &str, limit: i64) -> Result<(i64, Vec<Candle>), ApiError> {
+        let Some(&(_, interval_secs)) = INTERVALS.iter().find(|(label, _)| *label == interval) else {
+            return Err((StatusCode::BAD_REQUEST, format!("unknown interval {}", interval)));
+        };
+        let candles = self
+            .db
+            .get_candles(instrument, interval_secs, limit.clamp(1, 5000))
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+        Ok((interval_secs, candles))
+    }
+
+    /// Equity curve between `from` (default: the beginning) and `to` (default: now),
+    /// optionally thinned to one point per `interval` such as "15m", "1h" or "1d"
+    async fn pnl_history(
+        &self,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+        interval: Option<&str>,
+    ) -> Result<Vec<PnlSnapshot>, ApiError> {
+        let bucket_secs = match interval {
+            Some(label) => Some(
+                parse_interval(label)
+                    .ok_or_else(|| (StatusCode::BAD_REQUEST, format!("bad interval {}", label)))?,
+            ),
+            None => None,
+        };
+        self.db
+            .get_pnl_history(from.unwrap_or(DateTime::UNIX_EPOCH), to.unwrap_or_else(Utc::now), bucket_secs)
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))
+    }
+
+    /// Manual order entry, subject to the same risk, cap and participation checks as strategy signals
+    async fn place_order(&self, req: PlaceOrderRequest) -> Result<SignalOutcome, ApiError> {
+        let manual_orders = self.manual_orders()?;
+        if !(req.price > 0.0 && req.price < 1.0) || req.size <= 0.0 {
+            return Err((StatusCode::BAD_REQUEST, "price must be in (0, 1) and size positive".into()));
+        }
+
+        let signal = Signal {
+            strategy: "manual".into(),
+            market_id: req.market_id.unwrap_or_else(|| MarketId::new(req.token_id.as_str())),
+            side: req.side,
+            confidence: 1.0,
+            price: req.price,
+            size: req.size,
+            event_time: None,
+            token_id: Some(req.token_id),
+            order_type: req.order_type,
+            execution: Execution::Passive,
+            expires_at: None,
+            good_till: req.good_till,
+        };
+        submit_manual(manual_orders, signal).await
+    }
+
+    /// Exit one position with an opposing order sized from the current book
+    async fn close_position(&self, market_id: &MarketId, token_id: &TokenId) -> Result<SignalOutcome, ApiError> {
+        let manual_orders = self.manual_orders()?;
+        let positions = self
+            .positions()
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+        let Some(position) = positions
+            .iter()
+            .find(|p| &p.market_id == market_id && &p.token_id == token_id)
+        else {
+            return Err((StatusCode::NOT_FOUND, format!("no position in {} / {}", market_id, token_id)));
+        };
+
+        let signal = liquidation::close_signal(&self.poly_client, position)
+            .await
+            .map_err(|e| (StatusCode::BAD_GATEWAY, format!("order book fetch failed: {}", e)))?;
+        submit_manual(manual_orders, signal).await
+    }
+
+    /// Cancel one open order by local ID, returning its exchange ID
+    async fn cancel_order(&self, id: &OrderId) -> Result<String, ApiError> {
+        let Some(order) = self.orders.get(id) else {
+            return Err((StatusCode::NOT_FOUND, format!("no open order {}", id)));
+        };
+        let Some(remote_id) = order.remote_id else {
+            return Err((StatusCode::CONFLICT, format!("order {} not yet acknowledged by the exchange", id)));
+        };
+
+        match self.orders.cancel(&self.poly_client, id).await {
+            Ok(true) => Ok(remote_id),
+            Ok(false) => Err((StatusCode::BAD_GATEWAY, format!("exchange rejected cancel of {}", remote_id))),
+            Err(e) => {
+                error!("Cancel {} failed: {:?}", id, e);
+                Err((StatusCode::BAD_GATEWAY, e.to_string()))
+            }
+        }
+    }
+
+    /// Cancel every open order on one market, or from one strategy
+    async fn cancel_group(&self, scope: CancelScope<'_>) -> Result<CancelReport, ApiError> {
+        let result = match scope {
+            CancelScope::Market(market_id) => self.orders.cancel_market(&self.poly_client, market_id).await,
+            CancelScope::Strategy(strategy) => self.orders.cancel_strategy(&self.poly_client, strategy).await,
+        };
+        result.map_err(|e| {
+            error!("Cancel of {:?} failed: {:?}", scope, e);
+            (StatusCode::BAD_GATEWAY, e.to_string())
+        })
+    }
+
+    /// Stop trading and cancel everything, optionally flattening positions too
+    async fn kill(&self, liquidate: bool) -> Vec<LiquidationResult> {
+        self.risk.kill();
+        if let Err(e) = self.orders.cancel_all(&self.poly_client).await {
+            error!("Kill: cancel all failed: {:?}", e);
+        }
+
+        if liquidate {
+            let (fees, bankroll) = (&self.config.fees, &self.bankroll);
+            liquidation::liquidate_all(&self.poly_client, &self.db, &self.orders, fees, &self.events, bankroll).await
+        } else {
+            Vec::new()
+        }
+    }
+
+    async fn take_pnl_snapshot(&self) -> Result<PnlSnapshot, ApiError> {
+        let snapshots = self
+            .snapshots
+            .as_ref()
+            .ok_or_else(|| (StatusCode::SERVICE_UNAVAILABLE, "snapshots not running".to_string()))?;
+        snapshots
+            .snapshot()
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))
+    }
+
+    fn manual_orders(&self) -> Result<&mpsc::Sender<ManualOrder>, ApiError> {
+        self.manual_orders
+            .as_ref()
+            .ok_or_else(|| (StatusCode::SERVICE_UNAVAILABLE, "order manager not running".into()))
+    }
 }
 
 async fn status(State(state): State<Arc<AppState>>) -> Json<StatusResponse> {
-    let bankroll = *state.bankroll.read().await;
-    let positions = state.db.get_positions().await.unwrap_or_default();
-    let pnl_total = bankroll - 500.0; // starting bankroll
-    let uptime = state.start_time.elapsed().as_secs();
-
-    Json(StatusResponse {
-        bankroll,
-        pnl_total,
-        active_positions: positions.len(),
-        uptime_secs: uptime,
-        trading_active: state.risk.is_active(),
-    })
+    Json(state.status().await)
+}
+
+/// Supervised tasks: running or not, restarts, and why each last stopped
+async fn tasks(State(state): State<Arc<AppState>>) -> Result<Json<BTreeMap<String, TaskStatus>>, ApiError> {
+    let tasks = state
+        .tasks
+        .as_ref()
+        .ok_or_else(|| (StatusCode::SERVICE_UNAVAILABLE, "no watchdog running".to_string()))?;
+    let statuses = tasks.read().unwrap().clone();
+    Ok(Json(statuses))
+}
+
+/// Boot warm-up: whether trading has started, and what it is still waiting on
+async fn readiness(State(state): State<Arc<AppState>>) -> Result<Json<ReadinessStatus>, ApiError> {
+    let readiness = state
+        .readiness
+        .as_ref()
+        .ok_or_else(|| (StatusCode::SERVICE_UNAVAILABLE, "no warm-up gate running".to_string()))?;
+    Ok(Json(readiness.status()))
 }
 
 async fn positions(State(state): State<Arc<AppState>>) -> Result<Json<serde_json::Value>, StatusCode> {
-    let positions = state.db.get_positions().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let positions = state.positions().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
     Ok(Json(serde_json::to_value(positions).unwrap()))
 }
 
-async fn trades(State(state): State<Arc<AppState>>) -> Result<Json<serde_json::Value>, StatusCode> {
-    let trades = state.db.get_recent_trades(100).await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+/// Utilization of each risk limit plus kill-switch state
+async fn risk(State(state): State<Arc<AppState>>) -> Result<Json<RiskStatus>, StatusCode> {
+    state.risk_status().await.map(Json).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+}
+
+#[derive(Deserialize)]
+struct RuinQuery {
+    /// Lookback in days; RUIN_LOOKBACK_DAYS when omitted, 0 for all history
+    days: Option<i64>,
+    /// Only this strategy's closed trades
+    strategy: Option<String>,
+    /// Comma-separated stake fractions, e.g. "0.02,0.05"
+    sizes: Option<String>,
+    paths: Option<usize>,
+    horizon: Option<usize>,
+}
+
+/// Chance of the bankroll falling to min_bankroll, from closed-trade returns
+/// resampled over simulated paths
+async fn ruin(
+    State(state): State<Arc<AppState>>,
+    Query(q): Query<RuinQuery>,
+) -> Result<Json<RuinReport>, ApiError> {
+    let sizes = match q.sizes {
+        Some(list) => Some(
+            list.split(',')
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(|s| s.parse::<f64>().ok().filter(|f| *f > 0.0 && *f <= 1.0))
+                .collect::<Option<Vec<f64>>>()
+                .ok_or_else(|| (StatusCode::BAD_REQUEST, format!("invalid sizes {:?}", list)))?,
+        ),
+        None => None,
+    };
+    let mut config = state.config.ruin.clone();
+    config.lookback_days = q.days.unwrap_or(config.lookback_days).max(0);
+    // Bounded so one request can't tie up a blocking thread for minutes
+    config.paths = q.paths.unwrap_or(config.paths).min(100_000);
+    config.horizon = q.horizon.unwrap_or(config.horizon).min(10_000);
+    let bankroll = *state.bankroll.read().await;
+    ruin::report(&state.db, &state.config, config, bankroll, sizes, q.strategy.as_deref())
+        .await
+        .map(Json)
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))
+}
+
+#[derive(Deserialize)]
+struct EventLogQuery {
+    /// Lookback in hours
+    #[serde(default = "default_exposure_hours")]
+    hours: i64,
+    #[serde(default = "default_event_limit")]
+    limit: i64,
+}
+
+fn default_event_limit() -> i64 {
+    500
+}
+
+/// Audit log of risk decisions, newest first
+async fn risk_events(
+    State(state): State<Arc<AppState>>,
+    Query(q): Query<EventLogQuery>,
+) -> Result<Json<Vec<RiskEvent>>, StatusCode> {
+    let since = Utc::now() - chrono::Duration::hours(q.hours.max(1));
+    let events = state
+        .db
+        .get_risk_events(since, q.limit.clamp(1, 5000))
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    Ok(Json(events))
+}
+
+/// Whale prints and book sweeps flagged by the flow detector, newest first
+async fn flow_alerts(
+    State(state): State<Arc<AppState>>,
+    Query(q): Query<EventLogQuery>,
+) -> Result<Json<Vec<FlowAlert>>, StatusCode> {
+    let since = Utc::now() - chrono::Duration::hours(q.hours.max(1));
+    let alerts = state
+        .db
+        .get_flow_alerts(since, q.limit.clamp(1, 5000))
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    Ok(Json(alerts))
+}
+
+/// Positions settled at resolution and how each was paid out, newest first
+async fn redemptions(
+    State(state): State<Arc<AppState>>,
+    Query(q): Query<EventLogQuery>,
+) -> Result<Json<Vec<Redemption>>, StatusCode> {
+    let since = Utc::now() - chrono::Duration::hours(q.hours.max(1));
+    let redemptions = state
+        .db
+        .get_redemptions(since, q.limit.clamp(1, 5000))
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    Ok(Json(redemptions))
+}
+
+/// Stored market metadata, by question
+async fn markets(State(state): State<Arc<AppState>>) -> Result<Json<Vec<Market>>, StatusCode> {
+    let mut markets = state.db.get_markets().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    markets.sort_by(|a, b| a.question.cmp(&b.question));
+    Ok(Json(markets))
+}
+
+/// The stored market a token trades in
+async fn market_by_token(
+    State(state): State<Arc<AppState>>,
+    Path(token_id): Path<TokenId>,
+) -> Result<Json<Market>, ApiError> {
+    state
+        .db
+        .get_market_by_token(&token_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .map(Json)
+        .ok_or_else(|| (StatusCode::NOT_FOUND, format!("no stored market for token {}", token_id)))
+}
+
+/// Latest price per token off the live feeds
+async fn prices(State(state): State<Arc<AppState>>) -> Json<BTreeMap<TokenId, f64>> {
+    Json(state.prices.snapshot().iter().map(|(token_id, price)| (token_id.clone(), *price)).collect())
+}
+
+/// Latest order book for a token off the live feeds
+async fn orderbook(
+    State(state): State<Arc<AppState>>,
+    Path(token_id): Path<TokenId>,
+) -> Result<Json<OrderBook>, ApiError> {
+    state
+        .orderbooks
+        .get(&token_id)
+        .map(|book| Json(OrderBook::clone(&book)))
+        .ok_or_else(|| (StatusCode::NOT_FOUND, format!("no live book for token {}", token_id)))
+}
+
+/// Markets allowed and excluded by hand
+async fn market_lists(State(state): State<Arc<AppState>>) -> Json<MarketLists> {
+    Json(state.risk.market_lists().await)
+}
+
+/// Add a market to the allow or deny list
+async fn list_market(
+    State(state): State<Arc<AppState>>,
+    Path((list, market_id)): Path<(MarketList, MarketId)>,
+) -> Result<Json<MarketLists>, ApiError> {
+    set_market_listed(&state, list, &market_id, true).await
+}
+
+/// Remove a market from the allow or deny list
+async fn unlist_market(
+    State(state): State<Arc<AppState>>,
+    Path((list, market_id)): Path<(MarketList, MarketId)>,
+) -> Result<Json<MarketLists>, ApiError> {
+    set_market_listed(&state, list, &market_id, false).await
+}
+
+async fn set_market_listed(
+    state: &AppState,
+    list: MarketList,
+    market_id: &MarketId,
+    listed: bool,
+) -> Result<Json<MarketLists>, ApiError> {
+    state
+        .risk
+        .set_market_listed(&state.db, list, market_id, listed)
+        .await
+        .map(Json)
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))
+}
+
+/// Exit one position with an opposing order sized from the current book
+async fn close_position(
+    State(state): State<Arc<AppState>>,
+    Path((market_id, token_id)): Path<(MarketId, TokenId)>,
+) -> Result<Json<SignalOutcome>, ApiError> {
+    state.close_position(&market_id, &token_id).await.map(Json)
+}
+
+/// Positions netted per market, flagging YES/NO pairs that can be merged
+async fn net_positions(State(state): State<Arc<AppState>>) -> Result<Json<serde_json::Value>, StatusCode> {
+    let positions = state.positions().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    Ok(Json(serde_json::to_value(positions::net_positions(&positions)).unwrap()))
+}
+
+#[derive(Deserialize)]
+struct WhatIfQuery {
+    /// Comma-separated spot shocks in percent, e.g. "-10,-3,3,10"
+    shocks: Option<String>,
+}
+
+/// Open positions re-priced under hypothetical spot moves with the threshold model
+async fn whatif(
+    State(state): State<Arc<AppState>>,
+    Query(q): Query<WhatIfQuery>,
+) -> Result<Json<WhatIfReport>, ApiError> {
+    let shocks = match q.shocks {
+        Some(list) => whatif::parse_shocks(&list)
+            .ok_or_else(|| (StatusCode::BAD_REQUEST, format!("invalid shocks {:?}", list)))?,
+        None => whatif::DEFAULT_SHOCKS.to_vec(),
+    };
+    whatif::reprice(&state.db, &state.config.pricing, &shocks)
+        .await
+        .map(Json)
+        .map_err(|e| (StatusCode::BAD_GATEWAY, e.to_string()))
+}
+
+/// POL balance and gas price on the signing wallet, from the latest check
+async fn wallet(State(state): State<Arc<AppState>>) -> Result<Json<WalletHealth>, ApiError> {
+    let status = state
+        .wallet
+        .as_ref()
+        .ok_or_else(|| (StatusCode::SERVICE_UNAVAILABLE, "wallet monitor not running".to_string()))?;
+    let health = status.read().unwrap().clone();
+    health
+        .map(Json)
+        .ok_or_else(|| (StatusCode::SERVICE_UNAVAILABLE, "no wallet check yet".to_string()))
+}
+
+#[derive(Deserialize)]
+struct TradesQuery {
+    /// Every trade from this RFC 3339 time on, oldest first; otherwise the latest `limit`
+    since: Option<DateTime<Utc>>,
+    limit: Option<i64>,
+}
+
+async fn trades(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<TradesQuery>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let trades = match query.since {
+        Some(since) => state.db.get_trades_since(since).await,
+        None => state.db.get_recent_trades(query.limit.unwrap_or(100).clamp(1, 10_000)).await,
+    }
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
     Ok(Json(serde_json::to_value(trades).unwrap()))
 }
 
-async fn pnl(State(state): State<Arc<AppState>>) -> Result<Json<serde_json::Value>, StatusCode> {
-    let history = state.db.get_pnl_history().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    Ok(Json(serde_json::to_value(history).unwrap()))
+#[derive(Deserialize)]
+struct PnlQuery {
+    /// RFC 3339 bounds, both optional
+    from: Option<DateTime<Utc>>,
+    to: Option<DateTime<Utc>>,
+    /// Bucket size like 5m, 1h or 1d; every snapshot when omitted
+    interval: Option<String>,
+}
+
+async fn pnl(
+    State(state): State<Arc<AppState>>,
+    Query(q): Query<PnlQuery>,
+) -> Result<Json<Vec<PnlSnapshot>>, ApiError> {
+    let history = state.pnl_history(q.from, q.to, q.interval.as_deref()).await?;
+    Ok(Json(history))
+}
+
+async fn take_pnl_snapshot(State(state): State<Arc<AppState>>) -> Result<Json<PnlSnapshot>, ApiError> {
+    Ok(Json(state.take_pnl_snapshot().await?))
+}
+
+/// Seconds in an interval label: a count followed by s, m, h or d
+fn parse_interval(label: &str) -> Option<i64> {
+    let unit = match label.chars().last()? {
+        's' => 1,
+        'm' => 60,
+        'h' => 3600,
+        'd' => 86400,
+        _ => return None,
+    };
+    let count: i64 = label[..label.len() - 1].parse().ok()?;
+    (count > 0).then_some(count * unit)
+}
+
+#[derive(Deserialize)]
+struct CandlesQuery {
+    /// Token ID or spot symbol (e.g. BTCUSDT)
+    instrument: String,
+    /// 1m, 5m or 1h
+    #[serde(default = "default_candle_interval")]
+    interval: String,
+    #[serde(default = "default_candle_limit")]
+    limit: i64,
+}
+
+fn default_candle_interval() -> String {
+    "1m".into()
+}
+
+fn default_candle_limit() -> i64 {
+    200
+}
+
+async fn candles(
+    State(state): State<Arc<AppState>>,
+    Query(q): Query<CandlesQuery>,
+) -> Result<Json<Vec<Candle>>, ApiError> {
+    let (_, candles) = state.candles(&q.instrument, &q.interval, q.limit).await?;
+    Ok(Json(candles))
 }
 
 async fn orders(State(state): State<Arc<AppState>>) -> Result<Json<serde_json::Value>, StatusCode> {
@@ -80,28 +689,358 @@ async fn orders(State(state): State<Arc<AppState>>) -> Result<Json<serde_json::V
     Ok(Json(serde_json::to_value(orders).unwrap()))
 }
 
+#[derive(Deserialize)]
+struct PlaceOrderRequest {
+    token_id: TokenId,
+    /// Defaults to the market the token is stored under, else the token ID
+    market_id: Option<MarketId>,
+    side: Side,
+    price: f64,
+    size: f64,
+    order_type: Option<OrderType>,
+    /// Expiry for a resting order; submitted GTD unless `order_type` says otherwise
+    #[serde(default)]
+    good_till: Option<DateTime<Utc>>,
+}
+
+async fn place_order(
+    State(state): State<Arc<AppState>>,
+    Json(req): Json<PlaceOrderRequest>,
+) -> Result<Json<SignalOutcome>, ApiError> {
+    state.place_order(req).await.map(Json)
+}
+
+/// Hand a signal to the order manager and wait for its verdict
+async fn submit_manual(manual_orders: &mpsc::Sender<ManualOrder>, signal: Signal) -> Result<SignalOutcome, ApiError> {
+    let (reply, outcome) = oneshot::channel();
+    manual_orders
+        .send(ManualOrder { signal, reply })
+        .await
+        .map_err(|_| (StatusCode::SERVICE_UNAVAILABLE, "order manager stopped".into()))?;
+    outcome
+        .await
+        .map_err(|_| (StatusCode::INTERNAL_SERVER_ERROR, "order manager dropped the request".into()))
+}
+
+/// Cancel one open order by local ID
+async fn cancel_order(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<OrderId>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let remote_id = state.cancel_order(&id).await?;
+    Ok(Json(serde_json::json!({
+        "status": "cancelled",
+        "order_id": id,
+        "remote_id": remote_id,
+    })))
+}
+
+/// Which open orders a group cancel takes down
+#[derive(Debug, Clone, Copy)]
+enum CancelScope<'a> {
+    Market(&'a MarketId),
+    Strategy(&'a str),
+}
+
+/// Cancel every open order on one market, leaving the rest of the book alone
+async fn cancel_market_orders(
+    State(state): State<Arc<AppState>>,
+    Path(market_id): Path<MarketId>,
+) -> Result<Json<CancelReport>, ApiError> {
+    Ok(Json(state.cancel_group(CancelScope::Market(&market_id)).await?))
+}
+
+/// Cancel every open order one strategy placed
+async fn cancel_strategy_orders(
+    State(state): State<Arc<AppState>>,
+    Path(strategy): Path<String>,
+) -> Result<Json<CancelReport>, ApiError> {
+    Ok(Json(state.cancel_group(CancelScope::Strategy(&strategy)).await?))
+}
+
+/// Tape-inferred fills awaiting reconciliation
+async fn probable_fills(State(state): State<Arc<AppState>>) -> Result<Json<serde_json::Value>, StatusCode> {
+    let fills = state.db.get_unconfirmed_fills().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    Ok(Json(serde_json::to_value(fills).unwrap()))
+}
+
 #[derive(Serialize)]
 struct StrategiesResponse {
-    strategies: Vec<StrategyInfo>,
+    strategies: Vec<StrategyHeartbeat>,
+}
+
+/// Per-strategy heartbeat: evaluation/signal counts and how long since each
+async fn strategies(State(state): State<Arc<AppState>>) -> Json<StrategiesResponse> {
+    Json(StrategiesResponse {
+        strategies: state.strategies.snapshot(),
+    })
+}
+
+/// Stop evaluating a strategy until it is resumed; survives restarts
+async fn pause_strategy(
+    State(state): State<Arc<AppState>>,
+    Path(name): Path<String>,
+) -> Result<Json<StrategyHeartbeat>, ApiError> {
+    set_strategy_paused(&state, &name, true).await
+}
+
+async fn resume_strategy(
+    State(state): State<Arc<AppState>>,
+    Path(name): Path<String>,
+) -> Result<Json<StrategyHeartbeat>, ApiError> {
+    set_strategy_paused(&state, &name, false).await
+}
+
+async fn set_strategy_paused(state: &AppState, name: &str, paused: bool) -> Result<Json<StrategyHeartbeat>, ApiError> {
+    state
+        .strategies
+        .set_paused(&state.db, name, paused)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .map(Json)
+        .ok_or_else(|| (StatusCode::NOT_FOUND, format!("no strategy {}", name)))
+}
+
+#[derive(Serialize)]
+struct ConfigResponse {
+    /// live, replay, or dashboard (server process without an order manager)
+    trading_mode: &'static str,
+    trading_active: bool,
+    feeds: FeedsConfig,
+    strategies: Vec<StrategyParams>,
+    config: Config,
 }
 
 #[derive(Serialize)]
-struct StrategyInfo {
+struct FeedsConfig {
+    polymarket_ws: bool,
+    binance_ws: bool,
+    recording: bool,
+    fill_tape: bool,
+}
+
+#[derive(Serialize)]
+struct StrategyParams {
     name: String,
     enabled: bool,
+    instances: usize,
+    params: serde_json::Value,
 }
 
-async fn strategies(State(_state): State<Arc<AppState>>) -> Json<StrategiesResponse> {
-    Json(StrategiesResponse {
-        strategies: vec![
-            StrategyInfo { name: "latency_arb".into(), enabled: true },
-            StrategyInfo { name: "intra_arb".into(), enabled: true },
-        ],
+/// Effective runtime configuration with secrets redacted
+async fn config(State(state): State<Arc<AppState>>) -> Json<ConfigResponse> {
+    let config = &state.config;
+    let trading_mode = if config.replay_path.is_some() {
+        "replay"
+    } else if state.manual_orders.is_some() {
+        "live"
+    } else {
+        "dashboard"
+    };
+    let live = trading_mode == "live";
+
+    Json(ConfigResponse {
+        trading_mode,
+        trading_active: state.risk.is_active(),
+        feeds: FeedsConfig {
+            polymarket_ws: live,
+            binance_ws: live,
+            recording: config.record_path.is_some(),
+            fill_tape: live && config.fill_tape_poll_secs > 0,
+        },
+        strategies: state
+            .strategies
+            .snapshot()
+            .into_iter()
+            .map(|hb| StrategyParams {
+                name: hb.name,
+                enabled: hb.enabled,
+                instances: hb.instances,
+                params: hb.params,
+            })
+            .collect(),
+        config: config.redacted(),
     })
 }
 
-async fn kill(State(state): State<Arc<AppState>>) -> Json<serde_json::Value> {
-    state.risk.kill();
-    let _ = state.poly_client.cancel_all().await;
-    Json(serde_json::json!({ "status": "killed", "trading_active": false }))
+async fn execution_venues(State(state): State<Arc<AppState>>) -> Json<serde_json::Value> {
+    Json(serde_json::to_value(state.poly_client.venue_report()).unwrap())
+}
+
+#[derive(Deserialize)]
+struct ExposureQuery {
+    /// Lookback in hours
+    #[serde(default = "default_exposure_hours")]
+    hours: i64,
+}
+
+fn default_exposure_hours() -> i64 {
+    24
+}
+
+/// Exposure and free collateral over time, from the periodic snapshots
+async fn exposure_history(
+    State(state): State<Arc<AppState>>,
+    Query(q): Query<ExposureQuery>,
+) -> Result<Json<Vec<ExposureSnapshot>>, StatusCode> {
+    let since = Utc::now() - chrono::Duration::hours(q.hours.max(1));
+    let history = state
+        .db
+        .get_exposure_history(since)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    Ok(Json(history))
+}
+
+#[derive(Deserialize)]
+struct AnalyticsQuery {
+    /// Lookback in days; all history when omitted
+    days: Option<i64>,
+}
+
+/// Win rate, profit factor, Sharpe/Sortino and drawdown overall and per strategy
+async fn analytics(
+    State(state): State<Arc<AppState>>,
+    Query(q): Query<AnalyticsQuery>,
+) -> Result<Json<Vec<StrategyAnalytics>>, StatusCode> {
+    let since = q
+        .days
+        .map(|d| Utc::now() - chrono::Duration::days(d.max(1)))
+        .unwrap_or(DateTime::UNIX_EPOCH);
+    let trades = state
+        .db
+        .get_closed_trades(since)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    Ok(Json(analytics::by_strategy(&trades)))
+}
+
+/// Reported confidence against resolved outcomes, overall and per strategy
+async fn calibration(
+    State(state): State<Arc<AppState>>,
+    Query(q): Query<AnalyticsQuery>,
+) -> Result<Json<Vec<CalibrationCurve>>, StatusCode> {
+    let since = q
+        .days
+        .map(|d| Utc::now() - chrono::Duration::days(d.max(1)))
+        .unwrap_or(DateTime::UNIX_EPOCH);
+    let records = state
+        .db
+        .get_resolved_signal_records(since)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    Ok(Json(calibration::curves(&records, &state.config.calibration)))
+}
+
+/// Hypothetical results of shadow-mode strategies: fills resolved in the window,
+/// plus every one still open
+async fn shadow_report(
+    State(state): State<Arc<AppState>>,
+    Query(q): Query<AnalyticsQuery>,
+) -> Result<Json<Vec<ShadowReport>>, StatusCode> {
+    let since = q
+        .days
+        .map(|d| Utc::now() - chrono::Duration::days(d.max(1)))
+        .unwrap_or(DateTime::UNIX_EPOCH);
+    let mut records = state
+        .db
+        .get_resolved_signal_records(since)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    records.extend(
+        state
+            .db
+            .get_unresolved_signal_records()
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?,
+    );
+    Ok(Json(shadow::report(&records)))
+}
+
+#[derive(Serialize)]
+struct ExperimentsResponse {
+    experiments: Vec<Experiment>,
+    reports: Vec<VariantReport>,
+}
+
+/// Configured A/B tests with their arms compared over the window (the configured
+/// lookback when `days` is omitted)
+async fn experiments(
+    State(state): State<Arc<AppState>>,
+    Query(q): Query<AnalyticsQuery>,
+) -> Result<Json<ExperimentsResponse>, StatusCode> {
+    let config = &state.config.experiments;
+    let since = Utc::now() - chrono::Duration::days(q.days.unwrap_or(config.lookback_days).max(1));
+    let experiments = experiments::from_config(config);
+    let reports = experiments::compare(&state.db, &experiments, since)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    Ok(Json(ExperimentsResponse { experiments, reports }))
+}
+
+/// Persisted A/B comparisons over time (the configured lookback when `days` is omitted)
+async fn experiment_history(
+    State(state): State<Arc<AppState>>,
+    Query(q): Query<AnalyticsQuery>,
+) -> Result<Json<Vec<VariantReport>>, StatusCode> {
+    let days = q.days.unwrap_or(state.config.experiments.lookback_days).max(1);
+    let reports = state
+        .db
+        .get_variant_reports(Utc::now() - chrono::Duration::days(days))
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    Ok(Json(reports))
+}
+
+/// Our share of each recently traded market's trailing 1h volume
+async fn participation(State(state): State<Arc<AppState>>) -> Result<Json<serde_json::Value>, StatusCode> {
+    let estimates = state.participation.estimates().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    Ok(Json(serde_json::to_value(estimates).unwrap()))
+}
+
+async fn latency() -> Json<serde_json::Value> {
+    Json(serde_json::to_value(METRICS.summaries()).unwrap())
+}
+
+/// Prometheus scrape endpoint
+async fn prometheus_metrics() -> String {
+    METRICS.render_prometheus()
+}
+
+/// Allow order submission; the bot starts disarmed unless AUTO_ARM is set
+async fn arm(State(state): State<Arc<AppState>>) -> Json<serde_json::Value> {
+    state.risk.arm();
+    Json(serde_json::json!({ "armed": true, "trading_active": state.risk.is_active() }))
+}
+
+async fn disarm(State(state): State<Arc<AppState>>) -> Json<serde_json::Value> {
+    state.risk.disarm();
+    Json(serde_json::json!({ "armed": false, "trading_active": state.risk.is_active() }))
+}
+
+/// Lift a halt (kill switch, drawdown, watchdog); the warm-up gate is unaffected
+async fn resume(State(state): State<Arc<AppState>>) -> Json<serde_json::Value> {
+    state.risk.resume();
+    Json(serde_json::json!({ "trading_active": state.risk.is_active() }))
+}
+
+#[derive(Deserialize, Default)]
+struct KillRequest {
+    /// Also flatten every open position after cancelling orders
+    #[serde(default)]
+    liquidate: bool,
+}
+
+async fn kill(
+    State(state): State<Arc<AppState>>,
+    body: Option<Json<KillRequest>>,
+) -> Json<serde_json::Value> {
+    let req = body.map(|Json(r)| r).unwrap_or_default();
+    let liquidations = state.kill(req.liquidate).await;
+
+    Json(serde_json::json!({
+        "status": "killed",
+        "trading_active": false,
+        "liquidations": liquidations,
+    }))
 }
@@ -0,0 +1,366 @@
+use chrono::{DateTime, Utc};
+use clap::{Parser, Subcommand, ValueEnum};
+use eyre::{eyre, Result, WrapErr};
+use reqwest::{Method, RequestBuilder};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::io::Write;
+
+use polymarket_bot::domain;
+use polymarket_bot::adapters::database::Database;
+use polymarket_bot::adapters::recorder;
+use polymarket_bot::config::Config;
+use polymarket_bot::domain::{Order, Position, Trade};
+use polymarket_bot::engine::backtest::{self, Backtester};
+use polymarket_bot::engine::market_catalog::MarketCatalog;
+use polymarket_bot::engine::walk_forward;
+
+/// Operator commands for a running bot, over its dashboard API
+#[derive(Parser)]
+#[command(name = "botctl", version)]
+struct Cli {
+    /// Dashboard API to talk to; defaults to this host on DASHBOARD_PORT
+    #[arg(long, env = "BOTCTL_URL")]
+    url: Option<String>,
+    /// Admin token, needed by every command that changes something
+    #[arg(long, env = "DASHBOARD_TOKEN", hide_env_values = true)]
+    token: Option<String>,
+    /// Print the API's JSON instead of a table
+    #[arg(long, global = true)]
+    json: bool,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Bankroll, PnL and whether trading is on
+    Status,
+    /// Open positions
+    Positions,
+    /// Open orders
+    Orders,
+    /// Cancel one open order by its local ID
+    Cancel { order_id: String },
+    /// Halt trading and cancel every open order
+    Kill {
+        /// Also flatten every open position
+        #[arg(long)]
+        liquidate: bool,
+    },
+    /// Lift a halt
+    Resume,
+    /// Allow order submission
+    Arm,
+    /// Refuse order submission
+    Disarm,
+    /// Strategies with their state and activity
+    Strategies,
+    /// Pause or resume one strategy
+    Strategy { name: String, action: StrategyAction },
+    /// Write trades as CSV or JSON
+    ExportTrades {
+        /// Every trade from this RFC 3339 time on; otherwise the latest `limit`
+        #[arg(long)]
+        since: Option<DateTime<Utc>>,
+        #[arg(long, default_value_t = 1000)]
+        limit: i64,
+        #[arg(long, value_enum, default_value_t = ExportFormat::Csv)]
+        format: ExportFormat,
+        /// File to write; stdout when omitted
+        #[arg(long, short)]
+        output: Option<String>,
+    },
+    /// Backtest a strategy over a market data recording, locally
+    Backtest {
+        recording: String,
+        /// latency_arb or mean_reversion
+        strategy: String,
+        /// Start of the window scored (RFC 3339); earlier events only warm up
+        #[arg(long)]
+        from: Option<DateTime<Utc>>,
+        /// End of the window (RFC 3339)
+        #[arg(long)]
+        to: Option<DateTime<Utc>>,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum StrategyAction {
+    Pause,
+    Resume,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum ExportFormat {
+    Csv,
+    Json,
+}
+
+/// Dashboard API client
+struct Api {
+    http: reqwest::Client,
+    url: String,
+    token: Option<String>,
+}
+
+impl Api {
+    fn request(&self, method: Method, path: &str) -> RequestBuilder {
+        let request = self.http.request(method, format!("{}{}", self.url, path));
+        match &self.token {
+            Some(token) => request.bearer_auth(token),
+            None => request,
+        }
+    }
+
+    async fn send<T: DeserializeOwned>(&self, request: RequestBuilder) -> Result<T> {
+        let response = request
+            .send()
+            .await
+            .wrap_err_with(|| format!("no answer from {}", self.url))?;
+        let status = response.status();
+        if !status.is_success() {
+            let path = response.url().path().to_string();
+            let body = response.text().await.unwrap_or_default();
+            return Err(eyre!("{} {}: {} {}", path, status, body, auth_hint(status)));
+        }
+        Ok(response.json().await?)
+    }
+
+    async fn get<T: DeserializeOwned>(&self, path: &str) -> Result<T> {
+        self.send(self.request(Method::GET, path)).await
+    }
+
+    async fn act(&self, method: Method, path: &str) -> Result<Value> {
+        self.send(self.request(method, path)).await
+    }
+}
+
+fn auth_hint(status: reqwest::StatusCode) -> &'static str {
+    match status {
+        reqwest::StatusCode::UNAUTHORIZED => "(check --token / DASHBOARD_TOKEN)",
+        reqwest::StatusCode::FORBIDDEN => "(the bot has no DASHBOARD_TOKEN set, so changes are disabled)",
+        _ => "",
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    color_eyre::install()?;
+    dotenvy::dotenv().ok();
+    let cli = Cli::parse();
+    let url = cli.url.unwrap_or_else(|| {
+        let port = std::env::var("DASHBOARD_PORT").unwrap_or_else(|_| "3001".to_string());
+        format!("http://127.0.0.1:{}", port)
+    });
+    let api = Api {
+        http: reqwest::Client::new(),
+        url: url.trim_end_matches('/').to_string(),
+        token: cli.token,
+    };
+    let json = cli.json;
+
+    match cli.command {
+        Command::Status => show(&api.get("/api/status").await?, json),
+        Command::Positions => {
+            let positions: Vec<Position> = api.get("/api/positions").await?;
+            if json {
+                return print_json(&positions);
+            }
+            let rows = positions.iter().map(|p| {
+                vec![
+                    p.strategy.clone(),
+                    p.market_id.to_string(),
+                    p.token_id.to_string(),
+                    p.side.to_string(),
+                    format!("{:.2}", p.size),
+                    format!("{:.4}", p.avg_price),
+                    format!("{:.4}", p.current_price),
+                    format!("{:.2}", p.pnl),
+                ]
+            });
+            print_table(&["STRATEGY", "MARKET", "TOKEN", "SIDE", "SIZE", "AVG", "PRICE", "PNL"], rows);
+        }
+        Command::Orders => {
+            let orders: Vec<Order> = api.get("/api/orders").await?;
+            if json {
+                return print_json(&orders);
+            }
+            let rows = orders.iter().map(|o| {
+                vec![
+                    o.id.to_string(),
+                    o.strategy.clone(),
+                    o.market_id.to_string(),
+                    o.side.to_string(),
+                    format!("{:.2}", o.size),
+                    format!("{:.4}", o.price),
+                    format!("{:?}", o.order_type),
+                    format!("{:?}", o.status),
+                    o.created_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+                ]
+            });
+            print_table(&["ID", "STRATEGY", "MARKET", "SIDE", "SIZE", "PRICE", "TYPE", "STATUS", "CREATED"], rows);
+        }
+        Command::Cancel { order_id } => show(&api.act(Method::DELETE, &format!("/api/orders/{}", order_id)).await?, json),
+        Command::Kill { liquidate } => {
+            let request = api
+                .request(Method::POST, "/api/kill")
+                .json(&serde_json::json!({ "liquidate": liquidate }));
+            show(&api.send(request).await?, json);
+        }
+        Command::Resume => show(&api.act(Method::POST, "/api/resume").await?, json),
+        Command::Arm => show(&api.act(Method::POST, "/api/arm").await?, json),
+        Command::Disarm => show(&api.act(Method::POST, "/api/disarm").await?, json),
+        Command::Strategies => {
+            let response: Value = api.get("/api/strategies").await?;
+            if json {
+                return print_json(&response);
+            }
+            let strategies = response["strategies"].as_array().cloned().unwrap_or_default();
+            let rows = strategies.iter().map(|s| {
+                let state = if !s["enabled"].as_bool().unwrap_or(false) {
+                    "disabled"
+                } else if s["paused"].as_bool().unwrap_or(false) {
+                    "paused"
+                } else if !s["scheduled"].as_bool().unwrap_or(true) {
+                    "off schedule"
+                } else {
+                    "running"
+                };
+                vec![
+                    s["name"].as_str().unwrap_or_default().to_string(),
+                    state.to_string(),
+                    s["instances"].to_string(),
+                    s["evaluations"].to_string(),
+                    s["signals"].to_string(),
+                    s["secs_since_signal"].as_i64().map(|t| format!("{}s", t)).unwrap_or_else(|| "-".into()),
+                ]
+            });
+            print_table(&["NAME", "STATE", "INSTANCES", "EVALUATIONS", "SIGNALS", "LAST SIGNAL"], rows);
+        }
+        Command::Strategy { name, action } => {
+            let method = match action {
+                StrategyAction::Pause => Method::PUT,
+                StrategyAction::Resume => Method::DELETE,
+            };
+            show(&api.act(method, &format!("/api/strategies/{}/paused", name)).await?, json);
+        }
+        Command::ExportTrades { since, limit, format, output } => {
+            let query = match since {
+                Some(since) => vec![("since", since.to_rfc3339())],
+                None => vec![("limit", limit.to_string())],
+            };
+            let mut trades: Vec<Trade> = api.send(api.request(Method::GET, "/api/trades").query(&query)).await?;
+            trades.sort_by_key(|t| t.timestamp);
+            let mut out: Box<dyn Write> = match &output {
+                Some(path) => Box::new(std::fs::File::create(path).wrap_err_with(|| format!("creating {}", path))?),
+                None => Box::new(std::io::stdout().lock()),
+            };
+            match format {
+                ExportFormat::Json => serde_json::to_writer_pretty(&mut out, &trades)?,
+                ExportFormat::Csv => {
+                    writeln!(out, "timestamp,id,order_id,market_id,side,price,size,fee")?;
+                    for t in &trades {
+                        writeln!(
+                            out,
+                            "{},{},{},{},{},{},{},{}",
+                            t.timestamp.to_rfc3339(),
+                            t.id,
+                            t.order_id,
+                            t.market_id,
+                            t.side,
+                            t.price,
+                            t.size,
+                            t.fee
+                        )?;
+                    }
+                }
+            }
+            out.flush()?;
+            if let Some(path) = output {
+                eprintln!("{} trades written to {}", trades.len(), path);
+            }
+        }
+        Command::Backtest { recording, strategy, from, to } => {
+            let config = Config::load()?;
+            let db = Database::connect(&config).await?;
+            let events = recorder::read_recording(&recording).await?;
+            let (Some(first), Some(last)) = (events.first(), events.last()) else {
+                return Err(eyre!("{} has no events", recording));
+            };
+            let from = from.unwrap_or(first.0);
+            let to = to.unwrap_or(last.0 + chrono::Duration::seconds(1));
+            let tokens = backtest::recorded_tokens(&events);
+            let covered: Vec<domain::Market> = db
+                .get_markets()
+                .await?
+                .into_iter()
+                .filter(|m| m.outcomes.iter().any(|o| tokens.contains(&o.token_id)))
+                .collect();
+            let instances = walk_forward::instances(&strategy, &covered, &config)?;
+            let backtester = Backtester::new(
+                config.risk.starting_bankroll,
+                config.execution.max_slippage_bps,
+                config.stats.clone(),
+                config.fees.clone(),
+                MarketCatalog::load(&db).await?,
+            );
+            let result = backtester.run(&instances, &events, from, to).await;
+            if json {
+                return print_json(&result);
+            }
+            println!("{} × {} over {} → {}", strategy, instances.len(), from.to_rfc3339(), to.to_rfc3339());
+            println!("signals     {}", result.signals);
+            println!("fills       {}", result.fills);
+            println!("realized    {:.2}", result.realized);
+            println!("unrealized  {:.2}", result.unrealized);
+            println!("fees        {:.2}", result.fees);
+            println!("pnl         {:.2}", result.pnl());
+        }
+    }
+    Ok(())
+}
+
+fn print_json<T: serde::Serialize>(value: &T) -> Result<()> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
+
+/// Objects as `key  value` lines unless JSON was asked for; anything else as JSON
+fn show(value: &Value, json: bool) {
+    match value {
+        Value::Object(fields) if !json => {
+            let width = fields.keys().map(String::len).max().unwrap_or(0);
+            for (key, value) in fields {
+                let shown = match value {
+                    Value::String(s) => s.clone(),
+                    other => other.to_string(),
+                };
+                println!("{:width$}  {}", key, shown, width = width);
+            }
+        }
+        other => println!("{}", serde_json::to_string_pretty(other).unwrap_or_default()),
+    }
+}
+
+/// Left-aligned columns sized to their widest cell
+fn print_table(headers: &[&str], rows: impl Iterator<Item = Vec<String>>) {
+    let rows: Vec<Vec<String>> = rows.collect();
+    if rows.is_empty() {
+        println!("(none)");
+        return;
+    }
+    let widths: Vec<usize> = headers
+        .iter()
+        .enumerate()
+        .map(|(i, h)| rows.iter().map(|r| r[i].chars().count()).max().unwrap_or(0).max(h.len()))
+        .collect();
+    let line = |cells: Vec<&str>| {
+        let padded: Vec<String> = cells.iter().zip(&widths).map(|(c, w)| format!("{:w$}", c, w = w)).collect();
+        println!("{}", padded.join("  ").trim_end());
+    };
+    line(headers.to_vec());
+    for row in &rows {
+        line(row.iter().map(String::as_str).collect());
+    }
+}
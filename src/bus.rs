@@ -0,0 +1,238 @@
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+use std::marker::PhantomData;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::sync::Notify;
+
+use crate::domain::{FeedStatus, MarketData, Order, RiskEvent, Signal, Trade};
+
+/// A subscriber this far behind starts losing its oldest events
+const MAX_PENDING: usize = 65_536;
+
+/// Everything the engine publishes, on one bus
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", content = "data", rename_all = "snake_case")]
+pub enum Event {
+    MarketData(MarketData),
+    /// A signal as a strategy emits it, before risk checks
+    Signal(Signal),
+    /// An order whenever it changes: placed, acknowledged, filled or cancelled
+    OrderUpdate(Order),
+    /// A booked fill
+    Fill(Trade),
+    RiskEvent(RiskEvent),
+    /// A feed connecting or dropping
+    FeedStatus(FeedStatus),
+}
+
+/// What a receiver subscribes to: one kind of event, or `Event` for all of them
+pub trait Topic: Sized {
+    fn matches(event: &Event) -> bool;
+    fn from_event(event: Event) -> Option<Self>;
+}
+
+impl Topic for Event {
+    fn matches(_: &Event) -> bool {
+        true
+    }
+
+    fn from_event(event: Event) -> Option<Self> {
+        Some(event)
+    }
+}
+
+macro_rules! topic {
+    ($variant:ident, $ty:ty) => {
+        impl Topic for $ty {
+            fn matches(event: &Event) -> bool {
+                matches!(event, Event::$variant(_))
+            }
+
+            fn from_event(event: Event) -> Option<Self> {
+                match event {
+                    Event::$variant(inner) => Some(inner),
+                    _ => None,
+                }
+            }
+        }
+
+        impl From<$ty> for Event {
+            fn from(inner: $ty) -> Self {
+                Event::$variant(inner)
+            }
+        }
+    };
+}
+
+topic!(MarketData, MarketData);
+topic!(Signal, Signal);
+topic!(OrderUpdate, Order);
+topic!(Fill, Trade);
+topic!(RiskEvent, RiskEvent);
+topic!(FeedStatus, FeedStatus);
+
+/// Fan-out for every engine event, with latest-value semantics for market data.
+///
+/// Every subscriber gets its own queue holding just the kinds it subscribed to.
+/// If a subscriber falls behind, newer updates for the same token/symbol
+/// overwrite the pending one in place rather than queueing (or being dropped the
+/// way broadcast `Lagged` did), so a slow consumer always sees the freshest state
+/// and never an unbounded backlog. Likewise a feed's status replaces its pending
+/// one. Trades, flow alerts, signals, order updates, fills and risk events are
+/// the exception: each is its own event, so every one is delivered unless the
+/// subscriber is `MAX_PENDING` behind. A lossless subscriber gets that treatment
+/// for every event.
+#[derive(Clone, Default)]
+pub struct EventBus {
+    subscribers: Arc<Mutex<Vec<Subscriber>>>,
+    /// Gives each event that doesn't conflate a key of its own
+    seq: Arc<AtomicU64>,
+}
+
+struct Subscriber {
+    queue: Arc<ConflatingQueue>,
+    wants: fn(&Event) -> bool,
+    /// Every event queued on its own, none overwritten
+    lossless: bool,
+}
+
+/// One subscriber's end of the bus, yielding events of kind `T`
+pub struct EventReceiver<T> {
+    queue: Arc<ConflatingQueue>,
+    topic: PhantomData<fn() -> T>,
+}
+
+#[derive(Default)]
+struct ConflatingQueue {
+    pending: Mutex<Pending>,
+    notify: Notify,
+}
+
+#[derive(Default)]
+struct Pending {
+    order: VecDeque<String>,
+    latest: HashMap<String, Event>,
+    /// Events lost to the backlog limit since the receiver last asked
+    missed: u64,
+}
+
+/// Events sharing a key conflate: one price, one book per token, one ticker per
+/// symbol, one status per feed
+fn conflation_key(event: &Event, seq: &AtomicU64) -> String {
+    let unique = || format!("seq:{}", seq.fetch_add(1, Ordering::Relaxed));
+    let Event::MarketData(data) = event else {
+        return match event {
+            Event::FeedStatus(status) => format!("feed:{}", status.feed),
+            _ => unique(),
+        };
+    };
+    match data {
+        MarketData::PolymarketPrice { token_id, .. } => format!("price:{}", token_id),
+        MarketData::PolymarketOrderBook { token_id, .. } => format!("book:{}", token_id),
+        MarketData::PolymarketTrade { .. } => unique(),
+        MarketData::BinanceTicker { symbol, .. } => format!("binance:{}", symbol),
+        MarketData::BinanceOrderBook { symbol, .. } => format!("binance_book:{}", symbol),
+        MarketData::KalshiTicker { ticker, .. } => format!("kalshi:{}", ticker),
+        MarketData::GameScore { game } => format!("game:{}", game.game_id),
+        MarketData::Resolution { market_id, .. } => format!("resolution:{}", market_id),
+        MarketData::FlowAlert { .. } => unique(),
+    }
+}
+
+impl EventBus {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Receive events of kind `T` from now on; `Event` receives all of them
+    pub fn subscribe<T: Topic>(&self) -> EventReceiver<T> {
+        self.subscribe_with(false)
+    }
+
+    /// Like `subscribe`, but nothing conflates: every event is delivered in order
+    /// unless the receiver falls `MAX_PENDING` behind, which it can see in
+    /// `take_missed`. For consumers that must see each tick, such as the recorder.
+    pub fn subscribe_lossless<T: Topic>(&self) -> EventReceiver<T> {
+        self.subscribe_with(true)
+    }
+
+    fn subscribe_with<T: Topic>(&self, lossless: bool) -> EventReceiver<T> {
+        let queue = Arc::new(ConflatingQueue::default());
+        self.subscribers.lock().unwrap().push(Subscriber {
+            queue: queue.clone(),
+            wants: T::matches,
+            lossless,
+        });
+        EventReceiver {
+            queue,
+            topic: PhantomData,
+        }
+    }
+
+    pub fn publish(&self, event: impl Into<Event>) {
+        let event = event.into();
+        let mut subscribers = self.subscribers.lock().unwrap();
+        // A queue only the bus still holds belongs to a dropped receiver
+        subscribers.retain(|s| Arc::strong_count(&s.queue) > 1);
+        let mut key = None;
+        let mut unique = None;
+        for subscriber in subscribers.iter().filter(|s| (s.wants)(&event)) {
+            let key = if subscriber.lossless {
+                unique.get_or_insert_with(|| format!("seq:{}", self.seq.fetch_add(1, Ordering::Relaxed)))
+            } else {
+                key.get_or_insert_with(|| conflation_key(&event, &self.seq))
+            };
+            let mut pending = subscriber.queue.pending.lock().unwrap();
+            if pending.latest.insert(key.clone(), event.clone()).is_none() {
+                pending.order.push_back(key.clone());
+                if pending.order.len() > MAX_PENDING {
+                    if let Some(oldest) = pending.order.pop_front() {
+                        pending.latest.remove(&oldest);
+                        pending.missed += 1;
+                    }
+                }
+            }
+            drop(pending);
+            subscriber.queue.notify.notify_one();
+        }
+    }
+
+    /// Largest backlog across subscribers, for producers that want to pace themselves
+    pub fn max_pending(&self) -> usize {
+        self.subscribers
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|s| s.queue.pending.lock().unwrap().order.len())
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+impl<T: Topic> EventReceiver<T> {
+    /// Next pending event, oldest key first
+    pub async fn recv(&mut self) -> T {
+        loop {
+            {
+                let mut pending = self.queue.pending.lock().unwrap();
+                while let Some(event) = pending.order.pop_front().and_then(|key| pending.latest.remove(&key)) {
+                    if let Some(event) = T::from_event(event) {
+                        return event;
+                    }
+                }
+            }
+            self.queue.notify.notified().await;
+        }
+    }
+
+    /// True when every update has been consumed
+    pub fn is_empty(&self) -> bool {
+        self.queue.pending.lock().unwrap().order.is_empty()
+    }
+
+    /// Events lost to falling too far behind since the last call
+    pub fn take_missed(&mut self) -> u64 {
+        std::mem::take(&mut self.queue.pending.lock().unwrap().missed)
+    }
+}
@@ -0,0 +1,68 @@
+use arc_swap::ArcSwap;
+use std::borrow::Borrow;
+use std::hash::Hash;
+use std::sync::Arc;
+
+/// A point-in-time copy of a cache. Cloning one is a pointer copy, and a copy
+/// that is then edited shares every entry it didn't touch.
+pub type Snapshot<K, V> = im::HashMap<K, V>;
+
+/// Latest value per key, shared between tasks without a lock.
+///
+/// The map lives behind an atomically swapped `Arc`. Readers take a snapshot (a
+/// pointer load) and keep it as long as they like; a writer swaps in a new
+/// version with its entry changed, so no reader ever waits on a writer and no
+/// writer on a reader. The map is persistent, so a new version copies only the
+/// path to the changed entry rather than the whole map.
+pub struct LatestCache<K, V> {
+    map: Arc<ArcSwap<Snapshot<K, V>>>,
+}
+
+impl<K, V> Clone for LatestCache<K, V> {
+    fn clone(&self) -> Self {
+        Self { map: self.map.clone() }
+    }
+}
+
+impl<K, V> Default for LatestCache<K, V> {
+    fn default() -> Self {
+        Self {
+            map: Arc::new(ArcSwap::from_pointee(Snapshot::new())),
+        }
+    }
+}
+
+impl<K: Eq + Hash + Clone, V: Clone> LatestCache<K, V> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Every entry as of now; later writes don't show up in it
+    pub fn snapshot(&self) -> Snapshot<K, V> {
+        Snapshot::clone(&self.map.load())
+    }
+
+    pub fn get<Q>(&self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.map.load().get(key).cloned()
+    }
+
+    pub fn len(&self) -> usize {
+        self.map.load().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.load().is_empty()
+    }
+
+    pub fn insert(&self, key: K, value: V) {
+        self.map.rcu(|map| {
+            let mut map = Snapshot::clone(map);
+            map.insert(key.clone(), value.clone());
+            map
+        });
+    }
+}
@@ -0,0 +1,75 @@
+use chrono::{DateTime, Utc};
+use std::sync::{Arc, RwLock};
+use std::time::Instant;
+
+/// Where the trading path reads the time.
+///
+/// The feed aggregator, strategies (through the context's `now`), the order
+/// manager and the risk checks behind it take their time from a clock rather
+/// than the system, so the backtester can run them on recorded time and tests
+/// can freeze it. Background tasks that poll external services keep the
+/// wall clock: there is no simulated time for them to follow.
+pub trait Clock: Send + Sync {
+    fn now(&self) -> DateTime<Utc>;
+    /// Monotonic reading, for measuring intervals
+    fn instant(&self) -> Instant;
+}
+
+/// Shared handle on a clock; the system clock unless something injects another
+pub type SharedClock = Arc<dyn Clock>;
+
+pub fn system() -> SharedClock {
+    Arc::new(SystemClock)
+}
+
+/// The wall clock
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+
+    fn instant(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// Time that moves only when told to: set from event timestamps by a replay,
+/// or frozen. Its instants advance with it from the moment it was created.
+#[derive(Debug)]
+pub struct ManualClock {
+    start: DateTime<Utc>,
+    start_instant: Instant,
+    now: RwLock<DateTime<Utc>>,
+}
+
+impl ManualClock {
+    pub fn new(start: DateTime<Utc>) -> Self {
+        Self {
+            start,
+            start_instant: Instant::now(),
+            now: RwLock::new(start),
+        }
+    }
+
+    /// Move to `now`; earlier times are ignored, so instants never run backwards
+    pub fn set(&self, now: DateTime<Utc>) {
+        let mut current = self.now.write().unwrap();
+        if now > *current {
+            *current = now;
+        }
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> DateTime<Utc> {
+        *self.now.read().unwrap()
+    }
+
+    fn instant(&self) -> Instant {
+        let elapsed = (self.now() - self.start).to_std().unwrap_or_default();
+        self.start_instant + elapsed
+    }
+}
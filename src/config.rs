@@ -1,24 +1,109 @@
-use eyre::{Result, WrapErr};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
-#[derive(Debug, Clone, Deserialize)]
+use crate::domain::{EconImpact, MarketId};
+use crate::error::{Error, Result};
+
+const REDACTED: &str = "[redacted]";
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Config {
     pub private_key: String,
     pub polymarket_api_key: String,
     pub polymarket_secret: String,
     pub polymarket_passphrase: String,
     pub risk: RiskConfig,
+    pub execution: ExecutionConfig,
+    pub anomaly: AnomalyConfig,
+    pub stats: StatsConfig,
+    pub mean_reversion: MeanReversionConfig,
+    pub cross_strike: CrossStrikeConfig,
+    pub resolution_snipe: ResolutionSnipeConfig,
+    pub copy_trade: CopyTradeConfig,
+    pub kalshi: KalshiConfig,
+    pub sports: SportsConfig,
+    pub calendar: CalendarConfig,
+    pub flow_alerts: FlowAlertConfig,
+    pub binance: BinanceConfig,
+    pub hedge: HedgeConfig,
+    pub pricing: PricingConfig,
+    pub fees: FeeConfig,
+    pub calibration: CalibrationConfig,
+    pub resolution_monitor: ResolutionMonitorConfig,
+    pub wallet_health: WalletHealthConfig,
+    pub redemption: RedemptionConfig,
+    pub merge: MergeConfig,
+    pub allocator: AllocatorConfig,
+    pub walk_forward: WalkForwardConfig,
+    pub ruin: RuinConfig,
+    pub watchdog: WatchdogConfig,
+    pub shutdown: ShutdownConfig,
+    pub readiness: ReadinessConfig,
     pub db_path: String,
+    /// Postgres connection string; when set it replaces the SQLite file at `db_path`
+    pub database_url: Option<String>,
+    pub db: DbConfig,
     pub dashboard_port: u16,
+    /// gRPC interface port (needs the `grpc` build feature); None leaves it off
+    pub grpc_port: Option<u16>,
+    pub api_auth: ApiAuthConfig,
+    pub backup: BackupConfig,
+    pub watchlist: WatchlistConfig,
+    /// Order submission venues as `name|base_url|mode` specs; submissions rotate
+    /// across them for latency A/B comparison. Empty uses the default CLOB endpoint.
+    pub execution_venues: Vec<String>,
+    /// Strategy name → cron-style activation window (UTC); strategies not listed always run
+    pub strategy_schedules: BTreeMap<String, String>,
+    /// Strategies that run in shadow mode: signals filled in simulation and recorded,
+    /// never sent to the exchange
+    pub shadow_strategies: Vec<String>,
+    pub experiments: ExperimentConfig,
+    /// Seconds between public-tape fill detection passes (0 disables). This is the
+    /// fallback for when the authenticated user channel isn't available.
+    pub fill_tape_poll_secs: u64,
+    /// Seconds between passes that sync order status from the exchange (0 disables)
+    pub order_sync_secs: u64,
+    /// Seconds between scheduled PnL snapshots (0 disables; fills and the API still take them)
+    pub pnl_snapshot_secs: u64,
+    /// Seconds between engine state checkpoints (0 disables; shutdown still takes one)
+    pub checkpoint_secs: u64,
+    /// Seconds between passes that archive settled markets (0 disables)
+    pub archive_interval_secs: u64,
+    /// Seconds between refreshes of the stored market metadata (0 disables)
+    pub market_refresh_secs: u64,
+    /// Append all market data to this JSONL file when set
+    pub record_path: Option<String>,
+    /// Replay this recording instead of connecting live feeds (no orders are sent)
+    pub replay_path: Option<String>,
+    /// Replay speed multiplier; 0 replays as fast as consumers keep up
+    pub replay_speed: f64,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct RiskConfig {
     pub max_position_pct: f64,
     pub max_drawdown_pct: f64,
     pub min_bankroll: f64,
     pub starting_bankroll: f64,
     pub max_exposure: f64,
+    /// Maximum number of simultaneously open positions across all strategies
+    pub max_open_positions: usize,
+    /// Maximum number of simultaneously open positions per strategy
+    pub max_positions_per_strategy: usize,
+    /// Realized loss (USDC) on one market that puts it on cooldown (0 disables)
+    pub cooldown_loss: f64,
+    /// How long a market stays blocked for new entries after such a loss
+    pub cooldown_secs: u64,
+    /// Largest share of the visible depth an order may take at its limit price (0 disables)
+    pub max_book_share: f64,
+    /// Only these markets may be entered (empty allows all); seeds the list until it is edited via the API
+    pub market_allowlist: Vec<MarketId>,
+    /// Markets that may not be entered; seeds the list until it is edited via the API
+    pub market_denylist: Vec<MarketId>,
+    /// No new entries from this many seconds before a calendar release...
+    pub econ_blackout_before_secs: u64,
+    /// ...until this many seconds after it (both 0 disables the blackout)
+    pub econ_blackout_after_secs: u64,
 }
 
 impl Default for RiskConfig {
@@ -29,24 +114,893 @@ impl Default for RiskConfig {
             min_bankroll: 350.0,
             starting_bankroll: 500.0,
             max_exposure: 100.0,
+            max_open_positions: 10,
+            max_positions_per_strategy: 5,
+            cooldown_loss: 5.0,
+            cooldown_secs: 21600,
+            max_book_share: 0.25,
+            market_allowlist: Vec::new(),
+            market_denylist: Vec::new(),
+            econ_blackout_before_secs: 0,
+            econ_blackout_after_secs: 0,
+        }
+    }
+}
+
+/// Hard limits applied to every order at submission, independent of bankroll math
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ExecutionConfig {
+    /// Maximum shares in a single order
+    pub max_order_shares: f64,
+    /// Maximum notional (shares × price, USDC) in a single order
+    pub max_order_notional: f64,
+    /// Maximum share of a market's trailing 1h volume we may trade (0 disables)
+    pub max_participation: f64,
+    /// Smallest new order in shares; orders that reduce a position are exempt
+    pub min_order_shares: f64,
+    /// Smallest new order notional (USDC); orders that reduce a position are exempt
+    pub min_order_notional: f64,
+    /// Positions worth less than this (USDC) are swept as dust
+    pub dust_notional: f64,
+    /// Seconds between dust sweeps (0 disables)
+    pub dust_sweep_secs: u64,
+    /// Arm order submission automatically once startup preflight checks pass;
+    /// otherwise it waits for POST /api/arm
+    pub auto_arm: bool,
+    /// Signals reaching the order manager more than this long after the market data
+    /// that triggered them are dropped (0 disables)
+    pub signal_ttl_ms: u64,
+    /// Per-strategy overrides of `signal_ttl_ms`
+    pub signal_ttls: BTreeMap<String, u64>,
+    /// A working order on the same token and side priced within this of a new
+    /// signal makes it a duplicate (0 disables)
+    pub duplicate_price_band: f64,
+    /// How far past the signal price (basis points) a marketable order may walk the
+    /// book; thinner books downsize the order instead
+    pub max_slippage_bps: f64,
+    /// Take-style signals go out FOK (all or nothing) rather than FAK (whatever
+    /// the book has, remainder cancelled)
+    pub take_fill_or_kill: bool,
+    /// Signals a strategy may send through per minute (0 disables); exits are exempt
+    pub max_signals_per_minute: u32,
+    /// Per-strategy overrides of `max_signals_per_minute`
+    pub signal_rate_limits: BTreeMap<String, u32>,
+    /// After a fill, the same strategy's new entries in that market wait this long (0 disables)
+    pub fill_cooldown_secs: u64,
+    /// Per-strategy overrides of `fill_cooldown_secs`
+    pub fill_cooldowns: BTreeMap<String, u64>,
+}
+
+impl Default for ExecutionConfig {
+    fn default() -> Self {
+        Self {
+            max_order_shares: 1000.0,
+            max_order_notional: 50.0,
+            max_participation: 0.10,
+            min_order_shares: 5.0,
+            min_order_notional: 1.0,
+            dust_notional: 0.50,
+            dust_sweep_secs: 3600,
+            auto_arm: false,
+            signal_ttl_ms: 5000,
+            signal_ttls: BTreeMap::from([("latency_arb".to_string(), 500)]),
+            duplicate_price_band: 0.02,
+            max_slippage_bps: 50.0,
+            take_fill_or_kill: false,
+            max_signals_per_minute: 30,
+            signal_rate_limits: BTreeMap::new(),
+            fill_cooldown_secs: 30,
+            fill_cooldowns: BTreeMap::new(),
+        }
+    }
+}
+
+impl ExecutionConfig {
+    /// How long a strategy's signals stay actionable, if they expire at all
+    pub fn signal_ttl(&self, strategy: &str) -> Option<chrono::Duration> {
+        let ms = self.signal_ttls.get(strategy).copied().unwrap_or(self.signal_ttl_ms);
+        (ms > 0).then(|| chrono::Duration::milliseconds(ms as i64))
+    }
+
+    /// Signals per minute a strategy is held to, if any
+    pub fn signal_rate_limit(&self, strategy: &str) -> Option<u32> {
+        let limit = self.signal_rate_limits.get(strategy).copied().unwrap_or(self.max_signals_per_minute);
+        (limit > 0).then_some(limit)
+    }
+
+    /// How long a strategy waits to re-enter a market it just filled in, if at all
+    pub fn fill_cooldown(&self, strategy: &str) -> Option<std::time::Duration> {
+        let secs = self.fill_cooldowns.get(strategy).copied().unwrap_or(self.fill_cooldown_secs);
+        (secs > 0).then_some(std::time::Duration::from_secs(secs))
+    }
+}
+
+/// Midpoint-vs-spot consistency guard for latency-arb markets
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AnomalyConfig {
+    /// Lookback for comparing the two moves
+    pub window_secs: u64,
+    /// Absolute YES midpoint change that counts as a jump (0 disables the guard)
+    pub midpoint_jump: f64,
+    /// Spot moves smaller than this fraction don't explain a jump
+    pub spot_move_pct: f64,
+    /// How long new entries stay blocked after an anomaly
+    pub block_secs: u64,
+}
+
+impl Default for AnomalyConfig {
+    fn default() -> Self {
+        Self {
+            window_secs: 60,
+            midpoint_jump: 0.10,
+            spot_move_pct: 0.002,
+            block_secs: 300,
+        }
+    }
+}
+
+/// Rolling price statistics the aggregator maintains for strategies
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct StatsConfig {
+    /// Window for mean, std-dev, z-score and realized volatility
+    pub window_secs: u64,
+    /// EMA half-life
+    pub ema_half_life_secs: u64,
+}
+
+impl Default for StatsConfig {
+    fn default() -> Self {
+        Self {
+            window_secs: 300,
+            ema_half_life_secs: 60,
+        }
+    }
+}
+
+/// Mean-reversion strategy; its lookback is the stats window (`STATS_WINDOW_SECS`)
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct MeanReversionConfig {
+    pub enabled: bool,
+    /// |z| of the YES price that triggers a fade
+    pub entry_z: f64,
+    /// Exit once the price is back within this |z| of the mean
+    pub exit_z: f64,
+    /// Stop out if the deviation keeps growing past this |z|
+    pub stop_z: f64,
+    /// A same-direction spot move of at least this z means the deviation is news
+    pub spot_corroboration_z: f64,
+    /// Taker flow on YES at least this one-sided (imbalance, 0–1) in the move's
+    /// direction means it is being pushed by size; 0 ignores flow
+    pub flow_corroboration: f64,
+    /// Ticks required in the window before trusting its statistics
+    pub min_samples: usize,
+    pub max_position_pct: f64,
+}
+
+impl Default for MeanReversionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            entry_z: 2.5,
+            exit_z: 0.5,
+            stop_z: 4.0,
+            spot_corroboration_z: 1.5,
+            flow_corroboration: 0.6,
+            min_samples: 30,
+            max_position_pct: 0.02,
+        }
+    }
+}
+
+/// Cross-strike arbitrage across "<asset> above $X" ladders
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CrossStrikeConfig {
+    pub enabled: bool,
+    /// Gamma search queries, one per ladder (e.g. "bitcoin above on october 17");
+    /// matches are split by asset and expiry, each split with 2+ strikes is a ladder
+    pub groups: Vec<String>,
+    /// Minimum amount a higher strike's YES must cost over a lower strike's to trade
+    pub min_violation: f64,
+    pub max_position_pct: f64,
+}
+
+impl Default for CrossStrikeConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            groups: Vec::new(),
+            min_violation: 0.02,
+            max_position_pct: 0.02,
+        }
+    }
+}
+
+/// Buying the all-but-decided side of threshold markets shortly before they resolve
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ResolutionSnipeConfig {
+    pub enabled: bool,
+    /// Only markets ending within this many hours are considered
+    pub max_hours_to_end: f64,
+    /// Model probability the bought side must reach
+    pub min_probability: f64,
+    /// Never pay more than this for the near-certain side
+    pub max_price: f64,
+    /// Minimum annualized return over the capital lockup, as a fraction
+    pub min_annualized_return: f64,
+    /// Expected delay between end_date and payout, counted as lockup
+    pub settlement_hours: f64,
+    pub max_position_pct: f64,
+}
+
+impl Default for ResolutionSnipeConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_hours_to_end: 6.0,
+            min_probability: 0.98,
+            max_price: 0.97,
+            min_annualized_return: 0.5,
+            settlement_hours: 2.0,
+            max_position_pct: 0.05,
+        }
+    }
+}
+
+/// Mirroring entries made by tracked wallets
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CopyTradeConfig {
+    pub enabled: bool,
+    /// Proxy wallet addresses to follow (lowercased)
+    pub wallets: Vec<String>,
+    /// Seconds between polls of each wallet's trades
+    pub poll_secs: u64,
+    /// Our size as a fraction of theirs
+    pub size_ratio: f64,
+    /// Most USDC mirrored per wallet since startup, unless overridden per wallet
+    pub default_wallet_cap: f64,
+    /// Wallet → allocation cap (USDC)
+    pub wallet_caps: BTreeMap<String, f64>,
+    /// Entries older than this are not copied
+    pub max_age_secs: u64,
+    /// How far above their fill we will still pay
+    pub max_slippage: f64,
+    /// Never copy entries priced above this
+    pub max_price: f64,
+}
+
+impl Default for CopyTradeConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            wallets: Vec::new(),
+            poll_secs: 15,
+            size_ratio: 0.1,
+            default_wallet_cap: 50.0,
+            wallet_caps: BTreeMap::new(),
+            max_age_secs: 120,
+            max_slippage: 0.02,
+            max_price: 0.95,
+        }
+    }
+}
+
+impl CopyTradeConfig {
+    pub fn wallet_cap(&self, wallet: &str) -> f64 {
+        self.wallet_caps.get(wallet).copied().unwrap_or(self.default_wallet_cap)
+    }
+}
+
+/// Kalshi connectivity and the Polymarket/Kalshi cross-venue arb
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct KalshiConfig {
+    pub enabled: bool,
+    pub api_url: String,
+    pub ws_url: String,
+    /// API key ID; without it (and the key file) only public REST polling is available
+    pub key_id: Option<String>,
+    /// PEM file holding the RSA private key that signs requests
+    pub private_key_path: Option<String>,
+    /// Polymarket market ID → Kalshi ticker for contracts that settle on the same event
+    pub pairs: BTreeMap<MarketId, String>,
+    /// Minimum locked-in edge per contract after both venues' fees
+    pub min_edge: f64,
+    /// Kalshi's fee coefficient: fee per contract is rate × P × (1 − P)
+    pub kalshi_fee_rate: f64,
+    pub max_position_pct: f64,
+}
+
+impl Default for KalshiConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            api_url: "https://api.elections.kalshi.com/trade-api/v2".to_string(),
+            ws_url: "wss://api.elections.kalshi.com/trade-api/ws/v2".to_string(),
+            key_id: None,
+            private_key_path: None,
+            pairs: BTreeMap::new(),
+            min_edge: 0.01,
+            kalshi_fee_rate: 0.07,
+            max_position_pct: 0.02,
         }
     }
 }
 
+/// Live score feed and the in-game strategy that trades against it
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SportsConfig {
+    pub enabled: bool,
+    /// Scoreboard API root; league paths are appended (ESPN's public site API)
+    pub api_url: String,
+    /// Seconds between scoreboard polls
+    pub poll_secs: u64,
+    /// Polymarket market ID → `league:game_id:home|away`, the team its YES outcome backs
+    pub games: BTreeMap<MarketId, String>,
+    /// Minimum model probability over the all-in price of the side bought
+    pub min_edge: f64,
+    /// Scores older than this are too stale to trade against
+    pub max_score_age_secs: u64,
+    pub max_position_pct: f64,
+}
+
+impl Default for SportsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            api_url: "https://site.api.espn.com/apis/site/v2/sports".to_string(),
+            poll_secs: 10,
+            games: BTreeMap::new(),
+            min_edge: 0.08,
+            max_score_age_secs: 30,
+            max_position_pct: 0.02,
+        }
+    }
+}
+
+/// Economic calendar feeding scheduled releases to the strategies and the risk manager
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CalendarConfig {
+    pub enabled: bool,
+    /// JSON calendar in the Forex Factory export format (`title`, `country`,
+    /// `date`, `impact`); None uses only `events`
+    pub url: Option<String>,
+    /// Seconds between calendar fetches
+    pub poll_secs: u64,
+    /// Releases known ahead of time, as `name@RFC3339`; taken as high impact
+    pub events: Vec<String>,
+    /// Only releases about these currencies count
+    pub countries: Vec<String>,
+    /// Only releases at least this impactful count
+    pub min_impact: EconImpact,
+    /// How close to a release (either side, seconds) strategies treat it as live
+    pub window_secs: u64,
+    /// latency_arb multiplies its required edge by this inside the window;
+    /// mean_reversion stops opening fades
+    pub edge_mult: f64,
+}
+
+impl Default for CalendarConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            url: Some("https://nfs.faireconomy.media/ff_calendar_thisweek.json".to_string()),
+            poll_secs: 3600,
+            events: Vec::new(),
+            countries: vec!["USD".to_string()],
+            min_impact: EconImpact::High,
+            window_secs: 1800,
+            edge_mult: 2.0,
+        }
+    }
+}
+
+/// Whale detection on the Polymarket tape and books
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct FlowAlertConfig {
+    pub enabled: bool,
+    /// Any print at least this large (USDC) is flagged
+    pub large_notional: f64,
+    /// A print this many times the token's average trade size is flagged...
+    pub size_mult: f64,
+    /// ...when it is also at least this large (USDC)
+    pub min_notional: f64,
+    /// Prints in the rolling average trade size
+    pub baseline_trades: usize,
+    /// A sweep takes out at least this many levels between two book snapshots...
+    pub sweep_levels: usize,
+    /// ...worth at least this much (USDC)
+    pub sweep_notional: f64,
+    /// How long an alert stays in the strategy context
+    pub ttl_secs: u64,
+    /// URLs every alert is POSTed to as JSON
+    pub webhooks: Vec<String>,
+}
+
+impl Default for FlowAlertConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            large_notional: 5000.0,
+            size_mult: 10.0,
+            min_notional: 500.0,
+            baseline_trades: 200,
+            sweep_levels: 3,
+            sweep_notional: 1000.0,
+            ttl_secs: 300,
+            webhooks: Vec::new(),
+        }
+    }
+}
+
+/// Which Binance stream drives spot prices
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BinanceStream {
+    /// Rolling 24h ticker, pushed about once a second
+    Ticker,
+    /// Every aggregated trade, at its price
+    AggTrade,
+    /// Every best bid/ask change, at the midpoint
+    BookTicker,
+}
+
+impl BinanceStream {
+    /// Stream name suffix, as in `btcusdt@aggTrade`
+    pub fn stream_name(self) -> &'static str {
+        match self {
+            BinanceStream::Ticker => "ticker",
+            BinanceStream::AggTrade => "aggTrade",
+            BinanceStream::BookTicker => "bookTicker",
+        }
+    }
+}
+
+/// Binance spot feed and how latency_arb reads its depth
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct BinanceConfig {
+    /// Price stream; the trade and quote streams update many times a second
+    pub stream: BinanceStream,
+    /// Book levels per side to stream (5, 10 or 20); 0 leaves the depth streams off
+    pub depth_levels: usize,
+    /// latency_arb skips entries when the side of the spot book backing the trade
+    /// (bids for YES, asks for NO) holds less than this share of the streamed depth
+    pub min_depth_share: f64,
+}
+
+impl Default for BinanceConfig {
+    fn default() -> Self {
+        Self {
+            stream: BinanceStream::AggTrade,
+            depth_levels: 0,
+            min_depth_share: 0.0,
+        }
+    }
+}
+
+/// Polymarket trading fees, see `engine::fees`
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct FeeConfig {
+    /// Fee rate for resting (maker) fills
+    pub maker_rate: f64,
+    /// Fee rate for fills that take liquidity
+    pub taker_rate: f64,
+    /// Exponent on price × (1 − price); 0 makes the rate a flat fraction of notional
+    pub exponent: f64,
+}
+
+impl Default for FeeConfig {
+    fn default() -> Self {
+        Self {
+            maker_rate: 0.0,
+            taker_rate: 0.25,
+            exponent: 2.0,
+        }
+    }
+}
+
+/// Offsetting the spot delta of crypto threshold positions with Binance USDⓈ-M perps
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct HedgeConfig {
+    pub enabled: bool,
+    /// Futures REST endpoint; point at the testnet to try it out
+    pub base_url: String,
+    pub api_key: Option<String>,
+    pub api_secret: Option<String>,
+    /// Share of the delta to offset (1 hedges all of it)
+    pub hedge_ratio: f64,
+    /// Only trade once the perp is this far (USDT notional) off its target
+    pub band_usd: f64,
+    /// Cap on the perp position per symbol (USDT notional)
+    pub max_notional: f64,
+    /// Seconds between rebalances
+    pub rebalance_secs: u64,
+}
+
+impl Default for HedgeConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            base_url: "https://fapi.binance.com".to_string(),
+            api_key: None,
+            api_secret: None,
+            hedge_ratio: 1.0,
+            band_usd: 50.0,
+            max_notional: 1000.0,
+            rebalance_secs: 60,
+        }
+    }
+}
+
+/// Volatility inputs for the threshold-market probability model
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PricingConfig {
+    /// Annualized volatility for symbols without their own entry
+    pub default_vol: f64,
+    /// Spot symbol → annualized volatility
+    pub vols: BTreeMap<String, f64>,
+}
+
+impl Default for PricingConfig {
+    fn default() -> Self {
+        Self {
+            default_vol: 0.6,
+            vols: BTreeMap::new(),
+        }
+    }
+}
+
+impl PricingConfig {
+    pub fn vol(&self, symbol: &str) -> f64 {
+        self.vols.get(symbol).copied().unwrap_or(self.default_vol)
+    }
+}
+
+/// Oracle resolution tracking on held markets
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ResolutionMonitorConfig {
+    /// Seconds between resolution status checks (0 disables)
+    pub poll_secs: u64,
+    /// Sell out of markets whose resolution gets disputed instead of holding through the vote
+    pub exit_on_dispute: bool,
+}
+
+impl Default for ResolutionMonitorConfig {
+    fn default() -> Self {
+        Self {
+            poll_secs: 300,
+            exit_on_dispute: false,
+        }
+    }
+}
+
+/// Gas balance checks on the signing wallet, which pays for redeems and splits
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct WalletHealthConfig {
+    /// Polygon JSON-RPC endpoint
+    pub rpc_url: String,
+    /// Seconds between checks (0 disables)
+    pub poll_secs: u64,
+    /// Gas one redeem or split takes
+    pub gas_per_tx: f64,
+    /// Alert when the POL balance covers fewer redeems/splits than this at current gas
+    pub reserve_txs: f64,
+    /// Alert when gas is above this many gwei
+    pub max_gas_gwei: f64,
+}
+
+impl Default for WalletHealthConfig {
+    fn default() -> Self {
+        Self {
+            rpc_url: "https://polygon-rpc.com".to_string(),
+            poll_secs: 300,
+            gas_per_tx: 250_000.0,
+            reserve_txs: 20.0,
+            max_gas_gwei: 500.0,
+        }
+    }
+}
+
+/// Settling positions in resolved markets
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RedemptionConfig {
+    /// Seconds between passes over held markets for resolutions (0 disables)
+    pub poll_secs: u64,
+    /// Send the redeem transaction from the signing wallet; otherwise winners are
+    /// marked for manual redemption
+    pub on_chain: bool,
+    /// Gas limit on a redeem transaction
+    pub gas_limit: u64,
+    /// URLs each manual redemption is POSTed to as JSON
+    pub webhooks: Vec<String>,
+}
+
+impl Default for RedemptionConfig {
+    fn default() -> Self {
+        Self {
+            poll_secs: 600,
+            on_chain: false,
+            gas_limit: 300_000,
+            webhooks: Vec::new(),
+        }
+    }
+}
+
+/// Merging complete YES+NO sets back into collateral
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct MergeConfig {
+    /// Seconds between passes over held markets for sets to merge (0 disables).
+    /// Merges are sent from the signing wallet.
+    pub poll_secs: u64,
+    /// Fewest complete sets worth the gas of a merge
+    pub min_sets: f64,
+    /// Gas limit on a merge transaction
+    pub gas_limit: u64,
+}
+
+impl Default for MergeConfig {
+    fn default() -> Self {
+        Self {
+            poll_secs: 0,
+            min_sets: 10.0,
+            gas_limit: 300_000,
+        }
+    }
+}
+
+/// A/B tests of strategy parameters: extra parameterizations of a strategy that
+/// run alongside it, one of the set trading live and the rest in shadow mode
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ExperimentConfig {
+    /// Variant name → `base:param=value,...`: every instance of strategy `base`
+    /// again, with those parameters overridden, under the variant's name
+    pub variants: BTreeMap<String, String>,
+    /// Base strategy → the one of it and its variants that trades live (the base
+    /// when not listed); the others run in shadow mode
+    pub live: BTreeMap<String, String>,
+    /// Seconds between persisted comparison reports (0 disables; the API still compares on demand)
+    pub report_secs: u64,
+    /// Days of resolved signals a persisted report covers
+    pub lookback_days: i64,
+}
+
+impl Default for ExperimentConfig {
+    fn default() -> Self {
+        Self {
+            variants: BTreeMap::new(),
+            live: BTreeMap::new(),
+            report_secs: 86400,
+            lookback_days: 30,
+        }
+    }
+}
+
+/// Signal confidence vs. how the markets resolved
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CalibrationConfig {
+    /// Seconds between passes that look up resolutions for recorded signals (0 disables)
+    pub poll_secs: u64,
+    /// Days of resolved signals behind the curves and size scales
+    pub lookback_days: i64,
+    /// Scale down the size of strategies whose win rate trails their confidence
+    pub shrink_sizes: bool,
+    /// Resolved signals a strategy needs before its size is scaled
+    pub min_samples: usize,
+    /// Floor on the size scale
+    pub min_size_scale: f64,
+}
+
+impl Default for CalibrationConfig {
+    fn default() -> Self {
+        Self {
+            poll_secs: 3600,
+            lookback_days: 90,
+            shrink_sizes: false,
+            min_samples: 30,
+            min_size_scale: 0.25,
+        }
+    }
+}
+
+/// Walk-forward parameter search over a market data recording (`bot optimize`)
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct WalkForwardConfig {
+    /// Hours of each fold the candidates are ranked on
+    pub train_hours: i64,
+    /// Hours after that the fold's winner is scored on; folds step forward by this much
+    pub test_hours: i64,
+    /// Random draws from the parameter ranges (0 searches the full grid)
+    pub samples: usize,
+    /// Seed for the random draws, so a search can be rerun exactly
+    pub seed: u64,
+}
+
+impl Default for WalkForwardConfig {
+    fn default() -> Self {
+        Self {
+            train_hours: 24,
+            test_hours: 6,
+            samples: 0,
+            seed: 1,
+        }
+    }
+}
+
+/// Monte Carlo risk-of-ruin estimates from resampled closed-trade returns
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RuinConfig {
+    /// Simulated bankroll paths per sizing
+    pub paths: usize,
+    /// Trades along each path
+    pub horizon: usize,
+    /// Days of closed trades resampled (0 uses all history)
+    pub lookback_days: i64,
+    pub seed: u64,
+}
+
+impl Default for RuinConfig {
+    fn default() -> Self {
+        Self {
+            paths: 10_000,
+            horizon: 500,
+            lookback_days: 90,
+            seed: 1,
+        }
+    }
+}
+
+/// Supervision of the feeds, aggregator and order manager
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct WatchdogConfig {
+    /// Seconds between checks of each task's heartbeat
+    pub check_secs: u64,
+    /// A task stuck this long on one item is restarted (0 disables)
+    pub stall_secs: u64,
+    /// A feed silent this long is restarted, reconnecting it (0 disables)
+    pub feed_silence_secs: u64,
+    /// First restart delay, doubling on each restart in a row...
+    pub backoff_secs: u64,
+    /// ...up to this
+    pub max_backoff_secs: u64,
+    /// Trading halts once a critical task has been down this long
+    pub halt_after_secs: u64,
+}
+
+impl Default for WatchdogConfig {
+    fn default() -> Self {
+        Self {
+            check_secs: 5,
+            stall_secs: 60,
+            feed_silence_secs: 120,
+            backoff_secs: 1,
+            max_backoff_secs: 60,
+            halt_after_secs: 30,
+        }
+    }
+}
+
+/// What happens between CTRL+C (or SIGTERM) and exit
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ShutdownConfig {
+    /// Cancel every resting order once the order manager has stopped
+    pub cancel_orders: bool,
+    /// Longest wait for each step (the order manager finishing its signal, buffers
+    /// being written, API connections closing) before moving on
+    pub timeout_secs: u64,
+}
+
+impl Default for ShutdownConfig {
+    fn default() -> Self {
+        Self {
+            cancel_orders: false,
+            timeout_secs: 15,
+        }
+    }
+}
+
+/// Boot warm-up: trading waits until every feed has delivered data for each
+/// instrument it covers and open orders have been reconciled with the exchange
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct ReadinessConfig {
+    /// Start trading anyway after this long, logging what never arrived (0 waits indefinitely)
+    pub max_wait_secs: u64,
+}
+
+/// Per-strategy bankroll budgets from trailing performance
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AllocatorConfig {
+    /// Seconds between rebalances (0 disables; every strategy sizes off the full bankroll)
+    pub rebalance_secs: u64,
+    /// Days of closed trades behind each strategy's return and correlation estimates
+    pub lookback_days: i64,
+    /// Closed trades a strategy needs before it is sized on its own record
+    pub min_trades: usize,
+    /// Multiplier on the full-Kelly fraction
+    pub kelly_fraction: f64,
+    /// Share of the bankroll for strategies without enough history
+    pub default_share: f64,
+    /// Bounds on the share of a strategy with a track record
+    pub min_share: f64,
+    pub max_share: f64,
+}
+
+impl Default for AllocatorConfig {
+    fn default() -> Self {
+        Self {
+            rebalance_secs: 3600,
+            lookback_days: 30,
+            min_trades: 20,
+            kelly_fraction: 0.5,
+            default_share: 0.2,
+            min_share: 0.05,
+            max_share: 0.5,
+        }
+    }
+}
+
+/// SQLite locking and write-behind batching
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DbConfig {
+    /// How long a SQLite connection waits on a lock before failing, in milliseconds
+    pub busy_timeout_ms: u64,
+    /// Most queued writes committed in one transaction
+    pub write_batch: usize,
+}
+
+impl Default for DbConfig {
+    fn default() -> Self {
+        Self {
+            busy_timeout_ms: 5000,
+            write_batch: 256,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct BackupConfig {
+    /// Directory that scheduled backups are written to
+    pub dir: String,
+    /// Seconds between scheduled backups (0 disables them)
+    pub interval_secs: u64,
+    /// Number of most recent backups to keep
+    pub keep: usize,
+}
+
+impl Default for BackupConfig {
+    fn default() -> Self {
+        Self {
+            dir: "backups".to_string(),
+            interval_secs: 3600,
+            keep: 48,
+        }
+    }
+}
+
+/// Bearer tokens for the dashboard API
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct ApiAuthConfig {
+    /// Required for mutating (non-GET) routes; they are refused entirely when unset
+    pub admin_token: Option<String>,
+    /// When set, GET routes require this or the admin token; otherwise reads are open
+    pub read_token: Option<String>,
+}
+
+/// Markets the bot subscribes to and instantiates strategies for
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct WatchlistConfig {
+    /// Explicit market condition IDs
+    pub market_ids: Vec<MarketId>,
+    /// Gamma search queries; every active market they return is watched
+    pub queries: Vec<String>,
+}
+
 impl Config {
     pub fn load() -> Result<Self> {
         dotenvy::dotenv().ok();
 
-        let private_key =
-            std::env::var("PRIVATE_KEY").wrap_err("PRIVATE_KEY not set")?;
-        let polymarket_api_key =
-            std::env::var("POLYMARKET_API_KEY").wrap_err("POLYMARKET_API_KEY not set")?;
-        let polymarket_secret =
-            std::env::var("POLYMARKET_SECRET").wrap_err("POLYMARKET_SECRET not set")?;
-        let polymarket_passphrase =
-            std::env::var("POLYMARKET_PASSPHRASE").wrap_err("POLYMARKET_PASSPHRASE not set")?;
-        let db_path =
-            std::env::var("DB_PATH").unwrap_or_else(|_| "bot.db".to_string());
+        let private_key = required("PRIVATE_KEY")?;
+        let polymarket_api_key = required("POLYMARKET_API_KEY")?;
+        let polymarket_secret = required("POLYMARKET_SECRET")?;
+        let polymarket_passphrase = required("POLYMARKET_PASSPHRASE")?;
+        let db_path = Self::db_path();
         let dashboard_port: u16 = std::env::var("DASHBOARD_PORT")
             .unwrap_or_else(|_| "3001".to_string())
             .parse()
@@ -58,6 +1012,297 @@ impl Config {
             min_bankroll: env_f64("MIN_BANKROLL", 350.0),
             starting_bankroll: env_f64("STARTING_BANKROLL", 500.0),
             max_exposure: env_f64("MAX_EXPOSURE", 100.0),
+            max_open_positions: env_usize("MAX_OPEN_POSITIONS", 10),
+            max_positions_per_strategy: env_usize("MAX_POSITIONS_PER_STRATEGY", 5),
+            cooldown_loss: env_f64("LOSS_COOLDOWN_USD", 5.0),
+            cooldown_secs: env_u64("LOSS_COOLDOWN_SECS", 21600),
+            max_book_share: env_f64("MAX_BOOK_SHARE", 0.25),
+            market_allowlist: env_list("MARKET_ALLOWLIST").into_iter().map(MarketId::from).collect(),
+            market_denylist: env_list("MARKET_DENYLIST").into_iter().map(MarketId::from).collect(),
+            econ_blackout_before_secs: env_u64("ECON_BLACKOUT_BEFORE_SECS", 0),
+            econ_blackout_after_secs: env_u64("ECON_BLACKOUT_AFTER_SECS", 0),
+        };
+
+        let execution = ExecutionConfig {
+            max_order_shares: env_f64("MAX_ORDER_SHARES", 1000.0),
+            max_order_notional: env_f64("MAX_ORDER_NOTIONAL", 50.0),
+            max_participation: env_f64("MAX_PARTICIPATION_PCT", 0.10),
+            min_order_shares: env_f64("MIN_ORDER_SHARES", 5.0),
+            min_order_notional: env_f64("MIN_ORDER_NOTIONAL", 1.0),
+            dust_notional: env_f64("DUST_NOTIONAL", 0.50),
+            dust_sweep_secs: env_u64("DUST_SWEEP_SECS", 3600),
+            auto_arm: env_bool("AUTO_ARM"),
+            signal_ttl_ms: env_u64("SIGNAL_TTL_MS", 5000),
+            signal_ttls: ExecutionConfig::default()
+                .signal_ttls
+                .into_iter()
+                .chain(env_map("SIGNAL_TTLS").into_iter().filter_map(|(s, ms)| Some((s, ms.parse().ok()?))))
+                .collect(),
+            duplicate_price_band: env_f64("DUPLICATE_PRICE_BAND", 0.02),
+            max_slippage_bps: env_f64("MAX_SLIPPAGE_BPS", 50.0),
+            take_fill_or_kill: env_bool("TAKE_FILL_OR_KILL"),
+            max_signals_per_minute: env_u64("MAX_SIGNALS_PER_MINUTE", 30) as u32,
+            signal_rate_limits: env_map("SIGNAL_RATE_LIMITS")
+                .into_iter()
+                .filter_map(|(s, n)| Some((s, n.parse().ok()?)))
+                .collect(),
+            fill_cooldown_secs: env_u64("FILL_COOLDOWN_SECS", 30),
+            fill_cooldowns: env_map("FILL_COOLDOWNS")
+                .into_iter()
+                .filter_map(|(s, secs)| Some((s, secs.parse().ok()?)))
+                .collect(),
+        };
+
+        let anomaly = AnomalyConfig {
+            window_secs: env_u64("ANOMALY_WINDOW_SECS", 60),
+            midpoint_jump: env_f64("ANOMALY_MIDPOINT_JUMP", 0.10),
+            spot_move_pct: env_f64("ANOMALY_SPOT_MOVE_PCT", 0.002),
+            block_secs: env_u64("ANOMALY_BLOCK_SECS", 300),
+        };
+
+        let stats = StatsConfig {
+            window_secs: env_u64("STATS_WINDOW_SECS", 300),
+            ema_half_life_secs: env_u64("STATS_EMA_HALF_LIFE_SECS", 60),
+        };
+
+        let mean_reversion = MeanReversionConfig {
+            enabled: env_bool("MEAN_REVERSION_ENABLED"),
+            entry_z: env_f64("MEAN_REVERSION_ENTRY_Z", 2.5),
+            exit_z: env_f64("MEAN_REVERSION_EXIT_Z", 0.5),
+            stop_z: env_f64("MEAN_REVERSION_STOP_Z", 4.0),
+            spot_corroboration_z: env_f64("MEAN_REVERSION_SPOT_Z", 1.5),
+            flow_corroboration: env_f64("MEAN_REVERSION_FLOW_IMBALANCE", 0.6),
+            min_samples: env_usize("MEAN_REVERSION_MIN_SAMPLES", 30),
+            max_position_pct: env_f64("MEAN_REVERSION_MAX_POSITION_PCT", 0.02),
+        };
+
+        let cross_strike = CrossStrikeConfig {
+            enabled: env_bool("CROSS_STRIKE_ENABLED"),
+            groups: env_list("CROSS_STRIKE_GROUPS"),
+            min_violation: env_f64("CROSS_STRIKE_MIN_VIOLATION", 0.02),
+            max_position_pct: env_f64("CROSS_STRIKE_MAX_POSITION_PCT", 0.02),
+        };
+
+        let resolution_snipe = ResolutionSnipeConfig {
+            enabled: env_bool("SNIPE_ENABLED"),
+            max_hours_to_end: env_f64("SNIPE_MAX_HOURS_TO_END", 6.0),
+            min_probability: env_f64("SNIPE_MIN_PROBABILITY", 0.98),
+            max_price: env_f64("SNIPE_MAX_PRICE", 0.97),
+            min_annualized_return: env_f64("SNIPE_MIN_ANNUALIZED_RETURN", 0.5),
+            settlement_hours: env_f64("SNIPE_SETTLEMENT_HOURS", 2.0),
+            max_position_pct: env_f64("SNIPE_MAX_POSITION_PCT", 0.05),
+        };
+
+        let copy_trade = CopyTradeConfig {
+            enabled: env_bool("COPY_TRADE_ENABLED"),
+            wallets: env_list("COPY_WALLETS").iter().map(|w| w.to_lowercase()).collect(),
+            poll_secs: env_u64("COPY_POLL_SECS", 15),
+            size_ratio: env_f64("COPY_SIZE_RATIO", 0.1),
+            default_wallet_cap: env_f64("COPY_WALLET_CAP", 50.0),
+            wallet_caps: env_map("COPY_WALLET_CAPS")
+                .into_iter()
+                .filter_map(|(wallet, cap)| Some((wallet.to_lowercase(), cap.parse().ok()?)))
+                .collect(),
+            max_age_secs: env_u64("COPY_MAX_AGE_SECS", 120),
+            max_slippage: env_f64("COPY_MAX_SLIPPAGE", 0.02),
+            max_price: env_f64("COPY_MAX_PRICE", 0.95),
+        };
+
+        let kalshi = KalshiConfig {
+            enabled: env_bool("KALSHI_ENABLED"),
+            api_url: std::env::var("KALSHI_API_URL")
+                .unwrap_or_else(|_| "https://api.elections.kalshi.com/trade-api/v2".to_string()),
+            ws_url: std::env::var("KALSHI_WS_URL")
+                .unwrap_or_else(|_| "wss://api.elections.kalshi.com/trade-api/ws/v2".to_string()),
+            key_id: env_opt("KALSHI_KEY_ID"),
+            private_key_path: env_opt("KALSHI_PRIVATE_KEY_PATH"),
+            pairs: env_map("KALSHI_PAIRS").into_iter().map(|(k, v)| (k.into(), v)).collect(),
+            min_edge: env_f64("KALSHI_MIN_EDGE", 0.01),
+            kalshi_fee_rate: env_f64("KALSHI_FEE_RATE", 0.07),
+            max_position_pct: env_f64("KALSHI_MAX_POSITION_PCT", 0.02),
+        };
+
+        let sports = SportsConfig {
+            enabled: env_bool("SPORTS_ENABLED"),
+            api_url: std::env::var("SPORTS_API_URL")
+                .unwrap_or_else(|_| "https://site.api.espn.com/apis/site/v2/sports".to_string()),
+            poll_secs: env_u64("SPORTS_POLL_SECS", 10),
+            games: env_map("SPORTS_GAMES").into_iter().map(|(k, v)| (k.into(), v)).collect(),
+            min_edge: env_f64("SPORTS_MIN_EDGE", 0.08),
+            max_score_age_secs: env_u64("SPORTS_MAX_SCORE_AGE_SECS", 30),
+            max_position_pct: env_f64("SPORTS_MAX_POSITION_PCT", 0.02),
+        };
+
+        let calendar = CalendarConfig {
+            enabled: env_bool("CALENDAR_ENABLED"),
+            url: match std::env::var("CALENDAR_URL") {
+                Ok(url) => Some(url).filter(|u| !u.is_empty()),
+                Err(_) => CalendarConfig::default().url,
+            },
+            poll_secs: env_u64("CALENDAR_POLL_SECS", 3600),
+            events: env_list("CALENDAR_EVENTS"),
+            countries: match env_list("CALENDAR_COUNTRIES") {
+                countries if countries.is_empty() => vec!["USD".to_string()],
+                countries => countries.iter().map(|c| c.to_uppercase()).collect(),
+            },
+            min_impact: match std::env::var("CALENDAR_MIN_IMPACT").unwrap_or_default().to_lowercase().as_str() {
+                "low" => EconImpact::Low,
+                "medium" => EconImpact::Medium,
+                _ => EconImpact::High,
+            },
+            window_secs: env_u64("CALENDAR_WINDOW_SECS", 1800),
+            edge_mult: env_f64("CALENDAR_EDGE_MULT", 2.0),
+        };
+
+        let flow_alerts = FlowAlertConfig {
+            enabled: env_bool("FLOW_ALERTS_ENABLED"),
+            large_notional: env_f64("FLOW_ALERT_LARGE_NOTIONAL", 5000.0),
+            size_mult: env_f64("FLOW_ALERT_SIZE_MULT", 10.0),
+            min_notional: env_f64("FLOW_ALERT_MIN_NOTIONAL", 500.0),
+            baseline_trades: env_usize("FLOW_ALERT_BASELINE_TRADES", 200),
+            sweep_levels: env_usize("FLOW_ALERT_SWEEP_LEVELS", 3),
+            sweep_notional: env_f64("FLOW_ALERT_SWEEP_NOTIONAL", 1000.0),
+            ttl_secs: env_u64("FLOW_ALERT_TTL_SECS", 300),
+            webhooks: env_list("FLOW_ALERT_WEBHOOKS"),
+        };
+
+        let binance = BinanceConfig {
+            stream: match std::env::var("BINANCE_STREAM").unwrap_or_default().to_lowercase().replace('_', "").as_str() {
+                "ticker" => BinanceStream::Ticker,
+                "bookticker" => BinanceStream::BookTicker,
+                _ => BinanceStream::AggTrade,
+            },
+            depth_levels: env_usize("BINANCE_DEPTH_LEVELS", 0),
+            min_depth_share: env_f64("BINANCE_MIN_DEPTH_SHARE", 0.0),
+        };
+
+        let hedge = HedgeConfig {
+            enabled: env_bool("HEDGE_ENABLED"),
+            base_url: std::env::var("BINANCE_FUTURES_URL").unwrap_or_else(|_| "https://fapi.binance.com".to_string()),
+            api_key: env_opt("BINANCE_FUTURES_API_KEY"),
+            api_secret: env_opt("BINANCE_FUTURES_SECRET"),
+            hedge_ratio: env_f64("HEDGE_RATIO", 1.0),
+            band_usd: env_f64("HEDGE_BAND_USD", 50.0),
+            max_notional: env_f64("HEDGE_MAX_NOTIONAL", 1000.0),
+            rebalance_secs: env_u64("HEDGE_REBALANCE_SECS", 60),
+        };
+
+        let fees = FeeConfig {
+            maker_rate: env_f64("FEE_MAKER_RATE", 0.0),
+            taker_rate: env_f64("FEE_TAKER_RATE", 0.25),
+            exponent: env_f64("FEE_EXPONENT", 2.0),
+        };
+
+        let pricing = PricingConfig {
+            default_vol: env_f64("PRICING_DEFAULT_VOL", 0.6),
+            vols: env_map("PRICING_VOLS")
+                .into_iter()
+                .filter_map(|(symbol, vol)| Some((symbol.to_uppercase(), vol.parse().ok()?)))
+                .collect(),
+        };
+
+        let calibration = CalibrationConfig {
+            poll_secs: env_u64("CALIBRATION_POLL_SECS", 3600),
+            lookback_days: env_u64("CALIBRATION_LOOKBACK_DAYS", 90) as i64,
+            shrink_sizes: env_bool("CALIBRATION_SHRINK"),
+            min_samples: env_usize("CALIBRATION_MIN_SAMPLES", 30),
+            min_size_scale: env_f64("CALIBRATION_MIN_SCALE", 0.25),
+        };
+
+        let resolution_monitor = ResolutionMonitorConfig {
+            poll_secs: env_u64("RESOLUTION_POLL_SECS", 300),
+            exit_on_dispute: env_bool("RESOLUTION_EXIT_ON_DISPUTE"),
+        };
+
+        let wallet_health = WalletHealthConfig {
+            rpc_url: std::env::var("POLYGON_RPC_URL").unwrap_or_else(|_| "https://polygon-rpc.com".to_string()),
+            poll_secs: env_u64("WALLET_POLL_SECS", 300),
+            gas_per_tx: env_f64("WALLET_GAS_PER_TX", 250_000.0),
+            reserve_txs: env_f64("WALLET_RESERVE_TXS", 20.0),
+            max_gas_gwei: env_f64("WALLET_MAX_GAS_GWEI", 500.0),
+        };
+
+        let redemption = RedemptionConfig {
+            poll_secs: env_u64("REDEEM_POLL_SECS", 600),
+            on_chain: env_bool("REDEEM_ON_CHAIN"),
+            gas_limit: env_u64("REDEEM_GAS_LIMIT", 300_000),
+            webhooks: env_list("REDEEM_WEBHOOKS"),
+        };
+
+        let merge = MergeConfig {
+            poll_secs: env_u64("MERGE_POLL_SECS", 0),
+            min_sets: env_f64("MERGE_MIN_SETS", 10.0),
+            gas_limit: env_u64("MERGE_GAS_LIMIT", 300_000),
+        };
+
+        let experiments = ExperimentConfig {
+            variants: env_map("STRATEGY_VARIANTS"),
+            live: env_map("LIVE_VARIANTS"),
+            report_secs: env_u64("EXPERIMENT_REPORT_SECS", 86400),
+            lookback_days: env_u64("EXPERIMENT_LOOKBACK_DAYS", 30) as i64,
+        };
+
+        let walk_forward = WalkForwardConfig {
+            train_hours: env_u64("WALK_FORWARD_TRAIN_HOURS", 24) as i64,
+            test_hours: env_u64("WALK_FORWARD_TEST_HOURS", 6) as i64,
+            samples: env_u64("WALK_FORWARD_SAMPLES", 0) as usize,
+            seed: env_u64("WALK_FORWARD_SEED", 1),
+        };
+
+        let ruin = RuinConfig {
+            paths: env_u64("RUIN_PATHS", 10_000) as usize,
+            horizon: env_u64("RUIN_HORIZON", 500) as usize,
+            lookback_days: env_u64("RUIN_LOOKBACK_DAYS", 90) as i64,
+            seed: env_u64("RUIN_SEED", 1),
+        };
+
+        let watchdog = WatchdogConfig {
+            check_secs: env_u64("WATCHDOG_CHECK_SECS", 5),
+            stall_secs: env_u64("WATCHDOG_STALL_SECS", 60),
+            feed_silence_secs: env_u64("WATCHDOG_FEED_SILENCE_SECS", 120),
+            backoff_secs: env_u64("WATCHDOG_BACKOFF_SECS", 1),
+            max_backoff_secs: env_u64("WATCHDOG_MAX_BACKOFF_SECS", 60),
+            halt_after_secs: env_u64("WATCHDOG_HALT_AFTER_SECS", 30),
+        };
+
+        let shutdown = ShutdownConfig {
+            cancel_orders: env_bool("SHUTDOWN_CANCEL_ORDERS"),
+            timeout_secs: env_u64("SHUTDOWN_TIMEOUT_SECS", 15),
+        };
+
+        let readiness = ReadinessConfig {
+            max_wait_secs: env_u64("READINESS_MAX_WAIT_SECS", 0),
+        };
+
+        let allocator = AllocatorConfig {
+            rebalance_secs: env_u64("ALLOCATOR_REBALANCE_SECS", 3600),
+            lookback_days: env_u64("ALLOCATOR_LOOKBACK_DAYS", 30) as i64,
+            min_trades: env_usize("ALLOCATOR_MIN_TRADES", 20),
+            kelly_fraction: env_f64("ALLOCATOR_KELLY_FRACTION", 0.5),
+            default_share: env_f64("ALLOCATOR_DEFAULT_SHARE", 0.2),
+            min_share: env_f64("ALLOCATOR_MIN_SHARE", 0.05),
+            max_share: env_f64("ALLOCATOR_MAX_SHARE", 0.5),
+        };
+
+        let db = DbConfig {
+            busy_timeout_ms: env_u64("DB_BUSY_TIMEOUT_MS", 5000),
+            write_batch: env_usize("DB_WRITE_BATCH", 256),
+        };
+
+        let backup = BackupConfig {
+            dir: std::env::var("BACKUP_DIR").unwrap_or_else(|_| "backups".to_string()),
+            interval_secs: env_u64("BACKUP_INTERVAL_SECS", 3600),
+            keep: env_usize("BACKUP_KEEP", 48),
+        };
+
+        let api_auth = ApiAuthConfig {
+            admin_token: env_opt("DASHBOARD_TOKEN"),
+            read_token: env_opt("DASHBOARD_READ_TOKEN"),
+        };
+
+        let watchlist = WatchlistConfig {
+            market_ids: env_list("WATCHLIST_MARKETS").into_iter().map(MarketId::from).collect(),
+            queries: env_list("WATCHLIST_QUERIES"),
         };
 
         Ok(Config {
@@ -66,10 +1311,84 @@ impl Config {
             polymarket_secret,
             polymarket_passphrase,
             risk,
+            execution,
+            anomaly,
+            stats,
+            mean_reversion,
+            cross_strike,
+            resolution_snipe,
+            copy_trade,
+            kalshi,
+            sports,
+            calendar,
+            flow_alerts,
+            binance,
+            hedge,
+            pricing,
+            fees,
+            calibration,
+            resolution_monitor,
+            wallet_health,
+            redemption,
+            merge,
+            allocator,
+            walk_forward,
+            ruin,
+            watchdog,
+            shutdown,
+            readiness,
             db_path,
+            database_url: env_opt("DATABASE_URL"),
+            db,
             dashboard_port,
+            grpc_port: env_opt("GRPC_PORT").and_then(|p| p.parse().ok()),
+            api_auth,
+            backup,
+            watchlist,
+            execution_venues: env_list("EXECUTION_VENUES"),
+            strategy_schedules: env_map("STRATEGY_SCHEDULES"),
+            shadow_strategies: env_list("SHADOW_STRATEGIES"),
+            experiments,
+            fill_tape_poll_secs: env_u64("FILL_TAPE_POLL_SECS", 15),
+            order_sync_secs: env_u64("ORDER_SYNC_SECS", 30),
+            pnl_snapshot_secs: env_u64("PNL_SNAPSHOT_SECS", 300),
+            checkpoint_secs: env_u64("CHECKPOINT_SECS", 30),
+            archive_interval_secs: env_u64("ARCHIVE_INTERVAL_SECS", 6 * 3600),
+            market_refresh_secs: env_u64("MARKET_REFRESH_SECS", 3600),
+            record_path: std::env::var("RECORD_PATH").ok(),
+            replay_path: std::env::var("REPLAY_PATH").ok(),
+            replay_speed: env_f64("REPLAY_SPEED", 1.0),
         })
     }
+
+    /// Copy safe to expose over the API: credentials, tokens and the database URL
+    /// (which usually embeds a password) are masked, but their presence still shows
+    pub fn redacted(&self) -> Self {
+        let mask = |v: &Option<String>| v.as_ref().map(|_| REDACTED.to_string());
+        let mut config = self.clone();
+        config.private_key = REDACTED.to_string();
+        config.polymarket_api_key = REDACTED.to_string();
+        config.polymarket_secret = REDACTED.to_string();
+        config.polymarket_passphrase = REDACTED.to_string();
+        config.database_url = mask(&self.database_url);
+        config.api_auth.admin_token = mask(&self.api_auth.admin_token);
+        config.api_auth.read_token = mask(&self.api_auth.read_token);
+        config.kalshi.key_id = mask(&self.kalshi.key_id);
+        config.hedge.api_key = mask(&self.hedge.api_key);
+        config.hedge.api_secret = mask(&self.hedge.api_secret);
+        // Hosted RPC URLs usually embed an API key
+        config.wallet_health.rpc_url = REDACTED.to_string();
+        // Chat webhook URLs carry their own credentials
+        config.flow_alerts.webhooks = self.flow_alerts.webhooks.iter().map(|_| REDACTED.to_string()).collect();
+        config.redemption.webhooks = self.redemption.webhooks.iter().map(|_| REDACTED.to_string()).collect();
+        config
+    }
+
+    /// DB path alone, for offline tooling that shouldn't require API credentials
+    pub fn db_path() -> String {
+        dotenvy::dotenv().ok();
+        std::env::var("DB_PATH").unwrap_or_else(|_| "bot.db".to_string())
+    }
 }
 
 fn env_f64(key: &str, default: f64) -> f64 {
@@ -78,3 +1397,54 @@ fn env_f64(key: &str, default: f64) -> f64 {
         .and_then(|v| v.parse().ok())
         .unwrap_or(default)
 }
+
+fn env_usize(key: &str, default: usize) -> usize {
+    std::env::var(key)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(default)
+}
+
+fn env_u64(key: &str, default: u64) -> u64 {
+    std::env::var(key)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(default)
+}
+
+/// true/1/yes (case-insensitive); anything else, including unset, is false
+fn env_bool(key: &str) -> bool {
+    std::env::var(key)
+        .map(|v| matches!(v.trim().to_lowercase().as_str(), "true" | "1" | "yes"))
+        .unwrap_or(false)
+}
+
+/// Unset and empty are both None
+fn env_opt(key: &str) -> Option<String> {
+    std::env::var(key).ok().filter(|v| !v.is_empty())
+}
+
+fn required(key: &str) -> Result<String> {
+    std::env::var(key).map_err(|_| Error::invalid(format!("{} not set", key)))
+}
+
+/// Comma-separated list; empty entries are dropped
+fn env_list(key: &str) -> Vec<String> {
+    std::env::var(key)
+        .unwrap_or_default()
+        .split(',')
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+/// `key=value` entries separated by semicolons (values may contain spaces and commas)
+fn env_map(key: &str) -> BTreeMap<String, String> {
+    std::env::var(key)
+        .unwrap_or_default()
+        .split(';')
+        .filter_map(|entry| entry.split_once('='))
+        .map(|(k, v)| (k.trim().to_string(), v.trim().to_string()))
+        .filter(|(k, v)| !k.is_empty() && !v.is_empty())
+        .collect()
+}
@@ -1,7 +1,12 @@
 use chrono::{DateTime, Utc};
+use std::collections::BTreeMap;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+mod ids;
+
+pub use ids::{MarketId, OrderId, TokenId};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum Side {
     Buy,
     Sell,
@@ -21,6 +26,27 @@ pub enum OrderType {
     GTC,
     GTD,
     FOK,
+    /// Fill-and-kill: take what the book has at the limit, cancel the rest
+    FAK,
+}
+
+impl OrderType {
+    /// Whether an unfilled remainder rests on the book; FOK and FAK orders fill
+    /// on arrival or not at all
+    pub fn rests(&self) -> bool {
+        matches!(self, OrderType::GTC | OrderType::GTD)
+    }
+}
+
+/// How a signal wants to meet the book
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Execution {
+    /// Rest a GTC limit at the signal price
+    #[default]
+    Passive,
+    /// Take liquidity now: a FOK/FAK order priced through the book up to the slippage cap
+    Take,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -34,48 +60,231 @@ pub enum OrderStatus {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Market {
-    pub id: String,
+    pub id: MarketId,
     pub question: String,
-    pub tokens: Vec<TokenInfo>,
+    pub outcomes: Vec<Outcome>,
+    /// How the outcomes fit together and settle
+    #[serde(default)]
+    pub structure: MarketStructure,
     pub end_date: Option<DateTime<Utc>>,
     pub active: bool,
+    /// Trading has ended; the market is resolved or awaiting resolution
+    #[serde(default)]
+    pub closed: bool,
+    /// Token that resolved to 1, once the market has resolved
+    #[serde(default)]
+    pub winner: Option<TokenId>,
+    /// Where the UMA oracle is with this market's resolution, once a proposal is in
+    #[serde(default)]
+    pub uma_status: Option<UmaStatus>,
+    /// Smallest price increment the CLOB accepts, when known
+    #[serde(default)]
+    pub tick_size: Option<f64>,
+    /// Smallest order the CLOB accepts, in shares, when known
+    #[serde(default)]
+    pub min_order_size: Option<f64>,
+}
+
+impl Market {
+    /// Token of the named outcome ("Yes", "No", ...), matched case-insensitively
+    pub fn outcome_token(&self, outcome: &str) -> Option<&TokenId> {
+        self.outcomes
+            .iter()
+            .find(|o| o.name.eq_ignore_ascii_case(outcome))
+            .map(|o| &o.token_id)
+    }
+
+    /// YES and NO tokens of a Yes/No condition: a binary market or one leg of a
+    /// neg-risk event. None for categorical markets and other outcome names.
+    pub fn yes_no(&self) -> Option<(&TokenId, &TokenId)> {
+        if self.structure == MarketStructure::Categorical {
+            return None;
+        }
+        Some((self.outcome_token("yes")?, self.outcome_token("no")?))
+    }
+
+    /// The other token of a two-outcome market
+    pub fn complement(&self, token_id: &TokenId) -> Option<&TokenId> {
+        match self.outcomes.as_slice() {
+            [a, b] if &a.token_id == token_id => Some(&b.token_id),
+            [a, b] if &b.token_id == token_id => Some(&a.token_id),
+            _ => None,
+        }
+    }
+
+    /// Settles through the neg-risk adapter rather than the Conditional Tokens contract directly
+    pub fn neg_risk(&self) -> bool {
+        matches!(self.structure, MarketStructure::NegRisk { .. })
+    }
+}
+
+/// One outcome of a market and the token that pays $1 if it wins
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Outcome {
+    pub token_id: TokenId,
+    /// "Yes", "No", or a candidate or bucket in a categorical market
+    pub name: String,
+}
+
+/// How a market's outcomes fit together, which decides what a complete set of
+/// tokens is and how winning tokens settle
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum MarketStructure {
+    /// Yes and No on one condition, settled by the Conditional Tokens contract
+    #[default]
+    Binary,
+    /// Three or more mutually exclusive outcomes on one condition
+    Categorical,
+    /// One outcome of a multi-outcome event, traded as its own Yes/No condition.
+    /// At most one leg of the event resolves Yes; settlement goes through the
+    /// neg-risk adapter.
+    NegRisk {
+        /// Shared by every leg of the event, when known
+        event_id: Option<String>,
+    },
 }
 
+impl MarketStructure {
+    pub fn new(neg_risk: bool, event_id: Option<String>, outcomes: usize) -> Self {
+        if neg_risk {
+            MarketStructure::NegRisk { event_id }
+        } else if outcomes > 2 {
+            MarketStructure::Categorical
+        } else {
+            MarketStructure::Binary
+        }
+    }
+
+    pub fn event_id(&self) -> Option<&str> {
+        match self {
+            MarketStructure::NegRisk { event_id } => event_id.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+/// One leg of a neg-risk event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NegRiskLeg {
+    pub market_id: MarketId,
+    pub yes_token_id: TokenId,
+    pub no_token_id: TokenId,
+}
+
+/// The legs of a multi-outcome event known to the bot. Legs the bot hasn't
+/// loaded are missing, so the YES tokens here needn't make a complete set; any
+/// `n` of the NO tokens still pay at least `n - 1`.
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct TokenInfo {
-    pub token_id: String,
-    pub outcome: String,
+pub struct NegRiskEvent {
+    pub event_id: String,
+    pub legs: Vec<NegRiskLeg>,
+}
+
+impl NegRiskEvent {
+    /// Group the open neg-risk legs among `markets` by event, keeping events with two or more
+    pub fn group(markets: &[Market]) -> Vec<NegRiskEvent> {
+        let mut events: BTreeMap<&str, Vec<NegRiskLeg>> = BTreeMap::new();
+        for market in markets.iter().filter(|m| !m.closed) {
+            let (Some(event_id), Some((yes, no))) = (market.structure.event_id(), market.yes_no()) else {
+                continue;
+            };
+            events.entry(event_id).or_default().push(NegRiskLeg {
+                market_id: market.id.clone(),
+                yes_token_id: yes.clone(),
+                no_token_id: no.clone(),
+            });
+        }
+        events
+            .into_iter()
+            .filter(|(_, legs)| legs.len() >= 2)
+            .map(|(event_id, legs)| NegRiskEvent { event_id: event_id.to_string(), legs })
+            .collect()
+    }
+}
+
+/// What the CLOB accepts on one market
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct OrderRules {
+    pub tick_size: f64,
+    /// Shares
+    pub min_size: f64,
+}
+
+impl OrderRules {
+    /// `price` on the tick grid, never worse than asked (buys round down, sells
+    /// up) and kept one tick inside 0 and 1
+    pub fn round_price(&self, side: &Side, price: f64) -> f64 {
+        if self.tick_size <= 0.0 {
+            return price;
+        }
+        let ticks = price / self.tick_size;
+        // Tolerance so prices already on the grid don't drift a tick on float error
+        let ticks = match side {
+            Side::Buy => (ticks + 1e-9).floor(),
+            Side::Sell => (ticks - 1e-9).ceil(),
+        };
+        (ticks * self.tick_size).clamp(self.tick_size, 1.0 - self.tick_size)
+    }
+}
+
+/// Stage of a market's resolution on the UMA optimistic oracle
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum UmaStatus {
+    /// An outcome is proposed and in its challenge window
+    Proposed,
+    /// The proposal was challenged; resolution waits on a vote, often for days
+    Disputed,
+    Resolved,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Order {
-    pub id: String,
-    pub market_id: String,
+    /// Local ID, also the client order ID the exchange dedupes resubmissions on
+    pub id: OrderId,
+    pub market_id: MarketId,
     pub side: Side,
-    pub token_id: String,
+    pub token_id: TokenId,
     pub price: f64,
     pub size: f64,
     pub order_type: OrderType,
     pub status: OrderStatus,
     pub created_at: DateTime<Utc>,
+    /// Exchange-assigned order ID, once acknowledged
+    #[serde(default)]
+    pub remote_id: Option<String>,
+    /// Strategy that originated the order; empty for orders recorded before it was tracked
+    #[serde(default)]
+    pub strategy: String,
+    /// Order this one took over from in a cancel/replace
+    #[serde(default)]
+    pub replaces: Option<OrderId>,
+    /// When a resting order stops being good; GTD orders carry it to the exchange
+    #[serde(default)]
+    pub expires_at: Option<DateTime<Utc>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Position {
-    pub market_id: String,
-    pub token_id: String,
+    pub market_id: MarketId,
+    pub token_id: TokenId,
     pub side: Side,
     pub size: f64,
+    /// Cost basis per share: the average entry price with entry fees folded in
     pub avg_price: f64,
     pub current_price: f64,
     pub pnl: f64,
+    /// Strategy that opened the position
+    #[serde(default)]
+    pub strategy: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Trade {
     pub id: String,
-    pub order_id: String,
-    pub market_id: String,
+    pub order_id: OrderId,
+    pub market_id: MarketId,
     pub side: Side,
     pub price: f64,
     pub size: f64,
@@ -83,14 +292,47 @@ pub struct Trade {
     pub timestamp: DateTime<Utc>,
 }
 
+/// A public trade print that likely filled one of our resting orders,
+/// pending confirmation by reconciliation against the exchange
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProbableFill {
+    pub order_id: OrderId,
+    pub trade_key: String,
+    pub token_id: TokenId,
+    pub price: f64,
+    pub size: f64,
+    pub trade_timestamp: DateTime<Utc>,
+    pub detected_at: DateTime<Utc>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Signal {
     pub strategy: String,
-    pub market_id: String,
+    pub market_id: MarketId,
     pub side: Side,
     pub confidence: f64,
     pub price: f64,
     pub size: f64,
+    /// Receipt time of the market data event that triggered this signal
+    #[serde(default)]
+    pub event_time: Option<DateTime<Utc>>,
+    /// Explicit token to trade; when None the order manager derives it from market_id
+    #[serde(default)]
+    pub token_id: Option<TokenId>,
+    /// Order type override; None submits GTC, or FOK/FAK for `Execution::Take`
+    #[serde(default)]
+    pub order_type: Option<OrderType>,
+    /// Rest or take; takers are for edges that are gone by the time a resting order fills
+    #[serde(default)]
+    pub execution: Execution,
+    /// Drop the signal if it hasn't reached the exchange by then; None falls back to
+    /// the strategy's configured TTL counted from `event_time`
+    #[serde(default)]
+    pub expires_at: Option<DateTime<Utc>>,
+    /// Let the resulting order rest until then and no longer; submitted GTD unless
+    /// the signal asks for another type
+    #[serde(default)]
+    pub good_till: Option<DateTime<Utc>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -124,26 +366,412 @@ impl OrderBook {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum MarketData {
     PolymarketPrice {
-        market_id: String,
-        token_id: String,
+        market_id: MarketId,
+        token_id: TokenId,
         price: f64,
         timestamp: DateTime<Utc>,
     },
     PolymarketOrderBook {
-        market_id: String,
-        token_id: String,
+        market_id: MarketId,
+        token_id: TokenId,
         book: OrderBook,
     },
+    /// A print on the public tape; `side` is the aggressor's
+    PolymarketTrade {
+        market_id: MarketId,
+        token_id: TokenId,
+        price: f64,
+        size: f64,
+        side: Side,
+        timestamp: DateTime<Utc>,
+    },
     BinanceTicker {
         symbol: String,
         price: f64,
         timestamp: DateTime<Utc>,
     },
+    /// Top levels of a Binance spot book, quantities in base asset
+    BinanceOrderBook {
+        symbol: String,
+        book: OrderBook,
+    },
+    /// Score update from the live sports feed
+    GameScore {
+        game: GameState,
+    },
+    /// Outsized trade or book sweep flagged by the flow detector
+    FlowAlert {
+        alert: FlowAlert,
+    },
+    /// A held market's oracle resolution moved to a new stage
+    Resolution {
+        market_id: MarketId,
+        status: UmaStatus,
+        timestamp: DateTime<Utc>,
+    },
+    /// Kalshi top of book, in dollars; the NO side is the complement of YES
+    KalshiTicker {
+        ticker: String,
+        yes_bid: f64,
+        yes_ask: f64,
+        timestamp: DateTime<Utc>,
+    },
+}
+
+impl MarketData {
+    /// When the event was received from its feed
+    pub fn timestamp(&self) -> DateTime<Utc> {
+        match self {
+            MarketData::PolymarketPrice { timestamp, .. } => *timestamp,
+            MarketData::PolymarketOrderBook { book, .. } => book.timestamp,
+            MarketData::PolymarketTrade { timestamp, .. } => *timestamp,
+            MarketData::BinanceTicker { timestamp, .. } => *timestamp,
+            MarketData::BinanceOrderBook { book, .. } => book.timestamp,
+            MarketData::GameScore { game } => game.timestamp,
+            MarketData::FlowAlert { alert } => alert.timestamp,
+            MarketData::Resolution { timestamp, .. } => *timestamp,
+            MarketData::KalshiTicker { timestamp, .. } => *timestamp,
+        }
+    }
+}
+
+/// A feed's connection coming up or going down
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeedStatus {
+    pub feed: String,
+    pub connected: bool,
+    /// Why the connection went down
+    pub detail: Option<String>,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl FeedStatus {
+    pub fn connected(feed: &str) -> Self {
+        Self {
+            feed: feed.to_string(),
+            connected: true,
+            detail: None,
+            timestamp: Utc::now(),
+        }
+    }
+
+    pub fn disconnected(feed: &str, detail: impl Into<String>) -> Self {
+        Self {
+            feed: feed.to_string(),
+            connected: false,
+            detail: Some(detail.into()),
+            timestamp: Utc::now(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PnlSnapshot {
     pub timestamp: DateTime<Utc>,
     pub bankroll: f64,
+    /// Realized plus unrealized
     pub pnl_total: f64,
+    /// From closed positions, net of fees
+    #[serde(default)]
+    pub realized_pnl: f64,
+    /// Open positions marked to market
+    #[serde(default)]
+    pub unrealized_pnl: f64,
+}
+
+/// Point-in-time exposure reading, recorded alongside PnL snapshots
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExposureSnapshot {
+    pub timestamp: DateTime<Utc>,
+    /// Cost basis of all position legs
+    pub gross_exposure: f64,
+    /// Directional exposure after netting complementary YES/NO holdings
+    pub net_exposure: f64,
+    /// Collateral reserved by resting buy orders
+    pub open_order_notional: f64,
+    /// Bankroll not tied up in positions or resting buys
+    pub free_collateral: f64,
+    /// Net exposure per underlying: spot symbol for crypto markets, else the market ID
+    pub by_underlying: BTreeMap<String, f64>,
+}
+
+/// OHLC bar for a Polymarket token or a Binance symbol
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Candle {
+    /// Token ID or spot symbol
+    pub instrument: String,
+    pub interval_secs: i64,
+    /// Bucket start, unix seconds
+    pub open_time: i64,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+    pub ticks: i64,
+    /// Shares traded on the public tape; quotes add ticks but no volume
+    pub volume: f64,
+    /// Volume-weighted trade price; None without trades
+    pub vwap: Option<f64>,
+}
+
+/// Latest Kalshi quote for a ticker, as kept for strategies
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct KalshiQuote {
+    pub yes_bid: f64,
+    pub yes_ask: f64,
+}
+
+/// Where a game is, as the score feed reports it
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum GameStatus {
+    Scheduled,
+    InProgress,
+    Final,
+}
+
+/// Live score of one game
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameState {
+    /// The score feed's game ID
+    pub game_id: String,
+    /// League key, e.g. "nba"
+    pub league: String,
+    pub home_team: String,
+    pub away_team: String,
+    pub home_score: f64,
+    pub away_score: f64,
+    /// Regulation time left in seconds; in overtime, what is left of the overtime period
+    pub secs_remaining: f64,
+    pub status: GameStatus,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// What tripped a flow alert
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum FlowAlertKind {
+    /// One print far above the usual size
+    LargeTrade,
+    /// Several book levels taken out between two snapshots
+    BookSweep,
+}
+
+/// Unusually large aggressive flow on a watched token
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FlowAlert {
+    pub market_id: MarketId,
+    pub token_id: TokenId,
+    pub kind: FlowAlertKind,
+    /// Aggressor side: Buy lifted offers, Sell hit bids
+    pub side: Side,
+    /// Shares traded or swept
+    pub size: f64,
+    /// USDC value of `size`
+    pub notional: f64,
+    /// Trade price, or the best price left on the swept side
+    pub price: f64,
+    /// Book levels a sweep took out; 1 for a trade
+    pub levels: usize,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// How much a scheduled release tends to move markets
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum EconImpact {
+    Low,
+    Medium,
+    High,
+}
+
+/// A scheduled economic release: FOMC decision, CPI print, payrolls
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct EconEvent {
+    pub name: String,
+    /// Currency the release is about, e.g. "USD"
+    pub country: String,
+    pub impact: EconImpact,
+    pub scheduled_at: DateTime<Utc>,
+}
+
+impl EconEvent {
+    /// Whether `now` is within `before_secs` ahead of the release or `after_secs` past it
+    pub fn covers(&self, now: DateTime<Utc>, before_secs: u64, after_secs: u64) -> bool {
+        let lead = (self.scheduled_at - now).num_seconds();
+        lead <= before_secs as i64 && -lead <= after_secs as i64
+    }
+}
+
+/// A position closed (in full or in part) by an opposite fill
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClosedTrade {
+    pub id: String,
+    /// Strategy that opened the position
+    pub strategy: String,
+    pub market_id: MarketId,
+    pub token_id: TokenId,
+    /// Side of the position that was closed
+    pub side: Side,
+    pub size: f64,
+    pub entry_price: f64,
+    pub exit_price: f64,
+    /// Realized PnL, net of the closing fill's fee
+    pub pnl: f64,
+    pub closed_at: DateTime<Utc>,
+}
+
+/// Where a position settled at resolution stands
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum RedemptionStatus {
+    /// Redeem transaction confirmed on-chain
+    Redeemed,
+    /// Winning tokens left in the wallet for the operator to redeem
+    Manual,
+    /// Losing tokens, worth nothing
+    Lost,
+}
+
+/// A position settled at its market's resolution
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Redemption {
+    pub market_id: MarketId,
+    pub token_id: TokenId,
+    pub size: f64,
+    /// USDC the tokens pay out: their size if they won, else nothing
+    pub payout: f64,
+    /// Payout less what the position cost
+    pub pnl: f64,
+    pub status: RedemptionStatus,
+    /// Redeem transaction, when sent from the bot
+    pub tx_hash: Option<String>,
+    pub settled_at: DateTime<Utc>,
+}
+
+/// A submitted signal and, once its market resolves, whether the bet won
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignalRecord {
+    pub id: String,
+    pub strategy: String,
+    pub market_id: MarketId,
+    pub token_id: TokenId,
+    pub side: Side,
+    /// The strategy's reported probability that the bet wins
+    pub confidence: f64,
+    pub price: f64,
+    pub created_at: DateTime<Utc>,
+    /// 1 if the bet won, 0 if it lost; None until resolution
+    pub outcome: Option<f64>,
+    pub resolved_at: Option<DateTime<Utc>>,
+    /// Shares ordered, or filled in simulation for shadow signals; None on older records
+    #[serde(default)]
+    pub size: Option<f64>,
+    /// From a strategy in shadow mode: `price` is a simulated fill and nothing was sent
+    #[serde(default)]
+    pub shadow: bool,
+}
+
+impl SignalRecord {
+    /// What the bet made or lost at resolution, before fees
+    pub fn pnl(&self) -> Option<f64> {
+        let per_share = match self.side {
+            Side::Buy => self.outcome? - self.price,
+            Side::Sell => self.price - self.outcome?,
+        };
+        Some(per_share * self.size?)
+    }
+}
+
+/// How one arm of a strategy A/B test has done, as of `timestamp`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VariantReport {
+    pub timestamp: DateTime<Utc>,
+    /// Base strategy the arm varies
+    pub experiment: String,
+    pub strategy: String,
+    /// Whether the arm traded live rather than in shadow mode
+    pub live: bool,
+    pub signals: usize,
+    pub resolved: usize,
+    pub wins: usize,
+    /// PnL of resolved signals, before fees
+    pub pnl: f64,
+    /// Cost of signals still waiting on their market
+    pub open_notional: f64,
+}
+
+/// What a risk audit entry records
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RiskEventKind {
+    /// A signal refused by a risk limit
+    SignalRejected,
+    /// Trading halted on drawdown from peak
+    DrawdownHalt,
+    /// Trading halted with the bankroll below its floor
+    BankrollHalt,
+    /// Trading halted with a critical task down
+    TaskHalt,
+    /// Trading halted with the venue refusing the bot's credentials
+    AuthHalt,
+    /// Boot warm-up over: feeds warm and open orders reconciled, or the wait timed out
+    WarmUp,
+    /// A market put on loss cooldown
+    Cooldown,
+    /// A market added to or removed from the allow or deny list
+    MarketList,
+    /// A held market's resolution was disputed
+    Dispute,
+    Kill,
+    Resume,
+    Arm,
+    Disarm,
+}
+
+/// A risk decision, persisted so post-mortems don't depend on log scrollback
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RiskEvent {
+    pub timestamp: DateTime<Utc>,
+    pub kind: RiskEventKind,
+    pub strategy: Option<String>,
+    pub market_id: Option<MarketId>,
+    pub detail: String,
+}
+
+impl RiskEvent {
+    pub fn new(kind: RiskEventKind, detail: impl Into<String>) -> Self {
+        Self {
+            timestamp: Utc::now(),
+            kind,
+            strategy: None,
+            market_id: None,
+            detail: detail.into(),
+        }
+    }
+
+    /// A decision about `signal`
+    pub fn signal(kind: RiskEventKind, signal: &Signal, detail: impl Into<String>) -> Self {
+        Self {
+            strategy: Some(signal.strategy.clone()),
+            market_id: Some(signal.market_id.clone()),
+            ..Self::new(kind, detail)
+        }
+    }
+
+    pub fn market(kind: RiskEventKind, market_id: &MarketId, detail: impl Into<String>) -> Self {
+        Self {
+            market_id: Some(market_id.clone()),
+            ..Self::new(kind, detail)
+        }
+    }
+}
+
+/// A market blocked from new entries after a realized loss
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarketCooldown {
+    pub market_id: MarketId,
+    pub until: DateTime<Utc>,
+    /// The loss that started (or last extended) the block, as a positive amount
+    pub realized_loss: f64,
+    pub started_at: DateTime<Utc>,
 }
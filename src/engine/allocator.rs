@@ -0,0 +1,179 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use tracing::{info, warn};
+
+use crate::adapters::database::Database;
+use crate::config::AllocatorConfig;
+use crate::domain::ClosedTrade;
+use crate::error::Result;
+
+/// Strategy → share of the bankroll it sizes off; strategies not listed get all of it
+pub type Allocations = Arc<RwLock<HashMap<String, f64>>>;
+
+/// Daily returns of one strategy over the lookback
+struct Track {
+    returns: Vec<f64>,
+    trades: usize,
+}
+
+/// Budget shares from each strategy's trailing daily returns.
+///
+/// A strategy with `min_trades` closes in the window gets a fractional-Kelly share
+/// (mean over variance of its daily returns), divided by one plus its positive
+/// correlations with the other tracked strategies so that strategies making the
+/// same bet split one budget instead of each taking a full one. Shares are clamped
+/// to `[min_share, max_share]`, strategies without enough history get
+/// `default_share`, and the whole set is scaled down if it exceeds the bankroll.
+///
+/// Returns are measured against the budget each strategy was sizing off
+/// (`current`, or the full bankroll before the first rebalance).
+pub fn allocate(
+    trades: &[ClosedTrade],
+    strategies: &[String],
+    since: DateTime<Utc>,
+    current: &HashMap<String, f64>,
+    bankroll: f64,
+    config: &AllocatorConfig,
+) -> HashMap<String, f64> {
+    let days: Vec<NaiveDate> = since
+        .date_naive()
+        .iter_days()
+        .take_while(|day| *day <= Utc::now().date_naive())
+        .collect();
+
+    let mut tracks: BTreeMap<&str, Track> = BTreeMap::new();
+    for strategy in strategies {
+        let capital = current.get(strategy).copied().unwrap_or(1.0) * bankroll;
+        let own: Vec<&ClosedTrade> = trades.iter().filter(|t| &t.strategy == strategy).collect();
+        let mut by_day: HashMap<NaiveDate, f64> = HashMap::new();
+        for trade in &own {
+            *by_day.entry(trade.closed_at.date_naive()).or_default() += trade.pnl;
+        }
+        let returns = days
+            .iter()
+            .map(|day| by_day.get(day).copied().unwrap_or(0.0) / capital.max(f64::EPSILON))
+            .collect();
+        tracks.insert(strategy, Track { returns, trades: own.len() });
+    }
+
+    let proven: Vec<&str> = tracks
+        .iter()
+        .filter(|(_, t)| t.trades >= config.min_trades && t.returns.len() >= 2)
+        .map(|(s, _)| *s)
+        .collect();
+
+    let mut shares: HashMap<String, f64> = HashMap::new();
+    for (strategy, track) in &tracks {
+        if !proven.contains(strategy) {
+            shares.insert(strategy.to_string(), config.default_share);
+            continue;
+        }
+        let (mean, variance) = moments(&track.returns);
+        let kelly = if variance > 0.0 {
+            mean / variance
+        } else if mean > 0.0 {
+            f64::MAX
+        } else {
+            0.0
+        };
+        let overlap: f64 = proven
+            .iter()
+            .filter(|other| *other != strategy)
+            .map(|other| correlation(&track.returns, &tracks[other].returns).max(0.0))
+            .sum();
+        let share = (config.kelly_fraction * kelly / (1.0 + overlap)).clamp(config.min_share, config.max_share);
+        shares.insert(strategy.to_string(), share);
+    }
+
+    let total: f64 = shares.values().sum();
+    if total > 1.0 {
+        for share in shares.values_mut() {
+            *share /= total;
+        }
+    }
+    shares
+}
+
+/// Mean and sample variance
+fn moments(series: &[f64]) -> (f64, f64) {
+    let n = series.len() as f64;
+    if n < 2.0 {
+        return (series.first().copied().unwrap_or(0.0), 0.0);
+    }
+    let mean = series.iter().sum::<f64>() / n;
+    let variance = series.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0);
+    (mean, variance)
+}
+
+/// Pearson correlation of two equal-length series; 0 when either is flat
+fn correlation(a: &[f64], b: &[f64]) -> f64 {
+    let (mean_a, var_a) = moments(a);
+    let (mean_b, var_b) = moments(b);
+    if var_a <= 0.0 || var_b <= 0.0 {
+        return 0.0;
+    }
+    let n = a.len().min(b.len()) as f64;
+    let covariance = a.iter().zip(b).map(|(x, y)| (x - mean_a) * (y - mean_b)).sum::<f64>() / (n - 1.0);
+    covariance / (var_a * var_b).sqrt()
+}
+
+/// Rebalances strategy budgets on a fixed cadence; see `allocate`
+pub struct PortfolioAllocator {
+    db: Database,
+    bankroll: Arc<tokio::sync::RwLock<f64>>,
+    strategies: Vec<String>,
+    config: AllocatorConfig,
+    allocations: Allocations,
+}
+
+impl PortfolioAllocator {
+    pub fn new(
+        db: Database,
+        bankroll: Arc<tokio::sync::RwLock<f64>>,
+        mut strategies: Vec<String>,
+        config: AllocatorConfig,
+    ) -> Self {
+        strategies.sort();
+        strategies.dedup();
+        Self {
+            db,
+            bankroll,
+            strategies,
+            config,
+            allocations: Allocations::default(),
+        }
+    }
+
+    /// Shares the feed aggregator turns into per-strategy budgets
+    pub fn allocations(&self) -> Allocations {
+        self.allocations.clone()
+    }
+
+    pub async fn run(self) {
+        let interval = Duration::from_secs(self.config.rebalance_secs);
+        info!("Portfolio allocator started (every {:?})", interval);
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            if let Err(e) = self.rebalance().await {
+                warn!("Allocator rebalance failed: {:?}", e);
+            }
+        }
+    }
+
+    async fn rebalance(&self) -> Result<()> {
+        let since = Utc::now() - chrono::Duration::days(self.config.lookback_days.max(1));
+        let trades = self.db.get_closed_trades(since).await?;
+        let bankroll = *self.bankroll.read().await;
+        let current = self.allocations.read().unwrap().clone();
+        let shares = allocate(&trades, &self.strategies, since, &current, bankroll, &self.config);
+        for strategy in &self.strategies {
+            let share = shares.get(strategy).copied().unwrap_or_default();
+            info!("Allocator: {} gets {:.1}% (${:.2})", strategy, share * 100.0, share * bankroll);
+        }
+        *self.allocations.write().unwrap() = shares;
+        Ok(())
+    }
+}
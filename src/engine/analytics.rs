@@ -0,0 +1,144 @@
+use chrono::{DateTime, Days, NaiveDate, Utc};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use tracing::{info, warn};
+
+use crate::adapters::database::Database;
+use crate::domain::ClosedTrade;
+
+/// Row name for the figures across every strategy
+pub const ALL_STRATEGIES: &str = "all";
+/// Daily PnL series are annualized over calendar days; markets trade every day
+const DAYS_PER_YEAR: f64 = 365.0;
+
+/// Performance figures over a set of closed trades
+#[derive(Debug, Clone, Serialize)]
+pub struct StrategyAnalytics {
+    pub strategy: String,
+    pub trades: usize,
+    pub wins: usize,
+    pub losses: usize,
+    pub win_rate: f64,
+    pub avg_win: f64,
+    /// Average losing trade, as a negative amount
+    pub avg_loss: f64,
+    /// Gross profit over gross loss; None without a losing trade
+    pub profit_factor: Option<f64>,
+    pub total_pnl: f64,
+    /// Annualized Sharpe ratio of daily realized PnL; None with under two days
+    pub sharpe: Option<f64>,
+    /// Like `sharpe`, but only downside days count as volatility
+    pub sortino: Option<f64>,
+    /// Largest peak-to-trough fall of cumulative realized PnL (USDC)
+    pub max_drawdown: f64,
+}
+
+/// Figures for all trades together, then each strategy by name
+pub fn by_strategy(trades: &[ClosedTrade]) -> Vec<StrategyAnalytics> {
+    let mut groups: BTreeMap<&str, Vec<&ClosedTrade>> = BTreeMap::new();
+    for trade in trades {
+        groups.entry(trade.strategy.as_str()).or_default().push(trade);
+    }
+    let mut rows = vec![summarize(ALL_STRATEGIES, &trades.iter().collect::<Vec<_>>())];
+    rows.extend(groups.into_iter().map(|(strategy, trades)| summarize(strategy, &trades)));
+    rows
+}
+
+/// `trades` must be ordered by close time
+pub fn summarize(strategy: &str, trades: &[&ClosedTrade]) -> StrategyAnalytics {
+    let wins: Vec<f64> = trades.iter().map(|t| t.pnl).filter(|p| *p > 0.0).collect();
+    let losses: Vec<f64> = trades.iter().map(|t| t.pnl).filter(|p| *p < 0.0).collect();
+    let gross_win: f64 = wins.iter().sum();
+    let gross_loss: f64 = losses.iter().sum();
+    let daily = daily_pnl(trades);
+
+    let mut peak = 0.0_f64;
+    let mut cumulative = 0.0;
+    let mut max_drawdown = 0.0_f64;
+    for trade in trades {
+        cumulative += trade.pnl;
+        peak = peak.max(cumulative);
+        max_drawdown = max_drawdown.max(peak - cumulative);
+    }
+
+    StrategyAnalytics {
+        strategy: strategy.to_string(),
+        trades: trades.len(),
+        wins: wins.len(),
+        losses: losses.len(),
+        win_rate: ratio(wins.len() as f64, trades.len() as f64).unwrap_or(0.0),
+        avg_win: ratio(gross_win, wins.len() as f64).unwrap_or(0.0),
+        avg_loss: ratio(gross_loss, losses.len() as f64).unwrap_or(0.0),
+        profit_factor: ratio(gross_win, -gross_loss),
+        total_pnl: cumulative,
+        sharpe: annualized(&daily, |day, mean| day - mean),
+        sortino: annualized(&daily, |day, _| day.min(0.0)),
+        max_drawdown,
+    }
+}
+
+fn ratio(num: f64, den: f64) -> Option<f64> {
+    (den > 0.0).then(|| num / den)
+}
+
+/// Realized PnL per calendar day (UTC) from the first close to the last,
+/// with zeros for days without a close
+fn daily_pnl(trades: &[&ClosedTrade]) -> Vec<f64> {
+    let (Some(first), Some(last)) = (trades.first(), trades.last()) else {
+        return Vec::new();
+    };
+    let mut by_day: BTreeMap<NaiveDate, f64> = BTreeMap::new();
+    for trade in trades {
+        *by_day.entry(trade.closed_at.date_naive()).or_default() += trade.pnl;
+    }
+    first
+        .closed_at
+        .date_naive()
+        .iter_days()
+        .take_while(|day| *day <= last.closed_at.date_naive())
+        .map(|day| by_day.get(&day).copied().unwrap_or(0.0))
+        .collect()
+}
+
+/// Mean daily PnL over the root-mean-square of `deviation(day, mean)`, annualized
+fn annualized(daily: &[f64], deviation: impl Fn(f64, f64) -> f64) -> Option<f64> {
+    if daily.len() < 2 {
+        return None;
+    }
+    let n = daily.len() as f64;
+    let mean = daily.iter().sum::<f64>() / n;
+    let variance = daily.iter().map(|day| deviation(*day, mean).powi(2)).sum::<f64>() / (n - 1.0);
+    let sd = variance.sqrt();
+    (sd > 0.0).then(|| mean / sd * DAYS_PER_YEAR.sqrt())
+}
+
+/// Log the previous day's figures per strategy shortly after each UTC midnight
+pub async fn run_nightly_summary(db: Database) {
+    loop {
+        let now = Utc::now();
+        let Some(next) = (now.date_naive() + Days::new(1)).and_hms_opt(0, 0, 0) else {
+            return;
+        };
+        let wait = (next.and_utc() - now).to_std().unwrap_or_default();
+        tokio::time::sleep(wait).await;
+
+        let since: DateTime<Utc> = next.and_utc() - chrono::Duration::days(1);
+        match db.get_closed_trades(since).await {
+            Ok(trades) if trades.is_empty() => info!("Daily summary: no closed trades"),
+            Ok(trades) => {
+                for row in by_strategy(&trades) {
+                    info!(
+                        "Daily summary [{}]: {} trades, win rate {:.0}%, PnL ${:.2}, profit factor {}, max drawdown ${:.2}",
+                        row.strategy,
+                        row.trades,
+                        row.win_rate * 100.0,
+                        row.total_pnl,
+                        row.profit_factor.map_or("n/a".to_string(), |pf| format!("{:.2}", pf)),
+                        row.max_drawdown
+                    );
+                }
+            }
+            Err(e) => warn!("Daily summary failed: {:?}", e),
+        }
+    }
+}
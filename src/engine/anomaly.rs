@@ -0,0 +1,137 @@
+use chrono::{DateTime, Duration, Utc};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::Mutex;
+use tracing::{info, warn};
+
+use crate::config::AnomalyConfig;
+use crate::domain::{MarketData, MarketId, TokenId};
+
+/// Cross-feed consistency check for markets priced off a spot feed.
+///
+/// When a market's YES midpoint jumps sharply while its underlying spot barely
+/// moved, someone is probably trading on news we don't see. New latency-arb
+/// entries on that market are paused for a while rather than trading into that
+/// informed flow. All times are event times, so replays behave like live runs.
+pub struct AnomalyGuard {
+    config: AnomalyConfig,
+    /// YES token → (market ID, spot symbol)
+    links: HashMap<TokenId, (MarketId, String)>,
+    inner: Mutex<Inner>,
+}
+
+#[derive(Default)]
+struct Inner {
+    /// token → recent (time, midpoint)
+    midpoints: HashMap<TokenId, VecDeque<(DateTime<Utc>, f64)>>,
+    /// symbol → recent (time, spot)
+    spot: HashMap<String, VecDeque<(DateTime<Utc>, f64)>>,
+    /// market → blocked until
+    blocked: HashMap<MarketId, DateTime<Utc>>,
+}
+
+impl AnomalyGuard {
+    pub fn new(config: AnomalyConfig) -> Self {
+        Self {
+            config,
+            links: HashMap::new(),
+            inner: Mutex::new(Inner::default()),
+        }
+    }
+
+    /// Watch a market whose YES token should track `symbol`
+    pub fn watch(&mut self, market_id: &MarketId, yes_token_id: &TokenId, symbol: &str) {
+        self.links
+            .insert(yes_token_id.clone(), (market_id.clone(), symbol.to_string()));
+    }
+
+    fn enabled(&self) -> bool {
+        self.config.midpoint_jump > 0.0 && !self.links.is_empty()
+    }
+
+    pub fn observe(&self, event: &MarketData) {
+        if !self.enabled() {
+            return;
+        }
+        let window = Duration::seconds(self.config.window_secs as i64);
+        let now = event.timestamp();
+        let mut inner = self.inner.lock().unwrap();
+
+        let (token_id, midpoint) = match event {
+            MarketData::BinanceTicker { symbol, price, .. } => {
+                push(inner.spot.entry(symbol.clone()).or_default(), now, *price, window);
+                return;
+            }
+            MarketData::PolymarketPrice { token_id, price, .. } => (token_id, *price),
+            MarketData::PolymarketOrderBook { token_id, book, .. } => match book.midpoint() {
+                Some(mid) => (token_id, mid),
+                None => return,
+            },
+            MarketData::PolymarketTrade { .. }
+            | MarketData::BinanceOrderBook { .. }
+            | MarketData::KalshiTicker { .. }
+            | MarketData::GameScore { .. }
+            | MarketData::FlowAlert { .. }
+            | MarketData::Resolution { .. } => return,
+        };
+        let Some((market_id, symbol)) = self.links.get(token_id) else {
+            return;
+        };
+
+        let history = inner.midpoints.entry(token_id.clone()).or_default();
+        push(history, now, midpoint, window);
+        let jump = midpoint - history.front().map(|(_, p)| *p).unwrap_or(midpoint);
+        if jump.abs() < self.config.midpoint_jump {
+            return;
+        }
+
+        // No spot history means we can't tell either way; don't block on a blind spot
+        let Some(spot_move) = inner.spot.get(symbol).and_then(relative_move) else {
+            return;
+        };
+        if spot_move.abs() >= self.config.spot_move_pct {
+            return;
+        }
+
+        let until = now + Duration::seconds(self.config.block_secs as i64);
+        if inner.blocked.insert(market_id.clone(), until).is_none_or(|prev| prev <= now) {
+            warn!(
+                "ANOMALY on {}: midpoint moved {:+.3} in {}s while {} moved {:+.3}% — blocking entries until {}",
+                market_id,
+                jump,
+                self.config.window_secs,
+                symbol,
+                spot_move * 100.0,
+                until.format("%H:%M:%S")
+            );
+        }
+    }
+
+    /// Markets where new entries are paused as of `now`
+    pub fn blocked_markets(&self, now: DateTime<Utc>) -> HashSet<MarketId> {
+        let mut inner = self.inner.lock().unwrap();
+        inner.blocked.retain(|market_id, until| {
+            let active = *until > now;
+            if !active {
+                info!("Anomaly block on {} lifted", market_id);
+            }
+            active
+        });
+        inner.blocked.keys().cloned().collect()
+    }
+}
+
+/// Append a sample and drop those older than the window, keeping the last one at or
+/// before its start as the baseline, i.e. the value as of the start of the window
+fn push(history: &mut VecDeque<(DateTime<Utc>, f64)>, now: DateTime<Utc>, value: f64, window: Duration) {
+    history.push_back((now, value));
+    while history.len() > 1 && history[1].0 <= now - window {
+        history.pop_front();
+    }
+}
+
+/// Fractional change from the window baseline to the latest sample
+fn relative_move(history: &VecDeque<(DateTime<Utc>, f64)>) -> Option<f64> {
+    let (_, first) = history.front()?;
+    let (_, last) = history.back()?;
+    (*first > 0.0).then(|| (last - first) / first)
+}
@@ -0,0 +1,76 @@
+use std::collections::HashSet;
+use std::time::Duration;
+use tracing::{info, warn};
+
+use crate::adapters::database::Database;
+use crate::adapters::gamma::GammaClient;
+use crate::domain::MarketId;
+use crate::error::Result;
+
+/// Market IDs per Gamma lookup
+const LOOKUP_BATCH: usize = 50;
+
+/// Periodically moves settled markets out of the hot tables.
+///
+/// A market is settled once Gamma reports it closed and we have no open orders
+/// or non-zero positions left in it. Its trades, orders and (zero-size) positions
+/// then move to the `_archive` tables so `get_positions`/`get_open_orders` only
+/// scan live markets.
+pub struct MarketArchiver {
+    db: Database,
+    gamma: GammaClient,
+    interval: Duration,
+}
+
+impl MarketArchiver {
+    pub fn new(db: Database, gamma: GammaClient, interval: Duration) -> Self {
+        Self { db, gamma, interval }
+    }
+
+    pub async fn run(self) {
+        info!("Market archiver started (every {:?})", self.interval);
+        let mut interval = tokio::time::interval(self.interval);
+
+        loop {
+            interval.tick().await;
+            match self.archive_settled().await {
+                Ok(0) => {}
+                Ok(n) => info!("Archived {} settled markets", n),
+                Err(e) => warn!("Market archive pass failed: {:?}", e),
+            }
+        }
+    }
+
+    /// Returns the number of markets archived
+    pub async fn archive_settled(&self) -> Result<usize> {
+        let mut live: HashSet<MarketId> = self
+            .db
+            .get_open_orders()
+            .await?
+            .into_iter()
+            .map(|o| o.market_id)
+            .collect();
+        live.extend(self.db.get_positions().await?.into_iter().map(|p| p.market_id));
+
+        let idle: Vec<MarketId> = self
+            .db
+            .get_market_ids()
+            .await?
+            .into_iter()
+            .filter(|id| !live.contains(id))
+            .collect();
+
+        let mut archived = 0;
+        for batch in idle.chunks(LOOKUP_BATCH) {
+            for market in self.gamma.get_markets(batch).await? {
+                if !market.closed {
+                    continue;
+                }
+                let rows = self.db.archive_market(&market.id).await?;
+                info!("Archived market {} ({} rows): {}", market.id, rows, market.question);
+                archived += 1;
+            }
+        }
+        Ok(archived)
+    }
+}
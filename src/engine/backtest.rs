@@ -0,0 +1,303 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use crate::config::{FeeConfig, StatsConfig};
+use crate::domain::{Execution, KalshiQuote, MarketData, Position, Side, Signal, TokenId, UmaStatus};
+use crate::engine::fees;
+use crate::engine::market_catalog::MarketCatalog;
+use crate::engine::order_manager::{slippage_cap, sweep};
+use crate::strategy::flow::FlowTracker;
+use crate::strategy::stats::StatsTracker;
+use crate::strategy::{Intent, Interest, Strategy, StrategyContext};
+
+/// How a set of strategy instances did over one window of a recording
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct BacktestResult {
+    pub signals: usize,
+    pub fills: usize,
+    /// From closed positions, less every fee paid
+    pub realized: f64,
+    /// Open positions at the end of the window, settled where the market has a
+    /// winner and marked at the book midpoint (or last price) otherwise
+    pub unrealized: f64,
+    pub fees: f64,
+}
+
+impl BacktestResult {
+    pub fn pnl(&self) -> f64 {
+        self.realized + self.unrealized
+    }
+}
+
+/// Offline run of strategies over recorded market data.
+///
+/// Events update the same context the feed aggregator keeps, and intents are
+/// filled the way shadow mode fills them: marketable orders take what rests
+/// within the slippage cap at the last level swept, paying the taker fee, and
+/// passive ones that don't cross are assumed filled at their limit. Sells are
+/// backed by held tokens or turned into buys of the complement, as in the order
+/// manager. Cancels, replaces and quotes are not simulated; nothing rests.
+pub struct Backtester {
+    bankroll: f64,
+    max_slippage_bps: f64,
+    stats: StatsConfig,
+    fees: FeeConfig,
+    markets: MarketCatalog,
+}
+
+impl Backtester {
+    pub fn new(bankroll: f64, max_slippage_bps: f64, stats: StatsConfig, fees: FeeConfig, markets: MarketCatalog) -> Self {
+        Self {
+            bankroll,
+            max_slippage_bps,
+            stats,
+            fees,
+            markets,
+        }
+    }
+
+    /// Run `strategies` over the events received in [from, to). Earlier events
+    /// only warm up books and statistics, so a window starts from the state the
+    /// live bot would have had.
+    pub async fn run(
+        &self,
+        strategies: &[Box<dyn Strategy>],
+        events: &[(DateTime<Utc>, MarketData)],
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> BacktestResult {
+        let mut run = Run {
+            ctx: StrategyContext::new(self.bankroll),
+            stats: StatsTracker::new(self.stats.clone()),
+            flow: FlowTracker::new(&self.stats),
+            result: BacktestResult::default(),
+        };
+        run.ctx.fees = self.fees.clone();
+        run.ctx.markets = self.markets.clone();
+        let interests: Vec<Interest> = strategies.iter().map(|strategy| strategy.interest()).collect();
+
+        for (recv_at, event) in events.iter().take_while(|(recv_at, _)| *recv_at < to) {
+            run.update(event);
+            if *recv_at < from {
+                continue;
+            }
+            run.ctx.stats = run.stats.snapshot();
+            run.ctx.trade_flow = run.flow.snapshot();
+            run.ctx.latest_event = Some(event.clone());
+            run.ctx.now = *recv_at;
+            let due = strategies
+                .iter()
+                .zip(&interests)
+                .filter(|(strategy, interest)| strategy.enabled() && interest.covers(event));
+            for (strategy, _) in due {
+                for intent in strategy.intents(&run.ctx).await {
+                    self.execute(&mut run, intent);
+                }
+            }
+            // Let go of the snapshots so the trackers update in place
+            run.ctx.stats = Arc::default();
+            run.ctx.trade_flow = Arc::default();
+        }
+
+        run.result.unrealized = run
+            .ctx
+            .positions
+            .iter()
+            .map(|p| (self.exit_value(&run.ctx, p) - p.avg_price) * p.size)
+            .sum();
+        run.result
+    }
+
+    fn execute(&self, run: &mut Run, intent: Intent) {
+        match intent {
+            Intent::Order(signal) => self.fill(run, signal),
+            Intent::Legs(signals) => signals.into_iter().for_each(|signal| self.fill(run, signal)),
+            Intent::Exit { token_id, price, size, .. } => {
+                let Some(held) = run.ctx.positions.iter().find(|p| p.token_id == token_id) else {
+                    return;
+                };
+                let size = size.unwrap_or(held.size).min(held.size);
+                self.close(run, &token_id, price, size, Execution::Take);
+            }
+            Intent::Cancel { .. } | Intent::Replace { .. } | Intent::Quote { .. } => {}
+        }
+    }
+
+    fn fill(&self, run: &mut Run, mut signal: Signal) {
+        run.result.signals += 1;
+        let Some(token_id) = signal.token_id.clone().or_else(|| {
+            self.markets
+                .get(&signal.market_id)?
+                .outcome_token("yes")
+                .cloned()
+        }) else {
+            return;
+        };
+
+        if signal.side == Side::Buy {
+            self.open(run, &signal, token_id);
+        } else if let Some(held) = run.ctx.positions.iter().find(|p| p.token_id == token_id) {
+            let size = signal.size.min(held.size);
+            self.close(run, &token_id, signal.price, size, signal.execution);
+        } else if let Some(complement) = self.markets.complement(&token_id) {
+            signal.side = Side::Buy;
+            signal.price = 1.0 - signal.price;
+            self.open(run, &signal, complement);
+        }
+    }
+
+    /// Buy into `token_id` at what the book gives within the cap
+    fn open(&self, run: &mut Run, signal: &Signal, token_id: TokenId) {
+        let Some((price, size, fee)) = self.price(run, &token_id, &Side::Buy, signal.price, signal.size, signal.execution)
+        else {
+            return;
+        };
+        if size <= 0.0 || price * size + fee > run.ctx.bankroll {
+            return;
+        }
+        run.ctx.bankroll -= price * size + fee;
+        run.result.fees += fee;
+        run.result.realized -= fee;
+        run.result.fills += 1;
+        match run.ctx.positions.iter_mut().find(|p| p.token_id == token_id) {
+            Some(held) => {
+                held.avg_price = (held.avg_price * held.size + price * size) / (held.size + size);
+                held.size += size;
+            }
+            None => run.ctx.positions.push(Position {
+                market_id: signal.market_id.clone(),
+                token_id,
+                side: Side::Buy,
+                size,
+                avg_price: price,
+                current_price: price,
+                pnl: 0.0,
+                strategy: signal.strategy.clone(),
+            }),
+        }
+    }
+
+    /// Sell up to `size` of the held `token_id`
+    fn close(&self, run: &mut Run, token_id: &TokenId, limit: f64, size: f64, execution: Execution) {
+        let Some((price, size, fee)) = self.price(run, token_id, &Side::Sell, limit, size, execution) else {
+            return;
+        };
+        let Some(index) = run.ctx.positions.iter().position(|p| &p.token_id == token_id) else {
+            return;
+        };
+        let held = &mut run.ctx.positions[index];
+        let size = size.min(held.size);
+        let pnl = (price - held.avg_price) * size - fee;
+        held.size -= size;
+        if held.size <= 1e-9 {
+            run.ctx.positions.remove(index);
+        }
+        run.ctx.bankroll += price * size - fee;
+        run.result.fees += fee;
+        run.result.realized += pnl;
+        run.result.fills += 1;
+    }
+
+    /// Fill price, size and fee for an order on `token_id`, or None when a taker
+    /// finds nothing within the cap
+    fn price(
+        &self,
+        run: &Run,
+        token_id: &TokenId,
+        side: &Side,
+        limit: f64,
+        size: f64,
+        execution: Execution,
+    ) -> Option<(f64, f64, f64)> {
+        let cap = slippage_cap(side, limit, self.max_slippage_bps);
+        let swept = run
+            .ctx
+            .orderbooks
+            .get(token_id)
+            .and_then(|book| sweep(book, side, size, cap));
+        match (swept, execution) {
+            (Some((price, size)), _) => Some((price, size, fees::taker_fee(&self.fees, price, size))),
+            (None, Execution::Passive) => Some((limit, size, 0.0)),
+            (None, Execution::Take) => None,
+        }
+    }
+
+    /// What a held token is worth at the end of a window: 1 or 0 once its market
+    /// has a winner, else the book midpoint, else the last price, else cost
+    fn exit_value(&self, ctx: &StrategyContext, position: &Position) -> f64 {
+        if let Some(winner) = self.markets.by_token(&position.token_id).and_then(|m| m.winner) {
+            return if winner == position.token_id { 1.0 } else { 0.0 };
+        }
+        ctx.orderbooks
+            .get(&position.token_id)
+            .and_then(|book| book.midpoint())
+            .or_else(|| ctx.prices.get(&position.token_id).copied())
+            .unwrap_or(position.avg_price)
+    }
+}
+
+/// State of one backtest as it steps through the events
+struct Run {
+    ctx: StrategyContext,
+    stats: StatsTracker,
+    flow: FlowTracker,
+    result: BacktestResult,
+}
+
+impl Run {
+    /// The same bookkeeping as the feed aggregator's
+    fn update(&mut self, event: &MarketData) {
+        let ctx = &mut self.ctx;
+        match event {
+            MarketData::PolymarketPrice { token_id, price, timestamp, .. } => {
+                ctx.prices.insert(token_id.clone(), *price);
+                self.stats.update(token_id, *timestamp, *price);
+            }
+            MarketData::PolymarketOrderBook { token_id, book, .. } => {
+                ctx.orderbooks.insert(token_id.clone(), Arc::new(book.clone()));
+            }
+            MarketData::PolymarketTrade { token_id, price, size, side, timestamp, .. } => {
+                self.flow.record(token_id, *timestamp, *price, *size, side.clone());
+            }
+            MarketData::BinanceTicker { symbol, price, timestamp } => {
+                ctx.binance_prices.insert(symbol.clone(), *price);
+                self.stats.update(symbol, *timestamp, *price);
+            }
+            MarketData::BinanceOrderBook { symbol, book } => {
+                ctx.binance_books.insert(symbol.clone(), Arc::new(book.clone()));
+            }
+            MarketData::KalshiTicker { ticker, yes_bid, yes_ask, .. } => {
+                let quote = KalshiQuote { yes_bid: *yes_bid, yes_ask: *yes_ask };
+                ctx.kalshi_quotes.insert(ticker.clone(), quote);
+            }
+            MarketData::GameScore { game } => {
+                ctx.games.insert(game.game_id.clone(), game.clone());
+            }
+            MarketData::FlowAlert { alert } => {
+                ctx.flow_alerts.insert(alert.token_id.clone(), alert.clone());
+            }
+            MarketData::Resolution { market_id, status, .. } => {
+                if *status == UmaStatus::Disputed {
+                    ctx.blocked_markets.insert(market_id.clone());
+                } else {
+                    ctx.blocked_markets.remove(market_id);
+                }
+            }
+        }
+    }
+}
+
+/// Every Polymarket token a recording has data for, to find the markets it covers
+pub fn recorded_tokens(events: &[(DateTime<Utc>, MarketData)]) -> HashSet<TokenId> {
+    events
+        .iter()
+        .filter_map(|(_, event)| match event {
+            MarketData::PolymarketPrice { token_id, .. }
+            | MarketData::PolymarketOrderBook { token_id, .. }
+            | MarketData::PolymarketTrade { token_id, .. } => Some(token_id.clone()),
+            _ => None,
+        })
+        .collect()
+}
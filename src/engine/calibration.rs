@@ -0,0 +1,213 @@
+use chrono::Utc;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use tracing::{info, warn};
+
+use crate::adapters::database::Database;
+use crate::adapters::gamma::GammaClient;
+use crate::config::CalibrationConfig;
+use crate::domain::{Market, MarketId, Side, SignalRecord};
+use crate::engine::analytics::ALL_STRATEGIES;
+use crate::error::Result;
+
+/// Equal-width confidence buckets per curve
+const BUCKETS: usize = 10;
+/// Market IDs per Gamma lookup
+const LOOKUP_BATCH: usize = 50;
+
+/// Strategy → multiplier on its signal sizes, from its calibration
+pub type SizeScales = Arc<RwLock<HashMap<String, f64>>>;
+
+/// Resolved signals with confidence in [lower, upper)
+#[derive(Debug, Clone, Serialize)]
+pub struct CalibrationBucket {
+    pub lower: f64,
+    pub upper: f64,
+    pub signals: usize,
+    pub mean_confidence: f64,
+    /// Share of these signals that won; a calibrated strategy has this ≈ mean_confidence
+    pub win_rate: f64,
+}
+
+/// Reported confidence against realized outcomes for one strategy
+#[derive(Debug, Clone, Serialize)]
+pub struct CalibrationCurve {
+    pub strategy: String,
+    pub signals: usize,
+    pub mean_confidence: f64,
+    pub win_rate: f64,
+    /// Mean squared error of confidence vs. outcome; 0 is perfect, 0.25 is a coin flip at 50%
+    pub brier_score: f64,
+    /// Non-empty buckets only
+    pub buckets: Vec<CalibrationBucket>,
+    /// Size multiplier when shrinking is on; None below the sample threshold
+    pub size_scale: Option<f64>,
+}
+
+/// Curves for all resolved records together, then each strategy by name
+pub fn curves(records: &[SignalRecord], config: &CalibrationConfig) -> Vec<CalibrationCurve> {
+    let mut groups: BTreeMap<&str, Vec<&SignalRecord>> = BTreeMap::new();
+    for record in records.iter().filter(|r| r.outcome.is_some()) {
+        groups.entry(record.strategy.as_str()).or_default().push(record);
+    }
+    // Shadow strategies get their own curves but stay out of the live aggregate
+    let all: Vec<&SignalRecord> = groups.values().flatten().copied().filter(|r| !r.shadow).collect();
+    let mut rows = vec![curve(ALL_STRATEGIES, &all, config)];
+    rows.extend(groups.into_iter().map(|(strategy, records)| curve(strategy, &records, config)));
+    rows
+}
+
+/// `records` must all be resolved
+fn curve(strategy: &str, records: &[&SignalRecord], config: &CalibrationConfig) -> CalibrationCurve {
+    let mut buckets: Vec<(usize, f64, f64)> = vec![(0, 0.0, 0.0); BUCKETS];
+    let mut brier = 0.0;
+    for record in records {
+        let outcome = record.outcome.unwrap_or_default();
+        let index = ((record.confidence * BUCKETS as f64) as usize).min(BUCKETS - 1);
+        let bucket = &mut buckets[index];
+        bucket.0 += 1;
+        bucket.1 += record.confidence;
+        bucket.2 += outcome;
+        brier += (record.confidence - outcome).powi(2);
+    }
+
+    let n = records.len();
+    let mean = |total: f64| if n > 0 { total / n as f64 } else { 0.0 };
+    let mean_confidence = mean(buckets.iter().map(|b| b.1).sum());
+    let win_rate = mean(buckets.iter().map(|b| b.2).sum());
+    let size_scale = (n >= config.min_samples && mean_confidence > 0.0)
+        .then(|| (win_rate / mean_confidence).clamp(config.min_size_scale, 1.0));
+
+    CalibrationCurve {
+        strategy: strategy.to_string(),
+        signals: n,
+        mean_confidence,
+        win_rate,
+        brier_score: mean(brier),
+        buckets: buckets
+            .into_iter()
+            .enumerate()
+            .filter(|(_, (count, _, _))| *count > 0)
+            .map(|(i, (count, confidence, wins))| CalibrationBucket {
+                lower: i as f64 / BUCKETS as f64,
+                upper: (i + 1) as f64 / BUCKETS as f64,
+                signals: count,
+                mean_confidence: confidence / count as f64,
+                win_rate: wins / count as f64,
+            })
+            .collect(),
+        size_scale,
+    }
+}
+
+/// 1 if the bet in `record` won in the resolved `market`, 0 if it lost. A record
+/// whose token isn't one of the market's (orders placed on the market ID) counts
+/// as a bet on the first outcome.
+fn outcome(record: &SignalRecord, market: &Market) -> Option<f64> {
+    let winner = market.winner.as_deref()?;
+    let token = match market.outcomes.iter().find(|o| o.token_id == record.token_id) {
+        Some(o) => o.token_id.as_str(),
+        None => market.outcomes.first()?.token_id.as_str(),
+    };
+    let won = match record.side {
+        Side::Buy => token == winner,
+        Side::Sell => token != winner,
+    };
+    Some(if won { 1.0 } else { 0.0 })
+}
+
+/// Settles recorded signals as their markets resolve and keeps per-strategy
+/// size scales current.
+///
+/// Each pass looks up the markets of unresolved records on Gamma, marks the ones
+/// with a winner, then recomputes the curves over the lookback. With shrinking on,
+/// a strategy whose win rate trails its mean confidence gets its sizes scaled by
+/// the ratio (floored at `min_size_scale`); well-calibrated or underconfident
+/// strategies are left at full size.
+pub struct CalibrationTracker {
+    db: Database,
+    gamma: GammaClient,
+    config: CalibrationConfig,
+    scales: SizeScales,
+}
+
+impl CalibrationTracker {
+    pub fn new(db: Database, gamma: GammaClient, config: CalibrationConfig) -> Self {
+        Self {
+            db,
+            gamma,
+            config,
+            scales: SizeScales::default(),
+        }
+    }
+
+    /// Scales the order manager applies; empty unless shrinking is on
+    pub fn scales(&self) -> SizeScales {
+        self.scales.clone()
+    }
+
+    pub async fn run(self) {
+        let interval = Duration::from_secs(self.config.poll_secs);
+        info!("Calibration tracker started (every {:?})", interval);
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            match self.resolve().await {
+                Ok(0) => {}
+                Ok(n) => info!("Resolved {} recorded signals", n),
+                Err(e) => warn!("Signal resolution pass failed: {:?}", e),
+            }
+            if let Err(e) = self.refresh_scales().await {
+                warn!("Calibration refresh failed: {:?}", e);
+            }
+        }
+    }
+
+    /// Returns the number of records resolved
+    async fn resolve(&self) -> Result<usize> {
+        let pending = self.db.get_unresolved_signal_records().await?;
+        let mut market_ids: Vec<MarketId> = pending.iter().map(|r| r.market_id.clone()).collect();
+        market_ids.sort();
+        market_ids.dedup();
+
+        let mut resolved: HashMap<MarketId, Market> = HashMap::new();
+        for batch in market_ids.chunks(LOOKUP_BATCH) {
+            for market in self.gamma.get_markets(batch).await? {
+                if market.winner.is_some() {
+                    resolved.insert(market.id.clone(), market);
+                }
+            }
+        }
+
+        let now = Utc::now();
+        let mut count = 0;
+        for record in &pending {
+            let Some(outcome) = resolved.get(&record.market_id).and_then(|m| outcome(record, m)) else {
+                continue;
+            };
+            self.db.resolve_signal_record(&record.id, outcome, now).await?;
+            count += 1;
+        }
+        Ok(count)
+    }
+
+    async fn refresh_scales(&self) -> Result<()> {
+        if !self.config.shrink_sizes {
+            return Ok(());
+        }
+        let since = Utc::now() - chrono::Duration::days(self.config.lookback_days.max(1));
+        let records = self.db.get_resolved_signal_records(since).await?;
+        let scales: HashMap<String, f64> = curves(&records, &self.config)
+            .into_iter()
+            .filter(|c| c.strategy != ALL_STRATEGIES)
+            .filter_map(|c| Some((c.strategy, c.size_scale.filter(|s| *s < 1.0)?)))
+            .collect();
+        for (strategy, scale) in &scales {
+            info!("Calibration: {} sizes scaled by {:.2}", strategy, scale);
+        }
+        *self.scales.write().unwrap() = scales;
+        Ok(())
+    }
+}
@@ -0,0 +1,139 @@
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+use tracing::{error, info};
+
+use crate::adapters::database::Database;
+use crate::bus::EventReceiver;
+use crate::domain::{Candle, MarketData};
+use crate::shutdown::Shutdown;
+
+/// Bar sizes built for every instrument, by API label
+pub const INTERVALS: &[(&str, i64)] = &[("1m", 60), ("5m", 300), ("1h", 3600)];
+
+/// Changed and finished bars are written in batches this often
+const FLUSH_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Builds OHLC candles from Polymarket prices and Binance tickers on the bus, with
+/// volume and VWAP from Polymarket trades.
+///
+/// Buckets follow event time. Like any bus consumer it sees conflated updates when
+/// it falls behind, so a wick between two skipped ticks can be missed; close and
+/// the bucket boundaries are always right. Trades never conflate, so volume is exact.
+pub struct CandleBuilder {
+    db: Database,
+    rx: EventReceiver<MarketData>,
+    /// (instrument, interval) → bar in progress
+    open: HashMap<(String, i64), Candle>,
+    /// Open bars changed since the last write
+    dirty: HashSet<(String, i64)>,
+    /// Finished bars not yet written
+    closed: Vec<Candle>,
+    shutdown: Shutdown,
+}
+
+impl CandleBuilder {
+    pub fn new(db: Database, rx: EventReceiver<MarketData>) -> Self {
+        Self {
+            db,
+            rx,
+            open: HashMap::new(),
+            dirty: HashSet::new(),
+            closed: Vec::new(),
+            shutdown: Shutdown::default(),
+        }
+    }
+
+    /// Write what is buffered and stop once `shutdown` is triggered
+    pub fn with_shutdown(mut self, shutdown: Shutdown) -> Self {
+        self.shutdown = shutdown;
+        self
+    }
+
+    pub async fn run(mut self) {
+        info!("Candle builder started ({} intervals)", INTERVALS.len());
+        let mut flush = tokio::time::interval(FLUSH_INTERVAL);
+
+        loop {
+            tokio::select! {
+                event = self.rx.recv() => {
+                    let (instrument, price, volume) = match &event {
+                        MarketData::PolymarketPrice { token_id, price, .. } => (token_id.as_str(), *price, 0.0),
+                        MarketData::PolymarketTrade { token_id, price, size, .. } => (token_id.as_str(), *price, *size),
+                        MarketData::BinanceTicker { symbol, price, .. } => (symbol.as_str(), *price, 0.0),
+                        MarketData::PolymarketOrderBook { .. }
+                        | MarketData::BinanceOrderBook { .. }
+                        | MarketData::KalshiTicker { .. }
+                        | MarketData::GameScore { .. }
+                        | MarketData::FlowAlert { .. }
+                        | MarketData::Resolution { .. } => continue,
+                    };
+                    self.update(instrument, price, volume, event.timestamp().timestamp());
+                }
+                _ = flush.tick() => self.flush().await,
+                _ = self.shutdown.wait() => {
+                    self.flush().await;
+                    info!("Candle builder flushed for shutdown");
+                    return;
+                }
+            }
+        }
+    }
+
+    /// A tick at `price`; trades pass their size as `volume`, quotes zero
+    fn update(&mut self, instrument: &str, price: f64, volume: f64, ts: i64) {
+        let vwap = (volume > 0.0).then_some(price);
+        for &(_, interval_secs) in INTERVALS {
+            let open_time = ts - ts.rem_euclid(interval_secs);
+            let key = (instrument.to_string(), interval_secs);
+            let bar = Candle {
+                instrument: instrument.to_string(),
+                interval_secs,
+                open_time,
+                open: price,
+                high: price,
+                low: price,
+                close: price,
+                ticks: 1,
+                volume,
+                vwap,
+            };
+            match self.open.entry(key.clone()) {
+                Entry::Vacant(slot) => {
+                    slot.insert(bar);
+                }
+                Entry::Occupied(mut slot) => {
+                    let c = slot.get_mut();
+                    if c.open_time == open_time {
+                        c.high = c.high.max(price);
+                        c.low = c.low.min(price);
+                        c.close = price;
+                        c.ticks += 1;
+                        if volume > 0.0 {
+                            let notional = c.vwap.unwrap_or(0.0) * c.volume + price * volume;
+                            c.volume += volume;
+                            c.vwap = Some(notional / c.volume);
+                        }
+                    } else if c.open_time < open_time {
+                        self.closed.push(slot.insert(bar));
+                    } else {
+                        // Late tick for a bar that has already rolled over
+                        continue;
+                    }
+                }
+            }
+            self.dirty.insert(key);
+        }
+    }
+
+    async fn flush(&mut self) {
+        let mut batch = std::mem::take(&mut self.closed);
+        batch.extend(self.dirty.drain().filter_map(|key| self.open.get(&key).cloned()));
+        if batch.is_empty() {
+            return;
+        }
+        if let Err(e) = self.db.upsert_candles(&batch).await {
+            error!("Failed to write {} candles: {:?}", batch.len(), e);
+        }
+    }
+}
@@ -0,0 +1,129 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{info, warn};
+
+use crate::adapters::database::Database;
+use crate::domain::{MarketId, OrderId, TokenId};
+use crate::engine::order_manager::OrderManager;
+use crate::engine::risk::RiskManager;
+use crate::error::Result;
+use crate::shutdown::Shutdown;
+
+/// Config table key the checkpoint is stored under
+const KEY: &str = "engine_checkpoint";
+
+/// Engine state the tables don't hold, as of `saved_at`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Checkpoint {
+    pub saved_at: DateTime<Utc>,
+    pub peak_bankroll: f64,
+    pub quotes: Vec<RestingQuote>,
+    /// Fills whose post-fill cooldown was still running
+    pub fills: Vec<RecentFill>,
+}
+
+/// Orders resting for one strategy's quote on one token
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RestingQuote {
+    pub strategy: String,
+    pub token_id: TokenId,
+    pub bid: Option<OrderId>,
+    pub ask: Option<OrderId>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecentFill {
+    pub strategy: String,
+    pub market_id: MarketId,
+    pub at: DateTime<Utc>,
+}
+
+/// Periodic snapshot of in-memory engine state, restored at startup.
+///
+/// Orders, positions and loss cooldowns are written through to their tables as
+/// they change; what lives only in memory is the peak bankroll drawdown is
+/// measured from, which orders make up each quote, and the post-fill cooldowns.
+/// The bankroll itself is not checkpointed: boot rebuilds it from the starting
+/// bankroll and the closed-trade ledger. Without a checkpoint a restart measures
+/// drawdown from that rebuilt bankroll, loses track of its quotes (re-placing them
+/// next to the old ones) and lets strategies straight back into markets they just
+/// filled in. Multi-leg
+/// executions last a single request, so the order manager writes those through
+/// itself rather than waiting for the next checkpoint.
+#[derive(Clone)]
+pub struct Checkpointer {
+    db: Database,
+    risk: RiskManager,
+    order_manager: Arc<OrderManager>,
+    shutdown: Shutdown,
+}
+
+impl Checkpointer {
+    pub fn new(db: Database, risk: RiskManager, order_manager: Arc<OrderManager>) -> Self {
+        Self {
+            db,
+            risk,
+            order_manager,
+            shutdown: Shutdown::default(),
+        }
+    }
+
+    /// Stop the periodic saves once `shutdown` is triggered; the last one is left
+    /// to the caller, after the order manager has stopped
+    pub fn with_shutdown(mut self, shutdown: Shutdown) -> Self {
+        self.shutdown = shutdown;
+        self
+    }
+
+    pub async fn capture(&self) -> Checkpoint {
+        Checkpoint {
+            saved_at: Utc::now(),
+            peak_bankroll: self.risk.peak_bankroll().await,
+            quotes: self.order_manager.resting_quotes(),
+            fills: self.order_manager.recent_fills(),
+        }
+    }
+
+    pub async fn save(&self) -> Result<Checkpoint> {
+        let checkpoint = self.capture().await;
+        self.db.set_config(KEY, &serde_json::to_string(&checkpoint)?).await?;
+        Ok(checkpoint)
+    }
+
+    /// Put back the state saved by the previous run, if it left any. Quotes whose
+    /// orders are no longer open are dropped.
+    pub async fn restore(&self) -> Result<Option<Checkpoint>> {
+        let Some(stored) = self.db.get_config(KEY).await? else {
+            return Ok(None);
+        };
+        let checkpoint: Checkpoint = serde_json::from_str(&stored)?;
+        let peak = self.risk.restore_peak(checkpoint.peak_bankroll).await;
+        let quotes = self.order_manager.restore_quotes(&checkpoint.quotes);
+        self.order_manager.restore_fills(&checkpoint.fills);
+        info!(
+            "Restored checkpoint from {}: peak bankroll ${:.2}, {} quote(s), {} fill cooldown(s)",
+            checkpoint.saved_at.format("%Y-%m-%d %H:%M:%S UTC"),
+            peak,
+            quotes,
+            checkpoint.fills.len()
+        );
+        Ok(Some(checkpoint))
+    }
+
+    pub async fn run(self, interval: Duration) {
+        info!("Engine checkpoints every {:?}", interval);
+        let mut ticker = tokio::time::interval(interval);
+        ticker.tick().await;
+        loop {
+            tokio::select! {
+                _ = ticker.tick() => {}
+                _ = self.shutdown.wait() => return,
+            }
+            if let Err(e) = self.save().await {
+                warn!("Engine checkpoint failed: {:?}", e);
+            }
+        }
+    }
+}
@@ -0,0 +1,101 @@
+use std::collections::HashSet;
+use std::time::Duration;
+use tokio::sync::{mpsc, oneshot};
+use tracing::{info, warn};
+
+use crate::adapters::database::Database;
+use crate::adapters::polymarket::PolymarketClient;
+use crate::config::ExecutionConfig;
+use crate::domain::TokenId;
+use crate::engine::inflight::InflightOrders;
+use crate::engine::liquidation;
+use crate::engine::order_manager::{ManualOrder, SignalOutcome};
+use crate::error::Result;
+
+/// Periodically clears positions too small to matter.
+///
+/// A position worth less than `dust_notional` is closed through the order manager
+/// when it is still at least the minimum order size; below that the exchange won't
+/// take an order for it, so it is dropped from tracking (the tokens stay in the
+/// wallet and still redeem at resolution). Positions with an order already working
+/// are left alone.
+pub struct DustSweeper {
+    db: Database,
+    poly_client: PolymarketClient,
+    orders: InflightOrders,
+    manual_orders: mpsc::Sender<ManualOrder>,
+    config: ExecutionConfig,
+    interval: Duration,
+}
+
+impl DustSweeper {
+    pub fn new(
+        db: Database,
+        poly_client: PolymarketClient,
+        orders: InflightOrders,
+        manual_orders: mpsc::Sender<ManualOrder>,
+        config: ExecutionConfig,
+        interval: Duration,
+    ) -> Self {
+        Self {
+            db,
+            poly_client,
+            orders,
+            manual_orders,
+            config,
+            interval,
+        }
+    }
+
+    pub async fn run(self) {
+        info!(
+            "Dust sweeper started (every {:?}, below ${:.2})",
+            self.interval, self.config.dust_notional
+        );
+        let mut interval = tokio::time::interval(self.interval);
+
+        loop {
+            interval.tick().await;
+            if let Err(e) = self.sweep().await {
+                warn!("Dust sweep failed: {:?}", e);
+            }
+        }
+    }
+
+    async fn sweep(&self) -> Result<()> {
+        let working: HashSet<TokenId> = self.orders.open_orders().into_iter().map(|o| o.token_id).collect();
+
+        self.db.flush().await;
+        for position in self.db.get_positions().await? {
+            let price = if position.current_price > 0.0 { position.current_price } else { position.avg_price };
+            let value = position.size * price;
+            if value >= self.config.dust_notional || working.contains(&position.token_id) {
+                continue;
+            }
+
+            if position.size < self.config.min_order_shares {
+                warn!(
+                    "Dropping dust position {} / {}: {:.2} shares (${:.2}) is below the minimum order size",
+                    position.market_id, position.token_id, position.size, value
+                );
+                self.db.delete_position(&position.market_id, &position.token_id).await?;
+                continue;
+            }
+
+            let signal = liquidation::close_signal(&self.poly_client, &position).await?;
+            let (reply, outcome) = oneshot::channel();
+            if self.manual_orders.send(ManualOrder { signal, reply }).await.is_err() {
+                return Ok(());
+            }
+            match outcome.await {
+                Ok(SignalOutcome::Submitted { order }) => info!(
+                    "Closing dust position {} / {}: {:.2} shares (${:.2}) via order {}",
+                    position.market_id, position.token_id, position.size, value, order.id
+                ),
+                Ok(outcome) => warn!("Dust close for {} not submitted: {:?}", position.token_id, outcome),
+                Err(_) => return Ok(()),
+            }
+        }
+        Ok(())
+    }
+}
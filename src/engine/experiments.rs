@@ -0,0 +1,175 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::time::Duration;
+use tracing::{info, warn};
+
+use crate::adapters::database::Database;
+use crate::config::ExperimentConfig;
+use crate::domain::VariantReport;
+use crate::engine::shadow;
+use crate::error::{Error, Result};
+use crate::strategy::variant::{self, Variant};
+use crate::strategy::Strategy;
+
+/// A strategy A/B test: the base strategy and the variants configured against it
+#[derive(Debug, Clone, Serialize)]
+pub struct Experiment {
+    pub base: String,
+    /// The base first, then its variants by name
+    pub arms: Vec<String>,
+    /// The arm that trades live
+    pub live: String,
+}
+
+impl Experiment {
+    /// Arms that run in shadow mode
+    pub fn shadowed(&self) -> impl Iterator<Item = &str> {
+        self.arms.iter().map(String::as_str).filter(|arm| *arm != self.live)
+    }
+}
+
+/// The experiments the configured variants make up. Unparseable specs are left
+/// out (`register` reports them), as is a live choice that isn't one of the arms.
+pub fn from_config(config: &ExperimentConfig) -> Vec<Experiment> {
+    let mut experiments: Vec<Experiment> = Vec::new();
+    for (name, spec) in &config.variants {
+        let Ok((base, _)) = variant::parse_spec(spec) else {
+            continue;
+        };
+        match experiments.iter_mut().find(|e| e.base == base) {
+            Some(experiment) => experiment.arms.push(name.clone()),
+            None => experiments.push(Experiment {
+                arms: vec![base.clone(), name.clone()],
+                live: base.clone(),
+                base,
+            }),
+        }
+    }
+    for experiment in &mut experiments {
+        if let Some(live) = config.live.get(&experiment.base).filter(|l| experiment.arms.contains(l)) {
+            experiment.live = live.clone();
+        }
+    }
+    experiments
+}
+
+/// Add every configured variant to `strategies`: each instance of its base
+/// strategy again, re-parameterized and renamed. Variants of unknown strategies
+/// are skipped with a warning; a spec or parameter the base rejects is an error.
+pub fn register(config: &ExperimentConfig, strategies: &mut Vec<Box<dyn Strategy>>) -> Result<()> {
+    for (name, spec) in &config.variants {
+        let (base, params) = variant::parse_spec(spec)?;
+        if strategies.iter().any(|s| s.name() == name) {
+            warn!("Variant '{}' ignored: a strategy already has that name", name);
+            continue;
+        }
+        let variants = strategies
+            .iter()
+            .filter(|s| s.name() == base)
+            .map(|s| Ok(Box::new(Variant::new(name.clone(), s.with_params(&params)?)) as Box<dyn Strategy>))
+            .collect::<Result<Vec<_>>>()
+            .map_err(|e| Error::invalid(format!("variant '{}' of {}: {}", name, base, e)))?;
+        if variants.is_empty() {
+            warn!("Variant '{}' of unknown strategy '{}' ignored", name, base);
+            continue;
+        }
+        info!("Variant '{}' of {} registered on {} instances", name, base, variants.len());
+        strategies.extend(variants);
+    }
+    let experiments = from_config(config);
+    for (base, live) in &config.live {
+        if !experiments.iter().any(|e| &e.base == base && &e.live == live) {
+            warn!("Live variant '{}' for '{}' is not one of its arms; the base trades", live, base);
+        }
+    }
+    Ok(())
+}
+
+/// Every arm's results from its signal records created since `since`, resolved or
+/// not. Live arms are scored on what they ordered, at the order price and size,
+/// the same way shadow arms are scored on their simulated fills, so the arms
+/// compare like for like; actual fills are in the trade analytics.
+pub async fn compare(db: &Database, experiments: &[Experiment], since: DateTime<Utc>) -> Result<Vec<VariantReport>> {
+    let mut records = db.get_resolved_signal_records(since).await?;
+    records.extend(
+        db.get_unresolved_signal_records()
+            .await?
+            .into_iter()
+            .filter(|r| r.created_at >= since),
+    );
+    let now = Utc::now();
+    let mut reports = Vec::new();
+    for experiment in experiments {
+        let tallies = shadow::tally(records.iter().filter(|r| experiment.arms.contains(&r.strategy)));
+        for arm in &experiment.arms {
+            let tally = tallies.iter().find(|t| &t.strategy == arm);
+            reports.push(VariantReport {
+                timestamp: now,
+                experiment: experiment.base.clone(),
+                strategy: arm.clone(),
+                live: *arm == experiment.live,
+                signals: tally.map_or(0, |t| t.signals),
+                resolved: tally.map_or(0, |t| t.resolved),
+                wins: tally.map_or(0, |t| t.wins),
+                pnl: tally.map_or(0.0, |t| t.pnl),
+                open_notional: tally.map_or(0.0, |t| t.open_notional),
+            });
+        }
+    }
+    Ok(reports)
+}
+
+/// Persists a comparison of every experiment's arms on a fixed cadence, so how
+/// the variants stacked up can be followed over time
+pub struct ExperimentReporter {
+    db: Database,
+    experiments: Vec<Experiment>,
+    config: ExperimentConfig,
+}
+
+impl ExperimentReporter {
+    pub fn new(db: Database, config: ExperimentConfig) -> Self {
+        Self {
+            db,
+            experiments: from_config(&config),
+            config,
+        }
+    }
+
+    pub async fn run(self) {
+        let interval = Duration::from_secs(self.config.report_secs);
+        info!(
+            "Experiment reporter started for {} experiments (every {:?})",
+            self.experiments.len(),
+            interval
+        );
+        let mut ticker = tokio::time::interval(interval);
+        // The first tick fires immediately; there is nothing new to compare at startup
+        ticker.tick().await;
+        loop {
+            ticker.tick().await;
+            if let Err(e) = self.report().await {
+                warn!("Experiment report failed: {:?}", e);
+            }
+        }
+    }
+
+    async fn report(&self) -> Result<()> {
+        let since = Utc::now() - chrono::Duration::days(self.config.lookback_days.max(1));
+        let reports = compare(&self.db, &self.experiments, since).await?;
+        self.db.insert_variant_reports(&reports).await?;
+        for report in &reports {
+            info!(
+                "Experiment {}: {}{} {} signals, {} resolved, {} wins, PnL {:+.2}",
+                report.experiment,
+                report.strategy,
+                if report.live { " (live)" } else { "" },
+                report.signals,
+                report.resolved,
+                report.wins,
+                report.pnl
+            );
+        }
+        Ok(())
+    }
+}
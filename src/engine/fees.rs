@@ -0,0 +1,24 @@
+use crate::config::FeeConfig;
+
+/// Fee per share for a fill at `price`.
+///
+/// Polymarket charges `shares × price × rate × (price × (1 − price))^exponent`: the
+/// fee peaks at 50¢ and fades toward the extremes, where outcomes are nearly
+/// decided. Makers pay the maker rate, which on the current schedule is zero.
+pub fn per_share(fees: &FeeConfig, price: f64, maker: bool) -> f64 {
+    if price <= 0.0 || price >= 1.0 {
+        return 0.0;
+    }
+    let rate = if maker { fees.maker_rate } else { fees.taker_rate };
+    price * rate * (price * (1.0 - price)).powf(fees.exponent)
+}
+
+/// Fee (USDC) for taking `size` shares at `price`
+pub fn taker_fee(fees: &FeeConfig, price: f64, size: f64) -> f64 {
+    per_share(fees, price, false) * size
+}
+
+/// What a share bought at `price` by crossing the spread really costs
+pub fn taker_cost(fees: &FeeConfig, price: f64) -> f64 {
+    price + per_share(fees, price, false)
+}
@@ -0,0 +1,93 @@
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::time::Duration;
+use tracing::{info, warn};
+
+use crate::adapters::data_api::{DataApiClient, PublicTrade};
+use crate::adapters::database::Database;
+use crate::engine::inflight::InflightOrders;
+use crate::domain::{Order, ProbableFill};
+
+/// Trades fetched per market per poll
+const TAPE_LIMIT: u32 = 100;
+/// Our limit price is a tick multiple; anything closer than this is "at our price"
+const PRICE_EPSILON: f64 = 1e-6;
+
+/// Fallback fill detection from the public trade tape.
+///
+/// Without the authenticated user channel we can't see our own fills, so this
+/// polls public prints for markets where we have resting orders and flags prints
+/// at our exact token/price, no larger than our size, after our order was placed.
+/// Matches are only *probable* — someone else's order at the same level looks
+/// identical — and are left unconfirmed for the reconciliation pass.
+pub struct TapeFillDetector {
+    db: Database,
+    orders: InflightOrders,
+    data_api: DataApiClient,
+    poll_interval: Duration,
+}
+
+impl TapeFillDetector {
+    pub fn new(db: Database, orders: InflightOrders, data_api: DataApiClient, poll_interval: Duration) -> Self {
+        Self { db, orders, data_api, poll_interval }
+    }
+
+    pub async fn run(self) {
+        info!("Tape fill detector started (poll every {:?})", self.poll_interval);
+        let mut interval = tokio::time::interval(self.poll_interval);
+
+        loop {
+            interval.tick().await;
+            if let Err(e) = self.poll().await {
+                warn!("Tape fill detection pass failed: {:?}", e);
+            }
+        }
+    }
+
+    async fn poll(&self) -> crate::error::Result<()> {
+        let open_orders = self.orders.open_orders();
+        let mut by_market: HashMap<&str, Vec<&Order>> = HashMap::new();
+        for order in &open_orders {
+            by_market.entry(order.market_id.as_str()).or_default().push(order);
+        }
+
+        for (market_id, orders) in by_market {
+            let tape = match self.data_api.get_market_trades(market_id, TAPE_LIMIT).await {
+                Ok(t) => t,
+                Err(e) => {
+                    warn!("Tape fetch failed for {}: {:?}", market_id, e);
+                    continue;
+                }
+            };
+
+            for trade in &tape {
+                for order in orders.iter().filter(|o| matches(o, trade)) {
+                    let fill = ProbableFill {
+                        order_id: order.id.clone(),
+                        trade_key: trade.trade_key(),
+                        token_id: trade.asset.clone(),
+                        price: trade.price,
+                        size: trade.size,
+                        trade_timestamp: DateTime::from_timestamp(trade.timestamp, 0).unwrap_or_else(Utc::now),
+                        detected_at: Utc::now(),
+                    };
+                    if self.db.insert_probable_fill(&fill).await? {
+                        info!(
+                            "Probable fill: order {} {} {:.2}@{:.4} matched public trade {}",
+                            order.id, order.side, fill.size, fill.price, fill.trade_key
+                        );
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn matches(order: &Order, trade: &PublicTrade) -> bool {
+    trade.asset == order.token_id
+        && (trade.price - order.price).abs() < PRICE_EPSILON
+        && trade.size <= order.size + PRICE_EPSILON
+        && trade.timestamp >= order.created_at.timestamp()
+}
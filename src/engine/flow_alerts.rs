@@ -0,0 +1,169 @@
+use reqwest::Client;
+use std::collections::{HashMap, VecDeque};
+use std::time::Duration;
+use tracing::{info, warn};
+
+use crate::adapters::database::Database;
+use crate::bus::{EventBus, EventReceiver};
+use crate::config::FlowAlertConfig;
+use crate::domain::{BookLevel, FlowAlert, FlowAlertKind, MarketData, MarketId, OrderBook, Side, TokenId};
+use crate::error::Result;
+
+/// Prints needed before a token's average trade size means anything
+const MIN_BASELINE_TRADES: usize = 20;
+
+/// Rolling window of a token's trade sizes
+#[derive(Default)]
+struct Baseline {
+    sizes: VecDeque<f64>,
+    total: f64,
+}
+
+impl Baseline {
+    fn mean(&self) -> Option<f64> {
+        (self.sizes.len() >= MIN_BASELINE_TRADES).then(|| self.total / self.sizes.len() as f64)
+    }
+
+    fn push(&mut self, size: f64, capacity: usize) {
+        self.sizes.push_back(size);
+        self.total += size;
+        while self.sizes.len() > capacity.max(1) {
+            self.total -= self.sizes.pop_front().unwrap_or_default();
+        }
+    }
+}
+
+/// Levels of the previous side that the new best price has moved through, as
+/// (levels, shares, notional). `through` says whether a level is past the new best.
+fn taken(previous: &[BookLevel], through: impl Fn(f64) -> bool) -> (usize, f64, f64) {
+    previous
+        .iter()
+        .filter(|l| through(l.price))
+        .fold((0, 0.0, 0.0), |(n, size, notional), l| (n + 1, size + l.size, notional + l.price * l.size))
+}
+
+/// Flags whale flow on the Polymarket feeds.
+///
+/// A print is large when it clears `large_notional` outright, or is `size_mult`
+/// times the token's rolling average size and still clears `min_notional`. A
+/// sweep is the best price on one side moving through `sweep_levels` or more
+/// levels of the previous snapshot, worth `sweep_notional`; quotes pulled all at
+/// once look the same, so treat sweeps as a hint rather than proof of a taker.
+///
+/// Alerts go back on the event bus as `MarketData::FlowAlert` for the
+/// strategies, into the `flow_alerts` table for the API, and to each webhook.
+pub struct FlowAlertDetector {
+    bus: EventBus,
+    rx: EventReceiver<MarketData>,
+    db: Database,
+    client: Client,
+    config: FlowAlertConfig,
+    baselines: HashMap<TokenId, Baseline>,
+    books: HashMap<TokenId, OrderBook>,
+}
+
+impl FlowAlertDetector {
+    pub fn new(bus: EventBus, db: Database, config: FlowAlertConfig) -> Result<Self> {
+        Ok(Self {
+            rx: bus.subscribe(),
+            bus,
+            db,
+            client: Client::builder().timeout(Duration::from_secs(10)).build()?,
+            config,
+            baselines: HashMap::new(),
+            books: HashMap::new(),
+        })
+    }
+
+    pub async fn run(mut self) {
+        info!(
+            "Flow alert detector started (prints ≥ ${:.0} or {}x average, sweeps of {} levels ≥ ${:.0})",
+            self.config.large_notional, self.config.size_mult, self.config.sweep_levels, self.config.sweep_notional
+        );
+        loop {
+            let event = self.rx.recv().await;
+            let alert = match event {
+                MarketData::PolymarketTrade { market_id, token_id, price, size, side, timestamp } => {
+                    self.on_trade(&token_id, price, size).then_some(FlowAlert {
+                        market_id,
+                        token_id,
+                        kind: FlowAlertKind::LargeTrade,
+                        side,
+                        size,
+                        notional: price * size,
+                        price,
+                        levels: 1,
+                        timestamp,
+                    })
+                }
+                MarketData::PolymarketOrderBook { market_id, token_id, book } => self.on_book(market_id, token_id, book),
+                _ => None,
+            };
+            if let Some(alert) = alert {
+                self.publish(alert).await;
+            }
+        }
+    }
+
+    /// Whether the print is large against the token's baseline, which it then joins
+    fn on_trade(&mut self, token_id: &TokenId, price: f64, size: f64) -> bool {
+        let notional = price * size;
+        let baseline = self.baselines.entry(token_id.clone()).or_default();
+        let outsized = baseline
+            .mean()
+            .is_some_and(|mean| mean > 0.0 && size >= self.config.size_mult * mean);
+        baseline.push(size, self.config.baseline_trades);
+        notional >= self.config.large_notional || (outsized && notional >= self.config.min_notional)
+    }
+
+    fn on_book(&mut self, market_id: MarketId, token_id: TokenId, book: OrderBook) -> Option<FlowAlert> {
+        let previous = self.books.insert(token_id.clone(), book.clone())?;
+        let best = |levels: &[BookLevel]| levels.first().map(|l| l.price);
+
+        // Offers lifted: the new best ask sits above old ones (or the side emptied)
+        let new_ask = best(&book.asks);
+        let lifted = taken(&previous.asks, |p| new_ask.is_none_or(|ask| p < ask));
+        // Bids hit: the new best bid sits below old ones
+        let new_bid = best(&book.bids);
+        let hit = taken(&previous.bids, |p| new_bid.is_none_or(|bid| p > bid));
+
+        let (side, (levels, size, notional), price) = if lifted.2 >= hit.2 {
+            (Side::Buy, lifted, new_ask.or(previous.asks.last().map(|l| l.price)))
+        } else {
+            (Side::Sell, hit, new_bid.or(previous.bids.last().map(|l| l.price)))
+        };
+        if levels < self.config.sweep_levels.max(1) || notional < self.config.sweep_notional {
+            return None;
+        }
+        Some(FlowAlert {
+            market_id,
+            token_id,
+            kind: FlowAlertKind::BookSweep,
+            side,
+            size,
+            notional,
+            price: price.unwrap_or_default(),
+            levels,
+            timestamp: book.timestamp,
+        })
+    }
+
+    async fn publish(&self, alert: FlowAlert) {
+        info!(
+            "Flow alert: {:?} {} {:.0} shares (${:.0}) on {} at {:.3}",
+            alert.kind, alert.side, alert.size, alert.notional, alert.market_id, alert.price
+        );
+        if let Err(e) = self.db.insert_flow_alert(&alert).await {
+            warn!("Failed to record flow alert: {:?}", e);
+        }
+        for url in &self.config.webhooks {
+            let request = self.client.post(url).json(&alert);
+            tokio::spawn(async move {
+                if let Err(e) = request.send().await.and_then(|r| r.error_for_status()) {
+                    warn!("Flow alert webhook failed: {}", e.without_url());
+                }
+            });
+        }
+        self.bus.publish(MarketData::FlowAlert { alert });
+    }
+}
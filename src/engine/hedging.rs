@@ -0,0 +1,174 @@
+use chrono::{DateTime, Utc};
+use std::collections::{BTreeMap, HashMap};
+use std::time::Duration;
+use tracing::{info, warn};
+
+use crate::adapters::binance_futures::{BinanceFuturesClient, LotSize};
+use crate::adapters::database::Database;
+use crate::config::{HedgeConfig, PricingConfig};
+use crate::domain::{Market, MarketId, Position, Side, TokenId};
+use crate::engine::pricing;
+use crate::error::Result;
+use crate::strategy::latency_arb;
+
+/// Horizon for markets without an end date, as in latency_arb
+const DEFAULT_HORIZON_YEARS: f64 = 1.0 / 365.0;
+
+/// A watched "Will <asset> be above $<strike>?" market
+struct Threshold {
+    market_id: MarketId,
+    /// Any other token in the market is taken as YES
+    no_token_id: Option<TokenId>,
+    symbol: &'static str,
+    strike: f64,
+    end_date: Option<DateTime<Utc>>,
+}
+
+impl Threshold {
+    fn from_market(market: &Market) -> Option<Self> {
+        let (symbol, strike) = latency_arb::threshold_market(market)?;
+        Some(Self {
+            market_id: market.id.clone(),
+            no_token_id: market.outcome_token("no").cloned(),
+            symbol,
+            strike,
+            end_date: market.end_date,
+        })
+    }
+
+    /// Underlying units `position` gains value like, at `spot`: its shares times
+    /// the YES delta, negated for NO tokens and for short positions
+    fn delta(&self, position: &Position, spot: f64, vol: f64, now: DateTime<Utc>) -> f64 {
+        let years = self
+            .end_date
+            .map_or(DEFAULT_HORIZON_YEARS, |end| pricing::years_until(end, now));
+        let per_share = pricing::delta_above(spot, self.strike, vol, years);
+        let outcome = if self.no_token_id.as_deref() == Some(position.token_id.as_str()) { -1.0 } else { 1.0 };
+        let side = match position.side {
+            Side::Buy => 1.0,
+            Side::Sell => -1.0,
+        };
+        position.size * per_share * outcome * side
+    }
+}
+
+/// Offsets the directional crypto exposure of threshold-market positions with
+/// Binance USDⓈ-M perps.
+///
+/// Each pass adds up the spot delta of held positions per underlying (priced off
+/// the perp's mark with the configured volatility) and steers the perp toward
+/// `-hedge_ratio` times it, capped at `max_notional`. Nothing trades until the
+/// perp is `band_usd` off target, so small drifts in delta don't churn fees.
+/// Underlyings with no exposure left are unwound the same way.
+pub struct Hedger {
+    db: Database,
+    client: BinanceFuturesClient,
+    config: HedgeConfig,
+    pricing: PricingConfig,
+    thresholds: Vec<Threshold>,
+    lots: HashMap<String, LotSize>,
+}
+
+impl Hedger {
+    pub fn new(
+        db: Database,
+        client: BinanceFuturesClient,
+        config: HedgeConfig,
+        pricing: PricingConfig,
+        markets: &[Market],
+    ) -> Self {
+        Self {
+            db,
+            client,
+            config,
+            pricing,
+            thresholds: markets.iter().filter_map(Threshold::from_market).collect(),
+            lots: HashMap::new(),
+        }
+    }
+
+    pub async fn run(mut self) {
+        let interval = Duration::from_secs(self.config.rebalance_secs.max(1));
+        info!(
+            "Hedger started for {} threshold markets (ratio {:.2}, band ${:.0}, every {:?})",
+            self.thresholds.len(),
+            self.config.hedge_ratio,
+            self.config.band_usd,
+            interval
+        );
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            if let Err(e) = self.rebalance().await {
+                warn!("Hedge rebalance failed: {:?}", e);
+            }
+        }
+    }
+
+    async fn rebalance(&mut self) -> Result<()> {
+        // Hedge what is actually held, queued fills included
+        self.db.flush().await;
+        let positions = self.db.get_positions().await?;
+        let now = Utc::now();
+
+        let mut exposure: BTreeMap<&'static str, f64> = BTreeMap::new();
+        let mut marks: HashMap<&'static str, f64> = HashMap::new();
+        for threshold in &self.thresholds {
+            exposure.entry(threshold.symbol).or_default();
+            let held: Vec<&Position> = positions
+                .iter()
+                .filter(|p| p.market_id == threshold.market_id && p.size > 0.0)
+                .collect();
+            if held.is_empty() {
+                continue;
+            }
+            let spot = match marks.get(threshold.symbol) {
+                Some(&spot) => spot,
+                None => {
+                    let spot = self.client.mark_price(threshold.symbol).await?;
+                    marks.insert(threshold.symbol, spot);
+                    spot
+                }
+            };
+            let vol = self.pricing.vol(threshold.symbol);
+            *exposure.entry(threshold.symbol).or_default() +=
+                held.iter().map(|p| threshold.delta(p, spot, vol, now)).sum::<f64>();
+        }
+
+        for (symbol, delta) in exposure {
+            let mark = match marks.get(symbol) {
+                Some(&mark) => mark,
+                None => self.client.mark_price(symbol).await?,
+            };
+            if mark <= 0.0 {
+                continue;
+            }
+            let cap = self.config.max_notional.max(0.0) / mark;
+            let target = (-self.config.hedge_ratio * delta).clamp(-cap, cap);
+            let current = self.client.position(symbol).await?;
+            let gap = target - current;
+            if (gap * mark).abs() < self.config.band_usd {
+                continue;
+            }
+
+            let lot = match self.lots.get(symbol) {
+                Some(lot) => *lot,
+                None => {
+                    let lot = self.client.lot_size(symbol).await?;
+                    self.lots.insert(symbol.to_string(), lot);
+                    lot
+                }
+            };
+            let Some(quantity) = lot.round(gap.abs()) else {
+                continue;
+            };
+            let side = if gap > 0.0 { Side::Buy } else { Side::Sell };
+            let order_id = self.client.market_order(symbol, &side, quantity, &lot).await?;
+            info!(
+                "Hedge {} {} {} (delta {:+.4}, perp {:+.4} → target {:+.4}) as order {}",
+                side, quantity, symbol, delta, current, target, order_id
+            );
+        }
+        Ok(())
+    }
+}
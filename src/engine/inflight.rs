@@ -0,0 +1,193 @@
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, RwLock};
+
+use crate::adapters::database::{Database, Write};
+use crate::adapters::polymarket::{CancelResponse, PolymarketClient};
+use crate::bus::EventBus;
+use crate::domain::{MarketId, Order, OrderId, OrderStatus};
+use crate::error::{Error, Result};
+
+/// In-memory registry of live orders, keyed by local ID with an exchange-ID index.
+///
+/// The order manager, fill detection and cancel paths share this instead of each
+/// querying the database. Every mutation is queued for the database's write-behind
+/// writer rather than awaited, so recording an order never waits on the disk. A
+/// crash can lose the last few milliseconds of changes; an order left with a stale
+/// status is corrected by the order sync. Orders leave the map once they reach a
+/// terminal status. Each change is also published on the event bus.
+#[derive(Clone)]
+pub struct InflightOrders {
+    db: Database,
+    inner: Arc<RwLock<Inner>>,
+    bus: EventBus,
+}
+
+/// Outcome of cancelling a group of orders
+#[derive(Debug, Default, Serialize)]
+pub struct CancelReport {
+    /// Local IDs of the orders now cancelled
+    pub cancelled: Vec<OrderId>,
+    /// Local ID → why the order is still live
+    pub refused: BTreeMap<OrderId, String>,
+}
+
+#[derive(Default)]
+struct Inner {
+    by_id: HashMap<OrderId, Order>,
+    /// exchange order ID → local order ID
+    by_remote: HashMap<String, OrderId>,
+}
+
+impl InflightOrders {
+    /// Load open orders persisted by a previous run
+    pub async fn load(db: Database) -> Result<Self> {
+        let mut inner = Inner::default();
+        for order in db.get_open_orders().await? {
+            if let Some(remote_id) = &order.remote_id {
+                inner.by_remote.insert(remote_id.clone(), order.id.clone());
+            }
+            inner.by_id.insert(order.id.clone(), order);
+        }
+        Ok(Self {
+            db,
+            inner: Arc::new(RwLock::new(inner)),
+            bus: EventBus::default(),
+        })
+    }
+
+    /// Publish every order change on `bus`
+    pub fn with_bus(mut self, bus: EventBus) -> Self {
+        self.bus = bus;
+        self
+    }
+
+    fn publish(&self, order: Order) {
+        self.bus.publish(order);
+    }
+
+    pub fn insert(&self, order: Order) {
+        self.db.enqueue(Write::Order(order.clone()));
+        let mut inner = self.inner.write().unwrap();
+        if let Some(remote_id) = &order.remote_id {
+            inner.by_remote.insert(remote_id.clone(), order.id.clone());
+        }
+        inner.by_id.insert(order.id.clone(), order.clone());
+        drop(inner);
+        self.publish(order);
+    }
+
+    pub fn set_remote_id(&self, order_id: &OrderId, remote_id: &str) {
+        self.db.enqueue(Write::OrderRemoteId(order_id.clone(), remote_id.to_string()));
+        let mut inner = self.inner.write().unwrap();
+        let Some(order) = inner.by_id.get_mut(order_id) else {
+            return;
+        };
+        order.remote_id = Some(remote_id.to_string());
+        let order = order.clone();
+        inner.by_remote.insert(remote_id.to_string(), order_id.clone());
+        drop(inner);
+        self.publish(order);
+    }
+
+    /// Terminal statuses (filled, cancelled, failed) drop the order from the map
+    pub fn set_status(&self, order_id: &OrderId, status: OrderStatus) {
+        self.db.enqueue(Write::OrderStatus(order_id.clone(), status.clone()));
+        let mut inner = self.inner.write().unwrap();
+        let changed = if matches!(status, OrderStatus::Pending | OrderStatus::Open) {
+            inner.by_id.get_mut(order_id).map(|order| {
+                order.status = status;
+                order.clone()
+            })
+        } else {
+            inner.by_id.remove(order_id).map(|mut order| {
+                if let Some(remote_id) = &order.remote_id {
+                    inner.by_remote.remove(remote_id);
+                }
+                order.status = status;
+                order
+            })
+        };
+        drop(inner);
+        if let Some(order) = changed {
+            self.publish(order);
+        }
+    }
+
+    pub fn get(&self, order_id: &OrderId) -> Option<Order> {
+        self.inner.read().unwrap().by_id.get(order_id).cloned()
+    }
+
+    pub fn open_orders(&self) -> Vec<Order> {
+        self.inner.read().unwrap().by_id.values().cloned().collect()
+    }
+
+    /// Cancel one order on the CLOB by its exchange ID and mark it cancelled.
+    /// Returns false when the exchange refuses (typically already filled or gone).
+    pub async fn cancel(&self, poly_client: &PolymarketClient, order_id: &OrderId) -> Result<bool> {
+        let remote_id = self
+            .get(order_id)
+            .and_then(|o| o.remote_id)
+            .ok_or_else(|| Error::invalid(format!("Order {} has no exchange ID", order_id)))?;
+        if !poly_client.cancel_order(&remote_id).await? {
+            return Ok(false);
+        }
+        self.set_status(order_id, OrderStatus::Cancelled);
+        Ok(true)
+    }
+
+    /// Exchange-wide cancel, then mark every tracked order cancelled
+    pub async fn cancel_all(&self, poly_client: &PolymarketClient) -> Result<()> {
+        poly_client.cancel_all().await?;
+        for order in self.open_orders() {
+            self.set_status(&order.id, OrderStatus::Cancelled);
+        }
+        Ok(())
+    }
+
+    /// Cancel every resting order on `market_id` at the exchange, tracked or not,
+    /// and mark the tracked ones cancelled
+    pub async fn cancel_market(&self, poly_client: &PolymarketClient, market_id: &MarketId) -> Result<CancelReport> {
+        let targets: Vec<Order> = self
+            .open_orders()
+            .into_iter()
+            .filter(|o| &o.market_id == market_id)
+            .collect();
+        let response = poly_client.cancel_market_orders(market_id, None).await?;
+        self.settle_cancels(targets, response).await
+    }
+
+    /// Cancel the orders `strategy` has resting, leaving other strategies' alone
+    pub async fn cancel_strategy(&self, poly_client: &PolymarketClient, strategy: &str) -> Result<CancelReport> {
+        let targets: Vec<Order> = self
+            .open_orders()
+            .into_iter()
+            .filter(|o| o.strategy == strategy)
+            .collect();
+        let remote_ids: Vec<String> = targets.iter().filter_map(|o| o.remote_id.clone()).collect();
+        let response = poly_client.cancel_orders(&remote_ids).await?;
+        self.settle_cancels(targets, response).await
+    }
+
+    /// Mark the targets the exchange cancelled, and say why each other one is still live
+    async fn settle_cancels(&self, targets: Vec<Order>, mut response: CancelResponse) -> Result<CancelReport> {
+        let mut report = CancelReport::default();
+        for order in targets {
+            let Some(remote_id) = &order.remote_id else {
+                report.refused.insert(order.id, "not yet acknowledged by the exchange".into());
+                continue;
+            };
+            if response.canceled.contains(remote_id) {
+                self.set_status(&order.id, OrderStatus::Cancelled);
+                report.cancelled.push(order.id);
+            } else {
+                let reason = response
+                    .not_canceled
+                    .remove(remote_id)
+                    .unwrap_or_else(|| "not cancelled by the exchange".into());
+                report.refused.insert(order.id, reason);
+            }
+        }
+        Ok(report)
+    }
+}
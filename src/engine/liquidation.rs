@@ -0,0 +1,264 @@
+use chrono::Utc;
+use serde::Serialize;
+use tokio::sync::RwLock;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+use crate::adapters::database::{Database, Write};
+use crate::adapters::polymarket::{OrderLeg, PolymarketClient};
+use crate::bus::EventBus;
+use crate::config::FeeConfig;
+use crate::engine::fees;
+use crate::engine::inflight::InflightOrders;
+use crate::error::Result;
+use crate::logging;
+use crate::domain::{
+    BookLevel, ClosedTrade, Execution, MarketId, Order, OrderBook, OrderId, OrderStatus, OrderType, Position, Side, Signal,
+    TokenId, Trade,
+};
+
+/// Price floor/ceiling for a sweep when the book can't absorb the whole position
+const MIN_PRICE: f64 = 0.01;
+const MAX_PRICE: f64 = 0.99;
+/// Less than the CLOB's 0.01-share size step left over is too little to exit; the
+/// position is dropped rather than kept open
+const DUST_SHARES: f64 = 0.01;
+
+/// Outcome of flattening one position
+#[derive(Debug, Clone, Serialize)]
+pub struct LiquidationResult {
+    pub market_id: MarketId,
+    pub token_id: TokenId,
+    pub side: Side,
+    pub size: f64,
+    /// Marketable limit we submitted at
+    pub limit_price: Option<f64>,
+    pub order_id: Option<String>,
+    /// Exchange status, e.g. "matched" or "live" if part of it is resting
+    pub status: Option<String>,
+    /// Realized average exit price of the immediately matched part
+    pub exit_price: Option<f64>,
+    pub error: Option<String>,
+}
+
+/// Emergency flattening: cross the spread on every open position.
+///
+/// Each position gets a limit order priced at the level that sweeps enough
+/// opposite-side depth to cover its size, so it executes immediately against the
+/// current book. Failures are reported per position and never abort the rest.
+/// What the matched exits realize is credited to `bankroll`.
+pub async fn liquidate_all(
+    poly_client: &PolymarketClient,
+    db: &Database,
+    orders: &InflightOrders,
+    fees: &FeeConfig,
+    bus: &EventBus,
+    bankroll: &RwLock<f64>,
+) -> Vec<LiquidationResult> {
+    // Fills still queued would otherwise leave positions out
+    db.flush().await;
+    let positions = match db.get_positions().await {
+        Ok(p) => p,
+        Err(e) => {
+            error!("Liquidation could not load positions: {:?}", e);
+            return Vec::new();
+        }
+    };
+
+    warn!("LIQUIDATING {} POSITIONS", positions.len());
+    let mut results = Vec::with_capacity(positions.len());
+    for position in &positions {
+        results.push(liquidate(poly_client, db, orders, fees, bus, bankroll, position).await);
+    }
+    results
+}
+
+/// Exit order for one position, sized to the whole position and priced to sweep the
+/// current book. Unlike liquidation it is meant for the order manager, so it still
+/// passes risk, hard caps and participation limits.
+pub async fn close_signal(poly_client: &PolymarketClient, position: &Position) -> Result<Signal> {
+    let side = exit_side(position);
+    let book = poly_client.get_orderbook(&position.token_id).await?;
+    Ok(Signal {
+        strategy: "manual".into(),
+        market_id: position.market_id.clone(),
+        side: side.clone(),
+        confidence: 1.0,
+        price: exit_limit(&book, &side, position.size),
+        size: position.size,
+        event_time: None,
+        token_id: Some(position.token_id.clone()),
+        order_type: None,
+        execution: Execution::Passive,
+        expires_at: None,
+        good_till: None,
+    })
+}
+
+fn exit_side(position: &Position) -> Side {
+    match position.side {
+        Side::Buy => Side::Sell,
+        Side::Sell => Side::Buy,
+    }
+}
+
+/// Marketable limit for `size` on the side of the book an exit on `side` takes
+fn exit_limit(book: &OrderBook, side: &Side, size: f64) -> f64 {
+    match side {
+        Side::Sell => sweep_price(&book.bids, size, true),
+        Side::Buy => sweep_price(&book.asks, size, false),
+    }
+}
+
+async fn liquidate(
+    poly_client: &PolymarketClient,
+    db: &Database,
+    orders: &InflightOrders,
+    fees: &FeeConfig,
+    bus: &EventBus,
+    bankroll: &RwLock<f64>,
+    position: &Position,
+) -> LiquidationResult {
+    let side = exit_side(position);
+    let mut result = LiquidationResult {
+        market_id: position.market_id.clone(),
+        token_id: position.token_id.clone(),
+        side: side.clone(),
+        size: position.size,
+        limit_price: None,
+        order_id: None,
+        status: None,
+        exit_price: None,
+        error: None,
+    };
+
+    let book = match poly_client.get_orderbook(&position.token_id).await {
+        Ok(b) => b,
+        Err(e) => {
+            result.error = Some(format!("order book fetch failed: {}", e));
+            return result;
+        }
+    };
+    let limit_price = exit_limit(&book, &side, position.size);
+    result.limit_price = Some(limit_price);
+
+    let mut order = Order {
+        id: OrderId::generate(),
+        market_id: position.market_id.clone(),
+        side: side.clone(),
+        token_id: position.token_id.clone(),
+        price: limit_price,
+        size: position.size,
+        order_type: OrderType::GTC,
+        status: OrderStatus::Pending,
+        created_at: Utc::now(),
+        remote_id: None,
+        strategy: "liquidation".into(),
+        replaces: None,
+        expires_at: None,
+    };
+    orders.insert(order.clone());
+
+    let resp = match poly_client.post_order(&OrderLeg::from(&order)).await {
+        Ok(resp) if resp.success => resp,
+        Ok(resp) => {
+            result.error = Some(resp.error_msg.unwrap_or_else(|| "rejected".into()));
+            orders.set_status(&order.id, OrderStatus::Failed);
+            return result;
+        }
+        Err(e) => {
+            result.error = Some(e.to_string());
+            orders.set_status(&order.id, OrderStatus::Failed);
+            return result;
+        }
+    };
+
+    result.order_id = resp.order_id.clone();
+    if let Some(remote_id) = &resp.order_id {
+        orders.set_remote_id(&order.id, remote_id);
+    }
+    order.remote_id = resp.order_id.clone();
+    logging::order_submitted(&order);
+    result.status = resp.status.clone();
+    result.exit_price = resp.fill_price(&side);
+
+    // Only what matched on arrival is booked; the rest of a partial fill rests
+    let matched = resp.status.as_deref() == Some("matched");
+    let filled = resp
+        .filled_shares(&side)
+        .or(matched.then_some(order.size))
+        .unwrap_or(0.0)
+        .min(order.size);
+    let status = if order.size - filled < DUST_SHARES { OrderStatus::Filled } else { OrderStatus::Open };
+    orders.set_status(&order.id, status);
+    if filled > 0.0 {
+        let price = result.exit_price.unwrap_or(limit_price);
+        let trade = Trade {
+            id: Uuid::new_v4().to_string(),
+            order_id: order.id.clone(),
+            market_id: order.market_id.clone(),
+            side: side.clone(),
+            price,
+            size: filled,
+            fee: fees::taker_fee(fees, price, filled),
+            timestamp: Utc::now(),
+        };
+        db.enqueue(Write::Trade(trade.clone()));
+        logging::fill(&order, &trade, &position.strategy);
+        bus.publish(trade.clone());
+        // The entry fee is in the position's cost basis; the exit fee comes off here
+        let per_share = match position.side {
+            Side::Buy => price - position.avg_price,
+            Side::Sell => position.avg_price - price,
+        };
+        let closed = ClosedTrade {
+            id: Uuid::new_v4().to_string(),
+            strategy: position.strategy.clone(),
+            market_id: position.market_id.clone(),
+            token_id: position.token_id.clone(),
+            side: position.side.clone(),
+            size: filled,
+            entry_price: position.avg_price,
+            exit_price: price,
+            pnl: per_share * filled - trade.fee,
+            closed_at: trade.timestamp,
+        };
+        *bankroll.write().await += closed.pnl;
+        db.enqueue(Write::ClosedTrade(closed));
+        let remaining = position.size - filled;
+        if remaining < DUST_SHARES {
+            db.enqueue(Write::DeletePosition(position.market_id.clone(), position.token_id.clone()));
+        } else {
+            db.enqueue(Write::Position(Position {
+                size: remaining,
+                ..position.clone()
+            }));
+        }
+    }
+
+    info!(
+        "Liquidation {} {:.2} {} @ limit {:.2}: {:?} (exit {:?})",
+        side, order.size, position.token_id, limit_price, result.status, result.exit_price
+    );
+    result
+}
+
+/// Price of the level where cumulative depth first covers `size`, walking from the
+/// best price outward; falls back to the extreme tick if the book is too thin.
+fn sweep_price(levels: &[BookLevel], size: f64, bids: bool) -> f64 {
+    let mut sorted: Vec<&BookLevel> = levels.iter().collect();
+    if bids {
+        sorted.sort_by(|a, b| b.price.total_cmp(&a.price));
+    } else {
+        sorted.sort_by(|a, b| a.price.total_cmp(&b.price));
+    }
+
+    let mut cumulative = 0.0;
+    for level in sorted {
+        cumulative += level.size;
+        if cumulative >= size {
+            return level.price;
+        }
+    }
+    if bids { MIN_PRICE } else { MAX_PRICE }
+}
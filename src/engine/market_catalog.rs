@@ -0,0 +1,176 @@
+use std::collections::{BTreeSet, HashMap};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use tracing::{info, warn};
+
+use crate::adapters::database::Database;
+use crate::adapters::gamma::GammaClient;
+use crate::adapters::polymarket::PolymarketClient;
+use crate::domain::{Market, MarketId, TokenId};
+use crate::error::Result;
+
+/// Market IDs per Gamma lookup
+const LOOKUP_BATCH: usize = 50;
+
+#[derive(Debug, Default)]
+struct Catalog {
+    markets: HashMap<MarketId, Market>,
+    /// token ID → market ID
+    tokens: HashMap<TokenId, MarketId>,
+}
+
+/// In-memory view of the `markets` table, so token IDs resolve to their market
+/// without a Gamma round trip. Cheap to clone; every clone sees the refresher's
+/// updates.
+#[derive(Debug, Clone, Default)]
+pub struct MarketCatalog {
+    inner: Arc<RwLock<Catalog>>,
+}
+
+impl MarketCatalog {
+    /// Start from what the last run stored
+    pub async fn load(db: &Database) -> Result<Self> {
+        let catalog = Self::default();
+        catalog.insert(db.get_markets().await?);
+        Ok(catalog)
+    }
+
+    pub fn get(&self, market_id: &MarketId) -> Option<Market> {
+        self.inner.read().unwrap().markets.get(market_id).cloned()
+    }
+
+    /// The market `token_id` trades in
+    pub fn by_token(&self, token_id: &TokenId) -> Option<Market> {
+        let inner = self.inner.read().unwrap();
+        let market_id = inner.tokens.get(token_id)?;
+        inner.markets.get(market_id).cloned()
+    }
+
+    /// The other outcome's token in `token_id`'s binary market
+    pub fn complement(&self, token_id: &TokenId) -> Option<TokenId> {
+        self.by_token(token_id)?.complement(token_id).cloned()
+    }
+
+    fn ids(&self) -> Vec<(MarketId, bool)> {
+        self.inner
+            .read()
+            .unwrap()
+            .markets
+            .values()
+            .map(|m| (m.id.clone(), m.closed))
+            .collect()
+    }
+
+    fn insert(&self, markets: impl IntoIterator<Item = Market>) {
+        let mut inner = self.inner.write().unwrap();
+        for market in markets {
+            for outcome in &market.outcomes {
+                inner.tokens.insert(outcome.token_id.clone(), market.id.clone());
+            }
+            inner.markets.insert(market.id.clone(), market);
+        }
+    }
+}
+
+/// Keeps the `markets` table and its catalog current.
+///
+/// Each pass looks up on Gamma every stored market that hasn't closed, plus any
+/// market we hold or have orders in, and stores what comes back. Markets Gamma
+/// leaves without a tick or minimum size get them from the CLOB when a client is
+/// set. Closed markets stay in the table but are no longer looked up unless held.
+pub struct MarketRefresher {
+    db: Database,
+    gamma: GammaClient,
+    catalog: MarketCatalog,
+    poly_client: Option<PolymarketClient>,
+    interval: Duration,
+}
+
+impl MarketRefresher {
+    pub fn new(db: Database, gamma: GammaClient, catalog: MarketCatalog, interval: Duration) -> Self {
+        Self {
+            db,
+            gamma,
+            catalog,
+            poly_client: None,
+            interval,
+        }
+    }
+
+    /// Fill order rules Gamma leaves out from the CLOB
+    pub fn with_clob(mut self, poly_client: PolymarketClient) -> Self {
+        self.poly_client = Some(poly_client);
+        self
+    }
+
+    /// Store markets already fetched elsewhere (the watchlist at startup)
+    pub async fn store(&self, markets: Vec<Market>) -> Result<()> {
+        let markets = self.fill_rules(markets).await;
+        self.db.upsert_markets(&markets).await?;
+        self.catalog.insert(markets);
+        Ok(())
+    }
+
+    pub async fn run(self) {
+        info!("Market refresher started (every {:?}, {} markets stored)", self.interval, self.catalog.ids().len());
+        let mut interval = tokio::time::interval(self.interval);
+        // The first tick fires immediately; startup just stored the watchlist
+        interval.tick().await;
+        loop {
+            interval.tick().await;
+            match self.refresh().await {
+                Ok(n) => info!("Refreshed {} markets", n),
+                Err(e) => warn!("Market refresh failed: {:?}", e),
+            }
+        }
+    }
+
+    /// Returns the number of markets refreshed
+    async fn refresh(&self) -> Result<usize> {
+        let mut ids: BTreeSet<MarketId> = self
+            .catalog
+            .ids()
+            .into_iter()
+            .filter(|(_, closed)| !closed)
+            .map(|(id, _)| id)
+            .collect();
+        ids.extend(self.db.get_positions().await?.into_iter().map(|p| p.market_id));
+        ids.extend(self.db.get_open_orders().await?.into_iter().map(|o| o.market_id));
+        let ids: Vec<MarketId> = ids.into_iter().collect();
+
+        let mut refreshed = 0;
+        for batch in ids.chunks(LOOKUP_BATCH) {
+            let markets = self.gamma.get_markets(batch).await?;
+            refreshed += markets.len();
+            self.store(markets).await?;
+        }
+        Ok(refreshed)
+    }
+
+    /// Tick and minimum size from the CLOB where Gamma had none, falling back on
+    /// what was stored before
+    async fn fill_rules(&self, mut markets: Vec<Market>) -> Vec<Market> {
+        for market in markets.iter_mut().filter(|m| m.tick_size.is_none() || m.min_order_size.is_none()) {
+            let known = self.catalog.get(&market.id);
+            let fetched = match &self.poly_client {
+                Some(client) if !market.closed => match client.get_order_rules(&market.id).await {
+                    Ok(rules) => Some(rules),
+                    Err(e) => {
+                        warn!("Order rules for {} unavailable: {:?}", market.id, e);
+                        None
+                    }
+                },
+                _ => None,
+            };
+            market.tick_size = market
+                .tick_size
+                .or(fetched.map(|r| r.tick_size))
+                .or(known.as_ref().and_then(|m| m.tick_size));
+            market.min_order_size = market
+                .min_order_size
+                .or(fetched.map(|r| r.min_size))
+                .or(known.as_ref().and_then(|m| m.min_order_size));
+        }
+        markets
+    }
+}
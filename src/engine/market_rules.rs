@@ -0,0 +1,72 @@
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+use tracing::warn;
+
+use crate::adapters::polymarket::PolymarketClient;
+use crate::domain::{Market, MarketId, OrderRules};
+use crate::error::Result;
+
+/// How long fetched rules are trusted. The CLOB narrows a market's tick as its
+/// price nears 0 or 1, so they can't be cached for good.
+const REFRESH: Duration = Duration::from_secs(300);
+
+/// Tick size and minimum order size per market, for the order manager to round
+/// and check orders against before the exchange sees them. Seeded from market
+/// metadata, then fetched from the CLOB as entries go stale; a failed refresh
+/// keeps serving the last known rules.
+#[derive(Clone)]
+pub struct MarketRules {
+    poly_client: PolymarketClient,
+    /// condition ID → rules and when they were fetched
+    inner: Arc<RwLock<HashMap<MarketId, (OrderRules, Instant)>>>,
+}
+
+impl MarketRules {
+    pub fn new(poly_client: PolymarketClient) -> Self {
+        Self {
+            poly_client,
+            inner: Arc::default(),
+        }
+    }
+
+    /// Start from what market metadata already says, so the first orders don't
+    /// wait on a fetch
+    pub fn with_markets(self, markets: &[Market]) -> Self {
+        let now = Instant::now();
+        {
+            let mut inner = self.inner.write().unwrap();
+            for market in markets {
+                if let (Some(tick_size), Some(min_size)) = (market.tick_size, market.min_order_size) {
+                    inner.insert(market.id.clone(), (OrderRules { tick_size, min_size }, now));
+                }
+            }
+        }
+        self
+    }
+
+    pub async fn get(&self, market_id: &MarketId) -> Result<OrderRules> {
+        let cached = self.inner.read().unwrap().get(market_id).copied();
+        if let Some((rules, fetched_at)) = cached {
+            if fetched_at.elapsed() < REFRESH {
+                return Ok(rules);
+            }
+        }
+        match self.poly_client.get_order_rules(market_id).await {
+            Ok(rules) => {
+                self.inner
+                    .write()
+                    .unwrap()
+                    .insert(market_id.clone(), (rules, Instant::now()));
+                Ok(rules)
+            }
+            Err(e) => match cached {
+                Some((rules, _)) => {
+                    warn!("Order rules refresh for {} failed, keeping the last known: {:?}", market_id, e);
+                    Ok(rules)
+                }
+                None => Err(e.into()),
+            },
+        }
+    }
+}
@@ -0,0 +1,184 @@
+use chrono::Utc;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::adapters::database::Database;
+use crate::adapters::polygon::{self, PolygonRpc};
+use crate::config::MergeConfig;
+use crate::domain::{ClosedTrade, MarketId, MarketStructure, Position, Side};
+use crate::engine::market_catalog::MarketCatalog;
+use crate::engine::positions::{self, NetPosition};
+use crate::error::Result;
+use crate::wallet::Wallet;
+
+/// Collateral base units per share: outcome tokens carry USDC.e's 6 decimals
+const UNITS_PER_SHARE: f64 = 1e6;
+/// How long a pass waits on a merge transaction before leaving it to the next one
+const CONFIRM_TIMEOUT: Duration = Duration::from_secs(120);
+const CONFIRM_POLL: Duration = Duration::from_secs(5);
+
+/// Turns complete YES+NO sets held in a market back into collateral.
+///
+/// Each pass nets held positions per market; where at least `min_sets` full sets
+/// are held, they are merged from the signing wallet, through the Conditional
+/// Tokens contract for a binary market and through the neg-risk adapter for a leg
+/// of a neg-risk event. Once the transaction confirms, both legs shrink by the
+/// merged size, each closing at its cost plus half the set's locked PnL, and the
+/// bankroll is credited with that PnL. Categorical markets, whose sets span more
+/// than two tokens, are left alone, as are markets without stored metadata.
+pub struct PositionMerger {
+    db: Database,
+    markets: MarketCatalog,
+    bankroll: Arc<RwLock<f64>>,
+    rpc: PolygonRpc,
+    wallet: Wallet,
+    config: MergeConfig,
+    /// Merges sent but not yet confirmed: market → (transaction, sets)
+    pending: HashMap<MarketId, (String, f64)>,
+}
+
+impl PositionMerger {
+    pub fn new(
+        db: Database,
+        markets: MarketCatalog,
+        bankroll: Arc<RwLock<f64>>,
+        rpc: PolygonRpc,
+        wallet: Wallet,
+        config: MergeConfig,
+    ) -> Self {
+        Self {
+            db,
+            markets,
+            bankroll,
+            rpc,
+            wallet,
+            config,
+            pending: HashMap::new(),
+        }
+    }
+
+    pub async fn run(mut self) {
+        let interval = Duration::from_secs(self.config.poll_secs);
+        info!(
+            "Position merger started (every {:?}, at least {:.0} sets)",
+            interval, self.config.min_sets
+        );
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            if let Err(e) = self.check().await {
+                warn!("Merge pass failed: {:?}", e);
+            }
+        }
+    }
+
+    async fn check(&mut self) -> Result<()> {
+        // Positions are edited below; start from every fill, queued ones included
+        self.db.flush().await;
+        let held: Vec<Position> = self
+            .db
+            .get_positions()
+            .await?
+            .into_iter()
+            .filter(|p| p.side == Side::Buy)
+            .collect();
+        for net in positions::net_positions(&held) {
+            if !net.mergeable || net.matched_size < self.config.min_sets {
+                continue;
+            }
+            let structure = match self.markets.get(&net.market_id) {
+                Some(market) if market.structure != MarketStructure::Categorical => market.structure,
+                _ => continue,
+            };
+            let legs: Vec<&Position> = held.iter().filter(|p| p.market_id == net.market_id).collect();
+            if let Err(e) = self.merge(&net, &structure, &legs).await {
+                warn!("Failed to merge {} sets in {}: {:?}", net.matched_size, net.market_id, e);
+            }
+        }
+        Ok(())
+    }
+
+    async fn merge(&mut self, net: &NetPosition, structure: &MarketStructure, legs: &[&Position]) -> Result<()> {
+        let (tx_hash, sets) = match self.pending.get(&net.market_id) {
+            Some(pending) => pending.clone(),
+            None => {
+                let units = (net.matched_size * UNITS_PER_SHARE).floor() as u64;
+                let sets = units as f64 / UNITS_PER_SHARE;
+                let (to, data) = match structure {
+                    MarketStructure::NegRisk { .. } => (
+                        polygon::NEG_RISK_ADAPTER_ADDRESS,
+                        polygon::neg_risk_merge_call(&net.market_id, units)?,
+                    ),
+                    _ => (
+                        polygon::CTF_ADDRESS,
+                        polygon::merge_positions_call(&net.market_id, 2, units)?,
+                    ),
+                };
+                let tx_hash = self
+                    .rpc
+                    .send_transaction(&self.wallet, to, &data, self.config.gas_limit)
+                    .await?;
+                info!("Sent merge of {:.2} sets in {}: {}", sets, net.market_id, tx_hash);
+                self.pending.insert(net.market_id.clone(), (tx_hash.clone(), sets));
+                (tx_hash, sets)
+            }
+        };
+
+        let deadline = Instant::now() + CONFIRM_TIMEOUT;
+        loop {
+            match self.rpc.receipt_status(&tx_hash).await? {
+                Some(true) => break,
+                Some(false) => {
+                    warn!("Merge {} in {} reverted", tx_hash, net.market_id);
+                    self.pending.remove(&net.market_id);
+                    return Ok(());
+                }
+                None if Instant::now() >= deadline => return Ok(()),
+                None => tokio::time::sleep(CONFIRM_POLL).await,
+            }
+        }
+        self.pending.remove(&net.market_id);
+
+        // Exits of the two legs add up to the $1 a set redeems for
+        let locked_per_set = 1.0 - legs.iter().map(|p| p.avg_price).sum::<f64>();
+        let now = Utc::now();
+        for position in legs {
+            let exit_price = position.avg_price + locked_per_set / 2.0;
+            let closed = ClosedTrade {
+                id: Uuid::new_v4().to_string(),
+                strategy: position.strategy.clone(),
+                market_id: position.market_id.clone(),
+                token_id: position.token_id.clone(),
+                side: position.side.clone(),
+                size: sets,
+                entry_price: position.avg_price,
+                exit_price,
+                pnl: (exit_price - position.avg_price) * sets,
+                closed_at: now,
+            };
+            self.db.insert_closed_trade(&closed).await?;
+            let remaining = position.size - sets;
+            if remaining > 1e-9 {
+                self.db
+                    .upsert_position(&Position {
+                        size: remaining,
+                        ..(*position).clone()
+                    })
+                    .await?;
+            } else {
+                self.db.delete_position(&position.market_id, &position.token_id).await?;
+            }
+        }
+        let realized = locked_per_set * sets;
+        *self.bankroll.write().await += realized;
+        info!(
+            "Merged {:.2} sets in {} ({}), realized {:+.2}",
+            sets, net.market_id, tx_hash, realized
+        );
+        Ok(())
+    }
+}
@@ -1,2 +1,37 @@
+pub mod allocator;
+pub mod analytics;
+pub mod anomaly;
+pub mod archive;
+pub mod backtest;
+pub mod calibration;
+pub mod candles;
+pub mod checkpoint;
+pub mod dust;
+pub mod experiments;
+pub mod fees;
+pub mod fill_detector;
+pub mod flow_alerts;
+pub mod hedging;
+pub mod inflight;
+pub mod liquidation;
+pub mod market_catalog;
+pub mod market_rules;
+pub mod merge;
 pub mod order_manager;
+pub mod order_sync;
+pub mod participation;
+pub mod portfolio;
+pub mod pricing;
+pub mod readiness;
+pub mod redemption;
+pub mod resolutions;
 pub mod risk;
+pub mod ruin;
+pub mod shadow;
+pub mod positions;
+pub mod snapshots;
+pub mod throttle;
+pub mod wallet_health;
+pub mod wallet_tracker;
+pub mod walk_forward;
+pub mod whatif;
@@ -1,77 +1,716 @@
-use chrono::Utc;
-use eyre::Result;
-use std::sync::Arc;
-use tokio::sync::{broadcast, RwLock};
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use serde::{Deserialize, Serialize};
+use tokio::sync::{mpsc, oneshot, RwLock};
 use tracing::{error, info, warn};
 use uuid::Uuid;
 
-use crate::adapters::database::Database;
-use crate::adapters::polymarket::PolymarketClient;
-use crate::domain::{Order, OrderStatus, OrderType, Signal, Side, Trade};
+use crate::adapters::database::{Database, Write};
+use crate::adapters::kalshi::{self, KalshiClient};
+use crate::adapters::polymarket::{OrderLeg, OrderResponse, PolymarketClient};
+use crate::bus::EventBus;
+use crate::clock::{self, SharedClock};
+use crate::config::{ExecutionConfig, FeeConfig};
+use crate::domain::{
+    BookLevel, Execution, MarketId, Order, OrderBook, OrderId, OrderStatus, OrderType, RiskEvent, RiskEventKind, Signal,
+    SignalRecord, Side, TokenId, Trade,
+};
+use crate::engine::calibration::SizeScales;
+use crate::engine::checkpoint::{RecentFill, RestingQuote};
+use crate::engine::fees;
+use crate::engine::inflight::{CancelReport, InflightOrders};
+use crate::engine::market_catalog::MarketCatalog;
+use crate::engine::market_rules::MarketRules;
+use crate::engine::participation::ParticipationTracker;
+use crate::engine::portfolio::Portfolio;
 use crate::engine::risk::RiskManager;
+use crate::engine::snapshots::PnlSnapshotter;
+use crate::engine::throttle::StrategyThrottle;
+use crate::error::{ClobError, Result};
+use crate::feeds::BookCache;
+use crate::logging;
+use crate::metrics::METRICS;
+use crate::strategy::{Intent, QuoteLevel};
+use crate::shutdown::Shutdown;
+use crate::watchdog::Heartbeat;
+
+/// Two quotes at the same level differ by less than this
+const QUOTE_EPSILON: f64 = 1e-9;
+/// Matched shares that differ from those booked by less than this are already booked
+const SIZE_EPSILON: f64 = 1e-9;
+
+/// Config table key the multi-leg executions being submitted are written through to
+const EXECUTIONS_KEY: &str = "leg_executions";
+
+/// Resting (bid, ask) order IDs of one strategy's quote on one token
+type RestingQuotes = HashMap<(String, TokenId), [Option<OrderId>; 2]>;
+
+/// Legs of one trade, from their orders being recorded until every Polymarket
+/// leg's submission has finished
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct LegExecution {
+    id: String,
+    strategy: String,
+    started_at: DateTime<Utc>,
+    /// Local IDs of the legs' orders
+    order_ids: Vec<OrderId>,
+}
+
+/// What became of a signal handed to the order manager
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum SignalOutcome {
+    /// Stopped before submission: not armed, stale, unknown token, unbacked sell, duplicate, risk, hard caps,
+    /// participation limits or order floors
+    Rejected { reason: String },
+    Submitted { order: Order },
+    Failed { order: Order, error: String },
+}
+
+/// Where a signal stands after the pre-submission checks
+enum Checked {
+    /// Passed; `order` is sized and priced, but not yet recorded or posted
+    Ready { signal: Signal, order: Order },
+    /// Bound for Kalshi, which has its own submission path
+    Kalshi(Signal),
+    /// Stopped before submission
+    Rejected(SignalOutcome),
+}
+
+impl Checked {
+    fn rejected(reason: &str) -> Self {
+        Checked::Rejected(SignalOutcome::Rejected { reason: reason.into() })
+    }
+}
+
+/// A dashboard-entered order: goes through the same checks as strategy signals,
+/// with the outcome reported back to the caller
+pub struct ManualOrder {
+    pub signal: Signal,
+    pub reply: oneshot::Sender<SignalOutcome>,
+}
 
 pub struct OrderManager {
+    config: ExecutionConfig,
     poly_client: PolymarketClient,
     db: Database,
+    orders: InflightOrders,
+    portfolio: Portfolio,
     risk: RiskManager,
     bankroll: Arc<RwLock<f64>>,
-    signal_rx: broadcast::Receiver<Signal>,
+    participation: Arc<ParticipationTracker>,
+    /// Routes `kalshi:` legs of cross-venue signals
+    kalshi: Option<KalshiClient>,
+    /// (strategy, token) → resting bid and ask order IDs placed for `Intent::Quote`
+    quotes: Mutex<RestingQuotes>,
+    /// Multi-leg executions whose batch is being posted, by ID
+    executions: Mutex<HashMap<String, LegExecution>>,
+    /// Shares of each resting order booked so far, by local ID, while order sync follows it
+    booked: Mutex<HashMap<OrderId, f64>>,
+    /// Books that marketable orders are priced against; empty leaves signal prices as-is
+    orderbooks: BookCache,
+    /// Tick and minimum size orders are held to before submission; None submits unchecked
+    market_rules: Option<MarketRules>,
+    /// Stored market metadata that market-only signals resolve their token from
+    markets: MarketCatalog,
+    /// Schedule for the fee recorded on Polymarket fills
+    fees: FeeConfig,
+    /// Takes a PnL point whenever a fill realizes PnL
+    snapshots: Option<PnlSnapshotter>,
+    /// Where fills are published as they're booked
+    bus: EventBus,
+    throttle: StrategyThrottle,
+    /// Per-strategy size multipliers from confidence calibration
+    size_scales: SizeScales,
+    /// Time for expiry checks and records; ack latency is always measured on the wall clock
+    clock: SharedClock,
+    /// Stops `run` between signals
+    shutdown: Shutdown,
 }
 
 impl OrderManager {
     pub fn new(
+        config: ExecutionConfig,
         poly_client: PolymarketClient,
         db: Database,
+        portfolio: Portfolio,
         risk: RiskManager,
         bankroll: Arc<RwLock<f64>>,
-        signal_rx: broadcast::Receiver<Signal>,
+        participation: Arc<ParticipationTracker>,
     ) -> Self {
         Self {
+            throttle: StrategyThrottle::new(config.clone()),
+            config,
             poly_client,
             db,
+            orders: portfolio.orders.clone(),
+            portfolio,
             risk,
             bankroll,
-            signal_rx,
+            participation,
+            kalshi: None,
+            quotes: Mutex::new(HashMap::new()),
+            executions: Mutex::new(HashMap::new()),
+            booked: Mutex::new(HashMap::new()),
+            orderbooks: BookCache::default(),
+            market_rules: None,
+            markets: MarketCatalog::default(),
+            fees: FeeConfig::default(),
+            snapshots: None,
+            bus: EventBus::default(),
+            size_scales: SizeScales::default(),
+            clock: clock::system(),
+            shutdown: Shutdown::default(),
         }
     }
 
-    pub async fn run(mut self) -> Result<()> {
+    /// Stop taking signals once `shutdown` is triggered; the one in hand is finished
+    pub fn with_shutdown(mut self, shutdown: Shutdown) -> Self {
+        self.shutdown = shutdown;
+        self
+    }
+
+    /// Publish fills on `bus`
+    pub fn with_bus(mut self, bus: EventBus) -> Self {
+        self.bus = bus;
+        self
+    }
+
+    /// Read the time from `clock`, for the throttle too
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.throttle = StrategyThrottle::new(self.config.clone()).with_clock(clock.clone());
+        self.clock = clock;
+        self
+    }
+
+    /// Enable submission of Kalshi legs
+    pub fn with_kalshi(mut self, kalshi: Option<KalshiClient>) -> Self {
+        self.kalshi = kalshi;
+        self
+    }
+
+    pub fn with_fees(mut self, fees: FeeConfig) -> Self {
+        self.fees = fees;
+        self
+    }
+
+    pub fn with_snapshots(mut self, snapshots: PnlSnapshotter) -> Self {
+        self.snapshots = Some(snapshots);
+        self
+    }
+
+    pub fn with_size_scales(mut self, size_scales: SizeScales) -> Self {
+        self.size_scales = size_scales;
+        self
+    }
+
+    /// Price marketable orders against the feed's order books
+    pub fn with_orderbooks(mut self, orderbooks: BookCache) -> Self {
+        self.orderbooks = orderbooks;
+        self
+    }
+
+    /// Round and size-check Polymarket orders against each market's CLOB rules
+    pub fn with_market_rules(mut self, market_rules: MarketRules) -> Self {
+        self.market_rules = Some(market_rules);
+        self
+    }
+
+    /// Resolve the tokens of market-only signals, and the markets of token-only ones
+    pub fn with_markets(mut self, markets: MarketCatalog) -> Self {
+        self.markets = markets;
+        self
+    }
+
+    /// Runs until the signal queue closes. The queues are borrowed so that a
+    /// supervisor restarting the order manager keeps what is still in them.
+    pub async fn run(
+        &self,
+        signal_rx: &mut mpsc::Receiver<Intent>,
+        manual_rx: &mut mpsc::Receiver<ManualOrder>,
+        heartbeat: Heartbeat,
+    ) -> Result<()> {
         info!("Order manager started");
 
         loop {
-            match self.signal_rx.recv().await {
-                Ok(signal) => {
-                    if let Err(e) = self.handle_signal(signal).await {
-                        error!("Error handling signal: {:?}", e);
+            heartbeat.idle();
+            tokio::select! {
+                intent = signal_rx.recv() => {
+                    let Some(intent) = intent else { break };
+                    heartbeat.beat();
+                    if let Err(e) = self.handle_intent(intent).await {
+                        error!("Error handling intent: {:?}", e);
                     }
                 }
-                Err(broadcast::error::RecvError::Lagged(n)) => {
-                    warn!("Order manager lagged by {} signals", n);
+                Some(manual) = manual_rx.recv() => {
+                    heartbeat.beat();
+                    let outcome = self.handle_signal(manual.signal).await.unwrap_or_else(|e| {
+                        error!("Error handling manual order: {:?}", e);
+                        SignalOutcome::Rejected { reason: e.to_string() }
+                    });
+                    let _ = manual.reply.send(outcome);
+                }
+                _ = self.shutdown.wait() => {
+                    info!("Order manager stopped for shutdown");
+                    return Ok(());
+                }
+            }
+        }
+        info!("Signal channel closed, order manager shutting down");
+
+        Ok(())
+    }
+
+    async fn handle_intent(&self, intent: Intent) -> Result<()> {
+        match intent {
+            Intent::Order(signal) => {
+                self.handle_signal(signal).await?;
+            }
+            Intent::Legs(signals) => self.handle_legs(signals).await?,
+            Intent::Exit { strategy, market_id, token_id, price, size, event_time } => {
+                let positions = self.portfolio.positions();
+                let Some(position) = positions.iter().find(|p| p.token_id == token_id) else {
+                    info!("Exit from {} ignored: no position in {}", strategy, token_id);
+                    return Ok(());
+                };
+                let signal = Signal {
+                    strategy,
+                    market_id,
+                    side: match position.side {
+                        Side::Buy => Side::Sell,
+                        Side::Sell => Side::Buy,
+                    },
+                    confidence: 1.0,
+                    price,
+                    size: size.unwrap_or(position.size).min(position.size),
+                    event_time,
+                    token_id: Some(token_id),
+                    order_type: None,
+                    execution: Execution::Passive,
+                    expires_at: None,
+                    good_till: None,
+                };
+                self.handle_signal(signal).await?;
+            }
+            Intent::Cancel { strategy, order_id } => {
+                if self.orders.cancel(&self.poly_client, &order_id).await? {
+                    info!("Order {} cancelled for {}", order_id, strategy);
+                } else {
+                    warn!("Cancel of {} for {} refused by exchange", order_id, strategy);
+                }
+            }
+            Intent::Replace { strategy, order_id, price, size } => {
+                if let SignalOutcome::Rejected { reason } = self.replace(&order_id, price, size).await? {
+                    warn!("Replace of {} for {} not done: {}", order_id, strategy, reason);
+                }
+            }
+            Intent::Quote { strategy, market_id, token_id, bid, ask } => {
+                let key = (strategy, token_id);
+                let mut resting = self.quotes.lock().unwrap().get(&key).cloned().unwrap_or_default();
+                for (slot, side, level) in [(0, Side::Buy, bid), (1, Side::Sell, ask)] {
+                    resting[slot] = self.requote(&key, &market_id, side, level, resting[slot].take()).await?;
+                }
+                self.quotes.lock().unwrap().insert(key, resting);
+            }
+        }
+        Ok(())
+    }
+
+    /// Bring one side of a quote to `level`: keep the resting order if it already
+    /// matches, otherwise cancel it and place a new one. Returns the resting order ID.
+    async fn requote(
+        &self,
+        (strategy, token_id): &(String, TokenId),
+        market_id: &MarketId,
+        side: Side,
+        level: Option<QuoteLevel>,
+        current: Option<OrderId>,
+    ) -> Result<Option<OrderId>> {
+        let live = current
+            .and_then(|id| self.orders.get(&id))
+            .filter(|o| matches!(o.status, OrderStatus::Pending | OrderStatus::Open));
+        if let Some(order) = &live {
+            // An ask without inventory behind it rests as a bid on the complement
+            let order_price = |price: f64| if &order.token_id == token_id { price } else { 1.0 - price };
+            let unchanged = level.is_some_and(|l| {
+                (order_price(l.price) - order.price).abs() < QUOTE_EPSILON
+                    && (l.size - order.size).abs() < QUOTE_EPSILON
+            });
+            if unchanged {
+                return Ok(Some(order.id.clone()));
+            }
+            let Some(level) = level else {
+                if !self.orders.cancel(&self.poly_client, &order.id).await? {
+                    // Probably filled while we decided to pull it; leave it tracked
+                    warn!("Quote order {} could not be cancelled", order.id);
+                    return Ok(Some(order.id.clone()));
+                }
+                return Ok(None);
+            };
+            return Ok(match self.replace(&order.id, order_price(level.price), level.size).await? {
+                SignalOutcome::Submitted { order } => Some(order.id),
+                // Still tracked only when the cancel was refused
+                _ => self.orders.get(&order.id).map(|o| o.id),
+            });
+        }
+
+        let Some(level) = level else {
+            return Ok(None);
+        };
+        let signal = Signal {
+            strategy: strategy.clone(),
+            market_id: market_id.clone(),
+            side,
+            confidence: 1.0,
+            price: level.price,
+            size: level.size,
+            event_time: None,
+            token_id: Some(token_id.clone()),
+            order_type: Some(OrderType::GTC),
+            execution: Execution::Passive,
+            expires_at: None,
+            good_till: None,
+        };
+        Ok(match self.handle_signal(signal).await? {
+            SignalOutcome::Submitted { order } => Some(order.id),
+            _ => None,
+        })
+    }
+
+    async fn handle_signal(&self, signal: Signal) -> Result<SignalOutcome> {
+        match self.check(signal).await? {
+            Checked::Ready { signal, order } => self.submit(&signal, order).await,
+            Checked::Kalshi(signal) => self.submit_kalshi(&signal).await,
+            Checked::Rejected(outcome) => Ok(outcome),
+        }
+    }
+
+    /// Record and post an order that passed its checks
+    async fn submit(&self, signal: &Signal, order: Order) -> Result<SignalOutcome> {
+        self.orders.insert(order.clone());
+        if let Some(event_time) = signal.event_time {
+            METRICS.tick_to_submit.observe_since(event_time);
+        }
+        let submitted_at = Utc::now();
+        let result = self.poly_client.post_order(&OrderLeg::from(&order)).await;
+        METRICS.order_ack.observe_since(submitted_at);
+        match result {
+            Ok(resp) => self.finish(signal, order, Ok(resp)).await,
+            Err(e) => self.finish(signal, order, Err(&e)).await,
+        }
+    }
+
+    /// Amend a resting order to `price` and `size`: cancel it, then place the same
+    /// order at the new level, recorded as replacing it.
+    ///
+    /// The old order comes off the book before the new one goes through the checks,
+    /// so the replacement never trips the duplicate check against its own original
+    /// and the two are never both resting. If the cancel is refused (usually a fill
+    /// got there first) nothing is placed and the original stays tracked; if the
+    /// replacement is then rejected or fails, the level is left empty.
+    pub async fn replace(&self, order_id: &OrderId, price: f64, size: f64) -> Result<SignalOutcome> {
+        let Some(original) = self.orders.get(order_id) else {
+            return Ok(SignalOutcome::Rejected { reason: format!("no open order {}", order_id) });
+        };
+        if original.remote_id.is_none() {
+            return Ok(SignalOutcome::Rejected { reason: "original not yet acknowledged by the exchange".into() });
+        }
+        if !self.orders.cancel(&self.poly_client, order_id).await? {
+            return Ok(SignalOutcome::Rejected { reason: "original could not be cancelled".into() });
+        }
+
+        let signal = Signal {
+            strategy: original.strategy.clone(),
+            market_id: original.market_id.clone(),
+            side: original.side.clone(),
+            confidence: 1.0,
+            price,
+            size,
+            event_time: None,
+            token_id: Some(original.token_id.clone()),
+            order_type: Some(original.order_type.clone()),
+            execution: Execution::Passive,
+            expires_at: None,
+            good_till: original.expires_at,
+        };
+        let outcome = match self.check(signal).await? {
+            Checked::Ready { signal, mut order } => {
+                order.replaces = Some(original.id.clone());
+                self.submit(&signal, order).await?
+            }
+            Checked::Kalshi(signal) => self.submit_kalshi(&signal).await?,
+            Checked::Rejected(outcome) => outcome,
+        };
+        match &outcome {
+            SignalOutcome::Submitted { order } => info!(
+                "Order {} replaced by {}: {:.2}@{:.4} → {:.2}@{:.4}",
+                original.id, order.id, original.size, original.price, order.size, order.price
+            ),
+            _ => warn!("Order {} cancelled but its replacement was not placed: {:?}", original.id, outcome),
+        }
+        Ok(outcome)
+    }
+
+    /// Legs of one trade. Every leg has to pass its checks before any is sent, and
+    /// the Polymarket legs then go out in a single batch request so they reach the
+    /// book together instead of one ack apart.
+    async fn handle_legs(&self, signals: Vec<Signal>) -> Result<()> {
+        let mut ready = Vec::new();
+        let mut kalshi = Vec::new();
+        for signal in signals {
+            match self.check(signal).await? {
+                Checked::Ready { signal, order } => ready.push((signal, order)),
+                Checked::Kalshi(signal) => kalshi.push(signal),
+                Checked::Rejected(outcome) => {
+                    info!("Legs dropped: one leg did not pass its checks ({:?})", outcome);
+                    return Ok(());
+                }
+            }
+        }
+
+        if !ready.is_empty() {
+            let execution = LegExecution {
+                id: Uuid::new_v4().to_string(),
+                strategy: ready[0].0.strategy.clone(),
+                started_at: self.clock.now(),
+                order_ids: ready.iter().map(|(_, order)| order.id.clone()).collect(),
+            };
+            let id = execution.id.clone();
+            self.update_executions(|executions| {
+                executions.insert(id.clone(), execution);
+            })
+            .await?;
+            let posted = self.post_legs(ready).await;
+            self.update_executions(|executions| {
+                executions.remove(&id);
+            })
+            .await?;
+            posted?;
+        }
+        for signal in kalshi {
+            self.submit_kalshi(&signal).await?;
+        }
+        Ok(())
+    }
+
+    /// Record the legs' orders and post them in one batch
+    async fn post_legs(&self, ready: Vec<(Signal, Order)>) -> Result<()> {
+        let legs: Vec<OrderLeg> = ready
+            .iter()
+            .map(|(_, order)| OrderLeg::from(order))
+            .collect();
+        for (signal, order) in &ready {
+            self.orders.insert(order.clone());
+            if let Some(event_time) = signal.event_time {
+                METRICS.tick_to_submit.observe_since(event_time);
+            }
+        }
+        // Recovery matches the execution record against these orders, so they are
+        // on disk before anything reaches the exchange
+        self.db.flush().await;
+        let submitted_at = Utc::now();
+        let result = self.poly_client.post_orders(&legs).await;
+        METRICS.order_ack.observe_since(submitted_at);
+        match result {
+            Ok(responses) => {
+                for ((signal, order), resp) in ready.into_iter().zip(responses) {
+                    self.finish(&signal, order, Ok(resp)).await?;
                 }
-                Err(broadcast::error::RecvError::Closed) => {
-                    info!("Signal channel closed, order manager shutting down");
-                    break;
+            }
+            Err(e) => {
+                for (signal, order) in ready {
+                    self.finish(&signal, order, Err(&e)).await?;
                 }
             }
         }
+        Ok(())
+    }
 
+    /// Apply `change` to the executions in flight and write them through. The write
+    /// is awaited rather than queued: recovery after a crash relies on it.
+    async fn update_executions(&self, change: impl FnOnce(&mut HashMap<String, LegExecution>)) -> Result<()> {
+        let stored = {
+            let mut executions = self.executions.lock().unwrap();
+            change(&mut executions);
+            serde_json::to_string(&executions.values().collect::<Vec<_>>())?
+        };
+        self.db.set_config(EXECUTIONS_KEY, &stored).await?;
         Ok(())
     }
 
-    async fn handle_signal(&self, signal: Signal) -> Result<()> {
+    /// Settle the multi-leg executions a previous run was still submitting when it
+    /// stopped. A leg the exchange never acknowledged can't be cancelled or matched
+    /// to fills, so it is marked failed for the operator to check on the exchange;
+    /// acknowledged legs are tracked like any other order. Returns how many
+    /// executions were interrupted.
+    pub async fn recover_executions(&self) -> Result<usize> {
+        let Some(stored) = self.db.get_config(EXECUTIONS_KEY).await? else {
+            return Ok(0);
+        };
+        let interrupted: Vec<LegExecution> = serde_json::from_str(&stored)?;
+        for execution in &interrupted {
+            let mut legs = Vec::new();
+            for order_id in &execution.order_ids {
+                let state = match self.orders.get(order_id) {
+                    Some(order) if order.remote_id.is_none() => {
+                        self.orders.set_status(order_id, OrderStatus::Failed);
+                        "unacknowledged, marked failed"
+                    }
+                    Some(_) => "open",
+                    None => "closed or never recorded",
+                };
+                legs.push(format!("{} {}", order_id, state));
+            }
+            warn!(
+                "Legs {} of {} interrupted mid-submission at {}: {}",
+                execution.id,
+                execution.strategy,
+                execution.started_at.to_rfc3339(),
+                legs.join(", ")
+            );
+        }
+        self.update_executions(HashMap::clear).await?;
+        Ok(interrupted.len())
+    }
+
+    /// Resting quote orders, for the engine checkpoint
+    pub fn resting_quotes(&self) -> Vec<RestingQuote> {
+        self.quotes
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|((strategy, token_id), [bid, ask])| RestingQuote {
+                strategy: strategy.clone(),
+                token_id: token_id.clone(),
+                bid: bid.clone(),
+                ask: ask.clone(),
+            })
+            .collect()
+    }
+
+    /// Take over quotes from a checkpoint, keeping only orders still open, so the
+    /// next quote intent amends them rather than placing new ones beside them.
+    /// Returns how many quotes have an order left.
+    pub fn restore_quotes(&self, quotes: &[RestingQuote]) -> usize {
+        let open = |id: &Option<OrderId>| id.clone().filter(|id| self.orders.get(id).is_some());
+        let mut resting = self.quotes.lock().unwrap();
+        for quote in quotes {
+            let orders = [open(&quote.bid), open(&quote.ask)];
+            if orders.iter().any(Option::is_some) {
+                resting.insert((quote.strategy.clone(), quote.token_id.clone()), orders);
+            }
+        }
+        resting.len()
+    }
+
+    /// Fills still holding their strategy out of a market
+    pub fn recent_fills(&self) -> Vec<RecentFill> {
+        self.throttle.recent_fills()
+    }
+
+    pub fn restore_fills(&self, fills: &[RecentFill]) {
+        self.throttle.restore_fills(fills);
+    }
+
+    /// Run a signal through every pre-submission check, sizing the order it would place
+    async fn check(&self, mut signal: Signal) -> Result<Checked> {
+        logging::signal(&signal);
+        if !self.risk.is_armed() {
+            info!(
+                "Signal dropped, order submission not armed: {} {} on {}",
+                signal.side, signal.strategy, signal.market_id
+            );
+            return Ok(Checked::rejected("not armed"));
+        }
+
+        // Staleness — a latency edge that sat in a queue is usually gone, and the fill
+        // we'd get is the move we meant to front-run
+        let expires_at = signal
+            .expires_at
+            .or_else(|| Some(signal.event_time? + self.config.signal_ttl(&signal.strategy)?));
+        if let Some(expires_at) = expires_at {
+            let now = self.clock.now();
+            if now > expires_at {
+                warn!(
+                    "Stale signal dropped: {} {} on {} expired {}ms ago",
+                    signal.side,
+                    signal.strategy,
+                    signal.market_id,
+                    (now - expires_at).num_milliseconds()
+                );
+                return Ok(Checked::rejected("stale signal"));
+            }
+        }
+
+        // Expiry — a GTD order needs a time to expire at, and one still ahead
+        match signal.good_till {
+            Some(good_till) if good_till <= self.clock.now() => {
+                info!("Signal dropped: {} order on {} expired at {}", signal.strategy, signal.market_id, good_till);
+                return Ok(Checked::rejected("expiry already passed"));
+            }
+            None if signal.order_type == Some(OrderType::GTD) => {
+                return Ok(Checked::rejected("GTD order without an expiry"));
+            }
+            _ => {}
+        }
+
+        // Token — a signal naming only its market trades the market's YES token
+        if !self.resolve_token(&mut signal) {
+            warn!("Signal dropped: no YES token known for {} ({})", signal.market_id, signal.strategy);
+            return Ok(Checked::rejected("unknown token"));
+        }
+
+        // Complement — outcome tokens can't be shorted, so a Sell is backed by what we
+        // hold or placed as a Buy of the other outcome
+        if signal.side == Side::Sell && kalshi::ticker_of(&signal.market_id).is_none() {
+            if let Err(reason) = self.back_sell(&mut signal) {
+                info!("Signal skipped: {} Sell of {} ({})", signal.strategy, signal.market_id, reason);
+                return Ok(Checked::rejected(reason));
+            }
+        }
+        let token_id = signal.token_id.clone().unwrap_or_default();
+
+        // Duplicates — repeated ticks re-emit the same signal while its order is still working
+        if let Some(existing) = self.overlapping_order(&signal) {
+            info!(
+                "Signal skipped: order {} already working {} {:.2}@{:.4} on {} ({})",
+                existing.id, existing.side, existing.size, existing.price, existing.token_id, signal.strategy
+            );
+            return Ok(Checked::rejected("duplicate order"));
+        }
+
         let current_bankroll = *self.bankroll.read().await;
 
-        // Calculate total exposure from open positions
-        let positions = self.db.get_positions().await?;
-        let total_exposure: f64 = positions.iter().map(|p| p.size * p.avg_price).sum();
+        // Open positions drive exposure and position-count limits
+        let positions = self.portfolio.positions();
+
+        // Throttle — a price flapping around a threshold shouldn't machine-gun entries
+        let reduces = positions.iter().any(|p| p.token_id == token_id && p.side != signal.side);
+        if !reduces {
+            if let Some(reason) = self.throttle.admit(&signal.strategy, &signal.market_id) {
+                info!("Signal throttled: {} on {} ({})", signal.strategy, signal.market_id, reason);
+                return Ok(Checked::rejected("throttled"));
+            }
+            // Calibration — a strategy whose confidence has run ahead of its results trades smaller
+            if let Some(scale) = self.size_scales.read().unwrap().get(&signal.strategy) {
+                info!(
+                    "{} signal sized {:.2} → {:.2} by calibration",
+                    signal.strategy,
+                    signal.size,
+                    signal.size * scale
+                );
+                signal.size *= scale;
+            }
+        }
 
         // Risk check
-        if !self.risk.check_signal(&signal, current_bankroll, total_exposure).await? {
+        if let Err(reason) = self.risk.check_signal(&signal, current_bankroll, &positions).await {
             info!(
                 "Signal rejected by risk manager: {} {} on {}",
                 signal.side, signal.strategy, signal.market_id
             );
-            return Ok(());
+            return Ok(Checked::Rejected(self.reject(&signal, "risk check", reason.to_string())));
         }
 
         info!(
@@ -84,46 +723,495 @@ impl OrderManager {
             signal.confidence * 100.0
         );
 
-        // Determine token_id based on side
-        // For now, signal.market_id is used; in practice we'd look up the token
-        let token_id = &signal.market_id; // TODO: map market_id to correct token_id
+        // Hard per-order caps — last line of defence against upstream sizing bugs
+        if signal.size > self.config.max_order_shares {
+            error!(
+                "Order of {:.2} shares exceeds hard cap {:.2} — refusing ({} on {})",
+                signal.size, self.config.max_order_shares, signal.strategy, signal.market_id
+            );
+            let detail = format!("{:.2} shares over hard cap {:.2}", signal.size, self.config.max_order_shares);
+            return Ok(Checked::Rejected(self.reject(&signal, "max_order_shares", detail)));
+        }
+        if signal.size * signal.price > self.config.max_order_notional {
+            error!(
+                "Order notional ${:.2} exceeds hard cap ${:.2} — refusing ({} on {})",
+                signal.size * signal.price,
+                self.config.max_order_notional,
+                signal.strategy,
+                signal.market_id
+            );
+            let detail = format!(
+                "notional ${:.2} over hard cap ${:.2}",
+                signal.size * signal.price,
+                self.config.max_order_notional
+            );
+            return Ok(Checked::Rejected(self.reject(&signal, "max_order_notional", detail)));
+        }
+
+        // Kalshi legs skip the Polymarket-specific volume and floor rules
+        if kalshi::ticker_of(&signal.market_id).is_some() {
+            return Ok(Checked::Kalshi(signal));
+        }
+
+        // Participation limit — shrink to what the trailing volume allows, going by the
+        // estimate refreshed in the background; a market without one isn't capped
+        let mut size = signal.size;
+        if let Some(estimate) = self.participation.cached(&signal.market_id) {
+            if size * signal.price > estimate.remaining {
+                size = (estimate.remaining / signal.price * 100.0).floor() / 100.0;
+                if size <= 0.0 {
+                    info!(
+                        "Signal skipped: participation limit reached on {} (${:.2} of ${:.2} 1h volume)",
+                        signal.market_id, estimate.our_volume, estimate.market_volume
+                    );
+                    let detail = format!(
+                        "${:.2} of ${:.2} 1h volume already ours",
+                        estimate.our_volume, estimate.market_volume
+                    );
+                    return Ok(Checked::Rejected(self.reject(&signal, "participation limit", detail)));
+                }
+                info!(
+                    "Order shrunk {:.2} → {:.2} shares by participation limit on {}",
+                    signal.size, size, signal.market_id
+                );
+            }
+        }
+
+        let take = signal.execution == Execution::Take;
+        let order_type = signal.order_type.clone().unwrap_or(match signal.execution {
+            Execution::Take if self.config.take_fill_or_kill => OrderType::FOK,
+            Execution::Take => OrderType::FAK,
+            Execution::Passive if signal.good_till.is_some() => OrderType::GTD,
+            Execution::Passive => OrderType::GTC,
+        });
+
+        // Slippage — price marketable orders off the book rather than the signal, and
+        // take only what's there within the slippage cap. A passive order keeps its
+        // size: what doesn't cross rests.
+        let mut price = signal.price;
+        let cap = slippage_cap(&signal.side, signal.price, self.config.max_slippage_bps);
+        if let Some(book) = self.orderbooks.get(&token_id) {
+            let swept = sweep(&book, &signal.side, size, cap);
+            if take && swept.is_none() {
+                info!("Signal skipped: nothing to take within {:.4} on {}", cap, token_id);
+                let detail = format!("no liquidity within {:.4} on {}", cap, token_id);
+                return Ok(Checked::Rejected(self.reject(&signal, "slippage cap", detail)));
+            }
+            if let Some((marginal, available)) = swept {
+                price = marginal;
+                if take && available < size {
+                    let fillable = (available * 100.0).floor() / 100.0;
+                    info!(
+                        "Order shrunk {:.2} → {:.2} shares: book thin within {:.4} on {}",
+                        size, fillable, cap, token_id
+                    );
+                    size = fillable;
+                }
+            }
+            // Liquidity — don't become most of a thin book
+            if let Some(limit) = self.risk.liquidity_limit(&signal.side, price, &book) {
+                if size > limit {
+                    let capped = (limit * 100.0).floor() / 100.0;
+                    info!(
+                        "Order shrunk {:.2} → {:.2} shares by book share cap on {}",
+                        size, capped, token_id
+                    );
+                    size = capped;
+                }
+            }
+        } else if take {
+            // No book to walk: cross at the cap, kept inside the CLOB's price range,
+            // and let the exchange fill what rests there
+            price = cap.clamp(0.01, 0.99);
+        }
+        if size <= 0.0 {
+            info!("Signal skipped: no usable liquidity for {} on {}", signal.strategy, token_id);
+            let detail = format!("no usable liquidity on {}", token_id);
+            return Ok(Checked::Rejected(self.reject(&signal, "liquidity cap", detail)));
+        }
+
+        // Exchange rules — put the price on the market's tick and refuse what's under
+        // its minimum size, rather than have the CLOB bounce the order
+        if let Some(market_rules) = &self.market_rules {
+            match market_rules.get(&signal.market_id).await {
+                Ok(rules) => {
+                    let rounded = rules.round_price(&signal.side, price);
+                    if (rounded - price).abs() > 1e-9 {
+                        info!("Order price {:.4} → {:.4} on tick {} for {}", price, rounded, rules.tick_size, token_id);
+                        price = rounded;
+                    }
+                    if size < rules.min_size {
+                        info!(
+                            "Signal skipped: {:.2} shares under the exchange minimum {:.2} on {}",
+                            size, rules.min_size, signal.market_id
+                        );
+                        return Ok(Checked::rejected("below exchange minimum size"));
+                    }
+                }
+                Err(e) => warn!("No order rules for {}, submitting unchecked: {:?}", signal.market_id, e),
+            }
+        }
+
+        // Order floors — tiny entries pay fixed costs for nothing; exits are always allowed
+        if !reduces && (size < self.config.min_order_shares || size * price < self.config.min_order_notional) {
+            info!(
+                "Signal skipped: {:.2} shares (${:.2}) below order floor on {}",
+                size,
+                size * price,
+                signal.market_id
+            );
+            return Ok(Checked::rejected("below minimum order size"));
+        }
 
-        // Create order record
+        let expires_at = signal.good_till.filter(|_| order_type.rests());
         let order = Order {
-            id: Uuid::new_v4().to_string(),
+            id: OrderId::generate(),
             market_id: signal.market_id.clone(),
             side: signal.side.clone(),
-            token_id: token_id.clone(),
-            price: signal.price,
-            size: signal.size,
-            order_type: OrderType::GTC,
+            token_id,
+            price,
+            size,
+            order_type,
             status: OrderStatus::Pending,
-            created_at: Utc::now(),
-        };
-
-        self.db.insert_order(&order).await?;
-
-        // Submit to Polymarket
-        match self
-            .poly_client
-            .post_order(
-                &order.token_id,
-                order.price,
-                order.size,
-                order.side.clone(),
-                OrderType::GTC,
-            )
-            .await
-        {
+            created_at: self.clock.now(),
+            remote_id: None,
+            strategy: signal.strategy.clone(),
+            replaces: None,
+            expires_at,
+        };
+        Ok(Checked::Ready { signal, order })
+    }
+
+    /// Record what the exchange made of a posted order
+    async fn finish(
+        &self,
+        signal: &Signal,
+        mut order: Order,
+        result: Result<OrderResponse, &ClobError>,
+    ) -> Result<SignalOutcome> {
+        match result {
             Ok(resp) => {
                 if resp.success {
-                    let remote_id = resp.order_id.unwrap_or_default();
+                    let remote_id = resp.order_id.clone().unwrap_or_default();
                     info!("Order submitted: {} → remote {}", order.id, remote_id);
-                    self.db
-                        .update_order_status(&order.id, &OrderStatus::Open)
-                        .await?;
+                    if !remote_id.is_empty() {
+                        self.orders.set_remote_id(&order.id, &remote_id);
+                    }
+                    order.remote_id = Some(remote_id).filter(|id| !id.is_empty());
+                    logging::order_submitted(&order);
+                    if !order.order_type.rests() && resp.status.as_deref() != Some("delayed") {
+                        return self.settle_immediate(signal, order, &resp).await;
+                    }
+                    self.orders.set_status(&order.id, OrderStatus::Open);
+                    // A resting order books nothing until it fills; order sync picks
+                    // its fills up from the exchange as they happen
+                    self.record_signal(signal, &order);
+                    order.status = OrderStatus::Open;
+                    Ok(SignalOutcome::Submitted { order })
+                } else {
+                    let msg = resp.error_msg.unwrap_or_default();
+                    error!("Order rejected: {}", msg);
+                    self.orders.set_status(&order.id, OrderStatus::Failed);
+                    order.status = OrderStatus::Failed;
+                    Ok(SignalOutcome::Failed { order, error: msg })
+                }
+            }
+            Err(e) => {
+                error!("Order submission failed: {}", e);
+                self.refused(e);
+                self.orders.set_status(&order.id, OrderStatus::Failed);
+                order.status = OrderStatus::Failed;
+                Ok(SignalOutcome::Failed { order, error: e.to_string() })
+            }
+        }
+    }
+
+    /// FOK and FAK orders are done once acknowledged: book what matched and close
+    /// the order out, filled or killed
+    async fn settle_immediate(&self, signal: &Signal, mut order: Order, resp: &OrderResponse) -> Result<SignalOutcome> {
+        let filled = resp.filled_shares(&order.side).unwrap_or(0.0);
+        if filled <= 0.0 {
+            info!("{:?} order {} killed: nothing matched at {:.4}", order.order_type, order.id, order.price);
+            self.orders.set_status(&order.id, OrderStatus::Cancelled);
+            order.status = OrderStatus::Cancelled;
+            return Ok(SignalOutcome::Submitted { order });
+        }
+        let price = resp.fill_price(&order.side).unwrap_or(order.price);
+        if filled < order.size {
+            info!(
+                "{:?} order {} took {:.2} of {:.2} shares at {:.4}, remainder killed",
+                order.order_type, order.id, filled, order.size, price
+            );
+        }
+        self.record_trade(&order, price, filled, false).await?;
+        self.record_signal(signal, &order);
+        self.orders.set_status(&order.id, OrderStatus::Filled);
+        order.status = OrderStatus::Filled;
+        Ok(SignalOutcome::Submitted { order })
+    }
 
-                    // Record as trade (simplified — in production, wait for fill confirmation)
+    /// Bring what is booked for a resting order in line with the `matched` shares the
+    /// exchange reports. Newly matched shares are booked as a maker fill at the order's
+    /// price. Shares booked beyond what matched (a whole order booked on its ack by an
+    /// older build) stay booked while the order is live, so later matches fill them,
+    /// and are taken back out of the position once it is `done`: filled, cancelled or
+    /// expired.
+    pub async fn sync_fills(&self, order: &Order, matched: f64, done: bool) -> Result<()> {
+        let known = self.booked.lock().unwrap().get(&order.id).copied();
+        let booked = match known {
+            Some(booked) => booked,
+            None => self.booked_size(order).await?,
+        };
+        let delta = matched - booked;
+        if delta > SIZE_EPSILON {
+            info!("Order {} matched {:.2} more shares ({:.2} in all)", order.id, delta, matched);
+            self.record_trade(order, order.price, delta, true).await?;
+        } else if done && delta < -SIZE_EPSILON && self.portfolio.reverse_fill(order, order.price, -delta) {
+            info!("Took back {:.2} shares of order {} booked beyond the {:.2} that matched", -delta, order.id, matched);
+        }
+        let mut tracked = self.booked.lock().unwrap();
+        if done {
+            tracked.remove(&order.id);
+        } else {
+            tracked.insert(order.id.clone(), matched.max(booked));
+        }
+        Ok(())
+    }
+
+    /// Shares already on the trade ledger for `order`, for one first seen since startup
+    async fn booked_size(&self, order: &Order) -> Result<f64> {
+        self.db.flush().await;
+        let trades = self.db.get_trades_since(order.created_at).await?;
+        Ok(trades.iter().filter(|t| t.order_id == order.id).map(|t| t.size).sum())
+    }
+
+    /// Book `size` shares of `order` as filled at `price`
+    async fn record_trade(&self, order: &Order, price: f64, size: f64, maker: bool) -> Result<()> {
+        let trade = Trade {
+            id: Uuid::new_v4().to_string(),
+            order_id: order.id.clone(),
+            market_id: order.market_id.clone(),
+            side: order.side.clone(),
+            price,
+            size,
+            fee: fees::per_share(&self.fees, price, maker) * size,
+            timestamp: self.clock.now(),
+        };
+        self.db.enqueue(Write::Trade(trade.clone()));
+        self.record_fill(order, &trade, &order.strategy).await
+    }
+
+    /// A working order on the signal's token and side within the duplicate price band.
+    ///
+    /// Keyed on token rather than strategy/market: multi-leg strategies (intra-market
+    /// and cross-venue arb) place several orders in one market on purpose, while two
+    /// strategies chasing the same token and price would stack exposure all the same.
+    fn overlapping_order(&self, signal: &Signal) -> Option<Order> {
+        if self.config.duplicate_price_band <= 0.0 {
+            return None;
+        }
+        let token_id = signal.token_id.as_ref()?;
+        self.orders.open_orders().into_iter().find(|o| {
+            matches!(o.status, OrderStatus::Pending | OrderStatus::Open)
+                && &o.token_id == token_id
+                && o.side == signal.side
+                && (o.price - signal.price).abs() <= self.config.duplicate_price_band
+        })
+    }
+
+    /// Fill in whichever of token and market a signal leaves implicit. A signal
+    /// naming only its market trades the YES token, so a Sell sells YES; one whose
+    /// market ID is just its token ID (manual entry) gets the market the token
+    /// trades in. False when a market-only signal's YES token isn't known.
+    fn resolve_token(&self, signal: &mut Signal) -> bool {
+        match &signal.token_id {
+            Some(token_id) => {
+                if signal.market_id.as_str() == token_id.as_str() {
+                    if let Some(market) = self.markets.by_token(token_id) {
+                        signal.market_id = market.id;
+                    }
+                }
+                true
+            }
+            None => {
+                let token_id = self
+                    .markets
+                    .get(&signal.market_id)
+                    .and_then(|m| m.outcome_token("yes").cloned());
+                signal.token_id = token_id;
+                signal.token_id.is_some()
+            }
+        }
+    }
+
+    /// Make a Sell something the CLOB will take. With the token held, it sells at
+    /// most what isn't already offered by working sells. With none held, it becomes
+    /// a Buy of the market's other outcome at the complementary price: selling YES
+    /// at p and buying NO at 1 - p pay off alike.
+    fn back_sell(&self, signal: &mut Signal) -> Result<(), &'static str> {
+        let Some(token_id) = signal.token_id.clone() else {
+            return Err("unknown token");
+        };
+        let held: f64 = self
+            .portfolio
+            .positions()
+            .iter()
+            .filter(|p| p.token_id == token_id && p.side == Side::Buy)
+            .map(|p| p.size)
+            .sum();
+        if held > 0.0 {
+            let offered: f64 = self
+                .orders
+                .open_orders()
+                .iter()
+                .filter(|o| {
+                    matches!(o.status, OrderStatus::Pending | OrderStatus::Open)
+                        && o.token_id == token_id
+                        && o.side == Side::Sell
+                })
+                .map(|o| o.size)
+                .sum();
+            let available = held - offered;
+            if available <= 0.0 {
+                return Err("position already being sold");
+            }
+            if signal.size > available {
+                info!(
+                    "Sell shrunk {:.2} → {:.2} shares: only that much of {} held and not on offer",
+                    signal.size, available, token_id
+                );
+                signal.size = available;
+            }
+            return Ok(());
+        }
+
+        let Some(complement) = self.markets.complement(&token_id) else {
+            return Err("nothing held to sell");
+        };
+        info!(
+            "Sell of unheld {} @ {:.4} placed as a Buy of {} @ {:.4}",
+            token_id,
+            signal.price,
+            complement,
+            1.0 - signal.price
+        );
+        signal.side = Side::Buy;
+        signal.price = 1.0 - signal.price;
+        signal.token_id = Some(complement);
+        Ok(())
+    }
+
+    /// Keep the signal's confidence for calibration against how its market resolves
+    fn record_signal(&self, signal: &Signal, order: &Order) {
+        let record = SignalRecord {
+            id: order.id.to_string(),
+            strategy: signal.strategy.clone(),
+            market_id: order.market_id.clone(),
+            token_id: order.token_id.clone(),
+            side: order.side.clone(),
+            confidence: signal.confidence,
+            price: order.price,
+            created_at: order.created_at,
+            outcome: None,
+            resolved_at: None,
+            size: Some(order.size),
+            shadow: false,
+        };
+        self.db.enqueue(Write::SignalRecord(record));
+    }
+
+    /// A failed submission that no retry will fix halts trading: posting more
+    /// orders with credentials the venue refuses only piles up failures. Rate
+    /// limits and network errors leave the next signal to try again.
+    fn refused(&self, error: &ClobError) {
+        if let ClobError::Auth { .. } = error {
+            self.risk.halt_for_credentials(&error.to_string());
+        }
+    }
+
+    /// Refuse a signal on a risk limit, recording the decision in the audit log
+    fn reject(&self, signal: &Signal, reason: &str, detail: impl Into<String>) -> SignalOutcome {
+        let detail = detail.into();
+        logging::risk_reject(signal, reason, &detail);
+        let detail = format!("{}: {}", reason, detail);
+        self.risk.record_event(RiskEvent::signal(RiskEventKind::SignalRejected, signal, detail));
+        SignalOutcome::Rejected { reason: reason.into() }
+    }
+
+    /// Update positions from a trade the exchange confirmed: a FOK/FAK match, a fill
+    /// order sync found or an executed Kalshi leg. Closing out (part of) a position
+    /// realizes its PnL, which may start a loss cooldown, so nothing that is only
+    /// acknowledged comes through here.
+    async fn record_fill(&self, order: &Order, trade: &Trade, strategy: &str) -> Result<()> {
+        logging::fill(order, trade, strategy);
+        self.bus.publish(trade.clone());
+        self.throttle.record_fill(strategy, &order.market_id);
+        self.participation.record_fill(&order.market_id, trade.size * trade.price);
+        let Some(closed) = self.portfolio.apply_fill(order, trade, strategy) else {
+            return Ok(());
+        };
+        self.db.enqueue(Write::ClosedTrade(closed.clone()));
+        *self.bankroll.write().await += closed.pnl;
+        self.risk.record_realized(&self.db, &order.market_id, closed.pnl).await;
+        // The snapshot waits for the close to be written; the next signal shouldn't
+        if let Some(snapshots) = self.snapshots.clone() {
+            tokio::spawn(async move {
+                if let Err(e) = snapshots.snapshot().await {
+                    error!("PnL snapshot after close of {} failed: {:?}", closed.token_id, e);
+                }
+            });
+        }
+        Ok(())
+    }
+
+    /// Fill-or-kill the Kalshi leg of a cross-venue signal
+    async fn submit_kalshi(&self, signal: &Signal) -> Result<SignalOutcome> {
+        let Some(client) = self.kalshi.as_ref().filter(|c| c.can_sign()) else {
+            warn!("Kalshi leg for {} dropped: Kalshi trading not configured", signal.market_id);
+            return Ok(SignalOutcome::Rejected { reason: "kalshi not configured".into() });
+        };
+        let Some((ticker, yes)) = signal.token_id.as_deref().and_then(kalshi::parse_token) else {
+            return Ok(SignalOutcome::Rejected { reason: "invalid kalshi token".into() });
+        };
+        let count = signal.size.floor();
+        if count < 1.0 {
+            return Ok(SignalOutcome::Rejected { reason: "below one contract".into() });
+        }
+
+        let mut order = Order {
+            id: OrderId::generate(),
+            market_id: signal.market_id.clone(),
+            side: signal.side.clone(),
+            token_id: kalshi::token_id(ticker, yes),
+            price: signal.price,
+            size: count,
+            order_type: OrderType::FOK,
+            status: OrderStatus::Pending,
+            created_at: self.clock.now(),
+            remote_id: None,
+            strategy: signal.strategy.clone(),
+            replaces: None,
+            expires_at: None,
+        };
+        self.orders.insert(order.clone());
+
+        let submitted_at = Utc::now();
+        let result = client.create_order(&order.id, ticker, yes, &order.side, count as u64, order.price).await;
+        METRICS.order_ack.observe_since(submitted_at);
+
+        match result {
+            Ok(resp) => {
+                info!("Kalshi order {} → remote {} ({})", order.id, resp.order_id, resp.status);
+                self.orders.set_remote_id(&order.id, &resp.order_id);
+                order.remote_id = Some(resp.order_id);
+                logging::order_submitted(&order);
+                order.status = match resp.status.as_str() {
+                    "executed" => OrderStatus::Filled,
+                    "resting" => OrderStatus::Open,
+                    _ => OrderStatus::Cancelled,
+                };
+                self.orders.set_status(&order.id, order.status.clone());
+                if order.status == OrderStatus::Filled {
                     let trade = Trade {
                         id: Uuid::new_v4().to_string(),
                         order_id: order.id.clone(),
@@ -131,42 +1219,73 @@ impl OrderManager {
                         side: order.side.clone(),
                         price: order.price,
                         size: order.size,
-                        fee: order.size * order.price * 0.002, // ~20bps fee estimate
-                        timestamp: Utc::now(),
+                        fee: client.fee(order.size, order.price),
+                        timestamp: self.clock.now(),
                     };
-                    self.db.insert_trade(&trade).await?;
-                } else {
-                    let msg = resp.error_msg.unwrap_or_default();
-                    error!("Order rejected: {}", msg);
-                    self.db
-                        .update_order_status(&order.id, &OrderStatus::Failed)
-                        .await?;
+                    self.db.enqueue(Write::Trade(trade.clone()));
+                    self.record_fill(&order, &trade, &signal.strategy).await?;
                 }
+                Ok(SignalOutcome::Submitted { order })
             }
             Err(e) => {
-                error!("Order submission failed: {:?}", e);
-                self.db
-                    .update_order_status(&order.id, &OrderStatus::Failed)
-                    .await?;
+                error!("Kalshi order submission failed: {}", e);
+                self.refused(&e);
+                self.orders.set_status(&order.id, OrderStatus::Failed);
+                order.status = OrderStatus::Failed;
+                Ok(SignalOutcome::Failed { order, error: e.to_string() })
             }
         }
-
-        Ok(())
     }
 
     /// Emergency: cancel all open orders
     pub async fn cancel_all(&self) -> Result<()> {
         warn!("CANCELLING ALL ORDERS");
-        self.poly_client.cancel_all().await?;
+        self.orders.cancel_all(&self.poly_client).await
+    }
 
-        // Update local DB
-        let open_orders = self.db.get_open_orders().await?;
-        for order in open_orders {
-            self.db
-                .update_order_status(&order.id, &OrderStatus::Cancelled)
-                .await?;
-        }
+    /// Cancel every open order on one market
+    pub async fn cancel_market(&self, market_id: &MarketId) -> Result<CancelReport> {
+        warn!("Cancelling all orders on {}", market_id);
+        self.orders.cancel_market(&self.poly_client, market_id).await
+    }
 
-        Ok(())
+    /// Cancel every open order one strategy placed
+    pub async fn cancel_strategy(&self, strategy: &str) -> Result<CancelReport> {
+        warn!("Cancelling all orders from {}", strategy);
+        self.orders.cancel_strategy(&self.poly_client, strategy).await
+    }
+}
+
+/// Worst price a marketable order on `side` may pay, `bps` past the signal price
+pub fn slippage_cap(side: &Side, price: f64, bps: f64) -> f64 {
+    match side {
+        Side::Buy => price * (1.0 + bps / 10_000.0),
+        Side::Sell => price * (1.0 - bps / 10_000.0),
+    }
+}
+
+/// Walk the levels an order on `side` would take, best first, up to `cap`. Returns
+/// the price of the last level needed to fill `size` and the size available within
+/// the cap (less than `size` when the book is thin), or None when the order
+/// doesn't cross and will rest.
+pub fn sweep(book: &OrderBook, side: &Side, size: f64, cap: f64) -> Option<(f64, f64)> {
+    let mut levels: Vec<&BookLevel> = match side {
+        Side::Buy => book.asks.iter().filter(|l| l.price <= cap).collect(),
+        Side::Sell => book.bids.iter().filter(|l| l.price >= cap).collect(),
+    };
+    match side {
+        Side::Buy => levels.sort_by(|a, b| a.price.total_cmp(&b.price)),
+        Side::Sell => levels.sort_by(|a, b| b.price.total_cmp(&a.price)),
+    }
+
+    let mut available = 0.0;
+    let mut marginal = None;
+    for level in levels {
+        if available >= size {
+            break;
+        }
+        available += level.size;
+        marginal = Some(level.price);
     }
+    Some((marginal?, available.min(size)))
 }
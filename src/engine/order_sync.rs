@@ -0,0 +1,185 @@
+use chrono::Utc;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tracing::{info, warn};
+
+use crate::adapters::kalshi;
+use crate::adapters::polymarket::PolymarketClient;
+use crate::domain::{Order, OrderId, OrderStatus, OrderType};
+use crate::engine::inflight::InflightOrders;
+use crate::engine::order_manager::OrderManager;
+use crate::engine::readiness::Readiness;
+
+/// How late the CLOB may be expiring a GTD order before it is cancelled from here
+const GTD_GRACE: chrono::Duration = chrono::Duration::seconds(30);
+
+/// Reconciles tracked orders with the CLOB's view of them.
+///
+/// Orders leave the in-flight map only on a terminal status, and nothing else
+/// moves a resting order there once it fills or is cancelled on the exchange side
+/// (expiry, a cancel from another session, the operator's UI). Each pass lists our
+/// live orders on the CLOB; any tracked order missing from that list is looked up
+/// by ID and marked Filled or Cancelled to match.
+///
+/// Fills are booked from here too: whatever more of an order the exchange reports
+/// matched than has been booked, live orders' partial fills included, goes to the
+/// order manager as a fill. When an order is done, anything booked for it beyond
+/// what matched is taken back.
+///
+/// Expiring orders lean on the exchange where it can: GTD orders drop off the
+/// CLOB by themselves and are picked up here like any other cancel. A live order
+/// past its expiry that the exchange won't expire (any other type, or a GTD the
+/// CLOB is late on by more than `GTD_GRACE`) is cancelled from here.
+pub struct OrderSync {
+    orders: InflightOrders,
+    poly_client: PolymarketClient,
+    poll_interval: Duration,
+    /// Books the matched shares found; None only tracks status
+    fills: Option<Arc<OrderManager>>,
+    /// Orders whose fills are being booked, by local ID, with their exchange IDs
+    followed: Mutex<HashMap<OrderId, (String, Order)>>,
+    /// Told once the first pass has gone through, which is boot reconciliation
+    readiness: Option<Readiness>,
+}
+
+impl OrderSync {
+    pub fn new(orders: InflightOrders, poly_client: PolymarketClient, poll_interval: Duration) -> Self {
+        Self {
+            orders,
+            poly_client,
+            poll_interval,
+            fills: None,
+            followed: Mutex::new(HashMap::new()),
+            readiness: None,
+        }
+    }
+
+    pub fn with_fills(mut self, order_manager: Arc<OrderManager>) -> Self {
+        self.fills = Some(order_manager);
+        self
+    }
+
+    pub fn with_readiness(mut self, readiness: Readiness) -> Self {
+        self.readiness = Some(readiness);
+        self
+    }
+
+    pub async fn run(self) {
+        info!("Order status sync started (every {:?})", self.poll_interval);
+        let mut interval = tokio::time::interval(self.poll_interval);
+
+        loop {
+            interval.tick().await;
+            match self.sync().await {
+                Ok(()) => {
+                    if let Some(readiness) = &self.readiness {
+                        readiness.reconciled();
+                    }
+                }
+                Err(e) => warn!("Order status sync failed: {:?}", e),
+            }
+        }
+    }
+
+    async fn sync(&self) -> crate::error::Result<()> {
+        // Pending orders are mid-submission; Kalshi legs aren't on the CLOB
+        let tracked: Vec<_> = self
+            .orders
+            .open_orders()
+            .into_iter()
+            .filter(|o| o.status == OrderStatus::Open && kalshi::ticker_of(&o.market_id).is_none())
+            .filter_map(|o| Some((o.remote_id.clone()?, o)))
+            .collect();
+        // Orders followed last pass that have since left tracking (cancelled from here
+        // or by the order manager) get one last lookup, for fills that beat the cancel
+        let tracked_ids: HashSet<&OrderId> = tracked.iter().map(|(_, o)| &o.id).collect();
+        let mut gone = Vec::new();
+        self.followed.lock().unwrap().retain(|id, (remote_id, order)| {
+            let kept = tracked_ids.contains(id);
+            if !kept {
+                gone.push((order.clone(), remote_id.clone(), false));
+            }
+            kept
+        });
+        if tracked.is_empty() && gone.is_empty() {
+            return Ok(());
+        }
+
+        let live: HashMap<String, _> =
+            self.poly_client.get_open_orders().await?.into_iter().map(|o| (o.id.clone(), o)).collect();
+        let now = Utc::now();
+        for (remote_id, order) in tracked {
+            let Some(remote) = live.get(&remote_id) else {
+                gone.push((order, remote_id, true));
+                continue;
+            };
+            self.follow(&order, &remote_id);
+            self.book(&order, remote.matched(), false).await;
+            let Some(expires_at) = order.expires_at else {
+                continue;
+            };
+            let due = if order.order_type == OrderType::GTD { expires_at + GTD_GRACE } else { expires_at };
+            if now < due {
+                continue;
+            }
+            match self.orders.cancel(&self.poly_client, &order.id).await {
+                Ok(true) => info!("Order {} (remote {}) cancelled: expired at {}", order.id, remote_id, expires_at),
+                Ok(false) => warn!("Expired order {} (remote {}) could not be cancelled", order.id, remote_id),
+                Err(e) => warn!("Cancel of expired order {} failed: {:?}", order.id, e),
+            }
+        }
+
+        // `settle` is false for orders that left tracking already and only need their fills booked
+        for (order, remote_id, settle) in gone {
+            let (status, matched) = match self.poly_client.get_order(&remote_id).await {
+                Ok(Some(remote)) if remote.is_filled() => (OrderStatus::Filled, remote.matched().max(order.size)),
+                Ok(Some(remote)) if remote.status.eq_ignore_ascii_case("live") => continue,
+                Ok(Some(remote)) => (OrderStatus::Cancelled, remote.matched()),
+                Ok(None) => {
+                    warn!("Order {} (remote {}) unknown to the exchange", order.id, remote_id);
+                    continue;
+                }
+                Err(e) => {
+                    warn!("Status lookup failed for order {}: {:?}", order.id, e);
+                    if !settle {
+                        self.follow(&order, &remote_id);
+                    }
+                    continue;
+                }
+            };
+            // Left tracked when its fills can't be booked, so the next pass retries
+            if !self.book(&order, matched, true).await {
+                self.follow(&order, &remote_id);
+                continue;
+            }
+            self.followed.lock().unwrap().remove(&order.id);
+            if settle {
+                info!("Order {} (remote {}) is {:?} on the exchange", order.id, remote_id, status);
+                self.orders.set_status(&order.id, status);
+            }
+        }
+        Ok(())
+    }
+
+    /// Keep `order` in view for a final lookup if it leaves tracking before settling here
+    fn follow(&self, order: &Order, remote_id: &str) {
+        if self.fills.is_some() {
+            self.followed.lock().unwrap().insert(order.id.clone(), (remote_id.to_string(), order.clone()));
+        }
+    }
+
+    /// Book `matched` shares of `order` as filled so far; false if that failed
+    async fn book(&self, order: &Order, matched: f64, done: bool) -> bool {
+        let Some(order_manager) = &self.fills else {
+            return true;
+        };
+        match order_manager.sync_fills(order, matched, done).await {
+            Ok(()) => true,
+            Err(e) => {
+                warn!("Booking fills of order {} failed: {:?}", order.id, e);
+                false
+            }
+        }
+    }
+}
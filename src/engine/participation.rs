@@ -0,0 +1,192 @@
+use chrono::Duration as ChronoDuration;
+use serde::Serialize;
+use std::collections::{BTreeSet, HashMap};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::{info, warn};
+
+use crate::adapters::data_api::DataApiClient;
+use crate::adapters::database::Database;
+use crate::clock::{self, SharedClock};
+use crate::domain::MarketId;
+use crate::error::Result;
+
+/// Trailing window participation is measured over
+const WINDOW_SECS: i64 = 3600;
+/// Public prints fetched per market; in very busy markets this may not reach back
+/// the full hour, which understates volume and so errs on the strict side
+const TAPE_LIMIT: u32 = 1000;
+/// Market volume is re-fetched at most this often per market
+const VOLUME_TTL: Duration = Duration::from_secs(60);
+/// How often the cached estimates the order path reads are refreshed
+const REFRESH_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Our footprint in a market's trailing volume
+#[derive(Debug, Clone, Serialize)]
+pub struct ParticipationEstimate {
+    pub market_id: MarketId,
+    pub window_secs: i64,
+    /// All public volume in the window, USDC notional
+    pub market_volume: f64,
+    /// Our own traded notional in the window
+    pub our_volume: f64,
+    /// our_volume / market_volume; None when the market hasn't traded
+    pub participation: Option<f64>,
+    pub max_participation: f64,
+    /// Notional we can still trade before hitting the limit
+    pub remaining: f64,
+}
+
+/// Keeps the bot from becoming the market in thin books: our traded notional may
+/// not exceed `max_participation` of the market's trailing 1h volume.
+///
+/// Working the estimate out takes a database query and a tape fetch, so the order
+/// path never does it: it reads `cached`, which `run` keeps fresh in the background
+/// for every market an order has asked about. Fills booked since the last refresh
+/// are added in as they happen. A market with no estimate yet, or whose tape
+/// couldn't be read or shows no trading, is not capped.
+pub struct ParticipationTracker {
+    db: Database,
+    data_api: DataApiClient,
+    max_participation: f64,
+    volume_cache: Mutex<HashMap<MarketId, (Instant, f64)>>,
+    /// Latest estimate per market the order path has asked about; None until the
+    /// first refresh, or after one failed
+    cached: Mutex<HashMap<MarketId, Option<ParticipationEstimate>>>,
+    clock: SharedClock,
+}
+
+impl ParticipationTracker {
+    pub fn new(db: Database, data_api: DataApiClient, max_participation: f64) -> Self {
+        Self {
+            db,
+            data_api,
+            max_participation,
+            volume_cache: Mutex::new(HashMap::new()),
+            cached: Mutex::new(HashMap::new()),
+            clock: clock::system(),
+        }
+    }
+
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// A limit of 0 disables participation checks
+    pub fn enabled(&self) -> bool {
+        self.max_participation > 0.0
+    }
+
+    /// The last estimate for `market_id`, without waiting on anything; the first ask
+    /// about a market starts it being refreshed. None means no cap.
+    pub fn cached(&self, market_id: &MarketId) -> Option<ParticipationEstimate> {
+        if !self.enabled() {
+            return None;
+        }
+        self.cached
+            .lock()
+            .unwrap()
+            .entry(market_id.clone())
+            .or_default()
+            .clone()
+            .filter(|estimate| estimate.market_volume > 0.0)
+    }
+
+    /// Count a fill towards its market's cached estimate until the next refresh
+    pub fn record_fill(&self, market_id: &MarketId, notional: f64) {
+        if let Some(Some(estimate)) = self.cached.lock().unwrap().get_mut(market_id) {
+            estimate.our_volume += notional;
+            estimate.participation = (estimate.market_volume > 0.0).then(|| estimate.our_volume / estimate.market_volume);
+            estimate.remaining = (estimate.max_participation * estimate.market_volume - estimate.our_volume).max(0.0);
+        }
+    }
+
+    /// Refresh the cached estimates periodically
+    pub async fn run(self: Arc<Self>) {
+        if !self.enabled() {
+            return;
+        }
+        info!("Participation estimates refreshed every {:?}", REFRESH_INTERVAL);
+        let mut interval = tokio::time::interval(REFRESH_INTERVAL);
+        loop {
+            interval.tick().await;
+            let markets: Vec<MarketId> = self.cached.lock().unwrap().keys().cloned().collect();
+            for market_id in markets {
+                let estimate = match self.estimate(&market_id).await {
+                    Ok(estimate) => Some(estimate),
+                    Err(e) => {
+                        warn!("Participation estimate for {} failed, not capping it: {:?}", market_id, e);
+                        None
+                    }
+                };
+                self.cached.lock().unwrap().insert(market_id, estimate);
+            }
+        }
+    }
+
+    pub async fn estimate(&self, market_id: &MarketId) -> Result<ParticipationEstimate> {
+        let since = self.clock.now() - ChronoDuration::seconds(WINDOW_SECS);
+        let our_volume: f64 = self
+            .db
+            .get_trades_since(since)
+            .await?
+            .iter()
+            .filter(|t| &t.market_id == market_id)
+            .map(|t| t.size * t.price)
+            .sum();
+        let market_volume = self.market_volume(market_id).await?;
+
+        Ok(ParticipationEstimate {
+            market_id: market_id.clone(),
+            window_secs: WINDOW_SECS,
+            market_volume,
+            our_volume,
+            participation: (market_volume > 0.0).then(|| our_volume / market_volume),
+            max_participation: self.max_participation,
+            remaining: (self.max_participation * market_volume - our_volume).max(0.0),
+        })
+    }
+
+    /// Estimates for every market we've traded in the window
+    pub async fn estimates(&self) -> Result<Vec<ParticipationEstimate>> {
+        let since = self.clock.now() - ChronoDuration::seconds(WINDOW_SECS);
+        let markets: BTreeSet<MarketId> = self
+            .db
+            .get_trades_since(since)
+            .await?
+            .into_iter()
+            .map(|t| t.market_id)
+            .collect();
+
+        let mut out = Vec::with_capacity(markets.len());
+        for market_id in markets {
+            out.push(self.estimate(&market_id).await?);
+        }
+        Ok(out)
+    }
+
+    async fn market_volume(&self, market_id: &MarketId) -> Result<f64> {
+        if let Some((fetched, volume)) = self.volume_cache.lock().unwrap().get(market_id) {
+            if self.clock.instant().duration_since(*fetched) < VOLUME_TTL {
+                return Ok(*volume);
+            }
+        }
+
+        let cutoff = self.clock.now().timestamp() - WINDOW_SECS;
+        let volume = self
+            .data_api
+            .get_market_trades(market_id, TAPE_LIMIT)
+            .await?
+            .iter()
+            .filter(|t| t.timestamp >= cutoff)
+            .map(|t| t.size * t.price)
+            .sum();
+
+        self.volume_cache
+            .lock()
+            .unwrap()
+            .insert(market_id.clone(), (self.clock.instant(), volume));
+        Ok(volume)
+    }
+}
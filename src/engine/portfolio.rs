@@ -0,0 +1,203 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::adapters::database::{Database, Write};
+use crate::clock::{self, SharedClock};
+use crate::domain::{ClosedTrade, Order, Position, Side, TokenId, Trade};
+use crate::engine::inflight::InflightOrders;
+use crate::error::Result;
+
+/// Below this a position is considered closed
+const SIZE_EPSILON: f64 = 1e-9;
+/// How often out-of-band edits to the positions table are picked up
+const RELOAD_INTERVAL: Duration = Duration::from_secs(10);
+
+/// In-memory positions and open orders, for the order manager and strategies.
+///
+/// Positions are keyed by token and updated from fills, then queued for the
+/// database so API readers and restarts see the same state without a fill waiting
+/// on the write. Code that edits the positions table directly (dust sweeps,
+/// liquidation, another process) is picked up by a periodic reload.
+#[derive(Clone)]
+pub struct Portfolio {
+    db: Database,
+    pub orders: InflightOrders,
+    positions: Arc<RwLock<HashMap<TokenId, Position>>>,
+    /// Bumped on every fill so a reload that raced one is discarded
+    version: Arc<AtomicU64>,
+    clock: SharedClock,
+}
+
+impl Portfolio {
+    pub async fn load(db: Database, orders: InflightOrders) -> Result<Self> {
+        let portfolio = Self {
+            db,
+            orders,
+            positions: Arc::new(RwLock::new(HashMap::new())),
+            version: Arc::new(AtomicU64::new(0)),
+            clock: clock::system(),
+        };
+        portfolio.reload().await?;
+        Ok(portfolio)
+    }
+
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Open positions (size > 0)
+    pub fn positions(&self) -> Vec<Position> {
+        self.positions.read().unwrap().values().cloned().collect()
+    }
+
+    pub fn open_orders(&self) -> Vec<Order> {
+        self.orders.open_orders()
+    }
+
+    /// Apply `trade`, a fill of `order`. Returns the close when it reduced an
+    /// opposite position, with PnL net of fees.
+    ///
+    /// The fee is split by shares between what the fill closes and what it opens:
+    /// the closing part comes off the realized PnL, and the opening part goes into
+    /// the new shares' cost basis (`avg_price`), so their entry fee is charged when
+    /// they are marked or closed.
+    pub fn apply_fill(&self, order: &Order, trade: &Trade, strategy: &str) -> Option<ClosedTrade> {
+        let (price, size) = (trade.price, trade.size);
+        let fee_per_share = if size > 0.0 { trade.fee / size } else { 0.0 };
+        let basis = match order.side {
+            Side::Buy => price + fee_per_share,
+            Side::Sell => price - fee_per_share,
+        };
+        let current = self.positions.read().unwrap().get(&order.token_id).cloned();
+        let (updated, realized) = match current {
+            None => (Some(new_position(order, basis, price, size, strategy)), None),
+            Some(mut pos) if pos.side == order.side => {
+                pos.avg_price = (pos.avg_price * pos.size + basis * size) / (pos.size + size);
+                pos.size += size;
+                pos.current_price = price;
+                (Some(pos), None)
+            }
+            Some(mut pos) => {
+                let closed = size.min(pos.size);
+                let per_share = match pos.side {
+                    Side::Buy => price - pos.avg_price,
+                    Side::Sell => pos.avg_price - price,
+                };
+                let close = ClosedTrade {
+                    id: Uuid::new_v4().to_string(),
+                    strategy: pos.strategy.clone(),
+                    market_id: pos.market_id.clone(),
+                    token_id: pos.token_id.clone(),
+                    side: pos.side.clone(),
+                    size: closed,
+                    entry_price: pos.avg_price,
+                    exit_price: price,
+                    pnl: (per_share - fee_per_share) * closed,
+                    closed_at: self.clock.now(),
+                };
+                pos.size -= closed;
+                pos.current_price = price;
+                let updated = if pos.size > SIZE_EPSILON {
+                    Some(pos)
+                } else if size - closed > SIZE_EPSILON {
+                    // Overshot: the remainder opens the other way
+                    Some(new_position(order, basis, price, size - closed, strategy))
+                } else {
+                    None
+                };
+                (updated, Some(close))
+            }
+        };
+
+        self.store(order, updated);
+        realized
+    }
+
+    /// Take back `size` shares booked for `order` at `price` that never filled,
+    /// unwinding them from the average price as if they had never been added. A
+    /// booking that closed out an opposite position has realized its PnL already
+    /// and is only reported. Returns whether anything was taken back.
+    pub fn reverse_fill(&self, order: &Order, price: f64, size: f64) -> bool {
+        let Some(mut pos) = self.positions.read().unwrap().get(&order.token_id).cloned() else {
+            warn!("No position on {} to take back {:.2} unfilled shares from", order.token_id, size);
+            return false;
+        };
+        if pos.side != order.side {
+            warn!(
+                "{:.2} unfilled shares of {} closed a position on {}; left as booked",
+                size, order.id, order.token_id
+            );
+            return false;
+        }
+        let remaining = pos.size - size;
+        let updated = (remaining > SIZE_EPSILON).then(|| {
+            pos.avg_price = (pos.avg_price * pos.size - price * size) / remaining;
+            pos.size = remaining;
+            pos
+        });
+        self.store(order, updated);
+        true
+    }
+
+    /// Swap in the position on `order`'s token, or drop it when `None`, and queue the write
+    fn store(&self, order: &Order, updated: Option<Position>) {
+        self.db.enqueue(match &updated {
+            Some(pos) => Write::Position(pos.clone()),
+            None => Write::DeletePosition(order.market_id.clone(), order.token_id.clone()),
+        });
+        self.version.fetch_add(1, Ordering::SeqCst);
+        let mut positions = self.positions.write().unwrap();
+        match updated {
+            Some(pos) => positions.insert(order.token_id.clone(), pos),
+            None => positions.remove(&order.token_id),
+        };
+    }
+
+    /// Replace the cache with what the database holds, once queued fills are in it
+    pub async fn reload(&self) -> Result<()> {
+        let version = self.version.load(Ordering::SeqCst);
+        self.db.flush().await;
+        let loaded: HashMap<TokenId, Position> = self
+            .db
+            .get_positions()
+            .await?
+            .into_iter()
+            .filter(|p| p.size > SIZE_EPSILON)
+            .map(|p| (p.token_id.clone(), p))
+            .collect();
+        if self.version.load(Ordering::SeqCst) == version {
+            *self.positions.write().unwrap() = loaded;
+        }
+        Ok(())
+    }
+
+    /// Reload from the database periodically
+    pub async fn run(self) {
+        info!("Portfolio tracking {} positions", self.positions.read().unwrap().len());
+        let mut ticker = tokio::time::interval(RELOAD_INTERVAL);
+        loop {
+            ticker.tick().await;
+            if let Err(e) = self.reload().await {
+                warn!("Portfolio reload failed: {:?}", e);
+            }
+        }
+    }
+}
+
+fn new_position(order: &Order, basis: f64, price: f64, size: f64, strategy: &str) -> Position {
+    Position {
+        market_id: order.market_id.clone(),
+        token_id: order.token_id.clone(),
+        side: order.side.clone(),
+        size,
+        avg_price: basis,
+        current_price: price,
+        pnl: 0.0,
+        strategy: strategy.to_string(),
+    }
+}
@@ -0,0 +1,141 @@
+use chrono::Utc;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+
+use crate::domain::{ExposureSnapshot, MarketId, Order, Position, Side, Signal, TokenId};
+
+/// A market's holdings after netting complementary tokens against each other.
+///
+/// In a binary market one YES + one NO always redeems for $1, so `matched_size`
+/// full sets carry no directional risk and can be merged back into collateral.
+#[derive(Debug, Clone, Serialize)]
+pub struct NetPosition {
+    pub market_id: MarketId,
+    /// Complete YES+NO sets held (mergeable for $1 each)
+    pub matched_size: f64,
+    /// Certain PnL of the matched sets: matched_size * (1 - cost of a set)
+    pub locked_pnl: f64,
+    /// Token carrying the residual directional position, if any
+    pub net_token_id: Option<TokenId>,
+    pub net_size: f64,
+    /// Cost basis of the residual leg — what the market can still take from us
+    pub net_exposure: f64,
+    /// Cost basis of all legs, ignoring netting
+    pub gross_exposure: f64,
+    pub mergeable: bool,
+}
+
+/// Net positions per market. Markets holding a single token pass through unchanged.
+pub fn net_positions(positions: &[Position]) -> Vec<NetPosition> {
+    let mut by_market: BTreeMap<&MarketId, Vec<&Position>> = BTreeMap::new();
+    for p in positions.iter().filter(|p| p.size > 0.0) {
+        by_market.entry(&p.market_id).or_default().push(p);
+    }
+
+    by_market
+        .into_iter()
+        .map(|(market_id, legs)| {
+            let gross_exposure: f64 = legs.iter().map(|p| p.size * p.avg_price).sum();
+
+            // Only two-token (binary) holdings are complementary
+            let (yes, no) = match legs.as_slice() {
+                [a, b] if a.token_id != b.token_id => (*a, *b),
+                _ => {
+                    return NetPosition {
+                        market_id: market_id.clone(),
+                        matched_size: 0.0,
+                        locked_pnl: 0.0,
+                        net_token_id: (legs.len() == 1).then(|| legs[0].token_id.clone()),
+                        net_size: legs.iter().map(|p| p.size).sum(),
+                        net_exposure: gross_exposure,
+                        gross_exposure,
+                        mergeable: false,
+                    };
+                }
+            };
+
+            let matched = yes.size.min(no.size);
+            let (residual, residual_size) = if yes.size >= no.size {
+                (yes, yes.size - matched)
+            } else {
+                (no, no.size - matched)
+            };
+
+            NetPosition {
+                market_id: market_id.clone(),
+                matched_size: matched,
+                locked_pnl: matched * (1.0 - yes.avg_price - no.avg_price),
+                net_token_id: (residual_size > 0.0).then(|| residual.token_id.clone()),
+                net_size: residual_size,
+                net_exposure: residual_size * residual.avg_price,
+                gross_exposure,
+                mergeable: matched > 0.0,
+            }
+        })
+        .collect()
+}
+
+/// Directional exposure across all positions after netting complementary holdings
+pub fn net_exposure(positions: &[Position]) -> f64 {
+    net_positions(positions).iter().map(|n| n.net_exposure).sum()
+}
+
+/// Directional exposure once `signal` fills. A buy of the token complementing a
+/// held one nets against it instead of adding to the total.
+pub fn net_exposure_after(positions: &[Position], signal: &Signal) -> f64 {
+    let mut after = positions.to_vec();
+    let token_id = signal
+        .token_id
+        .clone()
+        .unwrap_or_else(|| TokenId::new(signal.market_id.as_str()));
+    match after.iter_mut().find(|p| p.token_id == token_id) {
+        Some(p) if p.side == signal.side => {
+            p.avg_price = (p.avg_price * p.size + signal.price * signal.size) / (p.size + signal.size);
+            p.size += signal.size;
+        }
+        Some(p) => p.size = (p.size - signal.size).max(0.0),
+        None => after.push(Position {
+            market_id: signal.market_id.clone(),
+            token_id,
+            side: signal.side.clone(),
+            size: signal.size,
+            avg_price: signal.price,
+            current_price: signal.price,
+            pnl: 0.0,
+            strategy: signal.strategy.clone(),
+        }),
+    }
+    net_exposure(&after)
+}
+
+/// Current exposure picture; `underlyings` maps market ID → underlying for markets
+/// that share one (e.g. several BTC strikes), all others are keyed by market ID
+pub fn exposure_snapshot(
+    positions: &[Position],
+    open_orders: &[Order],
+    bankroll: f64,
+    underlyings: &HashMap<MarketId, String>,
+) -> ExposureSnapshot {
+    let net = net_positions(positions);
+    let gross_exposure = net.iter().fold(0.0, |total, n| total + n.gross_exposure);
+    let net_exposure = net.iter().fold(0.0, |total, n| total + n.net_exposure);
+    let open_order_notional = open_orders
+        .iter()
+        .filter(|o| o.side == Side::Buy)
+        .fold(0.0, |total, o| total + o.size * o.price);
+
+    let mut by_underlying = BTreeMap::new();
+    for n in &net {
+        let underlying = underlyings.get(&n.market_id).map(String::as_str).unwrap_or(&n.market_id);
+        *by_underlying.entry(underlying.to_string()).or_insert(0.0) += n.net_exposure;
+    }
+
+    ExposureSnapshot {
+        timestamp: Utc::now(),
+        gross_exposure,
+        net_exposure,
+        open_order_notional,
+        free_collateral: bankroll - gross_exposure - open_order_notional,
+        by_underlying,
+    }
+}
@@ -0,0 +1,54 @@
+use chrono::{DateTime, Utc};
+
+const SECS_PER_YEAR: f64 = 365.0 * 24.0 * 3600.0;
+
+/// Probability that spot finishes above `strike`, with log-normal spot and no drift.
+///
+/// This is the model behind fair values for "<asset> above $X" markets: N(d2) with
+/// d2 = (ln(S/K) - σ²T/2) / (σ√T). At or past expiry it is 1 or 0.
+pub fn prob_above(spot: f64, strike: f64, vol: f64, years: f64) -> f64 {
+    if spot <= 0.0 || strike <= 0.0 {
+        return 0.0;
+    }
+    if years <= 0.0 || vol <= 0.0 {
+        return if spot > strike { 1.0 } else { 0.0 };
+    }
+    let sigma_t = vol * years.sqrt();
+    let d2 = ((spot / strike).ln() - 0.5 * sigma_t * sigma_t) / sigma_t;
+    normal_cdf(d2)
+}
+
+/// Change in `prob_above` per unit move in spot: φ(d2) / (Sσ√T). Zero at or past
+/// expiry, where the probability is a step.
+pub fn delta_above(spot: f64, strike: f64, vol: f64, years: f64) -> f64 {
+    if spot <= 0.0 || strike <= 0.0 || years <= 0.0 || vol <= 0.0 {
+        return 0.0;
+    }
+    let sigma_t = vol * years.sqrt();
+    let d2 = ((spot / strike).ln() - 0.5 * sigma_t * sigma_t) / sigma_t;
+    normal_pdf(d2) / (spot * sigma_t)
+}
+
+/// Years from `now` until `expiry`, never negative
+pub fn years_until(expiry: DateTime<Utc>, now: DateTime<Utc>) -> f64 {
+    ((expiry - now).num_seconds().max(0) as f64) / SECS_PER_YEAR
+}
+
+/// Standard normal density
+pub fn normal_pdf(x: f64) -> f64 {
+    (-0.5 * x * x).exp() / (2.0 * std::f64::consts::PI).sqrt()
+}
+
+/// Standard normal CDF via the Abramowitz–Stegun erf approximation (|error| < 1.5e-7)
+pub fn normal_cdf(x: f64) -> f64 {
+    let z = x.abs() / std::f64::consts::SQRT_2;
+    let t = 1.0 / (1.0 + 0.327_591_1 * z);
+    let poly = t
+        * (0.254_829_592 + t * (-0.284_496_736 + t * (1.421_413_741 + t * (-1.453_152_027 + t * 1.061_405_429))));
+    let erf = 1.0 - poly * (-z * z).exp();
+    if x >= 0.0 {
+        0.5 * (1.0 + erf)
+    } else {
+        0.5 * (1.0 - erf)
+    }
+}
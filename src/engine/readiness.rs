@@ -0,0 +1,191 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::{error, info};
+
+use crate::config::ReadinessConfig;
+use crate::domain::MarketData;
+use crate::engine::risk::RiskManager;
+
+/// How often what is still outstanding gets logged
+const REPORT_EVERY: Duration = Duration::from_secs(30);
+
+/// Instruments named in a waiting list before the rest are summed up
+const NAMED: usize = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WarmUp {
+    /// Waiting on feeds or reconciliation; the risk manager refuses every signal
+    WarmingUp,
+    Ready,
+    /// Started trading after `max_wait_secs` with data still missing
+    TimedOut,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ReadinessStatus {
+    pub state: WarmUp,
+    pub since: DateTime<Utc>,
+    pub ready_at: Option<DateTime<Utc>>,
+    /// Open orders have been checked against the exchange
+    pub reconciled: bool,
+    /// Feed → instruments it has yet to deliver data for
+    pub waiting: BTreeMap<String, BTreeSet<String>>,
+}
+
+/// Warm-up gate between boot and the first order.
+///
+/// Right after boot the strategy context holds at most the REST snapshot, and a
+/// strategy evaluating a token whose book hasn't arrived yet sees a market that
+/// isn't there. The risk manager is held in warm-up from construction until
+/// every expected instrument has had an event from its live feed and the first
+/// order sync pass has reconciled open orders with the exchange; then it opens
+/// once and stays open. Feeds dropping out later are the watchdog's business.
+#[derive(Clone)]
+pub struct Readiness {
+    status: Arc<Mutex<ReadinessStatus>>,
+    risk: RiskManager,
+    config: ReadinessConfig,
+}
+
+impl Readiness {
+    pub fn new(risk: RiskManager, config: ReadinessConfig) -> Self {
+        risk.begin_warm_up();
+        Self {
+            status: Arc::new(Mutex::new(ReadinessStatus {
+                state: WarmUp::WarmingUp,
+                since: Utc::now(),
+                ready_at: None,
+                reconciled: false,
+                waiting: BTreeMap::new(),
+            })),
+            risk,
+            config,
+        }
+    }
+
+    /// Wait for `feed` to deliver data on each of `instruments`
+    pub fn expect(&self, feed: &str, instruments: impl IntoIterator<Item = String>) {
+        let mut status = self.status.lock().unwrap();
+        if status.state != WarmUp::WarmingUp {
+            return;
+        }
+        let waiting = status.waiting.entry(feed.to_string()).or_default();
+        waiting.extend(instruments.into_iter().map(|i| instrument_key(feed, &i)));
+        if waiting.is_empty() {
+            status.waiting.remove(feed);
+        }
+    }
+
+    /// Tick off the instrument `event` carries data for
+    pub fn observe(&self, event: &MarketData) {
+        let Some((feed, instrument)) = source(event) else {
+            return;
+        };
+        let mut status = self.status.lock().unwrap();
+        let Some(waiting) = status.waiting.get_mut(feed) else {
+            return;
+        };
+        if !waiting.remove(&instrument_key(feed, instrument)) {
+            return;
+        }
+        if waiting.is_empty() {
+            status.waiting.remove(feed);
+            info!("Warm-up: {} feed has data for every instrument", feed);
+        }
+        self.advance(&mut status);
+    }
+
+    /// Open orders have been checked against the exchange
+    pub fn reconciled(&self) {
+        let mut status = self.status.lock().unwrap();
+        if !status.reconciled {
+            status.reconciled = true;
+            info!("Warm-up: open orders reconciled");
+            self.advance(&mut status);
+        }
+    }
+
+    pub fn status(&self) -> ReadinessStatus {
+        self.status.lock().unwrap().clone()
+    }
+
+    fn advance(&self, status: &mut ReadinessStatus) {
+        if status.state == WarmUp::WarmingUp && status.reconciled && status.waiting.is_empty() {
+            status.state = WarmUp::Ready;
+            status.ready_at = Some(Utc::now());
+            self.risk.warmed_up("feeds warm and open orders reconciled");
+        }
+    }
+
+    /// Log what is still outstanding until the gate opens, opening it regardless
+    /// once `max_wait_secs` have passed
+    pub async fn run(self) {
+        let started = Instant::now();
+        let max_wait = Duration::from_secs(self.config.max_wait_secs);
+        let mut ticker = tokio::time::interval(REPORT_EVERY);
+        ticker.tick().await;
+        loop {
+            ticker.tick().await;
+            let mut status = self.status.lock().unwrap();
+            if status.state != WarmUp::WarmingUp {
+                return;
+            }
+            let outstanding = outstanding(&status);
+            if !max_wait.is_zero() && started.elapsed() >= max_wait {
+                error!("Warm-up timed out after {}s, still waiting on {}", max_wait.as_secs(), outstanding);
+                status.state = WarmUp::TimedOut;
+                status.ready_at = Some(Utc::now());
+                self.risk
+                    .warmed_up(&format!("timed out after {}s waiting on {}", max_wait.as_secs(), outstanding));
+                return;
+            }
+            info!("Warming up, waiting on {}", outstanding);
+        }
+    }
+}
+
+/// Feed and instrument an event is data for; None for events no feed is waited on for
+fn source(event: &MarketData) -> Option<(&'static str, &str)> {
+    match event {
+        MarketData::PolymarketPrice { token_id, .. }
+        | MarketData::PolymarketOrderBook { token_id, .. }
+        | MarketData::PolymarketTrade { token_id, .. } => Some(("polymarket", token_id)),
+        MarketData::BinanceTicker { symbol, .. } | MarketData::BinanceOrderBook { symbol, .. } => {
+            Some(("binance", symbol))
+        }
+        MarketData::KalshiTicker { ticker, .. } => Some(("kalshi", ticker)),
+        _ => None,
+    }
+}
+
+/// Binance symbols are configured lower case and come back upper case
+fn instrument_key(feed: &str, instrument: &str) -> String {
+    if feed == "binance" {
+        instrument.to_uppercase()
+    } else {
+        instrument.to_string()
+    }
+}
+
+/// "polymarket: 2 (a, b); order reconciliation"
+fn outstanding(status: &ReadinessStatus) -> String {
+    let mut parts: Vec<String> = status
+        .waiting
+        .iter()
+        .map(|(feed, instruments)| {
+            let mut named: Vec<&str> = instruments.iter().take(NAMED).map(String::as_str).collect();
+            if instruments.len() > NAMED {
+                named.push("…");
+            }
+            format!("{}: {} ({})", feed, instruments.len(), named.join(", "))
+        })
+        .collect();
+    if !status.reconciled {
+        parts.push("order reconciliation".to_string());
+    }
+    parts.join("; ")
+}
@@ -0,0 +1,250 @@
+use chrono::Utc;
+use reqwest::Client;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::adapters::database::Database;
+use crate::adapters::gamma::GammaClient;
+use crate::adapters::polygon::{self, PolygonRpc};
+use crate::config::RedemptionConfig;
+use crate::domain::{ClosedTrade, Market, MarketId, MarketStructure, Position, Redemption, RedemptionStatus, Side};
+use crate::error::Result;
+use crate::wallet::Wallet;
+
+/// Market IDs per Gamma lookup
+const LOOKUP_BATCH: usize = 50;
+/// Collateral base units per share: outcome tokens carry USDC.e's 6 decimals
+const UNITS_PER_SHARE: f64 = 1e6;
+/// How long a pass waits on a redeem transaction before leaving it to the next one
+const CONFIRM_TIMEOUT: Duration = Duration::from_secs(120);
+const CONFIRM_POLL: Duration = Duration::from_secs(5);
+
+/// Settles positions in markets that have resolved.
+///
+/// Each pass looks up held markets on Gamma. Once one has a winner, its positions
+/// are closed at 1 (winning token) or 0 (losing token): the close is recorded, the
+/// position dropped and the bankroll moved by the realized PnL. Winning tokens
+/// are redeemed from the signing wallet when a chain is set up: through the
+/// Conditional Tokens contract for binary and categorical markets, through the
+/// neg-risk adapter for legs of a neg-risk event. The position stays open until
+/// the transaction confirms. Otherwise they are marked for manual redemption,
+/// with a warning and a POST to each webhook.
+pub struct Redeemer {
+    db: Database,
+    gamma: GammaClient,
+    bankroll: Arc<RwLock<f64>>,
+    client: Client,
+    config: RedemptionConfig,
+    /// Where redeem transactions go from; None leaves every winner to the operator
+    chain: Option<Chain>,
+}
+
+struct Chain {
+    rpc: PolygonRpc,
+    wallet: Wallet,
+    /// Redeems sent but not yet confirmed, by market
+    pending: HashMap<MarketId, String>,
+}
+
+impl Redeemer {
+    pub fn new(db: Database, gamma: GammaClient, bankroll: Arc<RwLock<f64>>, config: RedemptionConfig) -> Result<Self> {
+        Ok(Self {
+            db,
+            gamma,
+            bankroll,
+            client: Client::builder().timeout(Duration::from_secs(10)).build()?,
+            config,
+            chain: None,
+        })
+    }
+
+    /// Redeem winning tokens on-chain from `wallet`
+    pub fn with_chain(mut self, rpc: PolygonRpc, wallet: Wallet) -> Self {
+        self.chain = Some(Chain {
+            rpc,
+            wallet,
+            pending: HashMap::new(),
+        });
+        self
+    }
+
+    pub async fn run(mut self) {
+        let interval = Duration::from_secs(self.config.poll_secs);
+        info!(
+            "Redeemer started (every {:?}, {} redemption)",
+            interval,
+            if self.chain.is_some() { "on-chain" } else { "manual" }
+        );
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            if let Err(e) = self.check().await {
+                warn!("Redemption pass failed: {:?}", e);
+            }
+        }
+    }
+
+    async fn check(&mut self) -> Result<()> {
+        // Positions are edited below; start from every fill, queued ones included
+        self.db.flush().await;
+        let mut held: BTreeMap<MarketId, Vec<Position>> = BTreeMap::new();
+        for position in self.db.get_positions().await? {
+            held.entry(position.market_id.clone()).or_default().push(position);
+        }
+        let market_ids: Vec<MarketId> = held.keys().cloned().collect();
+
+        for batch in market_ids.chunks(LOOKUP_BATCH) {
+            for market in self.gamma.get_markets(batch).await? {
+                let (Some(winner), Some(positions)) = (&market.winner, held.get(&market.id)) else {
+                    continue;
+                };
+                if let Err(e) = self.settle(&market, winner, positions).await {
+                    warn!("Failed to settle {} ({}): {:?}", market.id, market.question, e);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    async fn settle(&mut self, market: &Market, winner: &str, positions: &[Position]) -> Result<()> {
+        let holds_winner = positions.iter().any(|p| p.token_id == winner && p.side == Side::Buy);
+        let (status, tx_hash) = match self.chain.as_mut() {
+            Some(chain) if holds_winner => {
+                match chain.redeem(market, positions, self.config.gas_limit).await? {
+                    Some(tx_hash) => (RedemptionStatus::Redeemed, Some(tx_hash)),
+                    None => return Ok(()),
+                }
+            }
+            _ => (RedemptionStatus::Manual, None),
+        };
+
+        let now = Utc::now();
+        let mut realized = 0.0;
+        for position in positions {
+            let won = position.token_id == winner;
+            let exit_price = if won { 1.0 } else { 0.0 };
+            let per_share = match position.side {
+                Side::Buy => exit_price - position.avg_price,
+                Side::Sell => position.avg_price - exit_price,
+            };
+            let pnl = per_share * position.size;
+            let closed = ClosedTrade {
+                id: Uuid::new_v4().to_string(),
+                strategy: position.strategy.clone(),
+                market_id: position.market_id.clone(),
+                token_id: position.token_id.clone(),
+                side: position.side.clone(),
+                size: position.size,
+                entry_price: position.avg_price,
+                exit_price,
+                pnl,
+                closed_at: now,
+            };
+            let redemption = Redemption {
+                market_id: position.market_id.clone(),
+                token_id: position.token_id.clone(),
+                size: position.size,
+                payout: if won && position.side == Side::Buy { position.size } else { 0.0 },
+                pnl,
+                status: if won { status } else { RedemptionStatus::Lost },
+                tx_hash: tx_hash.clone(),
+                settled_at: now,
+            };
+            self.db.insert_closed_trade(&closed).await?;
+            self.db.insert_redemption(&redemption).await?;
+            self.db.delete_position(&position.market_id, &position.token_id).await?;
+            realized += pnl;
+            if redemption.status == RedemptionStatus::Manual {
+                self.notify(&redemption);
+            }
+        }
+        *self.bankroll.write().await += realized;
+        info!(
+            "Settled {} ({}): {} positions, realized {:+.2}, {:?}",
+            market.id,
+            market.question,
+            positions.len(),
+            realized,
+            status
+        );
+        Ok(())
+    }
+
+    fn notify(&self, redemption: &Redemption) {
+        warn!(
+            "REDEEM MANUALLY: {:.2} winning shares of {} in {} (${:.2})",
+            redemption.size, redemption.token_id, redemption.market_id, redemption.payout
+        );
+        for url in &self.config.webhooks {
+            let request = self.client.post(url).json(redemption);
+            tokio::spawn(async move {
+                if let Err(e) = request.send().await.and_then(|r| r.error_for_status()) {
+                    warn!("Redemption webhook failed: {}", e.without_url());
+                }
+            });
+        }
+    }
+}
+
+impl Chain {
+    /// Redeem the market's tokens, returning the confirmed transaction. None while
+    /// the transaction is still pending (checked again next pass) or after it
+    /// reverted (sent again next pass).
+    async fn redeem(&mut self, market: &Market, positions: &[Position], gas_limit: u64) -> Result<Option<String>> {
+        let tx_hash = match self.pending.get(&market.id) {
+            Some(tx_hash) => tx_hash.clone(),
+            None => {
+                let (to, data) = match market.structure {
+                    MarketStructure::Binary | MarketStructure::Categorical => (
+                        polygon::CTF_ADDRESS,
+                        polygon::redeem_positions_call(&market.id, market.outcomes.len())?,
+                    ),
+                    // The adapter redeems exact amounts rather than the whole balance
+                    MarketStructure::NegRisk { .. } => {
+                        let amounts: Vec<u64> = market
+                            .outcomes
+                            .iter()
+                            .map(|o| {
+                                let held: f64 = positions
+                                    .iter()
+                                    .filter(|p| p.token_id == o.token_id && p.side == Side::Buy)
+                                    .map(|p| p.size)
+                                    .sum();
+                                (held * UNITS_PER_SHARE).floor() as u64
+                            })
+                            .collect();
+                        (
+                            polygon::NEG_RISK_ADAPTER_ADDRESS,
+                            polygon::neg_risk_redeem_call(&market.id, &amounts)?,
+                        )
+                    }
+                };
+                let tx_hash = self.rpc.send_transaction(&self.wallet, to, &data, gas_limit).await?;
+                info!("Sent redeem for {} ({}): {}", market.id, market.question, tx_hash);
+                self.pending.insert(market.id.clone(), tx_hash.clone());
+                tx_hash
+            }
+        };
+
+        let deadline = Instant::now() + CONFIRM_TIMEOUT;
+        loop {
+            match self.rpc.receipt_status(&tx_hash).await? {
+                Some(true) => {
+                    self.pending.remove(&market.id);
+                    return Ok(Some(tx_hash));
+                }
+                Some(false) => {
+                    warn!("Redeem {} for {} reverted", tx_hash, market.id);
+                    self.pending.remove(&market.id);
+                    return Ok(None);
+                }
+                None if Instant::now() >= deadline => return Ok(None),
+                None => tokio::time::sleep(CONFIRM_POLL).await,
+            }
+        }
+    }
+}
@@ -0,0 +1,161 @@
+use chrono::Utc;
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+use tokio::sync::{mpsc, oneshot};
+use tracing::{info, warn};
+
+use crate::adapters::database::Database;
+use crate::adapters::gamma::GammaClient;
+use crate::adapters::polymarket::PolymarketClient;
+use crate::bus::EventBus;
+use crate::config::ResolutionMonitorConfig;
+use crate::domain::{MarketData, MarketId, Position, RiskEvent, RiskEventKind, TokenId, UmaStatus};
+use crate::engine::inflight::InflightOrders;
+use crate::engine::liquidation;
+use crate::engine::order_manager::{ManualOrder, SignalOutcome};
+use crate::engine::risk::RiskManager;
+use crate::error::Result;
+
+/// Market IDs per Gamma lookup
+const LOOKUP_BATCH: usize = 50;
+
+/// Watches the oracle resolution of every held market.
+///
+/// Each pass looks up held markets on Gamma and publishes a
+/// `MarketData::Resolution` whenever one moves to a new UMA stage, which blocks
+/// new entries there while it is disputed. A dispute is recorded in the risk log
+/// and, with exits set up, the positions are closed through the order manager
+/// rather than held through a vote that can take days and go either way. Positions
+/// with an order already working are left alone until the next pass.
+pub struct ResolutionMonitor {
+    db: Database,
+    gamma: GammaClient,
+    bus: EventBus,
+    risk: RiskManager,
+    config: ResolutionMonitorConfig,
+    /// Where dispute exits go; None holds through disputes
+    exits: Option<Exits>,
+    /// Last stage seen per held market
+    statuses: HashMap<MarketId, UmaStatus>,
+}
+
+struct Exits {
+    poly_client: PolymarketClient,
+    orders: InflightOrders,
+    manual_orders: mpsc::Sender<ManualOrder>,
+}
+
+impl ResolutionMonitor {
+    pub fn new(
+        db: Database,
+        gamma: GammaClient,
+        bus: EventBus,
+        risk: RiskManager,
+        config: ResolutionMonitorConfig,
+    ) -> Self {
+        Self {
+            db,
+            gamma,
+            bus,
+            risk,
+            config,
+            exits: None,
+            statuses: HashMap::new(),
+        }
+    }
+
+    /// Close positions in disputed markets through the order manager
+    pub fn with_exits(
+        mut self,
+        poly_client: PolymarketClient,
+        orders: InflightOrders,
+        manual_orders: mpsc::Sender<ManualOrder>,
+    ) -> Self {
+        self.exits = Some(Exits {
+            poly_client,
+            orders,
+            manual_orders,
+        });
+        self
+    }
+
+    pub async fn run(mut self) {
+        let interval = Duration::from_secs(self.config.poll_secs);
+        info!(
+            "Resolution monitor started (every {:?}, {} on dispute)",
+            interval,
+            if self.exits.is_some() { "exit" } else { "hold" }
+        );
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            if let Err(e) = self.check().await {
+                warn!("Resolution check failed: {:?}", e);
+            }
+        }
+    }
+
+    async fn check(&mut self) -> Result<()> {
+        let positions = self.db.get_positions().await?;
+        let held: HashSet<MarketId> = positions.iter().map(|p| p.market_id.clone()).collect();
+        self.statuses.retain(|market_id, _| held.contains(market_id));
+        let market_ids: Vec<MarketId> = held.into_iter().collect();
+
+        for batch in market_ids.chunks(LOOKUP_BATCH) {
+            for market in self.gamma.get_markets(batch).await? {
+                let Some(status) = market.uma_status else {
+                    continue;
+                };
+                if self.statuses.insert(market.id.clone(), status) != Some(status) {
+                    self.publish(&market.id, &market.question, status);
+                }
+                if let (UmaStatus::Disputed, Some(exits)) = (status, &self.exits) {
+                    let held: Vec<&Position> = positions.iter().filter(|p| p.market_id == market.id).collect();
+                    exits.close(&held).await?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn publish(&self, market_id: &MarketId, question: &str, status: UmaStatus) {
+        match status {
+            UmaStatus::Disputed => {
+                warn!("Resolution of {} ({}) DISPUTED", market_id, question);
+                self.risk.record_event(RiskEvent::market(
+                    RiskEventKind::Dispute,
+                    market_id,
+                    format!("resolution disputed: {}", question),
+                ));
+            }
+            _ => info!("Resolution of {} ({}) is now {:?}", market_id, question, status),
+        }
+        self.bus.publish(MarketData::Resolution {
+            market_id: market_id.clone(),
+            status,
+            timestamp: Utc::now(),
+        });
+    }
+}
+
+impl Exits {
+    async fn close(&self, positions: &[&Position]) -> Result<()> {
+        let working: HashSet<TokenId> = self.orders.open_orders().into_iter().map(|o| o.token_id).collect();
+        for position in positions.iter().filter(|p| !working.contains(&p.token_id)) {
+            let signal = liquidation::close_signal(&self.poly_client, position).await?;
+            let (reply, outcome) = oneshot::channel();
+            if self.manual_orders.send(ManualOrder { signal, reply }).await.is_err() {
+                return Ok(());
+            }
+            match outcome.await {
+                Ok(SignalOutcome::Submitted { order }) => warn!(
+                    "Exiting disputed market {} / {}: {:.2} shares via order {}",
+                    position.market_id, position.token_id, position.size, order.id
+                ),
+                Ok(outcome) => warn!("Dispute exit for {} not submitted: {:?}", position.token_id, outcome),
+                Err(_) => return Ok(()),
+            }
+        }
+        Ok(())
+    }
+}
@@ -1,27 +1,296 @@
-use eyre::Result;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use tokio::sync::RwLock;
-use tracing::{error, warn};
+use tracing::{error, info, warn};
 
+use crate::adapters::calendar::EconEvents;
+use crate::adapters::database::{Database, Write};
+use crate::bus::EventBus;
+use crate::clock::{self, SharedClock};
 use crate::config::RiskConfig;
-use crate::domain::Signal;
+use crate::domain::{EconEvent, MarketCooldown, MarketId, OrderBook, Position, RiskEvent, RiskEventKind, Side, Signal};
+use crate::engine::positions;
+use crate::error::{Result, RiskError};
+
+/// Which operator-managed market list an entry belongs to
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum MarketList {
+    Allow,
+    Deny,
+}
+
+impl MarketList {
+    fn name(self) -> &'static str {
+        match self {
+            MarketList::Allow => "allow",
+            MarketList::Deny => "deny",
+        }
+    }
+
+    /// Config KV key the list is persisted under, as a JSON array
+    fn key(self) -> &'static str {
+        match self {
+            MarketList::Allow => "market_allowlist",
+            MarketList::Deny => "market_denylist",
+        }
+    }
+}
+
+/// Markets allowed or excluded by hand (disputed resolutions, manipulated books)
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct MarketLists {
+    /// When non-empty, the only markets new entries may open in
+    pub allow: BTreeSet<MarketId>,
+    pub deny: BTreeSet<MarketId>,
+}
+
+impl MarketLists {
+    fn get_mut(&mut self, list: MarketList) -> &mut BTreeSet<MarketId> {
+        match list {
+            MarketList::Allow => &mut self.allow,
+            MarketList::Deny => &mut self.deny,
+        }
+    }
+
+    /// Why new entries on `market_id` are refused, None if they aren't
+    fn blocks(&self, market_id: &MarketId) -> Option<&'static str> {
+        if self.deny.contains(market_id) {
+            Some("market is on the deny list")
+        } else if !self.allow.is_empty() && !self.allow.contains(market_id) {
+            Some("market is not on the allow list")
+        } else {
+            None
+        }
+    }
+}
+
+/// How close the bot is to each risk limit; utilizations are fractions of the limit
+#[derive(Debug, Clone, Serialize)]
+pub struct RiskStatus {
+    pub trading_active: bool,
+    /// Held at boot until the feeds are warm; trading_active reads false meanwhile
+    pub warming_up: bool,
+    pub armed: bool,
+    pub bankroll: f64,
+    pub peak_bankroll: f64,
+    pub min_bankroll: f64,
+    pub drawdown_pct: f64,
+    pub max_drawdown_pct: f64,
+    pub drawdown_utilization: f64,
+    /// Net directional exposure (complementary YES/NO holdings net out)
+    pub exposure: f64,
+    pub max_exposure: f64,
+    pub exposure_utilization: f64,
+    pub open_positions: usize,
+    pub max_open_positions: usize,
+    pub markets: Vec<MarketExposure>,
+    /// Bankroll change since the first PnL snapshot of the UTC day
+    pub daily_pnl: Option<f64>,
+    /// Markets blocked for new entries after a realized loss
+    pub cooldowns: Vec<MarketCooldown>,
+    /// Calendar release currently blocking new entries
+    pub econ_blackout: Option<EconEvent>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MarketExposure {
+    pub market_id: MarketId,
+    pub exposure: f64,
+    /// Share of max_exposure this market alone uses
+    pub utilization: f64,
+}
 
 #[derive(Clone)]
 pub struct RiskManager {
     config: RiskConfig,
     peak_bankroll: Arc<RwLock<f64>>,
     pub trading_active: Arc<AtomicBool>,
+    /// Set from boot until the readiness gate opens; refuses signals like a halt
+    warming_up: Arc<AtomicBool>,
+    /// Order submission is refused until an operator (or the preflight auto-arm) arms it
+    armed: Arc<AtomicBool>,
+    /// Per-market loss cooldowns, mirrored to the database so they survive restarts
+    cooldowns: Arc<RwLock<HashMap<MarketId, MarketCooldown>>>,
+    /// Where risk decisions are recorded; None leaves them to the logs
+    audit: Option<Database>,
+    /// Where risk decisions are published as they're made
+    bus: EventBus,
+    /// Seeded from config, replaced per list by what the config table holds once edited
+    market_lists: Arc<RwLock<MarketLists>>,
+    /// Scheduled releases to black out around; empty without the calendar
+    econ_events: EconEvents,
+    clock: SharedClock,
 }
 
 impl RiskManager {
     pub fn new(config: RiskConfig) -> Self {
         let starting = config.starting_bankroll;
+        let market_lists = MarketLists {
+            allow: config.market_allowlist.iter().cloned().collect(),
+            deny: config.market_denylist.iter().cloned().collect(),
+        };
         Self {
             config,
             peak_bankroll: Arc::new(RwLock::new(starting)),
             trading_active: Arc::new(AtomicBool::new(true)),
+            warming_up: Arc::new(AtomicBool::new(false)),
+            armed: Arc::new(AtomicBool::new(false)),
+            cooldowns: Arc::new(RwLock::new(HashMap::new())),
+            audit: None,
+            bus: EventBus::default(),
+            market_lists: Arc::new(RwLock::new(market_lists)),
+            econ_events: EconEvents::default(),
+            clock: clock::system(),
+        }
+    }
+
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Block new entries around the releases on this calendar
+    pub fn with_calendar(mut self, events: EconEvents) -> Self {
+        self.econ_events = events;
+        self
+    }
+
+    /// Calendar release whose blackout window `now` falls in
+    fn econ_blackout(&self, now: DateTime<Utc>) -> Option<EconEvent> {
+        let (before, after) = (self.config.econ_blackout_before_secs, self.config.econ_blackout_after_secs);
+        if before == 0 && after == 0 {
+            return None;
+        }
+        self.econ_events.read().unwrap().iter().find(|e| e.covers(now, before, after)).cloned()
+    }
+
+    /// Persist risk decisions to the risk_events table
+    pub fn with_audit(mut self, db: Database) -> Self {
+        self.audit = Some(db);
+        self
+    }
+
+    /// Publish risk decisions on `bus`
+    pub fn with_bus(mut self, bus: EventBus) -> Self {
+        self.bus = bus;
+        self
+    }
+
+    /// Publish a risk decision and record it without holding up the caller
+    pub fn record_event(&self, event: RiskEvent) {
+        self.bus.publish(event.clone());
+        let Some(db) = self.audit.clone() else {
+            return;
+        };
+        db.enqueue(Write::RiskEvent(event));
+    }
+
+    /// Restore cooldowns still in force from a previous run
+    pub async fn load_cooldowns(&self, db: &Database) -> Result<usize> {
+        let active = db.get_active_cooldowns(self.clock.now()).await?;
+        let mut cooldowns = self.cooldowns.write().await;
+        for cooldown in active {
+            cooldowns.insert(cooldown.market_id.clone(), cooldown);
+        }
+        Ok(cooldowns.len())
+    }
+
+    /// Replace the configured market lists with any persisted by an earlier edit
+    pub async fn load_market_lists(&self, db: &Database) -> Result<MarketLists> {
+        let mut lists = self.market_lists.write().await;
+        for list in [MarketList::Allow, MarketList::Deny] {
+            if let Some(stored) = db.get_config(list.key()).await? {
+                *lists.get_mut(list) = serde_json::from_str(&stored)?;
+            }
+        }
+        Ok(lists.clone())
+    }
+
+    pub async fn market_lists(&self) -> MarketLists {
+        self.market_lists.read().await.clone()
+    }
+
+    /// Add `market_id` to a list (or remove it when `listed` is false) and persist
+    /// that list; returns both lists after the change
+    pub async fn set_market_listed(
+        &self,
+        db: &Database,
+        list: MarketList,
+        market_id: &MarketId,
+        listed: bool,
+    ) -> Result<MarketLists> {
+        let mut lists = self.market_lists.write().await;
+        let mut updated = lists.get_mut(list).clone();
+        let changed = if listed { updated.insert(market_id.clone()) } else { updated.remove(market_id) };
+        if changed {
+            db.set_config(list.key(), &serde_json::to_string(&updated)?).await?;
+            *lists.get_mut(list) = updated;
+            let action = if listed { "added to" } else { "removed from" };
+            warn!("Market {} {} the {} list", market_id, action, list.name());
+            self.record_event(RiskEvent::market(
+                RiskEventKind::MarketList,
+                market_id,
+                format!("{} the {} list", action, list.name()),
+            ));
+        }
+        Ok(lists.clone())
+    }
+
+    /// Record PnL realized on a market by a confirmed fill; a loss above the
+    /// threshold starts (or extends) that market's cooldown, which is returned
+    pub async fn record_realized(&self, db: &Database, market_id: &MarketId, pnl: f64) -> Option<MarketCooldown> {
+        if self.config.cooldown_loss <= 0.0 || -pnl < self.config.cooldown_loss {
+            return None;
+        }
+        let now = self.clock.now();
+        let cooldown = MarketCooldown {
+            market_id: market_id.clone(),
+            until: now + Duration::seconds(self.config.cooldown_secs as i64),
+            realized_loss: -pnl,
+            started_at: now,
+        };
+        db.enqueue(Write::Cooldown(cooldown.clone()));
+        warn!(
+            "Market {} on cooldown until {} after realized loss ${:.2}",
+            market_id,
+            cooldown.until.format("%Y-%m-%d %H:%M UTC"),
+            -pnl
+        );
+        self.record_event(RiskEvent::market(
+            RiskEventKind::Cooldown,
+            market_id,
+            format!("realized loss ${:.2}, blocked until {}", -pnl, cooldown.until.to_rfc3339()),
+        ));
+        self.cooldowns.write().await.insert(market_id.clone(), cooldown.clone());
+        Some(cooldown)
+    }
+
+    /// Cooldowns still in force, expired ones pruned
+    pub async fn active_cooldowns(&self) -> Vec<MarketCooldown> {
+        let now = self.clock.now();
+        let mut cooldowns = self.cooldowns.write().await;
+        cooldowns.retain(|_, c| c.until > now);
+        let mut active: Vec<MarketCooldown> = cooldowns.values().cloned().collect();
+        active.sort_by_key(|c| c.until);
+        active
+    }
+
+    pub async fn peak_bankroll(&self) -> f64 {
+        *self.peak_bankroll.read().await
+    }
+
+    /// Carry over the peak a previous run reached, so drawdown is still measured
+    /// from it; a peak below the current one is ignored. Returns the peak in force.
+    pub async fn restore_peak(&self, peak: f64) -> f64 {
+        let mut current = self.peak_bankroll.write().await;
+        if peak > *current {
+            *current = peak;
         }
+        *current
     }
 
     /// Update bankroll and check drawdown. Returns false if trading should halt.
@@ -37,7 +306,12 @@ impl RiskManager {
                 "KILL SWITCH: Bankroll ${:.2} below minimum ${:.2}. HALTING ALL TRADING.",
                 current_bankroll, self.config.min_bankroll
             );
-            self.trading_active.store(false, Ordering::SeqCst);
+            if self.trading_active.swap(false, Ordering::SeqCst) {
+                self.record_event(RiskEvent::new(
+                    RiskEventKind::BankrollHalt,
+                    format!("bankroll ${:.2} below minimum ${:.2}", current_bankroll, self.config.min_bankroll),
+                ));
+            }
             return false;
         }
 
@@ -51,61 +325,252 @@ impl RiskManager {
                 *peak,
                 current_bankroll
             );
-            self.trading_active.store(false, Ordering::SeqCst);
+            if self.trading_active.swap(false, Ordering::SeqCst) {
+                self.record_event(RiskEvent::new(
+                    RiskEventKind::DrawdownHalt,
+                    format!(
+                        "{:.1}% drawdown exceeds {:.1}% limit (peak ${:.2}, current ${:.2})",
+                        drawdown * 100.0,
+                        self.config.max_drawdown_pct * 100.0,
+                        *peak,
+                        current_bankroll
+                    ),
+                ));
+            }
             return false;
         }
 
         true
     }
 
-    /// Check if a signal passes risk checks
-    pub async fn check_signal(&self, signal: &Signal, current_bankroll: f64, total_exposure: f64) -> Result<bool> {
+    /// Check a signal against the risk limits: the limit it fails, if any
+    pub async fn check_signal(
+        &self,
+        signal: &Signal,
+        current_bankroll: f64,
+        positions: &[Position],
+    ) -> Result<(), RiskError> {
+        if self.is_warming_up() {
+            info!("Warming up — rejecting signal for {}", signal.market_id);
+            return Err(RiskError::WarmingUp);
+        }
         if !self.trading_active.load(Ordering::SeqCst) {
             warn!("Trading halted — rejecting signal for {}", signal.market_id);
-            return Ok(false);
+            return Err(RiskError::Halted);
         }
 
         // Bankroll minimum
         if current_bankroll < self.config.min_bankroll {
             warn!("Bankroll ${:.2} below minimum — rejecting", current_bankroll);
-            return Ok(false);
+            return Err(RiskError::BankrollBelowMinimum { bankroll: current_bankroll });
+        }
+
+        // Market lists and loss cooldown — no new entries, but exits still go through
+        let token_id = signal.token_id.as_deref().unwrap_or(&signal.market_id);
+        let reduces = positions.iter().any(|p| p.token_id == token_id && p.side != signal.side);
+        if !reduces {
+            if let Some(reason) = self.market_lists.read().await.blocks(&signal.market_id) {
+                warn!("{}: {} — rejecting", signal.market_id, reason);
+                return Err(RiskError::MarketBlocked(reason.into()));
+            }
+            if let Some(event) = self.econ_blackout(self.clock.now()) {
+                warn!(
+                    "{} blackout ({} at {}) — rejecting {}",
+                    event.country,
+                    event.name,
+                    event.scheduled_at.format("%H:%M UTC"),
+                    signal.market_id
+                );
+                return Err(RiskError::Blackout {
+                    event: event.name.clone(),
+                    at: event.scheduled_at.to_rfc3339(),
+                });
+            }
+            if let Some(cooldown) = self.cooldowns.read().await.get(&signal.market_id) {
+                if cooldown.until > self.clock.now() {
+                    warn!(
+                        "{} on cooldown until {} after ${:.2} loss — rejecting",
+                        signal.market_id,
+                        cooldown.until.format("%H:%M UTC"),
+                        cooldown.realized_loss
+                    );
+                    return Err(RiskError::Cooldown { until: cooldown.until.to_rfc3339() });
+                }
+            }
         }
 
-        // Position size check
+        // Position size check — skipped for exits, so a position that grew past the
+        // cap can still be closed or cut
         let max_position = current_bankroll * self.config.max_position_pct;
-        if signal.size * signal.price > max_position {
+        if !reduces && signal.size * signal.price > max_position {
             warn!(
                 "Signal size ${:.2} exceeds max position ${:.2} — rejecting",
                 signal.size * signal.price,
                 max_position
             );
-            return Ok(false);
+            return Err(RiskError::PositionSize {
+                size: signal.size * signal.price,
+                max: max_position,
+            });
         }
 
-        // Total exposure check
-        let new_exposure = total_exposure + (signal.size * signal.price);
-        if new_exposure > self.config.max_exposure {
+        // Total exposure check — complementary YES/NO holdings net out, so a leg that
+        // offsets one already held goes through even at the limit
+        let total_exposure = positions::net_exposure(positions);
+        let new_exposure = positions::net_exposure_after(positions, signal);
+        if new_exposure > self.config.max_exposure && new_exposure > total_exposure {
             warn!(
                 "Total exposure ${:.2} would exceed max ${:.2} — rejecting",
                 new_exposure, self.config.max_exposure
             );
-            return Ok(false);
+            return Err(RiskError::Exposure {
+                exposure: new_exposure,
+                max: self.config.max_exposure,
+            });
+        }
+
+        // Concurrent position count checks — adding to an existing position doesn't open a new one
+        let adds_to_existing = positions.iter().any(|p| p.market_id == signal.market_id);
+        if !adds_to_existing {
+            if positions.len() >= self.config.max_open_positions {
+                warn!(
+                    "{} open positions at max {} — rejecting",
+                    positions.len(),
+                    self.config.max_open_positions
+                );
+                return Err(RiskError::OpenPositions { open: positions.len() });
+            }
+
+            let strategy_positions = positions.iter().filter(|p| p.strategy == signal.strategy).count();
+            if strategy_positions >= self.config.max_positions_per_strategy {
+                warn!(
+                    "{} has {} open positions at max {} — rejecting",
+                    signal.strategy, strategy_positions, self.config.max_positions_per_strategy
+                );
+                return Err(RiskError::StrategyPositions { open: strategy_positions });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Largest order on `side` the visible book supports at limit `price`: the
+    /// configured share of the opposite-side depth at or better than it. None when
+    /// uncapped — the check is disabled or the order doesn't cross and will rest.
+    pub fn liquidity_limit(&self, side: &Side, price: f64, book: &OrderBook) -> Option<f64> {
+        if self.config.max_book_share <= 0.0 {
+            return None;
         }
+        let depth: f64 = match side {
+            Side::Buy => book.asks.iter().filter(|l| l.price <= price).map(|l| l.size).sum(),
+            Side::Sell => book.bids.iter().filter(|l| l.price >= price).map(|l| l.size).sum(),
+        };
+        (depth > 0.0).then_some(depth * self.config.max_book_share)
+    }
+
+    pub async fn status(&self, current_bankroll: f64, positions: &[Position], day_start_bankroll: Option<f64>) -> RiskStatus {
+        let peak = *self.peak_bankroll.read().await;
+        let drawdown = if peak > 0.0 { ((peak - current_bankroll) / peak).max(0.0) } else { 0.0 };
+        let utilization = |v: f64, max: f64| if max > 0.0 { v / max } else { 0.0 };
+        let markets: Vec<MarketExposure> = positions::net_positions(positions)
+            .into_iter()
+            .map(|n| MarketExposure {
+                utilization: utilization(n.net_exposure, self.config.max_exposure),
+                market_id: n.market_id,
+                exposure: n.net_exposure,
+            })
+            .collect();
+        let exposure = markets.iter().fold(0.0, |total, m| total + m.exposure);
 
-        Ok(true)
+        RiskStatus {
+            trading_active: self.is_active(),
+            warming_up: self.is_warming_up(),
+            armed: self.is_armed(),
+            bankroll: current_bankroll,
+            peak_bankroll: peak,
+            min_bankroll: self.config.min_bankroll,
+            drawdown_pct: drawdown,
+            max_drawdown_pct: self.config.max_drawdown_pct,
+            drawdown_utilization: utilization(drawdown, self.config.max_drawdown_pct),
+            exposure,
+            max_exposure: self.config.max_exposure,
+            exposure_utilization: utilization(exposure, self.config.max_exposure),
+            open_positions: positions.len(),
+            max_open_positions: self.config.max_open_positions,
+            markets,
+            daily_pnl: day_start_bankroll.map(|start| current_bankroll - start),
+            cooldowns: self.active_cooldowns().await,
+            econ_blackout: self.econ_blackout(self.clock.now()),
+        }
     }
 
+    /// Neither halted nor still warming up
     pub fn is_active(&self) -> bool {
-        self.trading_active.load(Ordering::SeqCst)
+        self.trading_active.load(Ordering::SeqCst) && !self.is_warming_up()
+    }
+
+    pub fn is_warming_up(&self) -> bool {
+        self.warming_up.load(Ordering::SeqCst)
+    }
+
+    /// Refuse every signal until `warmed_up`, while the feeds fill in the context
+    pub fn begin_warm_up(&self) {
+        info!("Trading held until the feeds are warm");
+        self.warming_up.store(true, Ordering::SeqCst);
+    }
+
+    pub fn warmed_up(&self, detail: &str) {
+        if self.warming_up.swap(false, Ordering::SeqCst) {
+            warn!("Warm-up over ({}), trading enabled", detail);
+            self.record_event(RiskEvent::new(RiskEventKind::WarmUp, detail));
+        }
+    }
+
+    pub fn is_armed(&self) -> bool {
+        self.armed.load(Ordering::SeqCst)
+    }
+
+    pub fn arm(&self) {
+        warn!("Order submission ARMED");
+        self.armed.store(true, Ordering::SeqCst);
+        self.record_event(RiskEvent::new(RiskEventKind::Arm, "order submission armed"));
+    }
+
+    pub fn disarm(&self) {
+        warn!("Order submission disarmed");
+        self.armed.store(false, Ordering::SeqCst);
+        self.record_event(RiskEvent::new(RiskEventKind::Disarm, "order submission disarmed"));
     }
 
     pub fn kill(&self) {
         error!("MANUAL KILL SWITCH ACTIVATED");
         self.trading_active.store(false, Ordering::SeqCst);
+        self.record_event(RiskEvent::new(RiskEventKind::Kill, "manual kill switch"));
+    }
+
+    /// Stop trading because supervised tasks the bot can't trade safely without are down
+    pub fn halt_for_tasks(&self, tasks: &[String]) {
+        error!("WATCHDOG HALT: critical tasks down ({}). HALTING ALL TRADING.", tasks.join(", "));
+        if self.trading_active.swap(false, Ordering::SeqCst) {
+            self.record_event(RiskEvent::new(
+                RiskEventKind::TaskHalt,
+                format!("critical tasks down: {}", tasks.join(", ")),
+            ));
+        }
+    }
+
+    /// Stop trading because the venue refused the bot's credentials; retrying
+    /// won't help until they're fixed, so this holds until a manual resume
+    pub fn halt_for_credentials(&self, detail: &str) {
+        error!("AUTH HALT: credentials refused ({}). HALTING ALL TRADING.", detail);
+        if self.trading_active.swap(false, Ordering::SeqCst) {
+            self.record_event(RiskEvent::new(RiskEventKind::AuthHalt, detail));
+        }
     }
 
     pub fn resume(&self) {
         warn!("Trading resumed manually");
         self.trading_active.store(true, Ordering::SeqCst);
+        self.record_event(RiskEvent::new(RiskEventKind::Resume, "trading resumed manually"));
     }
 }
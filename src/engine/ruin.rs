@@ -0,0 +1,183 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::adapters::database::Database;
+use crate::config::{Config, RuinConfig};
+use crate::domain::ClosedTrade;
+use crate::engine::walk_forward::SplitMix64;
+use crate::error::Result;
+
+/// Stake fractions tried when searching for the growth-optimal one
+const KELLY_STEPS: usize = 200;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RuinReport {
+    pub generated_at: DateTime<Utc>,
+    /// Closed trades whose returns were resampled
+    pub trades: usize,
+    /// Mean return on the stake per trade
+    pub mean_return: f64,
+    pub bankroll: f64,
+    /// Bankroll at which the risk manager stops trading: ruin, for these purposes
+    pub min_bankroll: f64,
+    pub paths: usize,
+    pub horizon: usize,
+    /// Stake fraction that maximizes expected log growth over the sampled
+    /// returns; None when no stake grows the bankroll
+    pub full_kelly: Option<f64>,
+    pub scenarios: Vec<RuinScenario>,
+}
+
+/// Outcome of staking one fixed fraction of the bankroll on every trade
+#[derive(Debug, Clone, Serialize)]
+pub struct RuinScenario {
+    pub position_pct: f64,
+    /// `position_pct` as a multiple of full Kelly
+    pub kelly_multiple: Option<f64>,
+    /// Share of paths that fell below `min_bankroll`
+    pub ruin_probability: f64,
+    /// Bankroll at the end of the horizon (or at ruin): 5th, 50th and 95th percentiles
+    pub final_p5: f64,
+    pub final_median: f64,
+    pub final_p95: f64,
+    /// Largest peak-to-trough fall along a path, as a fraction of the peak
+    pub max_drawdown_median: f64,
+    pub max_drawdown_p95: f64,
+}
+
+/// Return on the stake of each closed trade: PnL over what the position cost
+pub fn returns(trades: &[ClosedTrade]) -> Vec<f64> {
+    trades
+        .iter()
+        .filter(|t| t.entry_price * t.size > 0.0)
+        .map(|t| t.pnl / (t.entry_price * t.size))
+        .collect()
+}
+
+/// The stake fraction in (0, 1] with the highest mean log growth over `returns`,
+/// if any grows at all. Fractions at which one sampled loss would wipe out the
+/// bankroll are never chosen.
+pub fn full_kelly(returns: &[f64]) -> Option<f64> {
+    if returns.is_empty() {
+        return None;
+    }
+    (1..=KELLY_STEPS)
+        .map(|i| i as f64 / KELLY_STEPS as f64)
+        .filter(|f| returns.iter().all(|r| 1.0 + f * r > 0.0))
+        .map(|f| (f, returns.iter().map(|r| (1.0 + f * r).ln()).sum::<f64>() / returns.len() as f64))
+        .filter(|(_, growth)| *growth > 0.0)
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(f, _)| f)
+}
+
+/// Bootstrap `config.paths` bankroll paths of `config.horizon` trades for each
+/// stake fraction in `sizes`, drawing trade returns from `returns` with
+/// replacement. Every sizing is run on the same draws, so they differ only in
+/// how much is staked.
+pub fn simulate(returns: &[f64], bankroll: f64, min_bankroll: f64, sizes: &[f64], config: &RuinConfig) -> Vec<RuinScenario> {
+    if returns.is_empty() || config.paths == 0 {
+        return Vec::new();
+    }
+    let kelly = full_kelly(returns);
+    sizes
+        .iter()
+        .map(|&position_pct| {
+            let mut rng = SplitMix64(config.seed);
+            let mut finals = Vec::with_capacity(config.paths);
+            let mut drawdowns = Vec::with_capacity(config.paths);
+            let mut ruined = 0;
+            for _ in 0..config.paths {
+                let mut balance = bankroll;
+                let mut peak = bankroll;
+                let mut drawdown = 0.0_f64;
+                let mut ruin = balance < min_bankroll;
+                for _ in 0..config.horizon {
+                    if ruin {
+                        break;
+                    }
+                    let r = returns[(rng.next_u64() % returns.len() as u64) as usize];
+                    balance += balance * position_pct * r;
+                    peak = peak.max(balance);
+                    drawdown = drawdown.max((peak - balance) / peak);
+                    ruin = balance < min_bankroll;
+                }
+                ruined += usize::from(ruin);
+                finals.push(balance);
+                drawdowns.push(drawdown);
+            }
+            finals.sort_by(f64::total_cmp);
+            drawdowns.sort_by(f64::total_cmp);
+            RuinScenario {
+                position_pct,
+                kelly_multiple: kelly.map(|k| position_pct / k),
+                ruin_probability: ruined as f64 / config.paths as f64,
+                final_p5: percentile(&finals, 0.05),
+                final_median: percentile(&finals, 0.5),
+                final_p95: percentile(&finals, 0.95),
+                max_drawdown_median: percentile(&drawdowns, 0.5),
+                max_drawdown_p95: percentile(&drawdowns, 0.95),
+            }
+        })
+        .collect()
+}
+
+/// Nearest-rank percentile of sorted values
+fn percentile(sorted: &[f64], q: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let rank = (q * (sorted.len() - 1) as f64).round() as usize;
+    sorted[rank.min(sorted.len() - 1)]
+}
+
+/// Risk of ruin from `bankroll` over the closed trades in `ruin.lookback_days`,
+/// optionally of one strategy only. Without `sizes`, the configured
+/// max_position_pct is compared with the allocator's fractional Kelly stake.
+pub async fn report(
+    db: &Database,
+    config: &Config,
+    ruin: RuinConfig,
+    bankroll: f64,
+    sizes: Option<Vec<f64>>,
+    strategy: Option<&str>,
+) -> Result<RuinReport> {
+    let since = if ruin.lookback_days > 0 {
+        Utc::now() - chrono::Duration::days(ruin.lookback_days)
+    } else {
+        DateTime::UNIX_EPOCH
+    };
+    let trades: Vec<ClosedTrade> = db
+        .get_closed_trades(since)
+        .await?
+        .into_iter()
+        .filter(|t| strategy.is_none_or(|s| t.strategy == s))
+        .collect();
+    let returns = returns(&trades);
+    let kelly = full_kelly(&returns);
+    let sizes = sizes.unwrap_or_else(|| {
+        let mut sizes = vec![config.risk.max_position_pct];
+        if let Some(kelly) = kelly {
+            sizes.push(kelly * config.allocator.kelly_fraction);
+        }
+        sizes
+    });
+
+    let min_bankroll = config.risk.min_bankroll;
+    let (paths, horizon) = (ruin.paths, ruin.horizon);
+    let mean_return = returns.iter().sum::<f64>() / returns.len().max(1) as f64;
+    let trades = returns.len();
+    // Tens of thousands of paths take a while; keep them off the async workers
+    let scenarios = tokio::task::spawn_blocking(move || simulate(&returns, bankroll, min_bankroll, &sizes, &ruin)).await?;
+
+    Ok(RuinReport {
+        generated_at: Utc::now(),
+        trades,
+        mean_return,
+        bankroll,
+        min_bankroll,
+        paths,
+        horizon,
+        full_kelly: kelly,
+        scenarios,
+    })
+}
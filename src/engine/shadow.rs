@@ -0,0 +1,163 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashSet};
+use std::sync::Mutex;
+use tracing::info;
+use uuid::Uuid;
+
+use crate::adapters::database::{Database, Write};
+use crate::domain::{Execution, Side, Signal, SignalRecord, TokenId};
+use crate::engine::order_manager::{slippage_cap, sweep};
+use crate::error::Result;
+use crate::strategy::{Intent, StrategyContext};
+
+/// Paper execution for strategies in shadow mode.
+///
+/// Their signals never reach the order manager. Each one is filled in simulation
+/// against the live book, the way the order manager would price it: marketable
+/// orders take what rests within the slippage cap at the last level swept,
+/// passive ones that don't cross are assumed filled at their limit. The fill is
+/// recorded as a shadow signal record, which calibration resolves like any other,
+/// giving hypothetical PnL per strategy. A strategy sees no positions from its
+/// shadow fills, so repeats of an entry are recorded once while it is unresolved.
+pub struct ShadowBook {
+    db: Database,
+    strategies: HashSet<String>,
+    max_slippage_bps: f64,
+    /// (strategy, token, side) of unresolved shadow entries
+    held: Mutex<HashSet<(String, TokenId, Side)>>,
+}
+
+impl ShadowBook {
+    pub async fn load(db: Database, strategies: Vec<String>, max_slippage_bps: f64) -> Result<Self> {
+        let held = db
+            .get_unresolved_signal_records()
+            .await?
+            .into_iter()
+            .filter(|r| r.shadow)
+            .map(|r| (r.strategy, r.token_id, r.side))
+            .collect();
+        Ok(Self {
+            db,
+            strategies: strategies.into_iter().collect(),
+            max_slippage_bps,
+            held: Mutex::new(held),
+        })
+    }
+
+    pub fn covers(&self, strategy: &str) -> bool {
+        self.strategies.contains(strategy)
+    }
+
+    /// Simulate an intent from a shadow strategy; only entries are filled
+    pub fn record(&self, intent: &Intent, ctx: &StrategyContext) {
+        let signals = match intent {
+            Intent::Order(signal) => std::slice::from_ref(signal),
+            Intent::Legs(signals) => signals.as_slice(),
+            other => {
+                info!("Shadow {} intent not simulated: {:?}", other.strategy(), other);
+                return;
+            }
+        };
+        for signal in signals {
+            self.fill(signal, ctx);
+        }
+    }
+
+    fn fill(&self, signal: &Signal, ctx: &StrategyContext) {
+        let Some(token_id) = signal.token_id.clone().or_else(|| {
+            ctx.markets
+                .get(&signal.market_id)?
+                .outcome_token("yes")
+                .cloned()
+        }) else {
+            return;
+        };
+        let key = (signal.strategy.clone(), token_id.clone(), signal.side.clone());
+        if self.held.lock().unwrap().contains(&key) {
+            return;
+        }
+
+        let cap = slippage_cap(&signal.side, signal.price, self.max_slippage_bps);
+        let swept = ctx
+            .orderbooks
+            .get(&token_id)
+            .and_then(|book| sweep(book, &signal.side, signal.size, cap));
+        let (price, size) = match (swept, &signal.execution) {
+            (Some(fill), _) => fill,
+            (None, Execution::Passive) => (signal.price, signal.size),
+            (None, Execution::Take) => {
+                info!("Shadow {} signal on {}: nothing to take within {:.4}", signal.strategy, token_id, cap);
+                return;
+            }
+        };
+
+        let record = SignalRecord {
+            id: Uuid::new_v4().to_string(),
+            strategy: signal.strategy.clone(),
+            market_id: signal.market_id.clone(),
+            token_id,
+            side: signal.side.clone(),
+            confidence: signal.confidence,
+            price,
+            created_at: ctx.now,
+            outcome: None,
+            resolved_at: None,
+            size: Some(size),
+            shadow: true,
+        };
+        info!(
+            "Shadow fill: {} {} {:.2}@{:.4} on {}",
+            record.strategy, record.side, size, price, record.token_id
+        );
+        self.db.enqueue(Write::SignalRecord(record));
+        self.held.lock().unwrap().insert(key);
+    }
+}
+
+/// How one shadow strategy would have done
+#[derive(Debug, Clone, Serialize)]
+pub struct ShadowReport {
+    pub strategy: String,
+    pub signals: usize,
+    pub resolved: usize,
+    pub wins: usize,
+    /// PnL of resolved fills, before fees
+    pub pnl: f64,
+    /// Cost of fills still waiting on their market
+    pub open_notional: f64,
+    pub first_signal: Option<DateTime<Utc>>,
+}
+
+/// Per-strategy results of shadow records; live records are ignored
+pub fn report(records: &[SignalRecord]) -> Vec<ShadowReport> {
+    tally(records.iter().filter(|r| r.shadow))
+}
+
+/// Per-strategy results of `records`, live or shadow, each scored at its recorded
+/// price and size
+pub fn tally<'a>(records: impl IntoIterator<Item = &'a SignalRecord>) -> Vec<ShadowReport> {
+    let mut by_strategy: BTreeMap<&str, ShadowReport> = BTreeMap::new();
+    for record in records {
+        let report = by_strategy.entry(record.strategy.as_str()).or_insert_with(|| ShadowReport {
+            strategy: record.strategy.clone(),
+            signals: 0,
+            resolved: 0,
+            wins: 0,
+            pnl: 0.0,
+            open_notional: 0.0,
+            first_signal: None,
+        });
+        report.signals += 1;
+        report.first_signal = Some(report.first_signal.map_or(record.created_at, |t| t.min(record.created_at)));
+        match record.pnl() {
+            Some(pnl) => {
+                report.resolved += 1;
+                report.wins += usize::from(pnl > 0.0);
+                report.pnl += pnl;
+            }
+            None => report.open_notional += record.price * record.size.unwrap_or_default(),
+        }
+    }
+    by_strategy.into_values().collect()
+}
@@ -0,0 +1,140 @@
+use chrono::Utc;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::RwLock;
+use tracing::{info, warn};
+
+use crate::adapters::database::Database;
+use crate::domain::{MarketId, OrderBook, PnlSnapshot, Position, Side};
+use crate::engine::inflight::InflightOrders;
+use crate::engine::positions;
+use crate::engine::risk::RiskManager;
+use crate::error::Result;
+use crate::feeds::BookCache;
+
+/// PnL split into what is locked in and what is still open
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct PnlBreakdown {
+    /// Closed trades to date, net of fees
+    pub realized: f64,
+    /// Open positions marked to market
+    pub unrealized: f64,
+}
+
+impl PnlBreakdown {
+    pub fn total(&self) -> f64 {
+        self.realized + self.unrealized
+    }
+}
+
+/// Realized PnL from the closed-trade ledger plus open positions marked against
+/// `orderbooks`; see `mark_price` for positions without a cached book
+pub async fn pnl_breakdown(db: &Database, orderbooks: &BookCache) -> Result<PnlBreakdown> {
+    let realized = db.get_realized_pnl().await?;
+    let positions = db.get_positions().await?;
+    let books = orderbooks.snapshot();
+    let unrealized = positions
+        .iter()
+        .map(|p| {
+            let mark = mark_price(p, books.get(&p.token_id).map(AsRef::as_ref));
+            match p.side {
+                Side::Buy => (mark - p.avg_price) * p.size,
+                Side::Sell => (p.avg_price - mark) * p.size,
+            }
+        })
+        .sum();
+    Ok(PnlBreakdown { realized, unrealized })
+}
+
+/// Book midpoint, else the last fill price, else cost (no unrealized PnL)
+fn mark_price(position: &Position, book: Option<&OrderBook>) -> f64 {
+    book.and_then(OrderBook::midpoint)
+        .or((position.current_price > 0.0).then_some(position.current_price))
+        .unwrap_or(position.avg_price)
+}
+
+/// Records PnL points (with an exposure reading alongside) for the equity curve.
+///
+/// Points are taken on a fixed cadence, by the order manager right after a fill
+/// realizes PnL, and on demand from the API, so the curve shows closes when they
+/// happen rather than at the next tick.
+#[derive(Clone)]
+pub struct PnlSnapshotter {
+    db: Database,
+    bankroll: Arc<RwLock<f64>>,
+    risk: RiskManager,
+    orders: InflightOrders,
+    /// Market ID → underlying for exposure grouping; see `positions::exposure_snapshot`
+    underlyings: Arc<HashMap<MarketId, String>>,
+    /// Books open positions are marked against
+    orderbooks: BookCache,
+}
+
+impl PnlSnapshotter {
+    pub fn new(db: Database, bankroll: Arc<RwLock<f64>>, risk: RiskManager, orders: InflightOrders) -> Self {
+        Self {
+            db,
+            bankroll,
+            risk,
+            orders,
+            underlyings: Arc::new(HashMap::new()),
+            orderbooks: BookCache::default(),
+        }
+    }
+
+    pub fn with_underlyings(mut self, underlyings: HashMap<MarketId, String>) -> Self {
+        self.underlyings = Arc::new(underlyings);
+        self
+    }
+
+    pub fn with_orderbooks(mut self, orderbooks: BookCache) -> Self {
+        self.orderbooks = orderbooks;
+        self
+    }
+
+    pub async fn breakdown(&self) -> Result<PnlBreakdown> {
+        pnl_breakdown(&self.db, &self.orderbooks).await
+    }
+
+    /// Record a PnL point for the current bankroll, and the exposure at that moment.
+    /// Queued fills and closes are written first so the point includes them.
+    pub async fn snapshot(&self) -> Result<PnlSnapshot> {
+        self.db.flush().await;
+        let bankroll = *self.bankroll.read().await;
+        self.risk.update_bankroll(bankroll).await;
+        let pnl = self.breakdown().await?;
+        let snapshot = PnlSnapshot {
+            timestamp: Utc::now(),
+            bankroll,
+            pnl_total: pnl.total(),
+            realized_pnl: pnl.realized,
+            unrealized_pnl: pnl.unrealized,
+        };
+        self.db.record_pnl_snapshot(&snapshot).await?;
+
+        match self.db.get_positions().await {
+            Ok(positions) => {
+                let exposure =
+                    positions::exposure_snapshot(&positions, &self.orders.open_orders(), bankroll, &self.underlyings);
+                if let Err(e) = self.db.record_exposure_snapshot(&exposure).await {
+                    warn!("Exposure snapshot failed: {:?}", e);
+                }
+            }
+            Err(e) => warn!("Exposure snapshot skipped: {:?}", e),
+        }
+        Ok(snapshot)
+    }
+
+    pub async fn run(self, interval: Duration) {
+        info!("PnL snapshots every {:?}", interval);
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            if let Err(e) = self.snapshot().await {
+                warn!("PnL snapshot failed: {:?}", e);
+            }
+        }
+    }
+}
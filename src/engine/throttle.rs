@@ -0,0 +1,107 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::clock::{self, SharedClock};
+use crate::config::ExecutionConfig;
+use crate::domain::MarketId;
+use crate::engine::checkpoint::RecentFill;
+
+const RATE_WINDOW: Duration = Duration::from_secs(60);
+
+/// Per-strategy signal rate limit and post-fill market cooldown.
+///
+/// A price flapping around a strategy's threshold re-fires the same signal on every
+/// tick; once the duplicate guard clears (the order filled or was cancelled) each of
+/// those would be a fresh order. This holds each strategy to a signal budget per
+/// minute and keeps it out of a market for a while after it filled there.
+pub struct StrategyThrottle {
+    config: ExecutionConfig,
+    /// Strategy → when its admitted signals in the last minute went through
+    recent: Mutex<HashMap<String, VecDeque<Instant>>>,
+    /// (strategy, market) → last fill
+    last_fill: Mutex<HashMap<(String, MarketId), Instant>>,
+    clock: SharedClock,
+}
+
+impl StrategyThrottle {
+    pub fn new(config: ExecutionConfig) -> Self {
+        Self {
+            config,
+            recent: Mutex::new(HashMap::new()),
+            last_fill: Mutex::new(HashMap::new()),
+            clock: clock::system(),
+        }
+    }
+
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Why a new entry from `strategy` on `market_id` has to wait, or None after
+    /// counting it against the strategy's budget
+    pub fn admit(&self, strategy: &str, market_id: &MarketId) -> Option<String> {
+        let now = self.clock.instant();
+        if let Some(cooldown) = self.config.fill_cooldown(strategy) {
+            let key = (strategy.to_string(), market_id.clone());
+            if let Some(filled) = self.last_fill.lock().unwrap().get(&key) {
+                let since = now.duration_since(*filled);
+                if since < cooldown {
+                    return Some(format!("filled {}s ago, cooldown {}s", since.as_secs(), cooldown.as_secs()));
+                }
+            }
+        }
+
+        if let Some(limit) = self.config.signal_rate_limit(strategy) {
+            let mut recent = self.recent.lock().unwrap();
+            let window = recent.entry(strategy.to_string()).or_default();
+            while window.front().is_some_and(|t| now.duration_since(*t) >= RATE_WINDOW) {
+                window.pop_front();
+            }
+            if window.len() >= limit as usize {
+                return Some(format!("{} signals in the last minute", window.len()));
+            }
+            window.push_back(now);
+        }
+        None
+    }
+
+    pub fn record_fill(&self, strategy: &str, market_id: &MarketId) {
+        self.last_fill
+            .lock()
+            .unwrap()
+            .insert((strategy.to_string(), market_id.clone()), self.clock.instant());
+    }
+
+    /// Fills whose cooldown is still running, stamped with the time they happened
+    pub fn recent_fills(&self) -> Vec<RecentFill> {
+        let (now, instant) = (self.clock.now(), self.clock.instant());
+        self.last_fill
+            .lock()
+            .unwrap()
+            .iter()
+            .filter_map(|((strategy, market_id), filled)| {
+                let since = instant.duration_since(*filled);
+                (since < self.config.fill_cooldown(strategy)?).then(|| RecentFill {
+                    strategy: strategy.clone(),
+                    market_id: market_id.clone(),
+                    at: now - chrono::Duration::from_std(since).unwrap_or_default(),
+                })
+            })
+            .collect()
+    }
+
+    /// Take over fills recorded by a previous run, so their cooldowns carry on
+    /// across the restart
+    pub fn restore_fills(&self, fills: &[RecentFill]) {
+        let (now, instant) = (self.clock.now(), self.clock.instant());
+        let mut last_fill = self.last_fill.lock().unwrap();
+        for fill in fills {
+            let since = (now - fill.at).to_std().unwrap_or_default();
+            if let Some(filled) = instant.checked_sub(since) {
+                last_fill.insert((fill.strategy.clone(), fill.market_id.clone()), filled);
+            }
+        }
+    }
+}
@@ -0,0 +1,332 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+use crate::config::{Config, WalkForwardConfig};
+use crate::domain::{Market, MarketData};
+use crate::engine::backtest::{BacktestResult, Backtester};
+use crate::error::{Error, Result};
+use crate::strategy::latency_arb::LatencyArbStrategy;
+use crate::strategy::mean_reversion::MeanReversionStrategy;
+use crate::strategy::Strategy;
+
+/// Points a `lo..hi` range is split into on the grid when no `:n` is given
+const DEFAULT_STEPS: usize = 5;
+/// Candidates listed in the report's ranking
+const RANKING_LEN: usize = 20;
+
+/// One candidate: parameter name → value
+pub type Params = BTreeMap<String, f64>;
+
+/// Where one parameter is searched
+#[derive(Debug, Clone)]
+enum Space {
+    Values(Vec<f64>),
+    Range { lo: f64, hi: f64, steps: usize },
+}
+
+impl Space {
+    fn points(&self) -> Vec<f64> {
+        match self {
+            Space::Values(values) => values.clone(),
+            Space::Range { lo, steps: 1, .. } => vec![*lo],
+            Space::Range { lo, hi, steps } => (0..*steps)
+                .map(|i| lo + (hi - lo) * i as f64 / (*steps - 1) as f64)
+                .collect(),
+        }
+    }
+
+    fn draw(&self, rng: &mut SplitMix64) -> f64 {
+        match self {
+            Space::Values(values) => values[(rng.next_u64() % values.len() as u64) as usize],
+            Space::Range { lo, hi, .. } => lo + (hi - lo) * rng.unit(),
+        }
+    }
+}
+
+/// The parameters a walk-forward search varies, and over what
+#[derive(Debug, Clone)]
+pub struct ParamGrid {
+    params: Vec<(String, Space)>,
+}
+
+impl ParamGrid {
+    /// `min_edge_pct=0.01..0.03:5,max_position_pct=0.02|0.05`: each parameter is
+    /// either a `lo..hi` range (split into `:n` grid points, 5 by default) or a
+    /// `|`-separated list of values
+    pub fn parse(spec: &str) -> Result<Self> {
+        let mut params = Vec::new();
+        for entry in spec.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+            let (name, space) = entry
+                .split_once('=')
+                .ok_or_else(|| Error::invalid(format!("grid entry '{}' is not param=values", entry)))?;
+            let number = |s: &str| {
+                s.trim()
+                    .parse::<f64>()
+                    .map_err(|_| Error::invalid(format!("grid entry '{}' has a non-numeric value '{}'", entry, s)))
+            };
+            let space = match space.split_once("..") {
+                Some((lo, rest)) => {
+                    let (hi, steps) = match rest.split_once(':') {
+                        Some((hi, steps)) => (hi, steps.trim().parse().map_err(|_| Error::invalid(format!("bad step count in '{}'", entry)))?),
+                        None => (rest, DEFAULT_STEPS),
+                    };
+                    let (lo, hi) = (number(lo)?, number(hi)?);
+                    if hi < lo || steps == 0 {
+                        return Err(Error::invalid(format!("grid range '{}' is empty", entry)));
+                    }
+                    Space::Range { lo, hi, steps }
+                }
+                None => Space::Values(space.split('|').map(number).collect::<Result<_>>()?),
+            };
+            params.push((name.trim().to_string(), space));
+        }
+        if params.is_empty() {
+            return Err(Error::invalid(format!("no parameters to search in '{}'", spec)));
+        }
+        Ok(Self { params })
+    }
+
+    /// The full grid, or `config.samples` random draws from it
+    pub fn candidates(&self, config: &WalkForwardConfig) -> Vec<Params> {
+        if config.samples > 0 {
+            let mut rng = SplitMix64(config.seed);
+            return (0..config.samples)
+                .map(|_| self.params.iter().map(|(name, space)| (name.clone(), space.draw(&mut rng))).collect())
+                .collect();
+        }
+        let mut grid = vec![Params::new()];
+        for (name, space) in &self.params {
+            grid = grid
+                .into_iter()
+                .flat_map(|partial| {
+                    space.points().into_iter().map(move |value| {
+                        let mut params = partial.clone();
+                        params.insert(name.clone(), value);
+                        params
+                    })
+                })
+                .collect();
+        }
+        grid
+    }
+}
+
+/// Small deterministic generator for random search, so a seed reproduces a run
+pub struct SplitMix64(pub u64);
+
+impl SplitMix64 {
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in [0, 1)
+    pub fn unit(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+/// One train/test split: the candidate that did best in training, and how it
+/// then did on the data after
+#[derive(Debug, Clone, Serialize)]
+pub struct Fold {
+    pub train_from: DateTime<Utc>,
+    pub test_from: DateTime<Utc>,
+    pub test_to: DateTime<Utc>,
+    pub best: Params,
+    pub train: BacktestResult,
+    pub test: BacktestResult,
+}
+
+/// How one candidate did across every fold
+#[derive(Debug, Clone, Serialize)]
+pub struct CandidateSummary {
+    pub params: Params,
+    pub mean_train_pnl: f64,
+    pub mean_test_pnl: f64,
+    /// Folds whose test window it made money in
+    pub profitable_folds: usize,
+    /// Folds it was the training winner of
+    pub folds_won: usize,
+}
+
+/// Spread of one parameter's winning values across folds. A value that jumps
+/// around from fold to fold is fitting noise rather than an edge.
+#[derive(Debug, Clone, Serialize)]
+pub struct ParamStability {
+    pub param: String,
+    pub mean: f64,
+    pub std_dev: f64,
+    pub min: f64,
+    pub max: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct WalkForwardReport {
+    pub generated_at: DateTime<Utc>,
+    pub strategy: String,
+    /// Strategy instances (one per market) each candidate ran as
+    pub instances: usize,
+    pub candidates: usize,
+    pub folds: Vec<Fold>,
+    /// Out-of-sample PnL of each fold's winner, added up
+    pub test_pnl: f64,
+    pub stability: Vec<ParamStability>,
+    /// Candidates by mean out-of-sample PnL, best first
+    pub ranking: Vec<CandidateSummary>,
+}
+
+/// Instances of a strategy with tunables for every market it applies to, built
+/// as the bot builds them. Disabled strategies are enabled, since a search of a
+/// strategy that never trades is empty.
+pub fn instances(strategy: &str, markets: &[Market], config: &Config) -> Result<Vec<Box<dyn Strategy>>> {
+    let instances: Vec<Box<dyn Strategy>> = match strategy {
+        "latency_arb" => markets
+            .iter()
+            .filter_map(LatencyArbStrategy::from_market)
+            .map(|s| {
+                s.with_pricing(&config.pricing)
+                    .with_min_depth_share(config.binance.min_depth_share)
+                    .with_calendar(&config.calendar)
+            })
+            .map(|s| Box::new(s) as Box<dyn Strategy>)
+            .collect(),
+        "mean_reversion" => {
+            let mean_reversion = crate::config::MeanReversionConfig {
+                enabled: true,
+                ..config.mean_reversion.clone()
+            };
+            markets
+                .iter()
+                .filter_map(|m| MeanReversionStrategy::from_market(m, &mean_reversion))
+                .map(|s| s.with_calendar(&config.calendar))
+                .map(|s| Box::new(s) as Box<dyn Strategy>)
+                .collect()
+        }
+        other => return Err(Error::invalid(format!("walk-forward search supports latency_arb and mean_reversion, not '{}'", other))),
+    };
+    if instances.is_empty() {
+        return Err(Error::invalid(format!("no markets in the recording suit {}", strategy)));
+    }
+    Ok(instances)
+}
+
+/// Walk-forward search: over rolling folds of `events`, rank every candidate on
+/// the train window by PnL and score the winner on the test window that follows.
+/// Folds start at the first event and step forward by the test length.
+pub async fn optimize(
+    backtester: &Backtester,
+    base: &[Box<dyn Strategy>],
+    grid: &ParamGrid,
+    config: &WalkForwardConfig,
+    events: &[(DateTime<Utc>, MarketData)],
+) -> Result<WalkForwardReport> {
+    let (Some((first, _)), Some((last, _))) = (events.first(), events.last()) else {
+        return Err(Error::invalid("recording is empty"));
+    };
+    let train = chrono::Duration::hours(config.train_hours.max(1));
+    let test = chrono::Duration::hours(config.test_hours.max(1));
+    let mut windows = Vec::new();
+    let mut train_from = *first;
+    while train_from + train + test <= *last {
+        windows.push((train_from, train_from + train, train_from + train + test));
+        train_from += test;
+    }
+    if windows.is_empty() {
+        return Err(Error::invalid(format!(
+            "recording spans {}h, less than one {}h train + {}h test window",
+            (*last - *first).num_hours(),
+            train.num_hours(),
+            test.num_hours()
+        )));
+    }
+
+    let candidates = grid.candidates(config);
+    let mut runs: Vec<Vec<(BacktestResult, BacktestResult)>> = Vec::with_capacity(candidates.len());
+    for params in &candidates {
+        let mut folds = Vec::with_capacity(windows.len());
+        for &(train_from, test_from, test_to) in &windows {
+            let strategies = with_params(base, params)?;
+            let train = backtester.run(&strategies, events, train_from, test_from).await;
+            let strategies = with_params(base, params)?;
+            let test = backtester.run(&strategies, events, test_from, test_to).await;
+            folds.push((train, test));
+        }
+        runs.push(folds);
+    }
+
+    let mut folds = Vec::with_capacity(windows.len());
+    let mut wins = vec![0; candidates.len()];
+    for (i, &(train_from, test_from, test_to)) in windows.iter().enumerate() {
+        let best = (0..candidates.len())
+            .max_by(|&a, &b| runs[a][i].0.pnl().total_cmp(&runs[b][i].0.pnl()))
+            .unwrap_or_default();
+        wins[best] += 1;
+        let (train, test) = runs[best][i].clone();
+        folds.push(Fold {
+            train_from,
+            test_from,
+            test_to,
+            best: candidates[best].clone(),
+            train,
+            test,
+        });
+    }
+
+    let n = windows.len() as f64;
+    let mut ranking: Vec<CandidateSummary> = candidates
+        .iter()
+        .zip(&runs)
+        .zip(&wins)
+        .map(|((params, folds), &folds_won)| CandidateSummary {
+            params: params.clone(),
+            mean_train_pnl: folds.iter().map(|(train, _)| train.pnl()).sum::<f64>() / n,
+            mean_test_pnl: folds.iter().map(|(_, test)| test.pnl()).sum::<f64>() / n,
+            profitable_folds: folds.iter().filter(|(_, test)| test.pnl() > 0.0).count(),
+            folds_won,
+        })
+        .collect();
+    ranking.sort_by(|a, b| b.mean_test_pnl.total_cmp(&a.mean_test_pnl));
+    ranking.truncate(RANKING_LEN);
+
+    Ok(WalkForwardReport {
+        generated_at: Utc::now(),
+        strategy: base.first().map(|s| s.name().to_string()).unwrap_or_default(),
+        instances: base.len(),
+        candidates: candidates.len(),
+        test_pnl: folds.iter().map(|f| f.test.pnl()).sum(),
+        stability: stability(&folds),
+        folds,
+        ranking,
+    })
+}
+
+fn with_params(base: &[Box<dyn Strategy>], params: &Params) -> Result<Vec<Box<dyn Strategy>>> {
+    base.iter().map(|s| s.with_params(params)).collect()
+}
+
+fn stability(folds: &[Fold]) -> Vec<ParamStability> {
+    let Some(first) = folds.first() else {
+        return Vec::new();
+    };
+    first
+        .best
+        .keys()
+        .map(|param| {
+            let values: Vec<f64> = folds.iter().filter_map(|f| f.best.get(param).copied()).collect();
+            let mean = values.iter().sum::<f64>() / values.len() as f64;
+            let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / values.len() as f64;
+            ParamStability {
+                param: param.clone(),
+                mean,
+                std_dev: variance.sqrt(),
+                min: values.iter().copied().fold(f64::INFINITY, f64::min),
+                max: values.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,111 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use tracing::{info, warn};
+
+use crate::adapters::polygon::PolygonRpc;
+use crate::config::WalletHealthConfig;
+use crate::error::Result;
+
+/// Gwei per POL
+const GWEI_PER_POL: f64 = 1e9;
+
+/// The signing wallet's gas position as of the last check
+#[derive(Debug, Clone, Serialize)]
+pub struct WalletHealth {
+    pub address: String,
+    /// Native balance, in POL
+    pub pol_balance: f64,
+    pub gas_price_gwei: f64,
+    /// POL one redeem or split costs at the current gas price
+    pub tx_cost: f64,
+    /// Redeems/splits the balance pays for at the current gas price
+    pub txs_covered: f64,
+    /// Fewer than the configured reserve of transactions covered
+    pub low_balance: bool,
+    /// Gas above the configured ceiling
+    pub high_gas: bool,
+    pub checked_at: DateTime<Utc>,
+}
+
+/// Latest wallet check; None until the first one lands
+pub type WalletStatus = Arc<RwLock<Option<WalletHealth>>>;
+
+/// Polls the signing wallet's POL balance and Polygon gas, and warns when the
+/// balance won't cover `reserve_txs` redeems or splits at current gas, or gas
+/// itself spikes past `max_gas_gwei`. Warnings fire on the way into either state,
+/// with a note on recovery; the latest reading is served at /api/wallet.
+pub struct WalletMonitor {
+    rpc: PolygonRpc,
+    address: String,
+    config: WalletHealthConfig,
+    status: WalletStatus,
+}
+
+impl WalletMonitor {
+    pub fn new(address: String, config: WalletHealthConfig) -> Result<Self> {
+        Ok(Self {
+            rpc: PolygonRpc::new(&config.rpc_url)?,
+            address,
+            config,
+            status: WalletStatus::default(),
+        })
+    }
+
+    /// Shared handle on the latest reading
+    pub fn status(&self) -> WalletStatus {
+        self.status.clone()
+    }
+
+    pub async fn run(self) {
+        let interval = Duration::from_secs(self.config.poll_secs);
+        info!("Wallet monitor started for {} (every {:?})", self.address, interval);
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            match self.check().await {
+                Ok(health) => self.update(health),
+                Err(e) => warn!("Wallet check failed: {:?}", e),
+            }
+        }
+    }
+
+    async fn check(&self) -> Result<WalletHealth> {
+        let pol_balance = self.rpc.balance(&self.address).await?;
+        let gas_price_gwei = self.rpc.gas_price().await?;
+        let tx_cost = self.config.gas_per_tx * gas_price_gwei / GWEI_PER_POL;
+        let txs_covered = if tx_cost > 0.0 { pol_balance / tx_cost } else { f64::INFINITY };
+        Ok(WalletHealth {
+            address: self.address.clone(),
+            pol_balance,
+            gas_price_gwei,
+            tx_cost,
+            txs_covered,
+            low_balance: txs_covered < self.config.reserve_txs,
+            high_gas: gas_price_gwei > self.config.max_gas_gwei,
+            checked_at: Utc::now(),
+        })
+    }
+
+    fn update(&self, health: WalletHealth) {
+        let previous = self.status.write().unwrap().replace(health.clone());
+        let (was_low, was_high) = previous.map_or((false, false), |p| (p.low_balance, p.high_gas));
+        if health.low_balance && !was_low {
+            warn!(
+                "Wallet {} LOW ON GAS: {:.4} POL covers {:.1} redeems/splits at {:.0} gwei (reserve {:.0})",
+                health.address, health.pol_balance, health.txs_covered, health.gas_price_gwei, self.config.reserve_txs
+            );
+        } else if was_low && !health.low_balance {
+            info!("Wallet {} gas balance recovered: {:.4} POL", health.address, health.pol_balance);
+        }
+        if health.high_gas && !was_high {
+            warn!(
+                "Polygon gas at {:.0} gwei, above {:.0} — redeems and splits are expensive",
+                health.gas_price_gwei, self.config.max_gas_gwei
+            );
+        } else if was_high && !health.high_gas {
+            info!("Polygon gas back to {:.0} gwei", health.gas_price_gwei);
+        }
+    }
+}
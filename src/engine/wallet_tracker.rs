@@ -0,0 +1,106 @@
+use chrono::{DateTime, Utc};
+use std::collections::{HashSet, VecDeque};
+use std::time::Duration;
+use tokio::sync::Mutex;
+use tracing::{info, warn};
+
+use crate::adapters::data_api::DataApiClient;
+use crate::domain::{MarketId, TokenId};
+
+/// Trades fetched per wallet per poll
+const TRADES_LIMIT: u32 = 50;
+/// Entries waiting for the strategy; older ones are dropped past this
+const MAX_PENDING: usize = 256;
+
+/// A buy by a tracked wallet
+#[derive(Debug, Clone)]
+pub struct WalletEntry {
+    pub wallet: String,
+    pub market_id: MarketId,
+    pub token_id: TokenId,
+    pub price: f64,
+    pub size: f64,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Watches tracked wallets' trades on the data API and queues their new entries.
+///
+/// The data API indexes the on-chain CTF exchange fills, so this sees a wallet's
+/// trades whichever interface placed them. Everything already on the tape at the
+/// first poll is treated as history and never queued.
+pub struct WalletTracker {
+    data_api: DataApiClient,
+    wallets: Vec<String>,
+    poll_interval: Duration,
+    seen: Mutex<HashSet<String>>,
+    pending: Mutex<VecDeque<WalletEntry>>,
+}
+
+impl WalletTracker {
+    pub fn new(data_api: DataApiClient, wallets: Vec<String>, poll_interval: Duration) -> Self {
+        Self {
+            data_api,
+            wallets,
+            poll_interval,
+            seen: Mutex::new(HashSet::new()),
+            pending: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    pub async fn run(&self) {
+        info!("Wallet tracker started for {} wallets (poll every {:?})", self.wallets.len(), self.poll_interval);
+        let mut interval = tokio::time::interval(self.poll_interval);
+        let mut first = true;
+
+        loop {
+            interval.tick().await;
+            for wallet in &self.wallets {
+                if let Err(e) = self.poll(wallet, first).await {
+                    warn!("Wallet trades fetch failed for {}: {:?}", wallet, e);
+                }
+            }
+            first = false;
+        }
+    }
+
+    async fn poll(&self, wallet: &str, history: bool) -> crate::error::Result<()> {
+        let trades = self.data_api.get_user_trades(wallet, TRADES_LIMIT).await?;
+        let mut seen = self.seen.lock().await;
+        let mut pending = self.pending.lock().await;
+
+        // Oldest first so entries queue in the order they were made
+        for trade in trades.iter().rev() {
+            if !seen.insert(format!("{}:{}", wallet, trade.trade_key())) || history {
+                continue;
+            }
+            let (Some(side), Some(market_id)) = (&trade.side, &trade.condition_id) else {
+                continue;
+            };
+            if !side.eq_ignore_ascii_case("buy") {
+                continue;
+            }
+            info!(
+                "Tracked wallet {} bought {:.2}@{:.4} of {} on {}",
+                wallet, trade.size, trade.price, trade.asset, market_id
+            );
+            pending.push_back(WalletEntry {
+                wallet: wallet.to_string(),
+                market_id: market_id.clone(),
+                token_id: trade.asset.clone(),
+                price: trade.price,
+                size: trade.size,
+                timestamp: DateTime::from_timestamp(trade.timestamp, 0).unwrap_or_else(Utc::now),
+            });
+            if pending.len() > MAX_PENDING {
+                pending.pop_front();
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Entries queued since the last call
+    pub async fn take_entries(&self) -> Vec<WalletEntry> {
+        self.pending.lock().await.drain(..).collect()
+    }
+}
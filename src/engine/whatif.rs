@@ -0,0 +1,170 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+
+use crate::adapters::binance;
+use crate::adapters::database::Database;
+use crate::adapters::gamma::GammaClient;
+use crate::config::PricingConfig;
+use crate::domain::{Market, MarketData, MarketId, Position, TokenId};
+use crate::engine::pricing;
+use crate::error::Result;
+use crate::strategy::latency_arb::threshold_market;
+
+/// Spot shocks (percent) used when the caller doesn't give any
+pub const DEFAULT_SHOCKS: &[f64] = &[-10.0, -3.0, 0.0, 3.0, 10.0];
+
+#[derive(Debug, Clone, Serialize)]
+pub struct WhatIfReport {
+    pub generated_at: DateTime<Utc>,
+    /// Spot each scenario is shocked from
+    pub spot: BTreeMap<String, f64>,
+    /// Current mark-to-market, for comparison with the scenarios
+    pub mark_value: f64,
+    pub mark_pnl: f64,
+    pub scenarios: Vec<Scenario>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Scenario {
+    /// Spot move applied to every underlying, in percent
+    pub shock_pct: f64,
+    pub value: f64,
+    /// Projected PnL against entry prices
+    pub pnl: f64,
+    /// Projected change from the current mark
+    pub pnl_change: f64,
+    pub positions: Vec<RepricedPosition>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RepricedPosition {
+    pub market_id: MarketId,
+    pub token_id: TokenId,
+    pub size: f64,
+    pub avg_price: f64,
+    pub mark_price: f64,
+    pub price: f64,
+    pub pnl: f64,
+    /// False when the market isn't a threshold market we can model (or spot is
+    /// unavailable); those are carried at their mark in every scenario
+    pub modelled: bool,
+}
+
+/// "-10,-3,3,10" → [-10, -3, 3, 10]; None if any entry isn't a number
+pub fn parse_shocks(list: &str) -> Option<Vec<f64>> {
+    list.split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(|s| s.trim_end_matches('%').parse().ok())
+        .collect()
+}
+
+/// How a position's token pays off in terms of its underlying
+struct Model {
+    symbol: &'static str,
+    strike: f64,
+    expiry: DateTime<Utc>,
+    /// YES pays when spot finishes above the strike, NO when it doesn't
+    yes: bool,
+}
+
+/// Re-price every open position under each spot shock with the threshold model.
+///
+/// Markets are looked up on Gamma and spot is fetched from Binance at call time, so
+/// this works offline from a running bot as well as through the dashboard.
+pub async fn reprice(db: &Database, pricing: &PricingConfig, shocks: &[f64]) -> Result<WhatIfReport> {
+    let positions = db.get_positions().await?;
+    let market_ids: Vec<MarketId> = positions.iter().map(|p| p.market_id.clone()).collect();
+    let markets: HashMap<MarketId, Market> = GammaClient::new()?
+        .get_markets(&market_ids)
+        .await?
+        .into_iter()
+        .map(|m| (m.id.clone(), m))
+        .collect();
+
+    let models: HashMap<&str, Model> = positions
+        .iter()
+        .filter_map(|p| Some((p.token_id.as_str(), model(markets.get(&p.market_id)?, &p.token_id)?)))
+        .collect();
+
+    let mut symbols: Vec<String> = models.values().map(|m| m.symbol.to_string()).collect();
+    symbols.sort();
+    symbols.dedup();
+    let spot: BTreeMap<String, f64> = binance::fetch_spot(&symbols)
+        .await
+        .into_iter()
+        .filter_map(|event| match event {
+            MarketData::BinanceTicker { symbol, price, .. } => Some((symbol, price)),
+            _ => None,
+        })
+        .collect();
+
+    let now = Utc::now();
+    let mark_value = positions.iter().fold(0.0, |acc, p| acc + p.size * mark(p));
+    let cost = positions.iter().fold(0.0, |acc, p| acc + p.size * p.avg_price);
+
+    let scenarios = shocks
+        .iter()
+        .map(|&shock_pct| {
+            let repriced: Vec<RepricedPosition> = positions
+                .iter()
+                .map(|p| {
+                    let modelled = models.get(p.token_id.as_str()).and_then(|m| {
+                        let s = spot.get(m.symbol)? * (1.0 + shock_pct / 100.0);
+                        let years = pricing::years_until(m.expiry, now);
+                        let above = pricing::prob_above(s, m.strike, pricing.vol(m.symbol), years);
+                        Some(if m.yes { above } else { 1.0 - above })
+                    });
+                    let price = modelled.unwrap_or_else(|| mark(p));
+                    RepricedPosition {
+                        market_id: p.market_id.clone(),
+                        token_id: p.token_id.clone(),
+                        size: p.size,
+                        avg_price: p.avg_price,
+                        mark_price: mark(p),
+                        price,
+                        pnl: (price - p.avg_price) * p.size,
+                        modelled: modelled.is_some(),
+                    }
+                })
+                .collect();
+            let value = repriced.iter().fold(0.0, |acc, r| acc + r.price * r.size);
+            Scenario {
+                shock_pct,
+                value,
+                pnl: value - cost,
+                pnl_change: value - mark_value,
+                positions: repriced,
+            }
+        })
+        .collect();
+
+    Ok(WhatIfReport {
+        generated_at: now,
+        spot,
+        mark_value,
+        mark_pnl: mark_value - cost,
+        scenarios,
+    })
+}
+
+fn model(market: &Market, token_id: &str) -> Option<Model> {
+    let (symbol, strike) = threshold_market(market)?;
+    let outcome = &market.outcomes.iter().find(|o| o.token_id == token_id)?.name;
+    Some(Model {
+        symbol,
+        strike,
+        expiry: market.end_date?,
+        yes: outcome.eq_ignore_ascii_case("yes"),
+    })
+}
+
+/// Last known price, falling back to entry when the position was never marked
+fn mark(position: &Position) -> f64 {
+    if position.current_price > 0.0 {
+        position.current_price
+    } else {
+        position.avg_price
+    }
+}
@@ -0,0 +1,293 @@
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use reqwest::{RequestBuilder, Response, StatusCode};
+use serde::de::DeserializeOwned;
+use std::time::Duration;
+use thiserror::Error;
+use tokio_tungstenite::tungstenite;
+
+/// Failure calling a venue's API: the Polymarket CLOB, Kalshi, Binance futures,
+/// or the Polygon RPC positions settle through.
+///
+/// The variants are what a caller decides on: a rate limit is waited out, an
+/// auth failure won't clear by retrying, a network error or a 5xx probably will.
+#[derive(Debug, Error)]
+pub enum ClobError {
+    #[error("{context}: rate limited")]
+    RateLimited { context: String, retry_after: Option<Duration> },
+    /// Credentials refused (401/403) or unusable as configured
+    #[error("{context}: {reason}")]
+    Auth { context: String, reason: String },
+    /// No response: connection refused or reset, timed out
+    #[error("{context}: {error}")]
+    Network { context: String, error: reqwest::Error },
+    /// Any other non-success status
+    #[error("{context}: HTTP {status}: {body}")]
+    Status { context: String, status: u16, body: String },
+    /// Understood and refused, e.g. a JSON-RPC error reply
+    #[error("{context}: rejected: {reason}")]
+    Rejected { context: String, reason: String },
+    /// A response that didn't parse, or didn't hold what was asked for
+    #[error("{context}: unexpected response: {reason}")]
+    Decode { context: String, reason: String },
+    /// A request that can't be built from what was given
+    #[error("{0}")]
+    Invalid(String),
+}
+
+impl ClobError {
+    /// Classify a request that failed before a usable response came back
+    pub fn request(context: impl Into<String>, error: reqwest::Error) -> Self {
+        let context = context.into();
+        match error.status() {
+            _ if error.is_decode() => Self::Decode { context, reason: error.to_string() },
+            Some(status) => Self::refused(context, status, String::new(), None),
+            None => Self::Network { context, error },
+        }
+    }
+
+    pub fn decode(context: impl Into<String>, reason: impl ToString) -> Self {
+        Self::Decode { context: context.into(), reason: reason.to_string() }
+    }
+
+    pub fn auth(context: impl Into<String>, reason: impl Into<String>) -> Self {
+        Self::Auth { context: context.into(), reason: reason.into() }
+    }
+
+    /// `resp` if it succeeded, otherwise what its status says went wrong
+    pub async fn check(context: impl Into<String>, resp: Response) -> Result<Response, Self> {
+        if resp.status().is_success() {
+            return Ok(resp);
+        }
+        let (status, body, retry_after) = refusal(resp).await;
+        Err(Self::refused(context.into(), status, body, retry_after))
+    }
+
+    /// Send `request` and read its JSON body, classifying whatever goes wrong
+    pub async fn send_json<T: DeserializeOwned>(context: &str, request: RequestBuilder) -> Result<T, Self> {
+        let resp = request.send().await.map_err(|e| Self::request(context, e))?;
+        Self::check(context, resp).await?.json().await.map_err(|e| Self::request(context, e))
+    }
+
+    fn refused(context: String, status: StatusCode, body: String, retry_after: Option<Duration>) -> Self {
+        match status {
+            StatusCode::TOO_MANY_REQUESTS => Self::RateLimited { context, retry_after },
+            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => Self::Auth {
+                context,
+                reason: format!("credentials rejected ({}): {}", status, body),
+            },
+            _ => Self::Status { context, status: status.as_u16(), body },
+        }
+    }
+
+    /// Worth trying again later: throttled, unreachable, or a server-side error
+    pub fn is_transient(&self) -> bool {
+        match self {
+            Self::RateLimited { .. } | Self::Network { .. } => true,
+            Self::Status { status, .. } => *status >= 500,
+            Self::Auth { .. } | Self::Rejected { .. } | Self::Decode { .. } | Self::Invalid(_) => false,
+        }
+    }
+
+    /// How long the venue asked us to wait, when it said
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            Self::RateLimited { retry_after, .. } => *retry_after,
+            _ => None,
+        }
+    }
+}
+
+/// Failure of a market data source: a WebSocket feed, one of the REST APIs the
+/// bot reads from, or a recording being replayed
+#[derive(Debug, Error)]
+pub enum FeedError {
+    #[error("{feed}: {error}")]
+    WebSocket { feed: String, error: Box<tungstenite::Error> },
+    #[error("{feed}: {error}")]
+    Network { feed: String, error: reqwest::Error },
+    #[error("{feed}: rate limited")]
+    RateLimited { feed: String, retry_after: Option<Duration> },
+    /// Credentials refused or not configured
+    #[error("{feed}: {reason}")]
+    Auth { feed: String, reason: String },
+    #[error("{feed}: HTTP {status}: {body}")]
+    Status { feed: String, status: u16, body: String },
+    #[error("{feed}: unexpected data: {reason}")]
+    Decode { feed: String, reason: String },
+    /// Every way of reaching the source has been tried and failed
+    #[error("{feed}: {reason}")]
+    Unavailable { feed: String, reason: String },
+    #[error("recording {path}: {error}")]
+    Recording { path: String, error: std::io::Error },
+}
+
+impl FeedError {
+    pub fn websocket(feed: impl Into<String>, error: tungstenite::Error) -> Self {
+        Self::WebSocket { feed: feed.into(), error: Box::new(error) }
+    }
+
+    /// Classify a request that failed before a usable response came back
+    pub fn request(feed: impl Into<String>, error: reqwest::Error) -> Self {
+        let feed = feed.into();
+        match error.status() {
+            _ if error.is_decode() => Self::Decode { feed, reason: error.to_string() },
+            Some(status) => Self::refused(feed, status, String::new(), None),
+            None => Self::Network { feed, error },
+        }
+    }
+
+    pub fn decode(feed: impl Into<String>, reason: impl ToString) -> Self {
+        Self::Decode { feed: feed.into(), reason: reason.to_string() }
+    }
+
+    pub fn auth(feed: impl Into<String>, reason: impl Into<String>) -> Self {
+        Self::Auth { feed: feed.into(), reason: reason.into() }
+    }
+
+    pub fn unavailable(feed: impl Into<String>, reason: impl Into<String>) -> Self {
+        Self::Unavailable { feed: feed.into(), reason: reason.into() }
+    }
+
+    pub fn recording(path: impl Into<String>, error: std::io::Error) -> Self {
+        Self::Recording { path: path.into(), error }
+    }
+
+    /// `resp` if it succeeded, otherwise what its status says went wrong
+    pub async fn check(feed: impl Into<String>, resp: Response) -> Result<Response, Self> {
+        if resp.status().is_success() {
+            return Ok(resp);
+        }
+        let (status, body, retry_after) = refusal(resp).await;
+        Err(Self::refused(feed.into(), status, body, retry_after))
+    }
+
+    /// Send `request` and read its JSON body, classifying whatever goes wrong
+    pub async fn send_json<T: DeserializeOwned>(feed: &str, request: RequestBuilder) -> Result<T, Self> {
+        let resp = request.send().await.map_err(|e| Self::request(feed, e))?;
+        Self::check(feed, resp).await?.json().await.map_err(|e| Self::request(feed, e))
+    }
+
+    fn refused(feed: String, status: StatusCode, body: String, retry_after: Option<Duration>) -> Self {
+        match status {
+            StatusCode::TOO_MANY_REQUESTS => Self::RateLimited { feed, retry_after },
+            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => Self::Auth {
+                feed,
+                reason: format!("credentials rejected ({}): {}", status, body),
+            },
+            _ => Self::Status { feed, status: status.as_u16(), body },
+        }
+    }
+
+    /// How long the source asked us to wait, when it said
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            Self::RateLimited { retry_after, .. } => *retry_after,
+            _ => None,
+        }
+    }
+}
+
+/// Why the risk manager refused a signal. The message is what gets recorded
+/// against the signal.
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum RiskError {
+    #[error("warming up")]
+    WarmingUp,
+    #[error("trading halted")]
+    Halted,
+    #[error("bankroll ${bankroll:.2} below minimum")]
+    BankrollBelowMinimum { bankroll: f64 },
+    /// Blocked or not allowed by the market lists
+    #[error("{0}")]
+    MarketBlocked(String),
+    #[error("blackout around {event} at {at}")]
+    Blackout { event: String, at: String },
+    #[error("market on loss cooldown until {until}")]
+    Cooldown { until: String },
+    #[error("size ${size:.2} exceeds max position ${max:.2}")]
+    PositionSize { size: f64, max: f64 },
+    #[error("exposure ${exposure:.2} would exceed max ${max:.2}")]
+    Exposure { exposure: f64, max: f64 },
+    #[error("{open} open positions at max")]
+    OpenPositions { open: usize },
+    #[error("{open} strategy positions at max")]
+    StrategyPositions { open: usize },
+}
+
+impl RiskError {
+    /// Refused because of the bot's state rather than anything about the signal;
+    /// the same signal would be refused again however it was sized
+    pub fn is_halt(&self) -> bool {
+        matches!(self, Self::WarmingUp | Self::Halted | Self::BankrollBelowMinimum { .. })
+    }
+}
+
+/// Failure of the storage layer
+#[derive(Debug, Error)]
+pub enum DbError {
+    #[error("could not connect: {0}")]
+    Connect(sqlx::Error),
+    #[error("database: {0}")]
+    Query(#[from] sqlx::Error),
+    #[error("migration failed: {0}")]
+    Migrate(#[from] sqlx::migrate::MigrateError),
+    /// A JSON column or config value that doesn't (de)serialize
+    #[error("stored JSON: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("{context}: {error}")]
+    Io { context: String, error: std::io::Error },
+    #[error("backup: {0}")]
+    Backup(String),
+}
+
+impl DbError {
+    pub fn io(context: impl Into<String>, error: std::io::Error) -> Self {
+        Self::Io { context: context.into(), error }
+    }
+}
+
+/// Any library failure, for code that works across layers
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error(transparent)]
+    Clob(#[from] ClobError),
+    #[error(transparent)]
+    Feed(#[from] FeedError),
+    #[error(transparent)]
+    Db(#[from] DbError),
+    /// Settings, specs or parameters that don't parse or don't make sense
+    #[error("{0}")]
+    Invalid(String),
+    /// An outbound call that isn't to a venue or feed, e.g. a webhook
+    #[error("HTTP client: {0}")]
+    Http(#[from] reqwest::Error),
+    /// A blocking job handed off the async workers panicked or was cancelled
+    #[error("background task: {0}")]
+    Task(#[from] tokio::task::JoinError),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+}
+
+impl Error {
+    pub fn invalid(reason: impl Into<String>) -> Self {
+        Self::Invalid(reason.into())
+    }
+}
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// Status, body and Retry-After of a response that wasn't a success
+async fn refusal(resp: Response) -> (StatusCode, String, Option<Duration>) {
+    let status = resp.status();
+    let retry_after = retry_after(resp.headers());
+    let body = resp.text().await.unwrap_or_default();
+    (status, body, retry_after)
+}
+
+/// Retry-After in its delay-seconds form; HTTP dates are left unread
+fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let secs = headers.get(RETRY_AFTER)?.to_str().ok()?.trim().parse().ok()?;
+    Some(Duration::from_secs(secs))
+}
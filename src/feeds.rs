@@ -1,98 +1,352 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
-use tokio::sync::{broadcast, RwLock};
+use tokio::sync::{mpsc, RwLock};
 use tracing::{info, warn};
 
-use crate::domain::{MarketData, OrderBook, Signal};
-use crate::strategy::{Strategy, StrategyContext};
+use crate::adapters::calendar::EconEvents;
+use crate::bus::{EventBus, EventReceiver};
+use crate::cache::LatestCache;
+use crate::clock::{self, SharedClock};
+use crate::domain::{FlowAlert, GameState, KalshiQuote, MarketData, MarketId, OrderBook, Signal, TokenId, UmaStatus};
+use crate::engine::allocator::Allocations;
+use crate::engine::anomaly::AnomalyGuard;
+use crate::engine::market_catalog::MarketCatalog;
+use crate::engine::readiness::Readiness;
+use crate::engine::shadow::ShadowBook;
+use crate::engine::portfolio::Portfolio;
+use crate::metrics::METRICS;
+use crate::config::{FeeConfig, StatsConfig};
+use crate::strategy::flow::FlowTracker;
+use crate::strategy::heartbeat::StrategyMonitor;
+use crate::strategy::stats::StatsTracker;
+use crate::strategy::{Intent, Interest, Strategy, StrategyContext};
+use crate::watchdog::Heartbeat;
+
+/// Latest Polymarket price per token, as last seen on the feeds
+pub type PriceCache = LatestCache<TokenId, f64>;
+/// Latest order book per token, as last seen on the feeds
+pub type BookCache = LatestCache<TokenId, Arc<OrderBook>>;
 
 /// Aggregates market data and drives strategy evaluation
 pub struct FeedAggregator {
-    market_rx: broadcast::Receiver<MarketData>,
-    signal_tx: broadcast::Sender<Signal>,
-    strategies: Vec<Box<dyn Strategy>>,
+    market_rx: EventReceiver<MarketData>,
+    /// Where emitted signals are published
+    events: EventBus,
+    signal_tx: mpsc::Sender<Intent>,
+    /// Each with the events it is evaluated on
+    strategies: Vec<(Box<dyn Strategy>, Interest)>,
     bankroll: Arc<RwLock<f64>>,
-    prices: Arc<RwLock<HashMap<String, f64>>>,
-    orderbooks: Arc<RwLock<HashMap<String, OrderBook>>>,
-    binance_prices: Arc<RwLock<HashMap<String, f64>>>,
+    monitor: StrategyMonitor,
+    anomaly: AnomalyGuard,
+    prices: PriceCache,
+    orderbooks: BookCache,
+    binance_prices: LatestCache<String, f64>,
+    binance_books: LatestCache<String, Arc<OrderBook>>,
+    kalshi_quotes: LatestCache<String, KalshiQuote>,
+    games: LatestCache<String, GameState>,
+    flow_alerts: RwLock<HashMap<TokenId, FlowAlert>>,
+    /// Markets whose resolution is under dispute; no new entries until it settles
+    disputed: RwLock<HashSet<MarketId>>,
+    /// How long a flow alert stays in the context
+    flow_alert_ttl: chrono::Duration,
+    stats: RwLock<StatsTracker>,
+    flow: RwLock<FlowTracker>,
+    /// Positions and open orders for the context; without it both are empty
+    portfolio: Option<Portfolio>,
+    fees: FeeConfig,
+    /// Per-strategy bankroll shares; empty sizes every strategy off the full bankroll
+    allocations: Allocations,
+    /// Economic calendar; empty unless the calendar feed is on
+    econ_events: EconEvents,
+    /// Stored market metadata; empty unless the market refresher is on
+    markets: MarketCatalog,
+    /// Paper execution for shadow-mode strategies; None sends every intent on
+    shadow: Option<ShadowBook>,
+    /// Boot warm-up gate, told about each live event; None when nothing gates trading
+    readiness: Option<Readiness>,
+    clock: SharedClock,
 }
 
 impl FeedAggregator {
     pub fn new(
-        market_rx: broadcast::Receiver<MarketData>,
-        signal_tx: broadcast::Sender<Signal>,
+        events: EventBus,
+        signal_tx: mpsc::Sender<Intent>,
         strategies: Vec<Box<dyn Strategy>>,
         bankroll: Arc<RwLock<f64>>,
+        monitor: StrategyMonitor,
+        anomaly: AnomalyGuard,
+        stats: StatsConfig,
     ) -> Self {
+        for strategy in &strategies {
+            monitor.register(strategy.name(), strategy.enabled(), strategy.params());
+        }
+        let strategies = strategies
+            .into_iter()
+            .map(|strategy| {
+                let interest = strategy.interest();
+                (strategy, interest)
+            })
+            .collect();
         Self {
-            market_rx,
+            market_rx: events.subscribe(),
+            events,
             signal_tx,
             strategies,
             bankroll,
-            prices: Arc::new(RwLock::new(HashMap::new())),
-            orderbooks: Arc::new(RwLock::new(HashMap::new())),
-            binance_prices: Arc::new(RwLock::new(HashMap::new())),
+            monitor,
+            anomaly,
+            prices: PriceCache::new(),
+            orderbooks: BookCache::new(),
+            binance_prices: LatestCache::new(),
+            binance_books: LatestCache::new(),
+            kalshi_quotes: LatestCache::new(),
+            games: LatestCache::new(),
+            flow_alerts: RwLock::new(HashMap::new()),
+            disputed: RwLock::new(HashSet::new()),
+            flow_alert_ttl: chrono::Duration::zero(),
+            flow: RwLock::new(FlowTracker::new(&stats)),
+            stats: RwLock::new(StatsTracker::new(stats)),
+            portfolio: None,
+            fees: FeeConfig::default(),
+            allocations: Allocations::default(),
+            econ_events: EconEvents::default(),
+            markets: MarketCatalog::default(),
+            shadow: None,
+            readiness: None,
+            clock: clock::system(),
         }
     }
 
-    pub async fn run(mut self) {
+    pub fn with_portfolio(mut self, portfolio: Portfolio) -> Self {
+        self.portfolio = Some(portfolio);
+        self
+    }
+
+    pub fn with_fees(mut self, fees: FeeConfig) -> Self {
+        self.fees = fees;
+        self
+    }
+
+    pub fn with_allocations(mut self, allocations: Allocations) -> Self {
+        self.allocations = allocations;
+        self
+    }
+
+    pub fn with_calendar(mut self, events: EconEvents) -> Self {
+        self.econ_events = events;
+        self
+    }
+
+    pub fn with_markets(mut self, markets: MarketCatalog) -> Self {
+        self.markets = markets;
+        self
+    }
+
+    /// Simulate, rather than send, the intents of the strategies `shadow` covers
+    pub fn with_shadow(mut self, shadow: ShadowBook) -> Self {
+        self.shadow = Some(shadow);
+        self
+    }
+
+    pub fn with_readiness(mut self, readiness: Readiness) -> Self {
+        self.readiness = Some(readiness);
+        self
+    }
+
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    pub fn with_flow_alert_ttl(mut self, secs: u64) -> Self {
+        self.flow_alert_ttl = chrono::Duration::seconds(secs as i64);
+        self
+    }
+
+    /// Shared handle on the price cache, for the API to serve
+    pub fn prices(&self) -> PriceCache {
+        self.prices.clone()
+    }
+
+    /// Shared handle on the order book cache, for execution to price against
+    pub fn orderbooks(&self) -> BookCache {
+        self.orderbooks.clone()
+    }
+
+    /// Seed cached state without evaluating strategies (cold-start bootstrap)
+    pub async fn seed(&self, events: &[MarketData]) {
+        for event in events {
+            self.update_state(event).await;
+        }
+        info!(
+            "Seeded {} prices, {} books, {} spot prices",
+            self.prices.len(),
+            self.orderbooks.len(),
+            self.binance_prices.len()
+        );
+    }
+
+    /// Runs until the signal queue closes. Borrowed rather than consumed so a
+    /// supervisor can start it again with its state intact.
+    pub async fn run(&mut self, heartbeat: Heartbeat) {
         info!("Feed aggregator started with {} strategies", self.strategies.len());
 
         loop {
-            match self.market_rx.recv().await {
-                Ok(event) => {
-                    self.update_state(&event).await;
-                    self.run_strategies(&event).await;
-                }
-                Err(broadcast::error::RecvError::Lagged(n)) => {
-                    warn!("Feed aggregator lagged by {} events", n);
-                }
-                Err(broadcast::error::RecvError::Closed) => {
-                    info!("Market data channel closed, feed aggregator shutting down");
-                    break;
-                }
+            heartbeat.idle();
+            let event = self.market_rx.recv().await;
+            heartbeat.beat();
+            self.update_state(&event).await;
+            if let Some(readiness) = &self.readiness {
+                readiness.observe(&event);
+            }
+            self.anomaly.observe(&event);
+            if !self.run_strategies(&event).await {
+                info!("Signal queue closed, feed aggregator shutting down");
+                break;
             }
         }
     }
 
     async fn update_state(&self, event: &MarketData) {
         match event {
-            MarketData::PolymarketPrice { token_id, price, .. } => {
-                self.prices.write().await.insert(token_id.clone(), *price);
+            MarketData::PolymarketPrice { token_id, price, timestamp, .. } => {
+                self.prices.insert(token_id.clone(), *price);
+                self.stats.write().await.update(token_id, *timestamp, *price);
             }
             MarketData::PolymarketOrderBook { token_id, book, .. } => {
-                self.orderbooks.write().await.insert(token_id.clone(), book.clone());
+                self.orderbooks.insert(token_id.clone(), Arc::new(book.clone()));
+            }
+            MarketData::PolymarketTrade { token_id, price, size, side, timestamp, .. } => {
+                self.flow.write().await.record(token_id, *timestamp, *price, *size, side.clone());
+            }
+            MarketData::BinanceTicker { symbol, price, timestamp } => {
+                self.binance_prices.insert(symbol.clone(), *price);
+                self.stats.write().await.update(symbol, *timestamp, *price);
+            }
+            MarketData::BinanceOrderBook { symbol, book } => {
+                self.binance_books.insert(symbol.clone(), Arc::new(book.clone()));
+            }
+            MarketData::KalshiTicker { ticker, yes_bid, yes_ask, .. } => {
+                let quote = KalshiQuote { yes_bid: *yes_bid, yes_ask: *yes_ask };
+                self.kalshi_quotes.insert(ticker.clone(), quote);
+            }
+            MarketData::GameScore { game } => {
+                self.games.insert(game.game_id.clone(), game.clone());
+            }
+            MarketData::FlowAlert { alert } => {
+                self.flow_alerts.write().await.insert(alert.token_id.clone(), alert.clone());
             }
-            MarketData::BinanceTicker { symbol, price, .. } => {
-                self.binance_prices.write().await.insert(symbol.clone(), *price);
+            MarketData::Resolution { market_id, status, .. } => {
+                let mut disputed = self.disputed.write().await;
+                if *status == UmaStatus::Disputed {
+                    disputed.insert(market_id.clone());
+                } else {
+                    disputed.remove(market_id);
+                }
             }
         }
     }
 
-    async fn run_strategies(&self, event: &MarketData) {
+    /// Log a strategy's signal and publish it on the event bus
+    fn announce(&self, signal: &Signal) {
+        if let Some(event_time) = signal.event_time {
+            METRICS.tick_to_signal.observe_since(event_time);
+        }
+        info!(
+            "Signal from {}: {} {} {:.2}@{:.4} (conf: {:.1}%)",
+            signal.strategy, signal.side, signal.market_id,
+            signal.size, signal.price, signal.confidence * 100.0
+        );
+        self.events.publish(signal.clone());
+    }
+
+    /// Returns false once the signal consumer has gone away
+    async fn run_strategies(&self, event: &MarketData) -> bool {
+        let now = self.clock.now();
+        let due: Vec<&dyn Strategy> = self
+            .strategies
+            .iter()
+            .filter(|(_, interest)| interest.covers(event))
+            .map(|(strategy, _)| strategy.as_ref())
+            .filter(|strategy| {
+                strategy.enabled()
+                    && !self.monitor.is_paused(strategy.name())
+                    && self.monitor.is_scheduled(strategy.name(), &now)
+            })
+            .collect();
+        // Nobody reads this event, so there is no context to build
+        if due.is_empty() {
+            return true;
+        }
+
+        let bankroll = *self.bankroll.read().await;
+        let allocated_bankroll = self
+            .allocations
+            .read()
+            .unwrap()
+            .iter()
+            .map(|(strategy, share)| (strategy.clone(), share * bankroll))
+            .collect();
+        let mut blocked_markets = self.anomaly.blocked_markets(event.timestamp());
+        blocked_markets.extend(self.disputed.read().await.iter().cloned());
         let ctx = StrategyContext {
-            bankroll: *self.bankroll.read().await,
-            positions: Vec::new(), // TODO: load from DB
-            prices: self.prices.read().await.clone(),
-            orderbooks: self.orderbooks.read().await.clone(),
-            binance_prices: self.binance_prices.read().await.clone(),
+            bankroll,
+            allocated_bankroll,
+            positions: self.portfolio.as_ref().map(Portfolio::positions).unwrap_or_default(),
+            open_orders: self.portfolio.as_ref().map(Portfolio::open_orders).unwrap_or_default(),
+            prices: self.prices.snapshot(),
+            orderbooks: self.orderbooks.snapshot(),
+            binance_prices: self.binance_prices.snapshot(),
+            binance_books: self.binance_books.snapshot(),
+            kalshi_quotes: self.kalshi_quotes.snapshot(),
+            games: self.games.snapshot(),
+            stats: self.stats.read().await.snapshot(),
+            trade_flow: self.flow.read().await.snapshot(),
+            flow_alerts: self
+                .flow_alerts
+                .read()
+                .await
+                .iter()
+                .filter(|(_, alert)| event.timestamp() - alert.timestamp <= self.flow_alert_ttl)
+                .map(|(token_id, alert)| (token_id.clone(), alert.clone()))
+                .collect(),
+            econ_events: self.econ_events.read().unwrap().clone(),
             latest_event: Some(event.clone()),
+            blocked_markets,
+            fees: self.fees.clone(),
+            markets: self.markets.clone(),
+            now,
         };
 
-        for strategy in &self.strategies {
-            if !strategy.enabled() {
+        for strategy in due {
+            let intents = strategy.intents(&ctx).await;
+            self.monitor.record_evaluation(strategy.name(), intents.len());
+            if let Some(shadow) = self.shadow.as_ref().filter(|s| s.covers(strategy.name())) {
+                for intent in &intents {
+                    shadow.record(intent, &ctx);
+                }
                 continue;
             }
-
-            let signals = strategy.evaluate(&ctx).await;
-            for signal in signals {
-                info!(
-                    "Signal from {}: {} {} {:.2}@{:.4} (conf: {:.1}%)",
-                    signal.strategy, signal.side, signal.market_id,
-                    signal.size, signal.price, signal.confidence * 100.0
-                );
-                let _ = self.signal_tx.send(signal);
+            for intent in intents {
+                match &intent {
+                    Intent::Order(signal) => self.announce(signal),
+                    Intent::Legs(signals) => signals.iter().for_each(|signal| self.announce(signal)),
+                    other => info!("Intent from {}: {:?}", other.strategy(), other),
+                }
+                // Bounded and lossless: if the order manager is behind, wait rather than drop
+                match self.signal_tx.try_send(intent) {
+                    Ok(()) => {}
+                    Err(mpsc::error::TrySendError::Full(intent)) => {
+                        warn!("Signal queue full, applying backpressure");
+                        if self.signal_tx.send(intent).await.is_err() {
+                            return false;
+                        }
+                    }
+                    Err(mpsc::error::TrySendError::Closed(_)) => return false,
+                }
             }
         }
+
+        true
     }
 }
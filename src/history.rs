@@ -0,0 +1,58 @@
+use chrono::Utc;
+use tracing::{info, warn};
+
+use crate::adapters::database::Database;
+use crate::adapters::polymarket::PolymarketClient;
+use crate::domain::Market;
+use crate::error::Result;
+
+/// The CLOB rejects very long ranges at fine fidelity, so fetch in week-long chunks
+const CHUNK_SECS: i64 = 7 * 24 * 3600;
+
+pub const DEFAULT_DAYS: i64 = 30;
+pub const DEFAULT_FIDELITY_MINS: u32 = 5;
+
+/// Backfill /prices-history into the market_data table for every token in `markets`.
+/// Resumes from the latest stored point per token; returns the number of new rows.
+pub async fn backfill_price_history(
+    client: &PolymarketClient,
+    db: &Database,
+    markets: &[Market],
+    days: i64,
+    fidelity_mins: u32,
+) -> Result<u64> {
+    let now = Utc::now().timestamp();
+    let mut total = 0;
+
+    for market in markets {
+        for outcome in &market.outcomes {
+            let start = match db.latest_price_timestamp(&outcome.token_id).await? {
+                Some(ts) => ts + 1,
+                None => now - days * 24 * 3600,
+            };
+
+            let mut chunk_start = start;
+            while chunk_start < now {
+                let chunk_end = (chunk_start + CHUNK_SECS).min(now);
+                match client
+                    .get_price_history(&outcome.token_id, chunk_start, chunk_end, fidelity_mins)
+                    .await
+                {
+                    Ok(points) => {
+                        let points: Vec<(i64, f64)> = points.into_iter().map(|p| (p.t, p.p)).collect();
+                        total += db.insert_price_history(&outcome.token_id, &points).await?;
+                    }
+                    Err(e) => {
+                        warn!("Price history failed for {} ({}): {:?}", outcome.token_id, outcome.name, e);
+                        break;
+                    }
+                }
+                chunk_start = chunk_end;
+            }
+
+            info!("Backfilled {} {} ({})", market.id, outcome.name, outcome.token_id);
+        }
+    }
+
+    Ok(total)
+}
@@ -0,0 +1,31 @@
+//! Polymarket trading engine.
+//!
+//! The `bot`, `server` and `botctl` binaries are thin wrappers over this crate;
+//! anything they wire together can be driven the same way from another crate or
+//! an integration test. The pieces most callers start from:
+//!
+//! - [`config::Config`] — every setting, loaded from the environment
+//! - [`adapters::database::Database`] — storage, SQLite or Postgres
+//! - [`feeds::FeedAggregator`] — market data in, strategy signals out
+//! - [`engine::risk::RiskManager`] and [`engine::order_manager::OrderManager`] —
+//!   the path from signal to order
+//! - [`engine::backtest::Backtester`] — strategies over recorded market data
+//! - [`strategy::Strategy`] — the trait every strategy implements
+
+pub mod adapters;
+pub mod api;
+pub mod bus;
+pub mod cache;
+pub mod clock;
+pub mod config;
+pub mod domain;
+pub mod engine;
+pub mod error;
+pub mod feeds;
+pub mod history;
+pub mod logging;
+pub mod metrics;
+pub mod shutdown;
+pub mod strategy;
+pub mod wallet;
+pub mod watchdog;
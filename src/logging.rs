@@ -0,0 +1,119 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use tracing::info;
+use tracing_subscriber::EnvFilter;
+
+use crate::domain::{Order, Signal, Trade};
+
+/// Target the trade events are logged under, so a log pipeline can pick them out
+const TARGET: &str = "trade_event";
+
+static JSON: AtomicBool = AtomicBool::new(false);
+
+/// Install the log subscriber. `LOG_FORMAT=json` writes one JSON object per line
+/// with the event's fields at the top level, and turns on the trade events below;
+/// anything else keeps the human-readable format. RUST_LOG overrides
+/// `default_filter` either way.
+pub fn init(default_filter: &str) {
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| default_filter.into());
+    let json = std::env::var("LOG_FORMAT").is_ok_and(|f| f.eq_ignore_ascii_case("json"));
+    if json {
+        JSON.store(true, Ordering::Relaxed);
+        let filter = match TARGET.parse() {
+            Ok(directive) => filter.add_directive(directive),
+            Err(_) => filter,
+        };
+        tracing_subscriber::fmt()
+            .json()
+            .flatten_event(true)
+            .with_current_span(false)
+            .with_span_list(false)
+            .with_env_filter(filter)
+            .init();
+    } else {
+        tracing_subscriber::fmt().with_env_filter(filter).init();
+    }
+}
+
+// Trade events: one per step of an order's life, with stable field names so they
+// can be joined with the orders, trades and signal records tables on `order_id`.
+// Only emitted in JSON mode; the text log already says the same in prose.
+
+/// A signal reaching the order manager, before any check
+pub fn signal(signal: &Signal) {
+    if !JSON.load(Ordering::Relaxed) {
+        return;
+    }
+    info!(
+        target: TARGET,
+        event = "signal",
+        strategy = %signal.strategy,
+        market_id = %signal.market_id,
+        token_id = signal.token_id.as_deref(),
+        side = %signal.side,
+        price = signal.price,
+        size = signal.size,
+        confidence = signal.confidence,
+        execution = ?signal.execution,
+        event_time = signal.event_time.map(|t| t.to_rfc3339()),
+    );
+}
+
+/// An order the exchange accepted
+pub fn order_submitted(order: &Order) {
+    if !JSON.load(Ordering::Relaxed) {
+        return;
+    }
+    info!(
+        target: TARGET,
+        event = "order_submitted",
+        order_id = %order.id,
+        remote_id = order.remote_id.as_deref(),
+        strategy = %order.strategy,
+        market_id = %order.market_id,
+        token_id = %order.token_id,
+        side = %order.side,
+        price = order.price,
+        size = order.size,
+        order_type = ?order.order_type,
+        replaces = order.replaces.as_deref(),
+    );
+}
+
+/// A trade booked against one of our orders
+pub fn fill(order: &Order, trade: &Trade, strategy: &str) {
+    if !JSON.load(Ordering::Relaxed) {
+        return;
+    }
+    info!(
+        target: TARGET,
+        event = "fill",
+        order_id = %order.id,
+        trade_id = %trade.id,
+        strategy = %strategy,
+        market_id = %trade.market_id,
+        token_id = %order.token_id,
+        side = %trade.side,
+        price = trade.price,
+        size = trade.size,
+        fee = trade.fee,
+    );
+}
+
+/// A signal turned away by the risk checks, with the rule that stopped it
+pub fn risk_reject(signal: &Signal, reason: &str, detail: &str) {
+    if !JSON.load(Ordering::Relaxed) {
+        return;
+    }
+    info!(
+        target: TARGET,
+        event = "risk_reject",
+        strategy = %signal.strategy,
+        market_id = %signal.market_id,
+        token_id = signal.token_id.as_deref(),
+        side = %signal.side,
+        price = signal.price,
+        size = signal.size,
+        reason = %reason,
+        detail = %detail,
+    );
+}
@@ -1,38 +1,146 @@
-mod adapters;
-mod api;
-mod config;
-mod domain;
-mod engine;
-mod feeds;
-mod strategy;
-
 use eyre::Result;
+use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Instant;
-use tokio::sync::{broadcast, RwLock};
-use tracing::info;
-
-use crate::adapters::binance::BinanceWsFeed;
-use crate::adapters::database::Database;
-use crate::adapters::polymarket::PolymarketClient;
-use crate::adapters::polymarket_ws::PolymarketWsFeed;
-use crate::config::Config;
-use crate::domain::{MarketData, Signal};
-use crate::engine::order_manager::OrderManager;
-use crate::engine::risk::RiskManager;
-use crate::feeds::FeedAggregator;
-use crate::strategy::latency_arb::LatencyArbStrategy;
-use crate::strategy::intra_arb::IntraArbStrategy;
+use tokio::sync::{mpsc, Mutex, RwLock};
+use tracing::{error, info, warn};
+
+use polymarket_bot::{api, clock, domain, engine, history, logging, strategy};
+use polymarket_bot::adapters::binance::BinanceWsFeed;
+use polymarket_bot::adapters::binance_futures::BinanceFuturesClient;
+use polymarket_bot::adapters::data_api::DataApiClient;
+use polymarket_bot::adapters::database::Database;
+use polymarket_bot::adapters::gamma::GammaClient;
+use polymarket_bot::adapters::kalshi::{KalshiClient, KalshiWsFeed};
+use polymarket_bot::adapters::calendar::CalendarFeed;
+use polymarket_bot::adapters::sports::SportsFeed;
+use polymarket_bot::adapters::polygon::PolygonRpc;
+use polymarket_bot::adapters::polymarket::PolymarketClient;
+use polymarket_bot::adapters::polymarket_ws::PolymarketWsFeed;
+use polymarket_bot::adapters::recorder::{self, MarketRecorder, ReplayFeed};
+use polymarket_bot::adapters::sqlite::SqliteStorage;
+use polymarket_bot::config::Config;
+use polymarket_bot::domain::{MarketId, NegRiskEvent};
+use polymarket_bot::bus::EventBus;
+use polymarket_bot::clock::{ManualClock, SharedClock};
+use polymarket_bot::engine::analytics;
+use polymarket_bot::engine::anomaly::AnomalyGuard;
+use polymarket_bot::engine::archive::MarketArchiver;
+use polymarket_bot::engine::allocator::PortfolioAllocator;
+use polymarket_bot::engine::calibration::CalibrationTracker;
+use polymarket_bot::engine::candles::CandleBuilder;
+use polymarket_bot::engine::checkpoint::Checkpointer;
+use polymarket_bot::engine::flow_alerts::FlowAlertDetector;
+use polymarket_bot::engine::hedging::Hedger;
+use polymarket_bot::engine::dust::DustSweeper;
+use polymarket_bot::engine::experiments::{self, ExperimentReporter};
+use polymarket_bot::engine::fill_detector::TapeFillDetector;
+use polymarket_bot::engine::inflight::InflightOrders;
+use polymarket_bot::engine::market_catalog::{MarketCatalog, MarketRefresher};
+use polymarket_bot::engine::market_rules::MarketRules;
+use polymarket_bot::engine::merge::PositionMerger;
+use polymarket_bot::engine::order_manager::OrderManager;
+use polymarket_bot::engine::order_sync::OrderSync;
+use polymarket_bot::engine::participation::ParticipationTracker;
+use polymarket_bot::engine::portfolio::Portfolio;
+use polymarket_bot::engine::readiness::Readiness;
+use polymarket_bot::engine::redemption::Redeemer;
+use polymarket_bot::engine::resolutions::ResolutionMonitor;
+use polymarket_bot::engine::risk::RiskManager;
+use polymarket_bot::engine::shadow::ShadowBook;
+use polymarket_bot::engine::snapshots::PnlSnapshotter;
+use polymarket_bot::engine::wallet_health::WalletMonitor;
+use polymarket_bot::engine::wallet_tracker::WalletTracker;
+use polymarket_bot::feeds::FeedAggregator;
+use polymarket_bot::strategy::copy_trade::CopyTradeStrategy;
+use polymarket_bot::strategy::cross_strike::CrossStrikeStrategy;
+use polymarket_bot::strategy::heartbeat::StrategyMonitor;
+use polymarket_bot::strategy::latency_arb::{self, LatencyArbStrategy};
+use polymarket_bot::strategy::intra_arb::IntraArbStrategy;
+use polymarket_bot::strategy::negrisk_arb::NegRiskArbStrategy;
+use polymarket_bot::strategy::in_game::InGameStrategy;
+use polymarket_bot::strategy::kalshi_arb::KalshiArbStrategy;
+use polymarket_bot::strategy::mean_reversion::MeanReversionStrategy;
+use polymarket_bot::strategy::resolution_snipe::ResolutionSnipeStrategy;
+use polymarket_bot::strategy::schedule::Schedule;
+use polymarket_bot::shutdown::Shutdown;
+use polymarket_bot::wallet::Wallet;
+use polymarket_bot::watchdog::Watchdog;
 
 #[tokio::main]
 async fn main() -> Result<()> {
     color_eyre::install()?;
-    tracing_subscriber::fmt()
-        .with_env_filter(
-            tracing_subscriber::EnvFilter::try_from_default_env()
-                .unwrap_or_else(|_| "polymarket_bot=info,tower_http=info".into()),
-        )
-        .init();
+    logging::init("polymarket_bot=info,bot=info,tower_http=info");
+
+    // Offline admin commands
+    let args: Vec<String> = std::env::args().collect();
+    match args.get(1).map(String::as_str) {
+        Some("restore") => {
+            let backup = args
+                .get(2)
+                .ok_or_else(|| eyre::eyre!("usage: bot restore <backup-file>"))?;
+            let db_path = Config::db_path();
+            SqliteStorage::restore(&db_path, backup).await?;
+            info!("Restored {} from {}", db_path, backup);
+            return Ok(());
+        }
+        Some("backfill") => {
+            let days = args.get(2).and_then(|d| d.parse().ok()).unwrap_or(history::DEFAULT_DAYS);
+            let fidelity = args
+                .get(3)
+                .and_then(|f| f.parse().ok())
+                .unwrap_or(history::DEFAULT_FIDELITY_MINS);
+            let config = Arc::new(Config::load()?);
+            let db = Database::connect(&config).await?;
+            let poly_client = PolymarketClient::new(config.clone())?;
+            let watched = GammaClient::new()?.resolve_watchlist(&config.watchlist).await;
+            let rows = history::backfill_price_history(&poly_client, &db, &watched, days, fidelity).await?;
+            info!("Backfill complete: {} new price points for {} markets", rows, watched.len());
+            return Ok(());
+        }
+        Some("optimize") => {
+            let (Some(path), Some(strategy), Some(grid)) = (args.get(2), args.get(3), args.get(4)) else {
+                return Err(eyre::eyre!(
+                    "usage: bot optimize <recording.jsonl> <strategy> <param=lo..hi[:n]|param=a|b,...>"
+                ));
+            };
+            let config = Config::load()?;
+            let grid = engine::walk_forward::ParamGrid::parse(grid)?;
+            let db = Database::connect(&config).await?;
+            let events = recorder::read_recording(path).await?;
+            let tokens = engine::backtest::recorded_tokens(&events);
+            let covered: Vec<domain::Market> = db
+                .get_markets()
+                .await?
+                .into_iter()
+                .filter(|m| m.outcomes.iter().any(|o| tokens.contains(&o.token_id)))
+                .collect();
+            let base = engine::walk_forward::instances(strategy, &covered, &config)?;
+            let backtester = engine::backtest::Backtester::new(
+                config.risk.starting_bankroll,
+                config.execution.max_slippage_bps,
+                config.stats.clone(),
+                config.fees.clone(),
+                MarketCatalog::load(&db).await?,
+            );
+            let report = engine::walk_forward::optimize(&backtester, &base, &grid, &config.walk_forward, &events).await?;
+            println!("{}", serde_json::to_string_pretty(&report)?);
+            return Ok(());
+        }
+        Some("whatif") => {
+            let config = Config::load()?;
+            let shocks = match args.get(2) {
+                Some(list) => engine::whatif::parse_shocks(list)
+                    .ok_or_else(|| eyre::eyre!("usage: bot whatif [shocks, e.g. -10,-3,3,10]"))?,
+                None => engine::whatif::DEFAULT_SHOCKS.to_vec(),
+            };
+            let db = Database::connect(&config).await?;
+            let report = engine::whatif::reprice(&db, &config.pricing, &shocks).await?;
+            println!("{}", serde_json::to_string_pretty(&report)?);
+            return Ok(());
+        }
+        _ => {}
+    }
 
     info!("🎰 Polymarket Bot starting up...");
 
@@ -40,95 +148,790 @@ async fn main() -> Result<()> {
     info!("Config loaded. Starting bankroll: ${:.2}", config.risk.starting_bankroll);
 
     // Database
-    let db = Database::new(&config.db_path).await?;
-    info!("Database initialized at {}", config.db_path);
+    let db = Database::connect(&config).await?;
+    info!("Database initialized ({}, schema v{})", db.backend(), db.schema_version().await?);
+    // One bus for everything the engine publishes. Market data is latest-value per
+    // instrument for each consumer; signals, order updates, fills and risk
+    // decisions are each delivered.
+    let bus = EventBus::new();
+    let orders = InflightOrders::load(db.clone()).await?.with_bus(bus.clone());
+    // One clock for the trading path: the aggregator, strategies, orders and risk.
+    // A replay runs it on the recording's time.
+    let replay_clock = match &config.replay_path {
+        Some(path) => recorder::first_timestamp(path).await?.map(|start| Arc::new(ManualClock::new(start))),
+        None => None,
+    };
+    let clock: SharedClock = match &replay_clock {
+        Some(replay_clock) => replay_clock.clone(),
+        None => clock::system(),
+    };
+    let portfolio = Portfolio::load(db.clone(), orders.clone())
+        .await?
+        .with_clock(clock.clone());
+    // Set on CTRL+C or SIGTERM, for the tasks that must wind down before exit
+    let shutdown = Shutdown::default();
 
-    // Shared state
-    let bankroll = Arc::new(RwLock::new(config.risk.starting_bankroll));
-    let risk = RiskManager::new(config.risk.clone());
+    // Shared state. The bankroll is the starting bankroll plus realized PnL: whatever
+    // records a close (fills, liquidation, redemption, merges) credits its PnL here,
+    // and a restart rebuilds it from the closed-trade ledger so the restored peak is
+    // measured against the same thing
+    let realized = db.get_realized_pnl().await?;
+    let bankroll = Arc::new(RwLock::new(config.risk.starting_bankroll + realized));
+    info!("Bankroll ${:.2} (${:.2} realized to date)", config.risk.starting_bankroll + realized, realized);
+    // Scheduled releases: strategies widen or stand down around them, risk blacks them out
+    let calendar = if config.calendar.enabled {
+        Some(CalendarFeed::new(&config.calendar)?)
+    } else {
+        None
+    };
+    let econ_events = calendar.as_ref().map(CalendarFeed::events).unwrap_or_default();
+    let risk = RiskManager::new(config.risk.clone())
+        .with_clock(clock.clone())
+        .with_audit(db.clone())
+        .with_bus(bus.clone())
+        .with_calendar(econ_events.clone());
+    let cooling = risk.load_cooldowns(&db).await?;
+    if cooling > 0 {
+        info!("{} market(s) on loss cooldown", cooling);
+    }
+    let lists = risk.load_market_lists(&db).await?;
+    if !lists.allow.is_empty() || !lists.deny.is_empty() {
+        info!("Market lists: {} allowed, {} denied", lists.allow.len(), lists.deny.len());
+    }
     let config = Arc::new(config);
 
-    // Polymarket REST client
+    // Polymarket REST client (validates PRIVATE_KEY and derives the wallet address)
     let poly_client = PolymarketClient::new(config.clone())?;
+    info!("Wallet address: {}", poly_client.address());
+    if config.replay_path.is_none() {
+        poly_client.verify_credentials().await?;
+    }
 
-    // Broadcast channels
-    let (market_tx, market_rx) = broadcast::channel::<MarketData>(1024);
-    let (signal_tx, signal_rx) = broadcast::channel::<Signal>(256);
+    // Whale prints and sweeps go back on the bus for the strategies
+    let flow_detector = if config.flow_alerts.enabled {
+        Some(FlowAlertDetector::new(bus.clone(), db.clone(), config.flow_alerts.clone())?)
+    } else {
+        None
+    };
+    // Signals to the order manager: bounded and lossless — a tick burst must never
+    // drop a trade signal
+    let (signal_tx, signal_rx) = mpsc::channel::<strategy::Intent>(256);
+    let (manual_tx, manual_rx) = mpsc::channel(16);
+    let dust_tx = manual_tx.clone();
+    let dispute_tx = manual_tx.clone();
 
-    // --- Market data feeds ---
-    // TODO: Configure actual market IDs from environment/config
-    let poly_ws = PolymarketWsFeed::new(market_tx.clone(), vec![]);
-    let binance_ws = BinanceWsFeed::new(market_tx.clone(), vec!["btcusdt".into()]);
+    // --- Watchlist ---
+    let gamma = GammaClient::new()?;
+    let watched = gamma.resolve_watchlist(&config.watchlist).await;
+    info!("Watching {} markets", watched.len());
+
+    // Strike ladders for cross-strike arb; their markets are fed but not traded by other strategies
+    let mut feed_markets = watched.clone();
+    let mut ladders = Vec::new();
+    if config.cross_strike.enabled {
+        for query in &config.cross_strike.groups {
+            let found = match gamma.search_markets(query).await {
+                Ok(found) => found,
+                Err(e) => {
+                    warn!("Cross-strike group {:?} failed: {:?}", query, e);
+                    continue;
+                }
+            };
+            for ladder in CrossStrikeStrategy::ladders(&found, &config.cross_strike) {
+                info!(
+                    "Cross-strike ladder {:?}: {} {} strikes",
+                    query,
+                    ladder.symbol,
+                    ladder.rungs.len()
+                );
+                for market_id in ladder.market_ids() {
+                    if !feed_markets.iter().any(|m| m.id == market_id) {
+                        feed_markets.extend(found.iter().find(|m| m.id == market_id).cloned());
+                    }
+                }
+                ladders.push(ladder);
+            }
+        }
+    }
+
+    // Polymarket/Kalshi pairs for cross-venue arb; paired markets are fed like ladders
+    let kalshi = if config.kalshi.enabled {
+        Some(KalshiClient::new(&config.kalshi)?)
+    } else {
+        None
+    };
+    let mut kalshi_pairs = Vec::new();
+    if kalshi.is_some() && !config.kalshi.pairs.is_empty() {
+        let market_ids: Vec<MarketId> = config.kalshi.pairs.keys().cloned().collect();
+        match gamma.get_markets(&market_ids).await {
+            Ok(found) => {
+                for market in found {
+                    let Some(ticker) = config.kalshi.pairs.get(&market.id) else {
+                        continue;
+                    };
+                    let Some(pair) = KalshiArbStrategy::from_market(&market, ticker, &config.kalshi) else {
+                        warn!("Kalshi pair {} → {} skipped: not a Yes/No market", market.id, ticker);
+                        continue;
+                    };
+                    info!("Kalshi pair: {} ↔ {}", market.question, ticker);
+                    if !feed_markets.iter().any(|m| m.id == market.id) {
+                        feed_markets.push(market);
+                    }
+                    kalshi_pairs.push(pair);
+                }
+            }
+            Err(e) => warn!("Kalshi pair lookup failed: {:?}", e),
+        }
+    }
+
+    // Game winner markets for in-game arb; the feed polls the scoreboards of their games
+    let mut game_strategies = Vec::new();
+    if config.sports.enabled && !config.sports.games.is_empty() {
+        let market_ids: Vec<MarketId> = config.sports.games.keys().cloned().collect();
+        match gamma.get_markets(&market_ids).await {
+            Ok(found) => {
+                for market in found {
+                    let Some(spec) = config.sports.games.get(&market.id) else {
+                        continue;
+                    };
+                    let Some(strategy) = InGameStrategy::from_market(&market, spec, &config.sports) else {
+                        warn!("Sports game {} → {} skipped: bad spec, unknown league or not Yes/No", market.id, spec);
+                        continue;
+                    };
+                    info!("In-game market: {} ↔ {}", market.question, spec);
+                    if !feed_markets.iter().any(|m| m.id == market.id) {
+                        feed_markets.push(market);
+                    }
+                    game_strategies.push(strategy);
+                }
+            }
+            Err(e) => warn!("Sports market lookup failed: {:?}", e),
+        }
+    }
+    let sports_feed = if game_strategies.is_empty() {
+        None
+    } else {
+        let games: Vec<(String, String)> = config
+            .sports
+            .games
+            .values()
+            .filter_map(|spec| {
+                let mut parts = spec.split(':');
+                Some((parts.next()?.to_string(), parts.next()?.to_string()))
+            })
+            .collect();
+        Some(SportsFeed::new(bus.clone(), &config.sports, &games)?)
+    };
+
+    // Market metadata for token → market lookups, starting with everything fed
+    let market_catalog = MarketCatalog::load(&db).await?;
+    let market_refresher = MarketRefresher::new(
+        db.clone(),
+        gamma.clone(),
+        market_catalog.clone(),
+        std::time::Duration::from_secs(config.market_refresh_secs),
+    );
+    let market_refresher = if config.replay_path.is_none() {
+        market_refresher.with_clob(poly_client.clone())
+    } else {
+        market_refresher
+    };
+    if let Err(e) = market_refresher.store(feed_markets.clone()).await {
+        warn!("Storing market metadata failed: {:?}", e);
+    }
 
     // --- Strategies ---
-    let strategies: Vec<Box<dyn strategy::Strategy>> = vec![
-        Box::new(LatencyArbStrategy::new(
-            "placeholder_market".into(),
-            "placeholder_yes_token".into(),
-            "placeholder_no_token".into(),
-            "BTCUSDT".into(),
-            100_000.0, // placeholder threshold
-        )),
-        Box::new(IntraArbStrategy::new(vec![])),
-    ];
+    let latency_strategies: Vec<LatencyArbStrategy> = watched
+        .iter()
+        .filter_map(LatencyArbStrategy::from_market)
+        .map(|s| {
+            s.with_pricing(&config.pricing)
+                .with_min_depth_share(config.binance.min_depth_share)
+                .with_calendar(&config.calendar)
+        })
+        .collect();
+    // Crypto strikes on the same asset share an underlying for exposure reporting
+    let underlyings: HashMap<MarketId, String> = latency_strategies
+        .iter()
+        .map(|s| (s.market_id.clone(), s.binance_symbol.clone()))
+        .collect();
+    let mut binance_symbols: Vec<String> = latency_strategies
+        .iter()
+        .map(|s| s.binance_symbol.to_lowercase())
+        .collect();
+    binance_symbols.sort();
+    binance_symbols.dedup();
+    if binance_symbols.is_empty() {
+        binance_symbols.push("btcusdt".into());
+    }
+
+    let mut anomaly_guard = AnomalyGuard::new(config.anomaly.clone());
+    for s in &latency_strategies {
+        anomaly_guard.watch(&s.market_id, &s.yes_token_id, &s.binance_symbol);
+    }
+
+    let mut strategies: Vec<Box<dyn strategy::Strategy>> = latency_strategies
+        .into_iter()
+        .map(|s| Box::new(s) as Box<dyn strategy::Strategy>)
+        .collect();
+    strategies.push(Box::new(IntraArbStrategy::from_markets(&watched)));
+    let neg_risk_events = NegRiskEvent::group(&watched);
+    if !neg_risk_events.is_empty() {
+        strategies.push(Box::new(NegRiskArbStrategy::new(neg_risk_events)));
+    }
+    strategies.extend(
+        watched
+            .iter()
+            .filter_map(|m| MeanReversionStrategy::from_market(m, &config.mean_reversion))
+            .map(|s| s.with_calendar(&config.calendar))
+            .map(|s| Box::new(s) as Box<dyn strategy::Strategy>),
+    );
+    strategies.extend(
+        watched
+            .iter()
+            .filter_map(|m| {
+                let vol = config.pricing.vol(latency_arb::threshold_market(m)?.0);
+                ResolutionSnipeStrategy::from_market(m, &config.resolution_snipe, vol)
+            })
+            .map(|s| Box::new(s) as Box<dyn strategy::Strategy>),
+    );
+    strategies.extend(ladders.into_iter().map(|s| Box::new(s) as Box<dyn strategy::Strategy>));
+    let kalshi_tickers: Vec<String> = kalshi_pairs.iter().map(|p| p.ticker.clone()).collect();
+    let kalshi_ws = kalshi.clone().filter(|_| !kalshi_tickers.is_empty()).map(|client| {
+        KalshiWsFeed::new(bus.clone(), client, config.kalshi.ws_url.clone(), kalshi_tickers.clone())
+    });
+    strategies.extend(kalshi_pairs.into_iter().map(|s| Box::new(s) as Box<dyn strategy::Strategy>));
+    strategies.extend(game_strategies.into_iter().map(|s| Box::new(s) as Box<dyn strategy::Strategy>));
+
+    // Copy trading: the tracker polls followed wallets, the strategy mirrors what it queues
+    if config.copy_trade.enabled && !config.copy_trade.wallets.is_empty() {
+        let tracker = Arc::new(WalletTracker::new(
+            DataApiClient::new()?,
+            config.copy_trade.wallets.clone(),
+            std::time::Duration::from_secs(config.copy_trade.poll_secs),
+        ));
+        if config.replay_path.is_none() {
+            let tracker = tracker.clone();
+            tokio::spawn(async move { tracker.run().await });
+        }
+        strategies.push(Box::new(CopyTradeStrategy::new(config.copy_trade.clone(), tracker)));
+    }
+
+    // A/B variants: more parameterizations of strategies built above, under their own names
+    experiments::register(&config.experiments, &mut strategies)?;
+
+    // Boot warm-up: nothing trades until each feed has delivered data for what it
+    // covers and open orders are reconciled
+    let readiness = config.replay_path.is_none().then(|| {
+        let readiness = Readiness::new(risk.clone(), config.readiness.clone());
+        let tokens = feed_markets
+            .iter()
+            .filter(|m| !m.closed)
+            .flat_map(|m| m.outcomes.iter().map(|o| o.token_id.to_string()));
+        readiness.expect("polymarket", tokens);
+        readiness.expect("binance", binance_symbols.iter().cloned());
+        if kalshi_ws.is_some() {
+            readiness.expect("kalshi", kalshi_tickers);
+        }
+        readiness
+    });
+
+    // --- Market data feeds ---
+    let poly_ws = PolymarketWsFeed::new(bus.clone(), feed_markets.iter().map(|m| m.id.clone()).collect());
+    let binance_ws = BinanceWsFeed::new(bus.clone(), binance_symbols)
+        .with_stream(config.binance.stream)
+        .with_depth(config.binance.depth_levels);
 
     // --- Feed aggregator (drives strategies) ---
-    let aggregator = FeedAggregator::new(market_rx, signal_tx.clone(), strategies, bankroll.clone());
+    let strategy_monitor = StrategyMonitor::new().with_clock(clock.clone());
+    for (name, expr) in &config.strategy_schedules {
+        if !strategies.iter().any(|s| s.name() == name) {
+            warn!("Schedule for unknown strategy '{}' ignored", name);
+            continue;
+        }
+        strategy_monitor.set_schedule(name, Schedule::parse(expr)?);
+        info!("Strategy {} scheduled: {}", name, expr);
+    }
+    let paused = strategy_monitor.load_paused(&db).await?;
+    if paused > 0 {
+        warn!("{} strategy(ies) paused by an operator", paused);
+    }
+    // Every experiment arm but the live one runs in shadow mode alongside the listed strategies
+    let experiments = experiments::from_config(&config.experiments);
+    let mut shadow_strategies = config.shadow_strategies.clone();
+    for experiment in &experiments {
+        info!("Experiment {}: {} live, arms {:?}", experiment.base, experiment.live, experiment.arms);
+        shadow_strategies.extend(experiment.shadowed().map(str::to_string));
+    }
+    for name in &shadow_strategies {
+        if strategies.iter().any(|s| s.name() == name) {
+            info!("Strategy {} runs in shadow mode", name);
+        } else {
+            warn!("Shadow mode for unknown strategy '{}' ignored", name);
+        }
+    }
+    // Per-strategy budgets; until the first rebalance (or with it off) each sizes off the full bankroll
+    let allocator = PortfolioAllocator::new(
+        db.clone(),
+        bankroll.clone(),
+        strategies.iter().map(|s| s.name().to_string()).collect(),
+        config.allocator.clone(),
+    );
+    let aggregator = FeedAggregator::new(
+        bus.clone(),
+        signal_tx.clone(),
+        strategies,
+        bankroll.clone(),
+        strategy_monitor.clone(),
+        anomaly_guard,
+        config.stats.clone(),
+    )
+    .with_portfolio(portfolio.clone())
+    .with_fees(config.fees.clone())
+    .with_allocations(allocator.allocations())
+    .with_calendar(econ_events)
+    .with_markets(market_catalog.clone())
+    .with_flow_alert_ttl(config.flow_alerts.ttl_secs)
+    .with_clock(clock.clone());
+    let aggregator = match &readiness {
+        Some(readiness) => aggregator.with_readiness(readiness.clone()),
+        None => aggregator,
+    };
+    // Replays send nothing anyway, and shadow fills from them would read as live
+    let aggregator = if shadow_strategies.is_empty() || config.replay_path.is_some() {
+        aggregator
+    } else {
+        let shadow = ShadowBook::load(
+            db.clone(),
+            shadow_strategies,
+            config.execution.max_slippage_bps,
+        )
+        .await?;
+        aggregator.with_shadow(shadow)
+    };
+
+    // --- Cold-start bootstrap: seed books, midpoints and spot via REST before strategies run ---
+    if config.replay_path.is_none() {
+        let mut snapshot = poly_client.snapshot(&feed_markets).await;
+        snapshot.extend(binance_ws.rest_snapshot().await);
+        aggregator.seed(&snapshot).await;
+    }
 
     // --- Order manager ---
+    let participation = Arc::new(
+        ParticipationTracker::new(db.clone(), DataApiClient::new()?, config.execution.max_participation)
+            .with_clock(clock.clone()),
+    );
+    tokio::spawn(participation.clone().run());
+    let snapshots = PnlSnapshotter::new(db.clone(), bankroll.clone(), risk.clone(), orders.clone())
+        .with_underlyings(underlyings)
+        .with_orderbooks(aggregator.orderbooks());
+
+    let calibration = CalibrationTracker::new(db.clone(), gamma.clone(), config.calibration.clone());
+
     let order_manager = OrderManager::new(
+        config.execution.clone(),
         poly_client.clone(),
         db.clone(),
+        portfolio.clone(),
         risk.clone(),
         bankroll.clone(),
-        signal_rx,
-    );
+        participation.clone(),
+    )
+    .with_kalshi(kalshi)
+    .with_orderbooks(aggregator.orderbooks())
+    .with_fees(config.fees.clone())
+    .with_snapshots(snapshots.clone())
+    .with_size_scales(calibration.scales())
+    .with_markets(market_catalog.clone())
+    .with_bus(bus.clone())
+    .with_clock(clock.clone())
+    .with_shutdown(shutdown.clone());
+    // Replays never reach the exchange, so there's nothing to hold orders to
+    let order_manager = if config.replay_path.is_none() {
+        order_manager.with_market_rules(MarketRules::new(poly_client.clone()).with_markets(&watched))
+    } else {
+        order_manager
+    };
+
+    // Gas on the signing wallet, which pays for redeems and splits
+    let wallet_monitor = if config.wallet_health.poll_secs > 0 && config.replay_path.is_none() {
+        Some(WalletMonitor::new(poly_client.address().to_string(), config.wallet_health.clone())?)
+    } else {
+        None
+    };
+
+    // Feeds, the aggregator and the order manager run under the watchdog, which
+    // restarts them when they die or stall
+    let watchdog = Watchdog::new(risk.clone(), config.watchdog.clone()).with_shutdown(shutdown.clone());
 
     // --- Dashboard API ---
     let app_state = Arc::new(api::AppState {
         db: db.clone(),
+        orders: orders.clone(),
+        manual_orders: config.replay_path.is_none().then_some(manual_tx),
+        events: bus.clone(),
+        prices: aggregator.prices(),
+        orderbooks: aggregator.orderbooks(),
+        snapshots: Some(snapshots.clone()),
+        wallet: wallet_monitor.as_ref().map(WalletMonitor::status),
+        tasks: Some(watchdog.statuses()),
+        readiness: readiness.clone(),
         risk: risk.clone(),
         poly_client: poly_client.clone(),
         bankroll: bankroll.clone(),
+        participation,
+        strategies: strategy_monitor,
+        config: config.clone(),
         start_time: Instant::now(),
     });
+    if config.api_auth.admin_token.is_none() {
+        warn!("DASHBOARD_TOKEN not set — mutating API routes (kill, etc.) are disabled");
+    }
+    api::spawn_grpc(&app_state);
     let app = api::router(app_state);
     let port = config.dashboard_port;
     let listener = tokio::net::TcpListener::bind(format!("0.0.0.0:{}", port)).await?;
     info!("Dashboard API running on http://0.0.0.0:{}", port);
 
+    // Credentials, database, watchlist and bootstrap are all through by now
+    if config.replay_path.is_none() {
+        if config.execution.auto_arm {
+            info!("Preflight checks passed, auto-arming order submission");
+            risk.arm();
+        } else {
+            warn!("Order submission is DISARMED — POST /api/arm once the state looks sane");
+        }
+    }
+
     // --- Spawn everything ---
-    tokio::spawn(async move { poly_ws.run().await });
-    tokio::spawn(async move { binance_ws.run().await });
-    tokio::spawn(async move { aggregator.run().await });
-    tokio::spawn(async move { order_manager.run().await });
+    let stall = std::time::Duration::from_secs(config.watchdog.stall_secs);
+    if let Some(path) = &config.record_path {
+        let recorder = MarketRecorder::new(bus.subscribe_lossless(), path.clone());
+        tokio::spawn(async move { recorder.run().await });
+    }
+    let candle_builder = CandleBuilder::new(db.clone(), bus.subscribe()).with_shutdown(shutdown.clone());
+    let candles = tokio::spawn(async move { candle_builder.run().await });
+    let mut order_manager_task = None;
+    if let Some(detector) = flow_detector {
+        tokio::spawn(detector.run());
+    }
+    if let Some(path) = &config.replay_path {
+        // Offline debugging: recorded data in, signals logged, nothing sent to the exchange
+        info!("Replay mode — live feeds and order submission disabled");
+        let mut replay = ReplayFeed::new(bus.clone(), path.clone(), config.replay_speed);
+        if let Some(replay_clock) = replay_clock {
+            replay = replay.with_clock(replay_clock);
+        }
+        tokio::spawn(async move { replay.run().await });
+        drop(order_manager);
+        // Keep the signal queue open so the aggregator keeps evaluating; signals are already logged
+        let mut signal_rx = signal_rx;
+        tokio::spawn(async move { while signal_rx.recv().await.is_some() {} });
+    } else {
+        let silence = std::time::Duration::from_secs(config.watchdog.feed_silence_secs);
+        watchdog.supervise("polymarket_ws", true, silence, move |heartbeat| {
+            poly_ws.clone().with_heartbeat(heartbeat).run()
+        });
+        watchdog.supervise("binance_ws", true, silence, move |heartbeat| {
+            binance_ws.clone().with_heartbeat(heartbeat).run()
+        });
+        if let Some(kalshi_ws) = kalshi_ws {
+            watchdog.supervise("kalshi_ws", false, silence, move |heartbeat| {
+                kalshi_ws.clone().with_heartbeat(heartbeat).run()
+            });
+        }
+        if let Some(sports_feed) = sports_feed {
+            tokio::spawn(sports_feed.run());
+        }
+        if let Some(calendar) = calendar {
+            tokio::spawn(calendar.run());
+        }
+        // The queues outlive any one run of the order manager
+        let order_manager = Arc::new(order_manager);
+        // Pick up where the last run left off before the order manager takes signals
+        let interrupted = order_manager.recover_executions().await?;
+        if interrupted > 0 {
+            warn!("{} multi-leg execution(s) were interrupted by the last shutdown", interrupted);
+        }
+        let checkpointer =
+            Checkpointer::new(db.clone(), risk.clone(), order_manager.clone()).with_shutdown(shutdown.clone());
+        checkpointer.restore().await?;
+        if config.checkpoint_secs > 0 {
+            let interval = std::time::Duration::from_secs(config.checkpoint_secs);
+            tokio::spawn(checkpointer.clone().run(interval));
+        }
+        let queues = Arc::new(Mutex::new((signal_rx, manual_rx)));
+        let supervised = order_manager.clone();
+        let task = watchdog.supervise("order_manager", true, stall, move |heartbeat| {
+            let order_manager = supervised.clone();
+            let queues = queues.clone();
+            async move {
+                let (signal_rx, manual_rx) = &mut *queues.lock().await;
+                order_manager.run(signal_rx, manual_rx, heartbeat).await
+            }
+        });
+        order_manager_task = Some((order_manager, task, checkpointer));
+    }
+    let aggregator = Arc::new(Mutex::new(aggregator));
+    watchdog.supervise("aggregator", true, stall, move |heartbeat| {
+        let aggregator = aggregator.clone();
+        async move {
+            aggregator.lock().await.run(heartbeat).await;
+            Ok::<_, polymarket_bot::error::Error>(())
+        }
+    });
+    tokio::spawn(watchdog.run());
+    tokio::spawn(async move { portfolio.run().await });
+
+    // Fill detection fallback from the public trade tape
+    if config.fill_tape_poll_secs > 0 && config.replay_path.is_none() {
+        let detector = TapeFillDetector::new(
+            db.clone(),
+            orders.clone(),
+            DataApiClient::new()?,
+            std::time::Duration::from_secs(config.fill_tape_poll_secs),
+        );
+        tokio::spawn(async move { detector.run().await });
+    }
+
+    tokio::spawn(analytics::run_nightly_summary(db.clone()));
+
+    // Exchange-side fills and cancels of resting orders
+    if config.order_sync_secs > 0 && config.replay_path.is_none() {
+        let mut sync = OrderSync::new(
+            orders.clone(),
+            poly_client.clone(),
+            std::time::Duration::from_secs(config.order_sync_secs),
+        );
+        if let Some((order_manager, ..)) = &order_manager_task {
+            sync = sync.with_fills(order_manager.clone());
+        }
+        if let Some(readiness) = &readiness {
+            sync = sync.with_readiness(readiness.clone());
+        }
+        tokio::spawn(async move { sync.run().await });
+    } else if let Some(readiness) = &readiness {
+        warn!("Order sync is off; open orders are not reconciled before trading");
+        readiness.reconciled();
+    }
+    if let Some(readiness) = readiness {
+        tokio::spawn(readiness.run());
+    }
+
+    // Rebalance strategy budgets from trailing performance
+    if config.allocator.rebalance_secs > 0 {
+        tokio::spawn(allocator.run());
+    }
+
+    // Settle recorded signals against market resolutions for confidence calibration
+    if config.calibration.poll_secs > 0 {
+        tokio::spawn(calibration.run());
+    }
+
+    // Move settled markets out of the hot tables
+    if config.archive_interval_secs > 0 {
+        let archiver = MarketArchiver::new(
+            db.clone(),
+            gamma.clone(),
+            std::time::Duration::from_secs(config.archive_interval_secs),
+        );
+        tokio::spawn(async move { archiver.run().await });
+    }
+
+    // Keep stored market metadata current
+    if config.market_refresh_secs > 0 {
+        tokio::spawn(market_refresher.run());
+    }
+
+    // Close or drop positions too small to be worth tracking
+    if config.execution.dust_sweep_secs > 0 && config.replay_path.is_none() {
+        let sweeper = DustSweeper::new(
+            db.clone(),
+            poly_client.clone(),
+            orders.clone(),
+            dust_tx,
+            config.execution.clone(),
+            std::time::Duration::from_secs(config.execution.dust_sweep_secs),
+        );
+        tokio::spawn(async move { sweeper.run().await });
+    }
+
+    if let Some(monitor) = wallet_monitor {
+        tokio::spawn(async move { monitor.run().await });
+    }
+
+    // Oracle resolution of held markets: disputes block new entries, and optionally exit
+    if config.resolution_monitor.poll_secs > 0 && config.replay_path.is_none() {
+        let monitor = ResolutionMonitor::new(
+            db.clone(),
+            gamma.clone(),
+            bus.clone(),
+            risk.clone(),
+            config.resolution_monitor.clone(),
+        );
+        let monitor = if config.resolution_monitor.exit_on_dispute {
+            monitor.with_exits(poly_client.clone(), orders.clone(), dispute_tx)
+        } else {
+            monitor
+        };
+        tokio::spawn(monitor.run());
+    }
+
+    // Settle resolved markets: redeem winners (or flag them for the operator) and credit the bankroll
+    if config.redemption.poll_secs > 0 && config.replay_path.is_none() {
+        let redeemer = Redeemer::new(db.clone(), gamma.clone(), bankroll.clone(), config.redemption.clone())?;
+        let redeemer = if config.redemption.on_chain {
+            redeemer.with_chain(
+                PolygonRpc::new(&config.wallet_health.rpc_url)?,
+                Wallet::from_private_key(&config.private_key)?,
+            )
+        } else {
+            redeemer
+        };
+        tokio::spawn(redeemer.run());
+    }
+
+    // Merge complete YES+NO sets back into collateral
+    if config.merge.poll_secs > 0 && config.replay_path.is_none() {
+        let merger = PositionMerger::new(
+            db.clone(),
+            market_catalog,
+            bankroll.clone(),
+            PolygonRpc::new(&config.wallet_health.rpc_url)?,
+            Wallet::from_private_key(&config.private_key)?,
+            config.merge.clone(),
+        );
+        tokio::spawn(merger.run());
+    }
+
+    // Persisted comparisons of A/B test arms
+    if !experiments.is_empty() && config.experiments.report_secs > 0 && config.replay_path.is_none() {
+        tokio::spawn(ExperimentReporter::new(db.clone(), config.experiments.clone()).run());
+    }
+
+    // Perp hedge against the spot delta of crypto threshold positions
+    if config.hedge.enabled && config.replay_path.is_none() {
+        let hedger = Hedger::new(
+            db.clone(),
+            BinanceFuturesClient::new(&config.hedge)?,
+            config.hedge.clone(),
+            config.pricing.clone(),
+            &watched,
+        );
+        tokio::spawn(hedger.run());
+    }
 
     // PnL snapshot task
-    let snapshot_db = db.clone();
-    let snapshot_bankroll = bankroll.clone();
-    let snapshot_risk = risk.clone();
+    if config.pnl_snapshot_secs > 0 {
+        let interval = std::time::Duration::from_secs(config.pnl_snapshot_secs);
+        let snapshots = snapshots.clone();
+        tokio::spawn(async move { snapshots.run(interval).await });
+    }
+
+    // Price history backfill for watched tokens (incremental after the first run)
+    let history_client = poly_client.clone();
+    let history_db = db.clone();
+    let history_markets = watched.clone();
     tokio::spawn(async move {
-        let mut interval = tokio::time::interval(std::time::Duration::from_secs(300));
-        loop {
-            interval.tick().await;
-            let br = *snapshot_bankroll.read().await;
-            snapshot_risk.update_bankroll(br).await;
-            let _ = snapshot_db.record_pnl_snapshot(br, br - 500.0).await;
+        match history::backfill_price_history(
+            &history_client,
+            &history_db,
+            &history_markets,
+            history::DEFAULT_DAYS,
+            history::DEFAULT_FIDELITY_MINS,
+        )
+        .await
+        {
+            Ok(rows) => info!("Price history backfill stored {} new points", rows),
+            Err(e) => error!("Price history backfill failed: {:?}", e),
         }
     });
 
-    // Serve API + graceful shutdown
-    axum::serve(listener, app)
-        .with_graceful_shutdown(shutdown_signal())
-        .await?;
+    // Scheduled DB backups (SQLite only; Postgres is backed up server-side)
+    if config.backup.interval_secs > 0 && config.database_url.is_none() {
+        let backup_db = db.clone();
+        let backup_cfg = config.backup.clone();
+        tokio::spawn(async move {
+            let mut interval =
+                tokio::time::interval(std::time::Duration::from_secs(backup_cfg.interval_secs));
+            loop {
+                interval.tick().await;
+                match backup_db.backup(&backup_cfg.dir, backup_cfg.keep).await {
+                    Ok(path) => info!("Database backed up to {}", path.display()),
+                    Err(e) => error!("Database backup failed: {:?}", e),
+                }
+            }
+        });
+    }
+
+    // Serve the API until told to stop
+    let server_shutdown = shutdown.clone();
+    let server = tokio::spawn(async move {
+        axum::serve(listener, app)
+            .with_graceful_shutdown(async move { server_shutdown.wait().await })
+            .await
+    });
+    shutdown_signal().await;
+    shutdown.trigger();
+
+    // Wind down: the order manager finishes the signal in hand and takes no more,
+    // resting orders are pulled if configured, engine state is checkpointed,
+    // buffered candles are written and a last PnL point is taken before queued
+    // writes are committed and the database is closed
+    let timeout = std::time::Duration::from_secs(config.shutdown.timeout_secs);
+    if let Some((order_manager, task, checkpointer)) = order_manager_task {
+        if tokio::time::timeout(timeout, task).await.is_err() {
+            warn!("Order manager still busy after {}s, shutting down anyway", timeout.as_secs());
+        }
+        if config.shutdown.cancel_orders {
+            match tokio::time::timeout(timeout, order_manager.cancel_all()).await {
+                Ok(Ok(())) => info!("Resting orders cancelled"),
+                Ok(Err(e)) => error!("Cancelling resting orders failed: {:?}", e),
+                Err(_) => error!("Cancelling resting orders timed out after {}s", timeout.as_secs()),
+            }
+        }
+        if let Err(e) = checkpointer.save().await {
+            error!("Final engine checkpoint failed: {:?}", e);
+        }
+    }
+    if tokio::time::timeout(timeout, candles).await.is_err() {
+        warn!("Candle builder did not flush within {}s", timeout.as_secs());
+    }
+    if let Err(e) = snapshots.snapshot().await {
+        error!("Final PnL snapshot failed: {:?}", e);
+    }
+    match tokio::time::timeout(timeout, server).await {
+        Ok(Ok(Err(e))) => error!("API server failed: {:?}", e),
+        Err(_) => warn!("API connections still open after {}s, closing them", timeout.as_secs()),
+        _ => {}
+    }
+    db.close().await;
 
     info!("🛑 Bot shutting down gracefully");
     Ok(())
 }
 
+/// CTRL+C, or SIGTERM from a service manager or container runtime
 async fn shutdown_signal() {
-    tokio::signal::ctrl_c()
-        .await
-        .expect("Failed to install CTRL+C signal handler");
+    let ctrl_c = async {
+        tokio::signal::ctrl_c()
+            .await
+            .expect("Failed to install CTRL+C signal handler");
+    };
+    #[cfg(unix)]
+    let terminate = async {
+        tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+            .expect("Failed to install SIGTERM handler")
+            .recv()
+            .await;
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {}
+        _ = terminate => {}
+    }
     info!("Shutdown signal received");
 }
@@ -0,0 +1,150 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::fmt::Write;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::LazyLock;
+
+/// Upper bounds (ms) of the latency buckets, Prometheus-style
+const BUCKETS_MS: &[f64] = &[
+    0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 25.0, 50.0, 100.0, 250.0, 500.0, 1000.0, 2500.0, 5000.0, 10000.0,
+];
+
+/// Process-wide hot-path latency metrics
+pub static METRICS: LazyLock<Metrics> = LazyLock::new(Metrics::default);
+
+/// Lock-free fixed-bucket histogram of millisecond latencies
+pub struct Histogram {
+    name: &'static str,
+    help: &'static str,
+    /// Per-bucket (non-cumulative) counts; last slot is +Inf
+    counts: Vec<AtomicU64>,
+    sum_us: AtomicU64,
+    count: AtomicU64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LatencySummary {
+    pub name: &'static str,
+    pub count: u64,
+    pub mean_ms: Option<f64>,
+    pub p50_ms: Option<f64>,
+    pub p95_ms: Option<f64>,
+    pub p99_ms: Option<f64>,
+}
+
+impl Histogram {
+    fn new(name: &'static str, help: &'static str) -> Self {
+        Self {
+            name,
+            help,
+            counts: (0..=BUCKETS_MS.len()).map(|_| AtomicU64::new(0)).collect(),
+            sum_us: AtomicU64::new(0),
+            count: AtomicU64::new(0),
+        }
+    }
+
+    pub fn observe_ms(&self, ms: f64) {
+        let ms = ms.max(0.0);
+        let idx = BUCKETS_MS.iter().position(|&b| ms <= b).unwrap_or(BUCKETS_MS.len());
+        self.counts[idx].fetch_add(1, Ordering::Relaxed);
+        self.sum_us.fetch_add((ms * 1000.0) as u64, Ordering::Relaxed);
+        self.count.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Observe the wall-clock time elapsed since `since`
+    pub fn observe_since(&self, since: DateTime<Utc>) {
+        let elapsed = Utc::now() - since;
+        self.observe_ms(elapsed.num_microseconds().unwrap_or(i64::MAX) as f64 / 1000.0);
+    }
+
+    /// Quantile estimate by linear interpolation within the containing bucket
+    pub fn quantile(&self, q: f64) -> Option<f64> {
+        let total = self.count.load(Ordering::Relaxed);
+        if total == 0 {
+            return None;
+        }
+        let rank = q * total as f64;
+        let mut cumulative = 0u64;
+        for (i, c) in self.counts.iter().enumerate() {
+            let c = c.load(Ordering::Relaxed);
+            if c > 0 && (cumulative + c) as f64 >= rank {
+                let lower = if i == 0 { 0.0 } else { BUCKETS_MS[i - 1] };
+                // +Inf bucket: best we can say is "above the last bound"
+                let Some(&upper) = BUCKETS_MS.get(i) else { return Some(lower) };
+                let frac = (rank - cumulative as f64) / c as f64;
+                return Some(lower + (upper - lower) * frac.clamp(0.0, 1.0));
+            }
+            cumulative += c;
+        }
+        None
+    }
+
+    pub fn summary(&self) -> LatencySummary {
+        let count = self.count.load(Ordering::Relaxed);
+        LatencySummary {
+            name: self.name,
+            count,
+            mean_ms: (count > 0).then(|| self.sum_us.load(Ordering::Relaxed) as f64 / 1000.0 / count as f64),
+            p50_ms: self.quantile(0.50),
+            p95_ms: self.quantile(0.95),
+            p99_ms: self.quantile(0.99),
+        }
+    }
+
+    fn render(&self, out: &mut String) {
+        let name = format!("polymarket_bot_{}_seconds", self.name);
+        let _ = writeln!(out, "# HELP {} {}", name, self.help);
+        let _ = writeln!(out, "# TYPE {} histogram", name);
+        let mut cumulative = 0u64;
+        for (i, c) in self.counts.iter().enumerate() {
+            cumulative += c.load(Ordering::Relaxed);
+            let le = BUCKETS_MS.get(i).map(|b| (b / 1000.0).to_string()).unwrap_or_else(|| "+Inf".into());
+            let _ = writeln!(out, "{}_bucket{{le=\"{}\"}} {}", name, le, cumulative);
+        }
+        let _ = writeln!(out, "{}_sum {}", name, self.sum_us.load(Ordering::Relaxed) as f64 / 1_000_000.0);
+        let _ = writeln!(out, "{}_count {}", name, self.count.load(Ordering::Relaxed));
+    }
+}
+
+/// Latency along the tick → signal → order pipeline, and of the database writes
+/// taken off it
+pub struct Metrics {
+    /// Market data receipt → strategy emits a signal
+    pub tick_to_signal: Histogram,
+    /// Market data receipt → order handed to the exchange client
+    pub tick_to_submit: Histogram,
+    /// Order POST → exchange acknowledgement
+    pub order_ack: Histogram,
+    /// One write-behind batch committed to the database, off the order path
+    pub db_batch: Histogram,
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self {
+            tick_to_signal: Histogram::new("tick_to_signal", "Market data receipt to signal emission"),
+            tick_to_submit: Histogram::new("tick_to_submit", "Market data receipt to order submission"),
+            order_ack: Histogram::new("order_ack", "Order POST to exchange acknowledgement"),
+            db_batch: Histogram::new("db_batch", "Queued database write batch commit"),
+        }
+    }
+}
+
+impl Metrics {
+    fn all(&self) -> [&Histogram; 4] {
+        [&self.tick_to_signal, &self.tick_to_submit, &self.order_ack, &self.db_batch]
+    }
+
+    /// Prometheus text exposition format
+    pub fn render_prometheus(&self) -> String {
+        let mut out = String::new();
+        for h in self.all() {
+            h.render(&mut out);
+        }
+        out
+    }
+
+    pub fn summaries(&self) -> Vec<LatencySummary> {
+        self.all().iter().map(|h| h.summary()).collect()
+    }
+}
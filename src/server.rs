@@ -1,44 +1,76 @@
-mod adapters;
-mod api;
-mod config;
-mod domain;
-mod engine;
-mod feeds;
-mod strategy;
-
 use eyre::Result;
 use std::sync::Arc;
 use std::time::Instant;
 use tokio::sync::RwLock;
-use tracing::info;
+use tracing::{info, warn};
 
-use crate::adapters::database::Database;
-use crate::adapters::polymarket::PolymarketClient;
-use crate::config::Config;
-use crate::engine::risk::RiskManager;
+use polymarket_bot::{api, logging};
+use polymarket_bot::adapters::data_api::DataApiClient;
+use polymarket_bot::adapters::database::Database;
+use polymarket_bot::adapters::polymarket::PolymarketClient;
+use polymarket_bot::bus::EventBus;
+use polymarket_bot::config::Config;
+use polymarket_bot::engine::inflight::InflightOrders;
+use polymarket_bot::engine::participation::ParticipationTracker;
+use polymarket_bot::engine::risk::RiskManager;
+use polymarket_bot::feeds::{BookCache, PriceCache};
+use polymarket_bot::strategy::heartbeat::StrategyMonitor;
 
 #[tokio::main]
 async fn main() -> Result<()> {
     color_eyre::install()?;
-    tracing_subscriber::fmt()
-        .with_env_filter(tracing_subscriber::EnvFilter::new("polymarket_bot=info,tower_http=info"))
-        .init();
+    logging::init("polymarket_bot=info,server=info,tower_http=info");
 
     let config = Config::load()?;
-    let db = Database::new(&config.db_path).await?;
-    let risk = RiskManager::new(config.risk.clone());
+    let db = Database::connect(&config).await?;
+    // Order changes and risk decisions made through the API, for streaming clients
+    let events = EventBus::new();
+    let orders = InflightOrders::load(db.clone()).await?.with_bus(events.clone());
+    let risk = RiskManager::new(config.risk.clone())
+        .with_audit(db.clone())
+        .with_bus(events.clone());
+    let cooling = risk.load_cooldowns(&db).await?;
+    if cooling > 0 {
+        info!("{} market(s) on loss cooldown", cooling);
+    }
+    let lists = risk.load_market_lists(&db).await?;
+    if !lists.allow.is_empty() || !lists.deny.is_empty() {
+        info!("Market lists: {} allowed, {} denied", lists.allow.len(), lists.deny.len());
+    }
     let config = Arc::new(config);
     let poly_client = PolymarketClient::new(config.clone())?;
-    let bankroll = Arc::new(RwLock::new(config.risk.starting_bankroll));
+    // Starting bankroll plus what the closed-trade ledger has realized, as the bot counts it
+    let bankroll = Arc::new(RwLock::new(config.risk.starting_bankroll + db.get_realized_pnl().await?));
+    let participation = Arc::new(ParticipationTracker::new(
+        db.clone(),
+        DataApiClient::new()?,
+        config.execution.max_participation,
+    ));
 
     let app_state = Arc::new(api::AppState {
         db,
+        orders,
+        manual_orders: None,
+        events,
+        prices: PriceCache::new(),
+        orderbooks: BookCache::new(),
+        snapshots: None,
+        wallet: None,
+        tasks: None,
+        readiness: None,
         risk,
         poly_client,
         bankroll,
+        participation,
+        strategies: StrategyMonitor::new(),
+        config: config.clone(),
         start_time: Instant::now(),
     });
+    if config.api_auth.admin_token.is_none() {
+        warn!("DASHBOARD_TOKEN not set — mutating API routes (kill, etc.) are disabled");
+    }
 
+    api::spawn_grpc(&app_state);
     let app = api::router(app_state);
     let port = config.dashboard_port;
     let listener = tokio::net::TcpListener::bind(format!("0.0.0.0:{}", port)).await?;
@@ -0,0 +1,35 @@
+use std::sync::Arc;
+use tokio::sync::watch;
+
+/// Process-wide shutdown flag. Set once on CTRL+C or SIGTERM; tasks that hold
+/// work in flight or in buffers watch it to finish up before the process exits.
+#[derive(Debug, Clone)]
+pub struct Shutdown {
+    tx: Arc<watch::Sender<bool>>,
+    rx: watch::Receiver<bool>,
+}
+
+impl Default for Shutdown {
+    fn default() -> Self {
+        let (tx, rx) = watch::channel(false);
+        Self { tx: Arc::new(tx), rx }
+    }
+}
+
+impl Shutdown {
+    pub fn trigger(&self) {
+        self.tx.send_replace(true);
+    }
+
+    pub fn is_triggered(&self) -> bool {
+        *self.rx.borrow()
+    }
+
+    /// Resolves once shutdown is triggered, at once if it already has been. Safe
+    /// to use as a `select!` branch.
+    pub async fn wait(&self) {
+        let mut rx = self.rx.clone();
+        // Only fails with the sender gone, which `self` keeps alive
+        let _ = rx.wait_for(|stopping| *stopping).await;
+    }
+}
@@ -0,0 +1,100 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+use crate::config::CopyTradeConfig;
+use crate::domain::{Execution, MarketData, Side, Signal};
+use crate::engine::wallet_tracker::WalletTracker;
+use crate::strategy::{Strategy, StrategyContext};
+
+/// Copy trading: mirror the entries of tracked wallets at a fraction of their size.
+///
+/// Each wallet has an allocation cap on the notional mirrored since startup, so a
+/// single prolific trader can't take over the book. Entries that are stale, priced
+/// near certainty, in markets known to have closed, or already moved more than
+/// `max_slippage` against us are skipped.
+pub struct CopyTradeStrategy {
+    pub config: CopyTradeConfig,
+    tracker: Arc<WalletTracker>,
+    /// Wallet → USDC mirrored so far
+    allocated: Mutex<HashMap<String, f64>>,
+}
+
+impl CopyTradeStrategy {
+    pub fn new(config: CopyTradeConfig, tracker: Arc<WalletTracker>) -> Self {
+        Self {
+            config,
+            tracker,
+            allocated: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Strategy for CopyTradeStrategy {
+    fn name(&self) -> &str {
+        "copy_trade"
+    }
+
+    fn enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    fn params(&self) -> serde_json::Value {
+        serde_json::to_value(&self.config).unwrap_or_default()
+    }
+
+    async fn evaluate(&self, ctx: &StrategyContext) -> Vec<Signal> {
+        let entries = self.tracker.take_entries().await;
+        if entries.is_empty() {
+            return Vec::new();
+        }
+
+        let now = ctx.now;
+        let mut allocated = self.allocated.lock().await;
+        let mut signals = Vec::new();
+        for entry in entries {
+            if (now - entry.timestamp).num_seconds() > self.config.max_age_secs as i64
+                || entry.price > self.config.max_price
+                || ctx.blocked_markets.contains(&entry.market_id)
+                || ctx.markets.by_token(&entry.token_id).is_some_and(|m| m.closed)
+            {
+                continue;
+            }
+            // Unwatched markets have no book; their fill price is the best we know
+            let price = ctx.cost(&entry.token_id).unwrap_or(entry.price);
+            if price > entry.price + self.config.max_slippage {
+                tracing::info!(
+                    "Copy of {} on {} skipped: price {:.4} ran from their {:.4}",
+                    entry.wallet, entry.market_id, price, entry.price
+                );
+                continue;
+            }
+
+            let used = allocated.entry(entry.wallet.clone()).or_insert(0.0);
+            let remaining = self.config.wallet_cap(&entry.wallet) - *used;
+            let notional = (entry.size * entry.price * self.config.size_ratio).min(remaining);
+            if notional <= 0.0 {
+                tracing::info!("Copy of {} skipped: wallet allocation cap reached", entry.wallet);
+                continue;
+            }
+            *used += notional;
+
+            signals.push(Signal {
+                strategy: self.name().to_string(),
+                market_id: entry.market_id,
+                side: Side::Buy,
+                confidence: 0.6,
+                price,
+                size: notional / price,
+                event_time: ctx.latest_event.as_ref().map(MarketData::timestamp),
+                token_id: Some(entry.token_id),
+                order_type: None,
+                execution: Execution::Passive,
+                expires_at: None,
+                good_till: None,
+            });
+        }
+        signals
+    }
+}
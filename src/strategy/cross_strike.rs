@@ -0,0 +1,154 @@
+use std::collections::BTreeMap;
+
+use crate::config::CrossStrikeConfig;
+use crate::domain::{Execution, Market, MarketData, MarketId, Side, Signal, TokenId};
+use crate::engine::fees;
+use crate::strategy::latency_arb::threshold_market;
+use crate::strategy::{Interest, Strategy, StrategyContext};
+
+/// One strike of a ladder
+#[derive(Debug, Clone)]
+pub struct Rung {
+    pub market_id: MarketId,
+    pub strike: f64,
+    pub yes_token_id: TokenId,
+    pub no_token_id: TokenId,
+}
+
+/// Cross-strike arbitrage on a ladder of "<asset> above $X" markets sharing an expiry.
+///
+/// P(spot > K) can only fall as K rises, so a higher strike's YES priced above a
+/// lower strike's is a monotonicity violation. Buying YES on the lower strike and
+/// NO on the higher one pays at least 1 whatever spot does (2 if it settles between
+/// them), for less than 1 — the violation is the locked-in edge.
+pub struct CrossStrikeStrategy {
+    pub config: CrossStrikeConfig,
+    pub symbol: String,
+    /// Sorted by strike, ascending
+    pub rungs: Vec<Rung>,
+}
+
+impl CrossStrikeStrategy {
+    /// Split the markets one group query matched into ladders by asset and expiry
+    pub fn ladders(markets: &[Market], config: &CrossStrikeConfig) -> Vec<Self> {
+        let mut groups: BTreeMap<(&str, Option<i64>), Vec<Rung>> = BTreeMap::new();
+        for market in markets {
+            let Some((symbol, strike)) = threshold_market(market) else {
+                continue;
+            };
+            let Some((yes_token_id, no_token_id)) = market.yes_no() else {
+                continue;
+            };
+            let expiry = market.end_date.map(|d| d.timestamp());
+            groups.entry((symbol, expiry)).or_default().push(Rung {
+                market_id: market.id.clone(),
+                strike,
+                yes_token_id: yes_token_id.clone(),
+                no_token_id: no_token_id.clone(),
+            });
+        }
+
+        groups
+            .into_iter()
+            .filter(|(_, rungs)| rungs.len() >= 2)
+            .map(|((symbol, _), mut rungs)| {
+                rungs.sort_by(|a, b| a.strike.total_cmp(&b.strike));
+                rungs.dedup_by(|a, b| a.strike == b.strike);
+                Self {
+                    config: config.clone(),
+                    symbol: symbol.to_string(),
+                    rungs,
+                }
+            })
+            .filter(|ladder| ladder.rungs.len() >= 2)
+            .collect()
+    }
+
+    /// Market IDs of every strike, for feed subscriptions
+    pub fn market_ids(&self) -> impl Iterator<Item = &str> {
+        self.rungs.iter().map(|r| r.market_id.as_str())
+    }
+}
+
+#[async_trait::async_trait]
+impl Strategy for CrossStrikeStrategy {
+    fn name(&self) -> &str {
+        "cross_strike"
+    }
+
+    fn enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    fn interest(&self) -> Interest {
+        Interest::markets(self.rungs.iter().map(|rung| &rung.market_id))
+    }
+
+    fn params(&self) -> serde_json::Value {
+        serde_json::json!({
+            "min_violation": self.config.min_violation,
+            "max_position_pct": self.config.max_position_pct,
+        })
+    }
+
+    async fn evaluate(&self, ctx: &StrategyContext) -> Vec<Signal> {
+        let held = |rung: &Rung| ctx.positions.iter().any(|p| p.market_id == rung.market_id && p.size > 0.0);
+        let tradable = |rung: &Rung| !ctx.blocked_markets.contains(&rung.market_id) && !held(rung);
+
+        // Widest violation across every (lower, higher) strike pair
+        let mut best: Option<(f64, &Rung, f64, &Rung, f64)> = None;
+        for (i, low) in self.rungs.iter().enumerate() {
+            let Some(low_yes) = ctx.cost(&low.yes_token_id) else {
+                continue;
+            };
+            for high in &self.rungs[i + 1..] {
+                let Some(high_no) = ctx.cost(&high.no_token_id) else {
+                    continue;
+                };
+                let edge = 1.0 - fees::taker_cost(&ctx.fees, low_yes) - fees::taker_cost(&ctx.fees, high_no);
+                if edge >= self.config.min_violation
+                    && best.is_none_or(|(best_edge, ..)| edge > best_edge)
+                    && tradable(low)
+                    && tradable(high)
+                {
+                    best = Some((edge, low, low_yes, high, high_no));
+                }
+            }
+        }
+        let Some((edge, low, low_yes, high, high_no)) = best else {
+            return Vec::new();
+        };
+
+        let size = ctx.budget(self.name()) * self.config.max_position_pct / (low_yes + high_no);
+        tracing::info!(
+            "Cross-strike violation on {}: >{} YES {:.3} + >{} NO {:.3} = {:.3} (edge {:.3} after fees)",
+            self.symbol,
+            low.strike,
+            low_yes,
+            high.strike,
+            high_no,
+            low_yes + high_no,
+            edge
+        );
+
+        let event_time = ctx.latest_event.as_ref().map(MarketData::timestamp);
+        let leg = |rung: &Rung, token_id: &str, price: f64| Signal {
+            strategy: self.name().to_string(),
+            market_id: rung.market_id.clone(),
+            side: Side::Buy,
+            confidence: (0.5 + edge * 5.0).min(0.99),
+            price,
+            size,
+            event_time,
+            token_id: Some(token_id.into()),
+            order_type: None,
+            execution: Execution::Passive,
+            expires_at: None,
+            good_till: None,
+        };
+        vec![
+            leg(low, &low.yes_token_id, low_yes),
+            leg(high, &high.no_token_id, high_no),
+        ]
+    }
+}
@@ -0,0 +1,84 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+
+use crate::config::StatsConfig;
+use crate::domain::Side;
+
+/// Aggressor flow on one token over the stats window, as of its latest trade
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct TradeFlow {
+    pub trades: usize,
+    /// Shares bought by takers
+    pub buy_volume: f64,
+    /// Shares sold by takers
+    pub sell_volume: f64,
+    /// Volume-weighted price over the window
+    pub vwap: f64,
+    pub last_price: f64,
+}
+
+impl TradeFlow {
+    /// Net taker buying as a share of volume, from -1 (all selling) to 1 (all buying)
+    pub fn imbalance(&self) -> f64 {
+        let volume = self.buy_volume + self.sell_volume;
+        if volume > 0.0 { (self.buy_volume - self.sell_volume) / volume } else { 0.0 }
+    }
+}
+
+struct Print {
+    ts: DateTime<Utc>,
+    price: f64,
+    size: f64,
+    side: Side,
+}
+
+/// Rolling trade flow per token from the public tape
+pub struct FlowTracker {
+    window: Duration,
+    prints: HashMap<String, VecDeque<Print>>,
+    /// Shared with the contexts built from it; see `snapshot`
+    latest: Arc<HashMap<String, TradeFlow>>,
+}
+
+impl FlowTracker {
+    pub fn new(config: &StatsConfig) -> Self {
+        Self {
+            window: Duration::seconds(config.window_secs as i64),
+            prints: HashMap::new(),
+            latest: Arc::default(),
+        }
+    }
+
+    pub fn record(&mut self, token_id: &str, ts: DateTime<Utc>, price: f64, size: f64, side: Side) {
+        let prints = self.prints.entry(token_id.to_string()).or_default();
+        prints.push_back(Print { ts, price, size, side });
+        while prints.front().is_some_and(|p| ts - p.ts > self.window) {
+            prints.pop_front();
+        }
+
+        let mut flow = TradeFlow {
+            trades: prints.len(),
+            last_price: price,
+            ..TradeFlow::default()
+        };
+        let mut notional = 0.0;
+        for print in prints.iter() {
+            match print.side {
+                Side::Buy => flow.buy_volume += print.size,
+                Side::Sell => flow.sell_volume += print.size,
+            }
+            notional += print.price * print.size;
+        }
+        let volume = flow.buy_volume + flow.sell_volume;
+        flow.vwap = if volume > 0.0 { notional / volume } else { price };
+        Arc::make_mut(&mut self.latest).insert(token_id.to_string(), flow);
+    }
+
+    /// Latest flow per token, for the strategy context; shared like
+    /// `StatsTracker::snapshot`
+    pub fn snapshot(&self) -> Arc<HashMap<String, TradeFlow>> {
+        self.latest.clone()
+    }
+}
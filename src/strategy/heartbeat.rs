@@ -0,0 +1,163 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::{Arc, Mutex};
+use tracing::warn;
+
+use crate::adapters::database::Database;
+use crate::clock::{self, SharedClock};
+use crate::error::Result;
+use crate::strategy::schedule::Schedule;
+
+/// Config KV key the paused strategies are persisted under, as a JSON array
+const PAUSED_KEY: &str = "paused_strategies";
+
+/// Liveness counters for one strategy (all instances sharing its name)
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct StrategyHeartbeat {
+    pub name: String,
+    pub instances: usize,
+    pub enabled: bool,
+    /// Paused by an operator: not evaluated until resumed, across restarts
+    pub paused: bool,
+    /// Parameters as loaded, from the first registered instance
+    pub params: serde_json::Value,
+    /// Activation window (cron expression, UTC); None runs around the clock
+    pub schedule: Option<String>,
+    /// Inside the activation window right now
+    pub scheduled: bool,
+    pub evaluations: u64,
+    pub signals: u64,
+    pub last_evaluation: Option<DateTime<Utc>>,
+    pub last_signal: Option<DateTime<Utc>>,
+    pub secs_since_evaluation: Option<i64>,
+    pub secs_since_signal: Option<i64>,
+}
+
+/// Shared per-strategy heartbeat registry, written by the feed aggregator and
+/// read by the dashboard, so a strategy that stopped seeing events stands out.
+#[derive(Clone)]
+pub struct StrategyMonitor {
+    inner: Arc<Mutex<BTreeMap<String, StrategyHeartbeat>>>,
+    schedules: Arc<Mutex<BTreeMap<String, Schedule>>>,
+    paused: Arc<Mutex<BTreeSet<String>>>,
+    clock: SharedClock,
+}
+
+impl Default for StrategyMonitor {
+    fn default() -> Self {
+        Self {
+            inner: Arc::default(),
+            schedules: Arc::default(),
+            paused: Arc::default(),
+            clock: clock::system(),
+        }
+    }
+}
+
+impl StrategyMonitor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Called once per strategy instance at startup so idle strategies still show up
+    pub fn register(&self, name: &str, enabled: bool, params: serde_json::Value) {
+        let mut inner = self.inner.lock().unwrap();
+        let hb = inner.entry(name.to_string()).or_insert_with(|| StrategyHeartbeat {
+            name: name.to_string(),
+            ..Default::default()
+        });
+        hb.instances += 1;
+        hb.enabled |= enabled;
+        if hb.params.is_null() {
+            hb.params = params;
+        }
+    }
+
+    /// Restrict a strategy (all its instances) to a cron-style activation window
+    pub fn set_schedule(&self, name: &str, schedule: Schedule) {
+        self.schedules.lock().unwrap().insert(name.to_string(), schedule);
+    }
+
+    /// Whether the strategy's schedule, if any, allows it to run at `now`
+    pub fn is_scheduled(&self, name: &str, now: &DateTime<Utc>) -> bool {
+        self.schedules
+            .lock()
+            .unwrap()
+            .get(name)
+            .is_none_or(|s| s.is_active(now))
+    }
+
+    pub fn is_paused(&self, name: &str) -> bool {
+        self.paused.lock().unwrap().contains(name)
+    }
+
+    /// Pause the strategies an operator left paused in an earlier run
+    pub async fn load_paused(&self, db: &Database) -> Result<usize> {
+        let Some(stored) = db.get_config(PAUSED_KEY).await? else {
+            return Ok(0);
+        };
+        let paused: BTreeSet<String> = serde_json::from_str(&stored)?;
+        let count = paused.len();
+        *self.paused.lock().unwrap() = paused;
+        Ok(count)
+    }
+
+    /// Pause `name` (or resume it when `paused` is false) and persist the paused
+    /// set; returns the strategy's heartbeat after the change, None when no
+    /// strategy goes by that name
+    pub async fn set_paused(&self, db: &Database, name: &str, paused: bool) -> Result<Option<StrategyHeartbeat>> {
+        if !self.inner.lock().unwrap().contains_key(name) {
+            return Ok(None);
+        }
+        let mut updated = self.paused.lock().unwrap().clone();
+        let changed = if paused { updated.insert(name.to_string()) } else { updated.remove(name) };
+        if changed {
+            db.set_config(PAUSED_KEY, &serde_json::to_string(&updated)?).await?;
+            *self.paused.lock().unwrap() = updated;
+            warn!("Strategy {} {}", name, if paused { "paused" } else { "resumed" });
+        }
+        Ok(self.snapshot().into_iter().find(|hb| hb.name == name))
+    }
+
+    pub fn record_evaluation(&self, name: &str, signals: usize) {
+        let now = self.clock.now();
+        let mut inner = self.inner.lock().unwrap();
+        let hb = inner.entry(name.to_string()).or_insert_with(|| StrategyHeartbeat {
+            name: name.to_string(),
+            ..Default::default()
+        });
+        hb.evaluations += 1;
+        hb.last_evaluation = Some(now);
+        if signals > 0 {
+            hb.signals += signals as u64;
+            hb.last_signal = Some(now);
+        }
+    }
+
+    pub fn snapshot(&self) -> Vec<StrategyHeartbeat> {
+        let now = self.clock.now();
+        let schedules = self.schedules.lock().unwrap();
+        let paused = self.paused.lock().unwrap();
+        self.inner
+            .lock()
+            .unwrap()
+            .values()
+            .cloned()
+            .map(|mut hb| {
+                hb.secs_since_evaluation = hb.last_evaluation.map(|t| (now - t).num_seconds());
+                hb.secs_since_signal = hb.last_signal.map(|t| (now - t).num_seconds());
+                let schedule = schedules.get(&hb.name);
+                hb.schedule = schedule.map(|s| s.to_string());
+                hb.scheduled = schedule.is_none_or(|s| s.is_active(&now));
+                hb.paused = paused.contains(&hb.name);
+                hb
+            })
+            .collect()
+    }
+}
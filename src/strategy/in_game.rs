@@ -0,0 +1,177 @@
+use std::sync::Arc;
+
+use crate::adapters::sports;
+use crate::config::SportsConfig;
+use crate::domain::{Execution, GameState, GameStatus, Market, MarketData, MarketId, Side, Signal, TokenId};
+use crate::engine::{fees, pricing};
+use crate::strategy::{Interest, Strategy, StrategyContext};
+
+/// Probability the home side wins from a live game state
+pub trait WinModel: Send + Sync {
+    fn home_win_prob(&self, game: &GameState) -> f64;
+}
+
+/// Final margin as a normal around the current lead plus the home edge still to
+/// come, with variance proportional to the share of regulation left. Fits sports
+/// that score often (basketball, football) and is rough but usable for hockey.
+pub struct MarginModel {
+    pub game_secs: f64,
+    /// Std-dev of the full-game margin
+    pub margin_sd: f64,
+    /// Expected home margin over a full game
+    pub home_edge: f64,
+}
+
+impl WinModel for MarginModel {
+    fn home_win_prob(&self, game: &GameState) -> f64 {
+        let lead = game.home_score - game.away_score;
+        let left = (game.secs_remaining / self.game_secs).clamp(0.0, 1.0);
+        if left <= 0.0 {
+            // Level at the horn goes to overtime: a coin flip
+            return if lead > 0.0 { 1.0 } else if lead < 0.0 { 0.0 } else { 0.5 };
+        }
+        pricing::normal_cdf((lead + self.home_edge * left) / (self.margin_sd * left.sqrt()))
+    }
+}
+
+/// Built-in model for a league; None for leagues without one
+pub fn model_for(league: &str) -> Option<Arc<dyn WinModel>> {
+    let (margin_sd, home_edge) = match league {
+        "nba" => (13.5, 2.5),
+        "wnba" => (11.0, 2.0),
+        "ncaab" => (11.0, 3.0),
+        "nfl" => (13.5, 1.5),
+        "ncaaf" => (16.0, 2.5),
+        "nhl" => (2.4, 0.2),
+        _ => return None,
+    };
+    Some(Arc::new(MarginModel {
+        game_secs: sports::league(league)?.game_secs(),
+        margin_sd,
+        home_edge,
+    }))
+}
+
+/// In-game arbitrage on a game's winner market.
+///
+/// The live score and clock imply a win probability through the league's model;
+/// when the price of either outcome, fees included, sits more than `min_edge`
+/// below that probability, buy it. Stale scores are ignored, since the book
+/// usually reprices faster than the scoreboard updates.
+pub struct InGameStrategy {
+    pub config: SportsConfig,
+    pub market_id: MarketId,
+    pub yes_token_id: TokenId,
+    pub no_token_id: TokenId,
+    pub game_id: String,
+    /// YES pays out if the home side wins; otherwise it backs the away side
+    pub yes_is_home: bool,
+    /// The league's built-in model unless replaced
+    pub model: Arc<dyn WinModel>,
+}
+
+impl InGameStrategy {
+    /// From a Yes/No market and its `league:game_id:home|away` spec; None when the
+    /// spec doesn't parse or the league has no model
+    pub fn from_market(market: &Market, spec: &str, config: &SportsConfig) -> Option<Self> {
+        let mut parts = spec.split(':');
+        let (league, game_id, team) = (parts.next()?.to_lowercase(), parts.next()?, parts.next()?);
+        let yes_is_home = match team.to_lowercase().as_str() {
+            "home" => true,
+            "away" => false,
+            _ => return None,
+        };
+        let (yes_token_id, no_token_id) = market.yes_no()?;
+        Some(Self {
+            config: config.clone(),
+            market_id: market.id.clone(),
+            yes_token_id: yes_token_id.clone(),
+            no_token_id: no_token_id.clone(),
+            model: model_for(&league)?,
+            game_id: game_id.to_string(),
+            yes_is_home,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl Strategy for InGameStrategy {
+    fn name(&self) -> &str {
+        "in_game"
+    }
+
+    fn enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    fn interest(&self) -> Interest {
+        Interest::markets([&self.market_id]).with_game(&self.game_id)
+    }
+
+    fn params(&self) -> serde_json::Value {
+        serde_json::json!({
+            "min_edge": self.config.min_edge,
+            "max_score_age_secs": self.config.max_score_age_secs,
+            "max_position_pct": self.config.max_position_pct,
+        })
+    }
+
+    async fn evaluate(&self, ctx: &StrategyContext) -> Vec<Signal> {
+        let Some(game) = ctx.games.get(&self.game_id) else {
+            return Vec::new();
+        };
+        if game.status != GameStatus::InProgress || ctx.blocked_markets.contains(&self.market_id) {
+            return Vec::new();
+        }
+        let now = ctx.latest_event.as_ref().map(MarketData::timestamp).unwrap_or(ctx.now);
+        if (now - game.timestamp).num_seconds() > self.config.max_score_age_secs as i64 {
+            return Vec::new();
+        }
+        let entered = ctx.positions.iter().any(|p| p.market_id == self.market_id && p.size > 0.0)
+            || ctx.open_orders.iter().any(|o| o.market_id == self.market_id);
+        if entered {
+            return Vec::new();
+        }
+
+        let home = self.model.home_win_prob(game);
+        let prob_yes = if self.yes_is_home { home } else { 1.0 - home };
+        let candidates = [(&self.yes_token_id, prob_yes), (&self.no_token_id, 1.0 - prob_yes)];
+        let best = candidates
+            .into_iter()
+            .filter_map(|(token_id, prob)| {
+                let price = ctx.cost(token_id)?;
+                let edge = prob - fees::taker_cost(&ctx.fees, price);
+                (edge >= self.config.min_edge).then_some((token_id, prob, price, edge))
+            })
+            .max_by(|a, b| a.3.total_cmp(&b.3));
+        let Some((token_id, prob, price, edge)) = best else {
+            return Vec::new();
+        };
+
+        let size = ctx.budget(self.name()) * self.config.max_position_pct / price;
+        tracing::info!(
+            "In-game {} {}-{} ({:.0}s left): model {:.3} vs {:.3}, edge {:.3}",
+            self.market_id,
+            game.home_score,
+            game.away_score,
+            game.secs_remaining,
+            prob,
+            price,
+            edge
+        );
+        vec![Signal {
+            strategy: self.name().to_string(),
+            market_id: self.market_id.clone(),
+            side: Side::Buy,
+            confidence: prob,
+            price,
+            size,
+            event_time: ctx.latest_event.as_ref().map(MarketData::timestamp),
+            token_id: Some(token_id.clone()),
+            order_type: None,
+            execution: Execution::Passive,
+            expires_at: None,
+            good_till: None,
+        }]
+    }
+}
@@ -1,19 +1,20 @@
-use crate::domain::{Side, Signal};
-use crate::strategy::{Strategy, StrategyContext};
+use crate::domain::{Execution, Market, MarketData, MarketId, Side, Signal, TokenId};
+use crate::engine::fees;
+use crate::strategy::{Intent, Interest, Strategy, StrategyContext};
 
 /// Intra-market arbitrage: if sum of all outcome YES prices < $1,
 /// buy all outcomes for guaranteed profit.
 pub struct IntraArbStrategy {
     pub enabled: bool,
     /// Markets to monitor: (market_id, vec of token_ids for each outcome)
-    pub markets: Vec<(String, Vec<String>)>,
+    pub markets: Vec<(MarketId, Vec<TokenId>)>,
     /// Minimum profit margin to act (e.g., 0.02 = 2 cents per dollar)
     pub min_margin: f64,
     pub max_position_pct: f64,
 }
 
 impl IntraArbStrategy {
-    pub fn new(markets: Vec<(String, Vec<String>)>) -> Self {
+    pub fn new(markets: Vec<(MarketId, Vec<TokenId>)>) -> Self {
         Self {
             enabled: true,
             markets,
@@ -21,6 +22,19 @@ impl IntraArbStrategy {
             max_position_pct: 0.05,
         }
     }
+
+    /// Every market's outcomes. Whatever the structure, a condition's own
+    /// outcomes form a complete set: Yes and No of a binary market or of one
+    /// neg-risk leg, or every outcome of a categorical market.
+    pub fn from_markets(markets: &[Market]) -> Self {
+        Self::new(
+            markets
+                .iter()
+                .filter(|m| m.outcomes.len() >= 2)
+                .map(|m| (m.id.clone(), m.outcomes.iter().map(|o| o.token_id.clone()).collect()))
+                .collect(),
+        )
+    }
 }
 
 #[async_trait::async_trait]
@@ -33,12 +47,23 @@ impl Strategy for IntraArbStrategy {
         self.enabled
     }
 
+    fn interest(&self) -> Interest {
+        Interest::markets(self.markets.iter().map(|(market_id, _)| market_id))
+    }
+
+    fn params(&self) -> serde_json::Value {
+        serde_json::json!({
+            "min_margin": self.min_margin,
+            "max_position_pct": self.max_position_pct,
+        })
+    }
+
     async fn evaluate(&self, ctx: &StrategyContext) -> Vec<Signal> {
         let mut signals = Vec::new();
 
         for (market_id, token_ids) in &self.markets {
             // Get prices for all outcomes
-            let prices: Vec<(String, f64)> = token_ids
+            let prices: Vec<(TokenId, f64)> = token_ids
                 .iter()
                 .filter_map(|tid| {
                     ctx.prices.get(tid).map(|&p| (tid.clone(), p))
@@ -50,14 +75,16 @@ impl Strategy for IntraArbStrategy {
                 continue;
             }
 
-            let total: f64 = prices.iter().map(|(_, p)| p).sum();
+            // All-in cost of one share of every outcome, taker fees included
+            let total: f64 = prices.iter().map(|(_, p)| fees::taker_cost(&ctx.fees, *p)).sum();
 
-            // If sum of YES prices < 1.0 - margin, there's an arb
+            // If the set costs < 1.0 - margin, there's an arb
             if total < 1.0 - self.min_margin {
                 let profit_per_dollar = 1.0 - total;
-                let max_size = ctx.bankroll * self.max_position_pct;
+                let budget = ctx.budget(self.name());
+                let max_size = budget * self.max_position_pct;
                 // Size in terms of "sets" — buy $size of each outcome
-                let size = max_size.min(ctx.bankroll * 0.10); // conservative
+                let size = max_size.min(budget * 0.10); // conservative
 
                 for (token_id, price) in &prices {
                     signals.push(Signal {
@@ -67,6 +94,12 @@ impl Strategy for IntraArbStrategy {
                         confidence: profit_per_dollar.min(1.0),
                         price: *price,
                         size: size * price, // dollar amount for this leg
+                        event_time: ctx.latest_event.as_ref().map(MarketData::timestamp),
+                        token_id: Some(token_id.clone()),
+                        order_type: None,
+                        execution: Execution::Passive,
+                        expires_at: None,
+                        good_till: None,
                     });
                 }
 
@@ -81,4 +114,17 @@ impl Strategy for IntraArbStrategy {
 
         signals
     }
+
+    /// Each market's legs go out as one batch: bought one at a time, the later
+    /// legs can move before they fill and leave the set unhedged
+    async fn intents(&self, ctx: &StrategyContext) -> Vec<Intent> {
+        let mut legs: Vec<Vec<Signal>> = Vec::new();
+        for signal in self.evaluate(ctx).await {
+            match legs.last_mut() {
+                Some(set) if set[0].market_id == signal.market_id => set.push(signal),
+                _ => legs.push(vec![signal]),
+            }
+        }
+        legs.into_iter().map(Intent::Legs).collect()
+    }
 }
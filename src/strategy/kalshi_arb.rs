@@ -0,0 +1,132 @@
+use crate::adapters::kalshi;
+use crate::config::KalshiConfig;
+use crate::domain::{Execution, Market, MarketData, MarketId, Side, Signal, TokenId};
+use crate::engine::fees;
+use crate::strategy::{Interest, Strategy, StrategyContext};
+
+/// Cross-venue arbitrage between a Polymarket market and the Kalshi contract that
+/// settles on the same event.
+///
+/// YES on one venue plus NO on the other pays exactly 1 whatever happens, so when
+/// the two asks plus both venues' fees come to less than 1 the difference is locked
+/// in. Each opportunity is emitted as a pair of Buy signals, one per venue.
+pub struct KalshiArbStrategy {
+    pub config: KalshiConfig,
+    pub market_id: MarketId,
+    pub yes_token_id: TokenId,
+    pub no_token_id: TokenId,
+    pub ticker: String,
+}
+
+impl KalshiArbStrategy {
+    /// Binary (Yes/No) Polymarket markets only
+    pub fn from_market(market: &Market, ticker: &str, config: &KalshiConfig) -> Option<Self> {
+        let (yes_token_id, no_token_id) = market.yes_no()?;
+        Some(Self {
+            config: config.clone(),
+            market_id: market.id.clone(),
+            yes_token_id: yes_token_id.clone(),
+            no_token_id: no_token_id.clone(),
+            ticker: ticker.to_string(),
+        })
+    }
+
+    /// Kalshi's fee per contract bought at `price`
+    fn kalshi_fee(&self, price: f64) -> f64 {
+        self.config.kalshi_fee_rate * price * (1.0 - price)
+    }
+}
+
+#[async_trait::async_trait]
+impl Strategy for KalshiArbStrategy {
+    fn name(&self) -> &str {
+        "kalshi_arb"
+    }
+
+    fn enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    fn interest(&self) -> Interest {
+        Interest::markets([&self.market_id]).with_ticker(&self.ticker)
+    }
+
+    fn params(&self) -> serde_json::Value {
+        serde_json::json!({
+            "min_edge": self.config.min_edge,
+            "kalshi_fee_rate": self.config.kalshi_fee_rate,
+            "max_position_pct": self.config.max_position_pct,
+        })
+    }
+
+    async fn evaluate(&self, ctx: &StrategyContext) -> Vec<Signal> {
+        let Some(quote) = ctx.kalshi_quotes.get(&self.ticker) else {
+            return Vec::new();
+        };
+        let kalshi_market = kalshi::market_id(&self.ticker);
+        if ctx.blocked_markets.contains(&self.market_id)
+            || ctx
+                .positions
+                .iter()
+                .any(|p| (p.market_id == self.market_id || p.market_id == kalshi_market) && p.size > 0.0)
+        {
+            return Vec::new();
+        }
+
+        // Polymarket YES + Kalshi NO, or Polymarket NO + Kalshi YES
+        let legs = [
+            (&self.yes_token_id, false, 1.0 - quote.yes_bid),
+            (&self.no_token_id, true, quote.yes_ask),
+        ];
+        let best = legs
+            .into_iter()
+            .filter_map(|(poly_token, kalshi_yes, kalshi_price)| {
+                if kalshi_price <= 0.0 || kalshi_price >= 1.0 {
+                    return None;
+                }
+                let poly_price = ctx.cost(poly_token)?;
+                let edge = 1.0 - fees::taker_cost(&ctx.fees, poly_price) - kalshi_price - self.kalshi_fee(kalshi_price);
+                Some((edge, poly_token, poly_price, kalshi_yes, kalshi_price))
+            })
+            .filter(|(edge, ..)| *edge >= self.config.min_edge)
+            .max_by(|a, b| a.0.total_cmp(&b.0));
+        let Some((edge, poly_token, poly_price, kalshi_yes, kalshi_price)) = best else {
+            return Vec::new();
+        };
+
+        // Kalshi trades whole contracts, so size both legs to match
+        let contracts = (ctx.budget(self.name()) * self.config.max_position_pct / (poly_price + kalshi_price)).floor();
+        if contracts < 1.0 {
+            return Vec::new();
+        }
+        tracing::info!(
+            "Kalshi arb on {} / {}: Polymarket {:.3} + Kalshi {} {:.3}, edge {:.3} after fees",
+            self.market_id,
+            self.ticker,
+            poly_price,
+            if kalshi_yes { "YES" } else { "NO" },
+            kalshi_price,
+            edge
+        );
+
+        let event_time = ctx.latest_event.as_ref().map(MarketData::timestamp);
+        let leg = |market_id: MarketId, token_id: TokenId, price: f64| Signal {
+            strategy: self.name().to_string(),
+            market_id,
+            side: Side::Buy,
+            confidence: (0.5 + edge * 5.0).min(0.99),
+            price,
+            size: contracts,
+            event_time,
+            token_id: Some(token_id),
+            order_type: None,
+            execution: Execution::Passive,
+            expires_at: None,
+            good_till: None,
+        };
+        vec![
+            leg(kalshi_market, kalshi::token_id(&self.ticker, kalshi_yes), kalshi_price),
+            leg(self.market_id.clone(), poly_token.clone(), poly_price),
+        ]
+    }
+}
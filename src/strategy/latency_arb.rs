@@ -1,31 +1,69 @@
-use crate::domain::{Side, Signal};
-use crate::strategy::{Strategy, StrategyContext};
+use chrono::{DateTime, Utc};
+use std::collections::BTreeMap;
+
+use crate::config::{CalendarConfig, PricingConfig};
+use crate::domain::{BookLevel, Execution, Market, MarketData, MarketId, Side, Signal, TokenId};
+use crate::engine::{fees, pricing};
+use crate::error::{Error, Result};
+use crate::strategy::{Interest, Strategy, StrategyContext};
+
+/// Ticks in the stats window before its realized volatility replaces the configured one
+const MIN_VOL_SAMPLES: usize = 20;
+/// Horizon the probability model uses for markets without an end date
+const DEFAULT_HORIZON_YEARS: f64 = 1.0 / 365.0;
+/// Confidence ceiling: the model ignores jumps, and resolution sources can disagree with Binance
+const MAX_CONFIDENCE: f64 = 0.95;
+
+/// Question keywords → Binance symbol for threshold markets we know how to price
+const CRYPTO_SYMBOLS: &[(&str, &str)] = &[
+    ("bitcoin", "BTCUSDT"),
+    ("btc", "BTCUSDT"),
+    ("ethereum", "ETHUSDT"),
+    ("eth", "ETHUSDT"),
+    ("solana", "SOLUSDT"),
+    ("sol", "SOLUSDT"),
+    ("xrp", "XRPUSDT"),
+];
 
 /// Crypto latency arbitrage: compare Binance spot vs Polymarket crypto markets.
 /// When Binance moves but Polymarket hasn't repriced yet, trade the stale price.
+#[derive(Clone)]
 pub struct LatencyArbStrategy {
     pub enabled: bool,
     /// Polymarket market ID for the crypto market we're trading
-    pub market_id: String,
+    pub market_id: MarketId,
     /// The token_id for YES outcome
-    pub yes_token_id: String,
+    pub yes_token_id: TokenId,
     /// The token_id for NO outcome
-    pub no_token_id: String,
+    pub no_token_id: TokenId,
     /// Binance symbol to watch (e.g. "BTCUSDT")
     pub binance_symbol: String,
     /// The threshold price in the Polymarket market (e.g. "Will BTC be above $X?")
     pub threshold_price: f64,
-    /// Minimum edge required (fraction past threshold, e.g. 0.02 = 2%)
+    /// Minimum edge required (fraction past threshold, e.g. 0.02 = 2%) at
+    /// `reference_vol`; it scales linearly with the symbol's volatility
     pub min_edge_pct: f64,
     /// Max fraction of bankroll per position
     pub max_position_pct: f64,
+    /// When the market resolves; the probability model prices the move over the time left
+    pub end_date: Option<DateTime<Utc>>,
+    /// Annualized volatility assumed until the rolling stats have enough ticks
+    pub model_vol: f64,
+    /// Volatility at which `min_edge_pct` applies as-is
+    pub reference_vol: f64,
+    /// Share of the streamed spot depth the side backing a trade must hold (0 disables)
+    pub min_depth_share: f64,
+    /// Within this many seconds of a calendar release the required edge is
+    /// multiplied by `econ_edge_mult` (0 ignores the calendar)
+    pub econ_window_secs: u64,
+    pub econ_edge_mult: f64,
 }
 
 impl LatencyArbStrategy {
     pub fn new(
-        market_id: String,
-        yes_token_id: String,
-        no_token_id: String,
+        market_id: MarketId,
+        yes_token_id: TokenId,
+        no_token_id: TokenId,
         binance_symbol: String,
         threshold_price: f64,
     ) -> Self {
@@ -38,11 +76,90 @@ impl LatencyArbStrategy {
             threshold_price,
             min_edge_pct: 0.02,
             max_position_pct: 0.05,
+            end_date: None,
+            model_vol: PricingConfig::default().default_vol,
+            reference_vol: PricingConfig::default().default_vol,
+            min_depth_share: 0.0,
+            econ_window_secs: 0,
+            econ_edge_mult: 1.0,
+        }
+    }
+
+    /// Volatility inputs from the pricing config: the symbol's own as the fallback,
+    /// the default as the reference for `min_edge_pct`
+    pub fn with_pricing(mut self, pricing: &PricingConfig) -> Self {
+        self.model_vol = pricing.vol(&self.binance_symbol);
+        self.reference_vol = pricing.default_vol;
+        self
+    }
+
+    pub fn with_min_depth_share(mut self, share: f64) -> Self {
+        self.min_depth_share = share;
+        self
+    }
+
+    pub fn with_calendar(mut self, calendar: &CalendarConfig) -> Self {
+        if calendar.enabled {
+            self.econ_window_secs = calendar.window_secs;
+            self.econ_edge_mult = calendar.edge_mult;
+        }
+        self
+    }
+
+    /// Whether the spot book has real size behind the side of the threshold spot
+    /// is on: bids for a YES entry, asks for NO, as a share of the notional in the
+    /// streamed levels. Without a book (depth streams off) there is nothing to check.
+    fn depth_backs(&self, ctx: &StrategyContext, side: &Side) -> bool {
+        if self.min_depth_share <= 0.0 {
+            return true;
+        }
+        let Some(book) = ctx.binance_books.get(&self.binance_symbol) else {
+            return true;
+        };
+        let notional = |levels: &[BookLevel]| levels.iter().map(|l| l.price * l.size).sum::<f64>();
+        let (bids, asks) = (notional(&book.bids), notional(&book.asks));
+        if bids + asks <= 0.0 {
+            return false;
         }
+        let backing = match side {
+            Side::Buy => bids,
+            Side::Sell => asks,
+        };
+        backing / (bids + asks) >= self.min_depth_share
+    }
+
+    /// Build from a watched "Will <asset> be above $<strike>?" market.
+    /// Returns None for markets that aren't crypto threshold markets.
+    pub fn from_market(market: &Market) -> Option<Self> {
+        let (symbol, threshold) = threshold_market(market)?;
+
+        let (yes_token_id, no_token_id) = market.yes_no()?;
+
+        let mut strategy = Self::new(
+            market.id.clone(),
+            yes_token_id.clone(),
+            no_token_id.clone(),
+            symbol.to_string(),
+            threshold,
+        );
+        strategy.end_date = market.end_date;
+        Some(strategy)
+    }
+
+    /// Realized volatility of the underlying over the stats window, once it has
+    /// enough ticks to mean something; the configured volatility until then
+    fn volatility(&self, ctx: &StrategyContext) -> f64 {
+        ctx.stats
+            .get(&self.binance_symbol)
+            .filter(|s| s.samples >= MIN_VOL_SAMPLES)
+            .and_then(|s| s.realized_vol)
+            .filter(|v| *v > 0.0)
+            .unwrap_or(self.model_vol)
     }
 
     /// Kelly criterion position sizing: f* = (bp - q) / b
-    /// where b = odds, p = probability of winning, q = 1-p
+    /// where b = odds, p = probability of winning, q = 1-p.
+    /// Returns shares, `price` being the all-in cost of one.
     fn kelly_size(&self, confidence: f64, price: f64, bankroll: f64) -> f64 {
         if price <= 0.0 || price >= 1.0 || confidence <= 0.0 {
             return 0.0;
@@ -55,8 +172,8 @@ impl LatencyArbStrategy {
         // Cap at max_position_pct of bankroll, and use half-Kelly for safety
         let half_kelly = kelly * 0.5;
         let max_size = bankroll * self.max_position_pct;
-        let size = (half_kelly * bankroll).min(max_size);
-        size.max(0.0)
+        let stake = (half_kelly * bankroll).min(max_size);
+        stake.max(0.0) / price
     }
 }
 
@@ -70,6 +187,39 @@ impl Strategy for LatencyArbStrategy {
         self.enabled
     }
 
+    fn interest(&self) -> Interest {
+        Interest::markets([&self.market_id]).with_symbol(&self.binance_symbol)
+    }
+
+    fn params(&self) -> serde_json::Value {
+        serde_json::json!({
+            "min_edge_pct": self.min_edge_pct,
+            "max_position_pct": self.max_position_pct,
+            "model_vol": self.model_vol,
+            "reference_vol": self.reference_vol,
+            "min_depth_share": self.min_depth_share,
+            "econ_window_secs": self.econ_window_secs,
+            "econ_edge_mult": self.econ_edge_mult,
+        })
+    }
+
+    fn with_params(&self, params: &BTreeMap<String, f64>) -> Result<Box<dyn Strategy>> {
+        let mut variant = self.clone();
+        for (key, &value) in params {
+            match key.as_str() {
+                "min_edge_pct" => variant.min_edge_pct = value,
+                "max_position_pct" => variant.max_position_pct = value,
+                "model_vol" => variant.model_vol = value,
+                "reference_vol" => variant.reference_vol = value,
+                "min_depth_share" => variant.min_depth_share = value,
+                "econ_window_secs" => variant.econ_window_secs = value.max(0.0) as u64,
+                "econ_edge_mult" => variant.econ_edge_mult = value,
+                _ => return Err(Error::invalid(format!("latency_arb has no parameter '{}'", key))),
+            }
+        }
+        Ok(Box::new(variant))
+    }
+
     async fn evaluate(&self, ctx: &StrategyContext) -> Vec<Signal> {
         let mut signals = Vec::new();
 
@@ -85,25 +235,47 @@ impl Strategy for LatencyArbStrategy {
             None => return signals,
         };
 
-        // Check if already have a position in this market
+        // Midpoint jumped without spot following — likely news we can't see
+        if ctx.blocked_markets.contains(&self.market_id) {
+            return signals;
+        }
+
+        // Check if already have a position, or an entry still working, in this market
         let has_position = ctx
             .positions
             .iter()
             .any(|p| p.market_id == self.market_id && p.size > 0.0);
-        if has_position {
+        let has_order = ctx.open_orders.iter().any(|o| o.market_id == self.market_id);
+        if has_position || has_order {
             return signals;
         }
 
         // Strategy logic:
         // If spot is significantly ABOVE threshold → YES should be worth ~1.0
         // If Polymarket YES price is still low → BUY YES
+        // "Significantly" is relative: the same distance from the strike means less
+        // in a volatile tape, so the required edge scales with volatility and the
+        // confidence is the model probability of finishing on that side.
         let edge_above = (spot_price - self.threshold_price) / self.threshold_price;
         let edge_below = (self.threshold_price - spot_price) / self.threshold_price;
+        let vol = self.volatility(ctx);
+        let now = ctx.latest_event.as_ref().map(MarketData::timestamp).unwrap_or(ctx.now);
+        let mut min_edge = self.min_edge_pct * vol / self.reference_vol.max(1e-9);
+        // A release can gap spot through the strike before the book catches up either way
+        if self.econ_window_secs > 0 && ctx.econ_event_near(now, self.econ_window_secs).is_some() {
+            min_edge *= self.econ_edge_mult;
+        }
+        let years = self
+            .end_date
+            .map_or(DEFAULT_HORIZON_YEARS, |end| pricing::years_until(end, now));
+        let prob_yes = pricing::prob_above(spot_price, self.threshold_price, vol, years);
 
-        if edge_above > self.min_edge_pct && poly_yes_price < 0.90 {
+        if edge_above > min_edge && poly_yes_price < 0.90 && self.depth_backs(ctx, &Side::Buy) {
             // Spot is well above threshold, YES should resolve to 1.0
-            let confidence = (0.5 + edge_above * 5.0).min(0.95);
-            let size = self.kelly_size(confidence, poly_yes_price, ctx.bankroll);
+            let confidence = prob_yes.min(MAX_CONFIDENCE);
+            // Size off the all-in cost: a thin edge that fees eat sizes to zero
+            let cost = fees::taker_cost(&ctx.fees, poly_yes_price);
+            let size = self.kelly_size(confidence, cost, ctx.budget(self.name()));
             if size > 1.0 {
                 signals.push(Signal {
                     strategy: self.name().to_string(),
@@ -112,21 +284,34 @@ impl Strategy for LatencyArbStrategy {
                     confidence,
                     price: poly_yes_price,
                     size,
+                    event_time: ctx.latest_event.as_ref().map(MarketData::timestamp),
+                    token_id: Some(self.yes_token_id.clone()),
+                    order_type: None,
+                    execution: Execution::Take,
+                    expires_at: None,
+                    good_till: None,
                 });
             }
-        } else if edge_below > self.min_edge_pct && poly_yes_price > 0.10 {
+        } else if edge_below > min_edge && poly_yes_price > 0.10 && self.depth_backs(ctx, &Side::Sell) {
             // Spot is well below threshold, NO should resolve to 1.0
             let poly_no_price = 1.0 - poly_yes_price;
-            let confidence = (0.5 + edge_below * 5.0).min(0.95);
-            let size = self.kelly_size(confidence, poly_no_price, ctx.bankroll);
+            let confidence = (1.0 - prob_yes).min(MAX_CONFIDENCE);
+            let cost = poly_no_price + fees::per_share(&ctx.fees, poly_yes_price, false);
+            let size = self.kelly_size(confidence, cost, ctx.budget(self.name()));
             if size > 1.0 {
                 signals.push(Signal {
                     strategy: self.name().to_string(),
                     market_id: self.market_id.clone(),
-                    side: Side::Sell, // Selling YES ≈ Buying NO
+                    side: Side::Sell, // Without YES held, the order manager buys NO instead
                     confidence,
                     price: poly_yes_price,
                     size,
+                    event_time: ctx.latest_event.as_ref().map(MarketData::timestamp),
+                    token_id: Some(self.yes_token_id.clone()),
+                    order_type: None,
+                    execution: Execution::Take,
+                    expires_at: None,
+                    good_till: None,
                 });
             }
         }
@@ -134,3 +319,45 @@ impl Strategy for LatencyArbStrategy {
         signals
     }
 }
+
+/// Binance symbol for a market whose question names a crypto asset we track
+pub fn spot_symbol(market: &Market) -> Option<&'static str> {
+    let question = market.question.to_lowercase();
+    let words = question_words(&question);
+    CRYPTO_SYMBOLS
+        .iter()
+        .find(|(name, _)| words.contains(name))
+        .map(|(_, symbol)| *symbol)
+}
+
+/// Spot symbol and strike of a "Will <asset> be above $<strike>?" market
+pub fn threshold_market(market: &Market) -> Option<(&'static str, f64)> {
+    let question = market.question.to_lowercase();
+    if !question_words(&question).contains(&"above") {
+        return None;
+    }
+    Some((spot_symbol(market)?, parse_dollar_amount(&question)?))
+}
+
+fn question_words(question: &str) -> Vec<&str> {
+    question
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .collect()
+}
+
+/// First "$110,000" / "$110k" / "$3,500.50" style amount in the text
+fn parse_dollar_amount(text: &str) -> Option<f64> {
+    let start = text.find('$')? + 1;
+    let rest = &text[start..];
+    let end = rest
+        .find(|c: char| !(c.is_ascii_digit() || c == ',' || c == '.'))
+        .unwrap_or(rest.len());
+    let number: f64 = rest[..end].trim_end_matches('.').replace(',', "").parse().ok()?;
+    let multiplier = match rest[end..].chars().next() {
+        Some('k') | Some('K') => 1_000.0,
+        Some('m') | Some('M') => 1_000_000.0,
+        _ => 1.0,
+    };
+    Some(number * multiplier)
+}
@@ -0,0 +1,194 @@
+use std::collections::BTreeMap;
+
+use crate::config::{CalendarConfig, MeanReversionConfig};
+use crate::domain::{Execution, Market, MarketData, MarketId, Side, Signal, TokenId};
+use crate::error::Result;
+use crate::strategy::latency_arb::spot_symbol;
+use crate::strategy::{variant, Intent, Interest, Strategy, StrategyContext};
+
+/// Mean reversion on a binary market's YES price.
+///
+/// When YES strays more than `entry_z` standard deviations from its rolling mean
+/// (the aggregator's stats window is the lookback), fade it by buying the cheap
+/// side. For crypto markets a same-direction move in the Binance index means the
+/// deviation is information rather than noise, so it is left alone, as is a move
+/// driven by one-sided taker flow or a whale alert on the public tape, or one
+/// around a scheduled economic release. Positions are exited once the price is
+/// back within `exit_z`, or stopped out past `stop_z`.
+#[derive(Clone)]
+pub struct MeanReversionStrategy {
+    pub config: MeanReversionConfig,
+    pub market_id: MarketId,
+    pub yes_token_id: TokenId,
+    pub no_token_id: TokenId,
+    /// Index that corroborates moves, for crypto markets
+    pub spot_symbol: Option<String>,
+    /// No new fades within this many seconds of a calendar release (0 ignores the calendar)
+    pub econ_window_secs: u64,
+}
+
+impl MeanReversionStrategy {
+    /// Binary (Yes/No) markets only
+    pub fn from_market(market: &Market, config: &MeanReversionConfig) -> Option<Self> {
+        let (yes_token_id, no_token_id) = market.yes_no()?;
+        Some(Self {
+            config: config.clone(),
+            market_id: market.id.clone(),
+            yes_token_id: yes_token_id.clone(),
+            no_token_id: no_token_id.clone(),
+            spot_symbol: spot_symbol(market).map(str::to_string),
+            econ_window_secs: 0,
+        })
+    }
+
+    pub fn with_calendar(mut self, calendar: &CalendarConfig) -> Self {
+        self.econ_window_secs = if calendar.enabled { calendar.window_secs } else { 0 };
+        self
+    }
+
+    fn signal(&self, ctx: &StrategyContext, token_id: &str, side: Side, price: f64, size: f64, z: f64) -> Signal {
+        Signal {
+            strategy: self.name().to_string(),
+            market_id: self.market_id.clone(),
+            side,
+            confidence: (0.5 + (z.abs() - self.config.entry_z).max(0.0) * 0.1).min(0.9),
+            price,
+            size,
+            event_time: ctx.latest_event.as_ref().map(MarketData::timestamp),
+            token_id: Some(token_id.into()),
+            order_type: None,
+            execution: Execution::Passive,
+            expires_at: None,
+            good_till: None,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Strategy for MeanReversionStrategy {
+    fn name(&self) -> &str {
+        "mean_reversion"
+    }
+
+    fn enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    fn interest(&self) -> Interest {
+        let interest = Interest::markets([&self.market_id]);
+        match &self.spot_symbol {
+            Some(symbol) => interest.with_symbol(symbol),
+            None => interest,
+        }
+    }
+
+    fn params(&self) -> serde_json::Value {
+        serde_json::to_value(&self.config).unwrap_or_default()
+    }
+
+    fn with_params(&self, params: &BTreeMap<String, f64>) -> Result<Box<dyn Strategy>> {
+        Ok(Box::new(Self {
+            config: variant::override_config(&self.config, params)?,
+            ..self.clone()
+        }))
+    }
+
+    async fn evaluate(&self, ctx: &StrategyContext) -> Vec<Signal> {
+        self.intents(ctx)
+            .await
+            .into_iter()
+            .filter_map(|intent| match intent {
+                Intent::Order(signal) => Some(signal),
+                _ => None,
+            })
+            .collect()
+    }
+
+    async fn intents(&self, ctx: &StrategyContext) -> Vec<Intent> {
+        let Some(stats) = ctx.stats.get(self.yes_token_id.as_str()) else {
+            return Vec::new();
+        };
+        let Some(z) = stats.z_score else {
+            return Vec::new();
+        };
+        if stats.samples < self.config.min_samples {
+            return Vec::new();
+        }
+        let yes_price = stats.last;
+        let no_price = ctx.prices.get(&self.no_token_id).copied().unwrap_or(1.0 - yes_price);
+
+        // Exits: reverted (take profit) or kept running (stop)
+        let held = ctx
+            .positions
+            .iter()
+            .find(|p| p.market_id == self.market_id && p.size > 0.0);
+        if let Some(position) = held {
+            // Holding YES was a bet on z rising back to 0, holding NO on it falling
+            let toward_mean = if position.token_id == self.yes_token_id { z } else { -z };
+            let price = if position.token_id == self.yes_token_id { yes_price } else { no_price };
+            if toward_mean >= -self.config.exit_z || toward_mean <= -self.config.stop_z {
+                return vec![Intent::Exit {
+                    strategy: self.name().to_string(),
+                    market_id: self.market_id.clone(),
+                    token_id: position.token_id.clone(),
+                    price,
+                    size: None,
+                    event_time: ctx.latest_event.as_ref().map(MarketData::timestamp),
+                }];
+            }
+            return Vec::new();
+        }
+
+        if z.abs() < self.config.entry_z {
+            return Vec::new();
+        }
+
+        // The index moved the same way: that's news, not noise
+        let corroborated = self
+            .spot_symbol
+            .as_ref()
+            .and_then(|symbol| ctx.stats.get(symbol))
+            .and_then(|spot| spot.z_score)
+            .is_some_and(|spot_z| spot_z.signum() == z.signum() && spot_z.abs() >= self.config.spot_corroboration_z);
+        // Takers pushing the price that way: aggressive size, not a thin-book wobble
+        let pushed = self.config.flow_corroboration > 0.0
+            && ctx.trade_flow.get(self.yes_token_id.as_str()).is_some_and(|flow| {
+                let imbalance = flow.imbalance();
+                imbalance.signum() == z.signum() && imbalance.abs() >= self.config.flow_corroboration
+            });
+        // Around a scheduled release moves are repricing, not noise
+        let now = ctx.latest_event.as_ref().map(MarketData::timestamp).unwrap_or(ctx.now);
+        let scheduled = self.econ_window_secs > 0 && ctx.econ_event_near(now, self.econ_window_secs).is_some();
+        // A fresh whale print or sweep the same way: someone paid up to move it
+        let whale = [(&self.yes_token_id, 1.0), (&self.no_token_id, -1.0)]
+            .into_iter()
+            .filter_map(|(token_id, sign)| Some((ctx.flow_alerts.get(token_id)?, sign)))
+            .any(|(alert, sign)| {
+                let direction = if alert.side == Side::Buy { sign } else { -sign };
+                direction == z.signum()
+            });
+        if corroborated || pushed || whale || scheduled || ctx.blocked_markets.contains(&self.market_id) {
+            return Vec::new();
+        }
+
+        // Rich YES → buy NO; cheap YES → buy YES
+        let (token_id, price) = if z > 0.0 {
+            (&self.no_token_id, no_price)
+        } else {
+            (&self.yes_token_id, yes_price)
+        };
+        if price <= 0.0 || price >= 1.0 {
+            return Vec::new();
+        }
+        let size = ctx.budget(self.name()) * self.config.max_position_pct / price;
+
+        tracing::info!(
+            "Mean reversion on {}: YES {:.3} vs mean {:.3} (z={:.2})",
+            self.market_id,
+            yes_price,
+            stats.mean,
+            z
+        );
+        vec![Intent::Order(self.signal(ctx, token_id, Side::Buy, price, size, z))]
+    }
+}
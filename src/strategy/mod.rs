@@ -1,36 +1,287 @@
+pub mod heartbeat;
+pub mod in_game;
+pub mod copy_trade;
+pub mod cross_strike;
+pub mod flow;
 pub mod latency_arb;
 pub mod intra_arb;
+pub mod kalshi_arb;
+pub mod mean_reversion;
+pub mod negrisk_arb;
+pub mod resolution_snipe;
+pub mod schedule;
+pub mod stats;
+pub mod variant;
 
-use std::collections::HashMap;
-use crate::domain::{MarketData, OrderBook, Position, Signal};
+use chrono::{DateTime, Utc};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::Arc;
+use crate::cache::Snapshot;
+use crate::config::FeeConfig;
+use crate::domain::{
+    EconEvent, FlowAlert, GameState, KalshiQuote, MarketData, MarketId, Order, OrderBook, OrderId, Position, Signal, TokenId,
+};
+use crate::engine::market_catalog::MarketCatalog;
+use crate::error::{Error, Result};
+use crate::strategy::flow::TradeFlow;
+use crate::strategy::stats::PriceStats;
 
-/// Context passed to strategies for evaluation
+/// Context passed to strategies for evaluation.
+///
+/// The market state maps are snapshots of the aggregator's caches, so building a
+/// context per event costs a pointer copy each rather than a copy of every price
+/// and book. Code that owns a context outright (the backtester) inserts into them
+/// directly; the persistent maps copy only what the insert touches.
 #[derive(Debug, Clone)]
 pub struct StrategyContext {
     pub bankroll: f64,
+    /// Strategy name → its budget from the portfolio allocator; see `budget`
+    pub allocated_bankroll: HashMap<String, f64>,
     pub positions: Vec<Position>,
-    pub prices: HashMap<String, f64>,           // token_id -> price
-    pub orderbooks: HashMap<String, OrderBook>,  // token_id -> orderbook
-    pub binance_prices: HashMap<String, f64>,    // symbol -> price
+    /// Our resting orders, so strategies don't stack entries behind unfilled ones
+    pub open_orders: Vec<Order>,
+    pub prices: Snapshot<TokenId, f64>,
+    pub orderbooks: Snapshot<TokenId, Arc<OrderBook>>,
+    pub binance_prices: Snapshot<String, f64>,            // symbol -> price
+    /// Spot books by symbol; empty unless the Binance depth streams are on
+    pub binance_books: Snapshot<String, Arc<OrderBook>>,
+    pub kalshi_quotes: Snapshot<String, KalshiQuote>,     // ticker -> quote
+    pub games: Snapshot<String, GameState>,               // game_id -> live score
+    pub stats: Arc<HashMap<String, PriceStats>>,          // token_id or symbol -> rolling stats
+    pub trade_flow: Arc<HashMap<String, TradeFlow>>,      // token_id -> rolling taker flow
+    /// Latest whale alert per token, while it is fresh; empty without the detector
+    pub flow_alerts: HashMap<TokenId, FlowAlert>,
+    /// Scheduled economic releases, soonest first; empty without the calendar
+    pub econ_events: Arc<Vec<EconEvent>>,
     pub latest_event: Option<MarketData>,
+    /// Markets where the anomaly guard has paused new entries, or under oracle dispute
+    pub blocked_markets: HashSet<MarketId>,
+    /// Fee schedule to net out of edges
+    pub fees: FeeConfig,
+    /// Stored metadata for resolving token IDs to their market without a lookup
+    pub markets: MarketCatalog,
+    /// When the context was built, by the aggregator's clock; strategies read the
+    /// time from here rather than the system
+    pub now: DateTime<Utc>,
 }
 
 impl StrategyContext {
     pub fn new(bankroll: f64) -> Self {
         Self {
             bankroll,
+            allocated_bankroll: HashMap::new(),
             positions: Vec::new(),
-            prices: HashMap::new(),
-            orderbooks: HashMap::new(),
-            binance_prices: HashMap::new(),
+            open_orders: Vec::new(),
+            prices: Snapshot::new(),
+            orderbooks: Snapshot::new(),
+            binance_prices: Snapshot::new(),
+            binance_books: Snapshot::new(),
+            kalshi_quotes: Snapshot::new(),
+            games: Snapshot::new(),
+            stats: Arc::default(),
+            trade_flow: Arc::default(),
+            flow_alerts: HashMap::new(),
+            econ_events: Arc::default(),
             latest_event: None,
+            blocked_markets: HashSet::new(),
+            fees: FeeConfig::default(),
+            markets: MarketCatalog::default(),
+            now: Utc::now(),
         }
     }
+
+    /// Bankroll a strategy sizes off: its allocation, or all of it when unallocated
+    pub fn budget(&self, strategy: &str) -> f64 {
+        self.allocated_bankroll.get(strategy).copied().unwrap_or(self.bankroll)
+    }
+
+    /// What we'd pay for a token now: best ask when we have a book, else the last price
+    pub fn cost(&self, token_id: &str) -> Option<f64> {
+        self.orderbooks
+            .get(token_id)
+            .and_then(|book| book.asks.first())
+            .map(|level| level.price)
+            .or_else(|| self.prices.get(token_id).copied())
+            .filter(|p| *p > 0.0 && *p < 1.0)
+    }
+
+    /// First calendar release within `window_secs` either side of `now`
+    pub fn econ_event_near(&self, now: DateTime<Utc>, window_secs: u64) -> Option<&EconEvent> {
+        self.econ_events.iter().find(|e| e.covers(now, window_secs, window_secs))
+    }
+}
+
+/// One side of a resting quote
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QuoteLevel {
+    pub price: f64,
+    pub size: f64,
+}
+
+/// What a strategy asks the order manager to do
+#[derive(Debug, Clone)]
+pub enum Intent {
+    /// Submit an order for this signal
+    Order(Signal),
+    /// Submit these orders together, or none of them if any fails its checks
+    Legs(Vec<Signal>),
+    /// Close a held position, or `size` shares of it, at `price`
+    Exit {
+        strategy: String,
+        market_id: MarketId,
+        token_id: TokenId,
+        price: f64,
+        size: Option<f64>,
+        event_time: Option<DateTime<Utc>>,
+    },
+    /// Cancel one of our resting orders
+    Cancel { strategy: String, order_id: OrderId },
+    /// Move one of our resting orders to a new price and size: cancel it and place
+    /// the replacement, without it racing its own original
+    Replace {
+        strategy: String,
+        order_id: OrderId,
+        price: f64,
+        size: f64,
+    },
+    /// Keep resting GTC orders on a token at these levels. An unchanged level is left
+    /// alone, a moved one is cancelled and replaced, and a `None` side is pulled.
+    Quote {
+        strategy: String,
+        market_id: MarketId,
+        token_id: TokenId,
+        bid: Option<QuoteLevel>,
+        ask: Option<QuoteLevel>,
+    },
+}
+
+impl Intent {
+    pub fn strategy(&self) -> &str {
+        match self {
+            Intent::Order(signal) => &signal.strategy,
+            Intent::Legs(signals) => signals.first().map_or("", |s| s.strategy.as_str()),
+            Intent::Exit { strategy, .. }
+            | Intent::Cancel { strategy, .. }
+            | Intent::Replace { strategy, .. }
+            | Intent::Quote { strategy, .. } => strategy,
+        }
+    }
+
+    /// Attribute the intent, and every signal in it, to `name`
+    pub fn set_strategy(&mut self, name: &str) {
+        match self {
+            Intent::Order(signal) => signal.strategy = name.to_string(),
+            Intent::Legs(signals) => signals.iter_mut().for_each(|s| s.strategy = name.to_string()),
+            Intent::Exit { strategy, .. }
+            | Intent::Cancel { strategy, .. }
+            | Intent::Replace { strategy, .. }
+            | Intent::Quote { strategy, .. } => *strategy = name.to_string(),
+        }
+    }
+}
+
+/// The market data a strategy reads, so events about anything else skip it.
+///
+/// Polymarket events (prices, books, prints, flow alerts, resolutions) match on
+/// their market or token; the outside feeds on their own keys. Rolling stats and
+/// flow are keyed the same way, so a strategy reading a symbol's stats lists the
+/// symbol. Derived state that no event carries (positions, the calendar, time)
+/// is only looked at when one of the listed instruments ticks.
+#[derive(Debug, Clone, Default)]
+pub struct Interest {
+    /// Every event, whatever the lists below say
+    pub all: bool,
+    pub market_ids: HashSet<MarketId>,
+    pub token_ids: HashSet<TokenId>,
+    /// Binance symbols, for tickers and books
+    pub symbols: HashSet<String>,
+    /// Kalshi tickers
+    pub tickers: HashSet<String>,
+    pub game_ids: HashSet<String>,
+}
+
+impl Interest {
+    /// Evaluate on every event
+    pub fn all() -> Self {
+        Self {
+            all: true,
+            ..Self::default()
+        }
+    }
+
+    /// Every event about these Polymarket markets, any outcome
+    pub fn markets<'a>(market_ids: impl IntoIterator<Item = &'a MarketId>) -> Self {
+        Self {
+            market_ids: market_ids.into_iter().cloned().collect(),
+            ..Self::default()
+        }
+    }
+
+    pub fn with_tokens<'a>(mut self, token_ids: impl IntoIterator<Item = &'a TokenId>) -> Self {
+        self.token_ids.extend(token_ids.into_iter().cloned());
+        self
+    }
+
+    pub fn with_symbol(mut self, symbol: &str) -> Self {
+        self.symbols.insert(symbol.to_string());
+        self
+    }
+
+    pub fn with_ticker(mut self, ticker: &str) -> Self {
+        self.tickers.insert(ticker.to_string());
+        self
+    }
+
+    pub fn with_game(mut self, game_id: &str) -> Self {
+        self.game_ids.insert(game_id.to_string());
+        self
+    }
+
+    /// Whether `event` could change what the strategy decides
+    pub fn covers(&self, event: &MarketData) -> bool {
+        let polymarket = |market_id: &MarketId, token_id: Option<&TokenId>| {
+            self.market_ids.contains(market_id) || token_id.is_some_and(|t| self.token_ids.contains(t))
+        };
+        self.all
+            || match event {
+                MarketData::PolymarketPrice { market_id, token_id, .. }
+                | MarketData::PolymarketOrderBook { market_id, token_id, .. }
+                | MarketData::PolymarketTrade { market_id, token_id, .. } => polymarket(market_id, Some(token_id)),
+                MarketData::FlowAlert { alert } => polymarket(&alert.market_id, Some(&alert.token_id)),
+                MarketData::Resolution { market_id, .. } => polymarket(market_id, None),
+                MarketData::BinanceTicker { symbol, .. } | MarketData::BinanceOrderBook { symbol, .. } => {
+                    self.symbols.contains(symbol)
+                }
+                MarketData::KalshiTicker { ticker, .. } => self.tickers.contains(ticker),
+                MarketData::GameScore { game } => self.game_ids.contains(&game.game_id),
+            }
+    }
 }
 
 #[async_trait::async_trait]
 pub trait Strategy: Send + Sync {
     fn name(&self) -> &str;
     async fn evaluate(&self, ctx: &StrategyContext) -> Vec<Signal>;
+    /// Everything the strategy wants done after this event. By default each
+    /// `evaluate` signal becomes an order; stateful strategies (market making,
+    /// stop management) override this to exit, cancel and quote as well.
+    async fn intents(&self, ctx: &StrategyContext) -> Vec<Intent> {
+        self.evaluate(ctx).await.into_iter().map(Intent::Order).collect()
+    }
     fn enabled(&self) -> bool;
+    /// The events this strategy is evaluated on, asked once when it is loaded.
+    /// Defaults to all of them; a strategy that only reads its own instruments
+    /// narrows it so unrelated ticks don't pay for a pass over it.
+    fn interest(&self) -> Interest {
+        Interest::all()
+    }
+    /// Tunables shared by every instance, reported by the dashboard
+    fn params(&self) -> serde_json::Value {
+        serde_json::Value::Null
+    }
+    /// A copy of this instance with the named `params` overridden, for A/B
+    /// variants. Strategies without tunables to vary refuse.
+    fn with_params(&self, _params: &BTreeMap<String, f64>) -> Result<Box<dyn Strategy>> {
+        Err(Error::invalid(format!("{} has no tunable parameters", self.name())))
+    }
 }
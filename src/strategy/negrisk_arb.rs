@@ -0,0 +1,124 @@
+use crate::domain::{Execution, MarketData, NegRiskEvent, Side, Signal};
+use crate::engine::fees;
+use crate::strategy::{Intent, Interest, Strategy, StrategyContext};
+
+/// Neg-risk arbitrage: at most one leg of a neg-risk event resolves Yes, so
+/// holding NO on each of `n` legs pays at least `n - 1`. If NO on every known
+/// leg costs less than that, buy one of each for a guaranteed profit.
+///
+/// Legs the bot hasn't loaded only add to the payout (every NO pays if the
+/// winner is among them), so an event needn't be complete to trade.
+pub struct NegRiskArbStrategy {
+    pub enabled: bool,
+    /// Events to monitor, each with two or more legs
+    pub events: Vec<NegRiskEvent>,
+    /// Minimum profit to act, per dollar of guaranteed payout
+    pub min_margin: f64,
+    pub max_position_pct: f64,
+}
+
+impl NegRiskArbStrategy {
+    pub fn new(events: Vec<NegRiskEvent>) -> Self {
+        Self {
+            enabled: true,
+            events,
+            min_margin: 0.02,
+            max_position_pct: 0.05,
+        }
+    }
+
+    /// One buy of NO per leg for every event priced below its payout
+    fn arbs(&self, ctx: &StrategyContext) -> Vec<Vec<Signal>> {
+        let mut arbs = Vec::new();
+
+        for event in &self.events {
+            // Need a NO price on every leg
+            let Some(prices) = event
+                .legs
+                .iter()
+                .map(|leg| ctx.prices.get(&leg.no_token_id).map(|&p| (leg, p)))
+                .collect::<Option<Vec<_>>>()
+            else {
+                continue;
+            };
+            if prices.iter().any(|(leg, _)| ctx.blocked_markets.contains(&leg.market_id)) {
+                continue;
+            }
+
+            // All-in cost of one NO on every leg, taker fees included, against
+            // what the set pays whichever leg wins
+            let total: f64 = prices.iter().map(|(_, p)| fees::taker_cost(&ctx.fees, *p)).sum();
+            let payout = (prices.len() - 1) as f64;
+            if total >= payout * (1.0 - self.min_margin) {
+                continue;
+            }
+
+            let profit_per_dollar = 1.0 - total / payout;
+            let budget = ctx.budget(self.name());
+            // Equal shares on every leg, so each is covered whichever leg wins
+            let sets = budget * self.max_position_pct / total;
+
+            let legs = prices
+                .iter()
+                .map(|(leg, price)| Signal {
+                    strategy: self.name().to_string(),
+                    market_id: leg.market_id.clone(),
+                    side: Side::Buy,
+                    confidence: profit_per_dollar.min(1.0),
+                    price: *price,
+                    size: sets,
+                    event_time: ctx.latest_event.as_ref().map(MarketData::timestamp),
+                    token_id: Some(leg.no_token_id.clone()),
+                    order_type: None,
+                    execution: Execution::Passive,
+                    expires_at: None,
+                    good_till: None,
+                })
+                .collect();
+            arbs.push(legs);
+
+            tracing::info!(
+                "Neg-risk arb found: event={}, legs={}, total={:.4}, payout={:.0}, profit={:.4}",
+                event.event_id,
+                prices.len(),
+                total,
+                payout,
+                profit_per_dollar
+            );
+        }
+
+        arbs
+    }
+}
+
+#[async_trait::async_trait]
+impl Strategy for NegRiskArbStrategy {
+    fn name(&self) -> &str {
+        "negrisk_arb"
+    }
+
+    fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn interest(&self) -> Interest {
+        Interest::markets(self.events.iter().flat_map(|event| &event.legs).map(|leg| &leg.market_id))
+    }
+
+    fn params(&self) -> serde_json::Value {
+        serde_json::json!({
+            "min_margin": self.min_margin,
+            "max_position_pct": self.max_position_pct,
+        })
+    }
+
+    async fn evaluate(&self, ctx: &StrategyContext) -> Vec<Signal> {
+        self.arbs(ctx).into_iter().flatten().collect()
+    }
+
+    /// Each event's legs go out as one batch: bought one at a time, the later
+    /// legs can move before they fill and leave the set short
+    async fn intents(&self, ctx: &StrategyContext) -> Vec<Intent> {
+        self.arbs(ctx).into_iter().map(Intent::Legs).collect()
+    }
+}
@@ -0,0 +1,132 @@
+use chrono::{DateTime, Utc};
+
+use crate::config::ResolutionSnipeConfig;
+use crate::domain::{Execution, Market, MarketData, MarketId, Side, Signal, TokenId};
+use crate::engine::{fees, pricing};
+use crate::strategy::latency_arb::threshold_market;
+use crate::strategy::{Interest, Strategy, StrategyContext};
+
+const HOURS_PER_YEAR: f64 = 365.0 * 24.0;
+
+/// Resolution sniping on "<asset> above $X" markets about to end.
+///
+/// Close to end_date, spot far enough from the strike all but decides the outcome,
+/// yet the winning side often still trades a few cents under 1. Buy it when the
+/// model probability clears `min_probability`, the price is under `max_price`, and
+/// the payout — received only after settlement — beats `min_annualized_return`
+/// for the time the capital is tied up.
+pub struct ResolutionSnipeStrategy {
+    pub config: ResolutionSnipeConfig,
+    pub market_id: MarketId,
+    pub yes_token_id: TokenId,
+    pub no_token_id: TokenId,
+    pub binance_symbol: String,
+    pub strike: f64,
+    pub end_date: DateTime<Utc>,
+    /// Annualized volatility of the underlying
+    pub vol: f64,
+}
+
+impl ResolutionSnipeStrategy {
+    /// Threshold markets with a known end date only
+    pub fn from_market(market: &Market, config: &ResolutionSnipeConfig, vol: f64) -> Option<Self> {
+        let (symbol, strike) = threshold_market(market)?;
+        let (yes_token_id, no_token_id) = market.yes_no()?;
+        Some(Self {
+            config: config.clone(),
+            market_id: market.id.clone(),
+            yes_token_id: yes_token_id.clone(),
+            no_token_id: no_token_id.clone(),
+            binance_symbol: symbol.to_string(),
+            strike,
+            end_date: market.end_date?,
+            vol,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl Strategy for ResolutionSnipeStrategy {
+    fn name(&self) -> &str {
+        "resolution_snipe"
+    }
+
+    fn enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    fn interest(&self) -> Interest {
+        Interest::markets([&self.market_id]).with_symbol(&self.binance_symbol)
+    }
+
+    fn params(&self) -> serde_json::Value {
+        serde_json::to_value(&self.config).unwrap_or_default()
+    }
+
+    async fn evaluate(&self, ctx: &StrategyContext) -> Vec<Signal> {
+        let now = ctx.latest_event.as_ref().map(MarketData::timestamp).unwrap_or(ctx.now);
+        let hours_left = (self.end_date - now).num_seconds() as f64 / 3600.0;
+        if hours_left <= 0.0 || hours_left > self.config.max_hours_to_end {
+            return Vec::new();
+        }
+        if ctx.blocked_markets.contains(&self.market_id)
+            || ctx.positions.iter().any(|p| p.market_id == self.market_id && p.size > 0.0)
+        {
+            return Vec::new();
+        }
+        let Some(&spot) = ctx.binance_prices.get(&self.binance_symbol) else {
+            return Vec::new();
+        };
+
+        let above = pricing::prob_above(spot, self.strike, self.vol, pricing::years_until(self.end_date, now));
+        let (token_id, probability) = if above >= self.config.min_probability {
+            (&self.yes_token_id, above)
+        } else if 1.0 - above >= self.config.min_probability {
+            (&self.no_token_id, 1.0 - above)
+        } else {
+            return Vec::new();
+        };
+        let Some(price) = ctx.cost(token_id) else {
+            return Vec::new();
+        };
+        let all_in = fees::taker_cost(&ctx.fees, price);
+        if price > self.config.max_price || all_in >= probability {
+            return Vec::new();
+        }
+
+        // Capital is locked until payout, not just until end_date
+        let lockup_years = (hours_left + self.config.settlement_hours) / HOURS_PER_YEAR;
+        let annualized = (1.0 - all_in) / all_in / lockup_years;
+        if annualized < self.config.min_annualized_return {
+            return Vec::new();
+        }
+
+        let size = ctx.budget(self.name()) * self.config.max_position_pct / price;
+        tracing::info!(
+            "Resolution snipe on {}: {} {:.0} vs strike {:.0}, {:.1}h left, p={:.3} at {:.3} ({:.0}% annualized)",
+            self.market_id,
+            self.binance_symbol,
+            spot,
+            self.strike,
+            hours_left,
+            probability,
+            price,
+            annualized * 100.0
+        );
+
+        vec![Signal {
+            strategy: self.name().to_string(),
+            market_id: self.market_id.clone(),
+            side: Side::Buy,
+            confidence: probability,
+            price,
+            size,
+            event_time: ctx.latest_event.as_ref().map(MarketData::timestamp),
+            token_id: Some(token_id.clone()),
+            order_type: None,
+            execution: Execution::Passive,
+            expires_at: None,
+            good_till: None,
+        }]
+    }
+}
@@ -0,0 +1,101 @@
+use chrono::{DateTime, Datelike, Timelike, Utc};
+use std::fmt;
+
+use crate::error::{Error, Result};
+
+/// Cron-style activation window, evaluated in UTC at minute granularity.
+///
+/// Five fields — minute, hour, day of month, month, day of week (0 or 7 = Sunday) —
+/// each `*`, a value, a range `a-b`, a step `*/n` or `a-b/n`, or a comma list of those.
+/// A strategy is active during every minute the expression matches, so
+/// `* 13-21 * * 1-5` means weekdays 13:00–21:59 UTC and `* * * * *` means always.
+#[derive(Debug, Clone)]
+pub struct Schedule {
+    expr: String,
+    minutes: u64,
+    hours: u64,
+    days: u64,
+    months: u64,
+    weekdays: u64,
+    /// Day-of-month and day-of-week were both restricted; as in cron, either may match
+    either_day: bool,
+}
+
+impl Schedule {
+    pub fn parse(expr: &str) -> Result<Self> {
+        let fields: Vec<&str> = expr.split_whitespace().collect();
+        let [minute, hour, day, month, weekday] = fields[..] else {
+            return Err(Error::invalid(format!("Schedule '{}' needs 5 fields, got {}", expr, fields.len())));
+        };
+
+        let field = |f: &str, min, max| parse_field(f, min, max).map_err(|e| Error::invalid(format!("Schedule '{}': {}", expr, e)));
+        let mut weekdays = field(weekday, 0, 7)?;
+        // Sunday may be written as 7
+        if weekdays & (1 << 7) != 0 {
+            weekdays |= 1;
+        }
+        Ok(Self {
+            expr: expr.split_whitespace().collect::<Vec<_>>().join(" "),
+            minutes: field(minute, 0, 59)?,
+            hours: field(hour, 0, 23)?,
+            days: field(day, 1, 31)?,
+            months: field(month, 1, 12)?,
+            weekdays,
+            either_day: day != "*" && weekday != "*",
+        })
+    }
+
+    pub fn is_active(&self, now: &DateTime<Utc>) -> bool {
+        let has = |mask: u64, v: u32| mask & (1 << v) != 0;
+        let day_of_month = has(self.days, now.day());
+        let day_of_week = has(self.weekdays, now.weekday().num_days_from_sunday());
+        let day = if self.either_day {
+            day_of_month || day_of_week
+        } else {
+            day_of_month && day_of_week
+        };
+        day && has(self.minutes, now.minute()) && has(self.hours, now.hour()) && has(self.months, now.month())
+    }
+}
+
+impl fmt::Display for Schedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.expr)
+    }
+}
+
+/// Bitmask of the values a single field matches
+fn parse_field(field: &str, min: u32, max: u32) -> Result<u64> {
+    let mut mask = 0u64;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => (range, step.parse::<u32>().map_err(|_| Error::invalid(format!("bad step in '{}'", part)))?),
+            None => (part, 1),
+        };
+        if step == 0 {
+            return Err(Error::invalid(format!("zero step in '{}'", part)));
+        }
+
+        let (lo, hi) = if range == "*" {
+            (min, max)
+        } else if let Some((lo, hi)) = range.split_once('-') {
+            (parse_value(lo, part)?, parse_value(hi, part)?)
+        } else {
+            let v = parse_value(range, part)?;
+            // `5/15` means from 5 to the end in steps of 15
+            (v, if part.contains('/') { max } else { v })
+        };
+        if lo < min || hi > max || lo > hi {
+            return Err(Error::invalid(format!("'{}' outside {}-{}", part, min, max)));
+        }
+
+        for v in (lo..=hi).step_by(step as usize) {
+            mask |= 1 << v;
+        }
+    }
+    Ok(mask)
+}
+
+fn parse_value(v: &str, part: &str) -> Result<u32> {
+    v.parse().map_err(|_| Error::invalid(format!("bad value in '{}'", part)))
+}
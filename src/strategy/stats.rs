@@ -0,0 +1,146 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+
+use crate::config::StatsConfig;
+
+const SECS_PER_YEAR: f64 = 365.0 * 24.0 * 3600.0;
+
+/// Rolling statistics for one instrument as of its latest tick
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct PriceStats {
+    pub last: f64,
+    /// Time-decayed exponential moving average
+    pub ema: f64,
+    /// Mean over the rolling window
+    pub mean: f64,
+    pub std_dev: f64,
+    /// (last - mean) / std_dev; None until the window has spread
+    pub z_score: Option<f64>,
+    /// Annualized std-dev of log returns over the window; None with fewer than 3 ticks
+    pub realized_vol: Option<f64>,
+    pub samples: usize,
+}
+
+/// Price window with running sums, so each tick costs O(evicted) rather than O(window)
+struct Series {
+    samples: VecDeque<(DateTime<Utc>, f64)>,
+    sum: f64,
+    sum_sq: f64,
+    /// Log returns between consecutive samples in the window
+    ret_sum: f64,
+    ret_sum_sq: f64,
+    ema: f64,
+}
+
+impl Series {
+    fn new(first: f64) -> Self {
+        Self {
+            samples: VecDeque::new(),
+            sum: 0.0,
+            sum_sq: 0.0,
+            ret_sum: 0.0,
+            ret_sum_sq: 0.0,
+            ema: first,
+        }
+    }
+
+    fn push(&mut self, ts: DateTime<Utc>, price: f64, config: &StatsConfig) {
+        if let Some(&(prev_ts, prev)) = self.samples.back() {
+            // Time-aware EMA: the weight of the old value halves every half-life
+            let dt = (ts - prev_ts).num_milliseconds().max(0) as f64 / 1000.0;
+            let keep = 0.5f64.powf(dt / config.ema_half_life_secs.max(1) as f64);
+            self.ema = self.ema * keep + price * (1.0 - keep);
+
+            let r = log_return(prev, price);
+            self.ret_sum += r;
+            self.ret_sum_sq += r * r;
+        }
+        self.samples.push_back((ts, price));
+        self.sum += price;
+        self.sum_sq += price * price;
+
+        let cutoff = ts - chrono::Duration::seconds(config.window_secs as i64);
+        while self.samples.len() > 1 && self.samples[0].0 < cutoff {
+            let (_, old) = self.samples.pop_front().unwrap();
+            self.sum -= old;
+            self.sum_sq -= old * old;
+            // The return from the evicted sample to the new front leaves the window too
+            let r = log_return(old, self.samples[0].1);
+            self.ret_sum -= r;
+            self.ret_sum_sq -= r * r;
+        }
+    }
+
+    fn stats(&self) -> PriceStats {
+        let n = self.samples.len();
+        let last = self.samples.back().map(|(_, p)| *p).unwrap_or_default();
+        let mean = self.sum / n as f64;
+        let std_dev = variance(self.sum, self.sum_sq, n).sqrt();
+
+        let returns = n.saturating_sub(1);
+        let realized_vol = (returns >= 2).then(|| {
+            let span = (self.samples[n - 1].0 - self.samples[0].0).num_milliseconds() as f64 / 1000.0;
+            let per_return = variance(self.ret_sum, self.ret_sum_sq, returns);
+            let returns_per_year = SECS_PER_YEAR / (span / returns as f64).max(1e-3);
+            (per_return * returns_per_year).sqrt()
+        });
+
+        PriceStats {
+            last,
+            ema: self.ema,
+            mean,
+            std_dev,
+            z_score: (std_dev > 1e-9).then(|| (last - mean) / std_dev),
+            realized_vol,
+            samples: n,
+        }
+    }
+}
+
+fn log_return(from: f64, to: f64) -> f64 {
+    if from > 0.0 && to > 0.0 { (to / from).ln() } else { 0.0 }
+}
+
+/// Population variance from running sums, clamped against rounding below zero
+fn variance(sum: f64, sum_sq: f64, n: usize) -> f64 {
+    if n == 0 {
+        return 0.0;
+    }
+    let mean = sum / n as f64;
+    (sum_sq / n as f64 - mean * mean).max(0.0)
+}
+
+/// Rolling stats for every price the aggregator sees, keyed by token ID or spot symbol
+pub struct StatsTracker {
+    config: StatsConfig,
+    series: HashMap<String, Series>,
+    /// Shared with the contexts built from it; see `snapshot`
+    latest: Arc<HashMap<String, PriceStats>>,
+}
+
+impl StatsTracker {
+    pub fn new(config: StatsConfig) -> Self {
+        Self {
+            config,
+            series: HashMap::new(),
+            latest: Arc::default(),
+        }
+    }
+
+    pub fn update(&mut self, instrument: &str, ts: DateTime<Utc>, price: f64) {
+        let series = self
+            .series
+            .entry(instrument.to_string())
+            .or_insert_with(|| Series::new(price));
+        series.push(ts, price, &self.config);
+        Arc::make_mut(&mut self.latest).insert(instrument.to_string(), series.stats());
+    }
+
+    /// Latest stats per instrument, for the strategy context. Shared, not copied:
+    /// the next update copies the map only if a context still holds this one.
+    pub fn snapshot(&self) -> Arc<HashMap<String, PriceStats>> {
+        self.latest.clone()
+    }
+}
@@ -0,0 +1,102 @@
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+use crate::domain::Signal;
+use crate::error::{Error, Result};
+use crate::strategy::{Intent, Interest, Strategy, StrategyContext};
+
+/// One strategy instance run under a variant's name, so its signals, orders and
+/// records are kept apart from the base strategy it was re-parameterized from
+pub struct Variant {
+    name: String,
+    inner: Box<dyn Strategy>,
+}
+
+impl Variant {
+    pub fn new(name: String, inner: Box<dyn Strategy>) -> Self {
+        Self { name, inner }
+    }
+}
+
+#[async_trait::async_trait]
+impl Strategy for Variant {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn enabled(&self) -> bool {
+        self.inner.enabled()
+    }
+
+    fn interest(&self) -> Interest {
+        self.inner.interest()
+    }
+
+    fn params(&self) -> serde_json::Value {
+        self.inner.params()
+    }
+
+    async fn evaluate(&self, ctx: &StrategyContext) -> Vec<Signal> {
+        let mut signals = self.inner.evaluate(ctx).await;
+        for signal in &mut signals {
+            signal.strategy = self.name.clone();
+        }
+        signals
+    }
+
+    async fn intents(&self, ctx: &StrategyContext) -> Vec<Intent> {
+        let mut intents = self.inner.intents(ctx).await;
+        for intent in &mut intents {
+            intent.set_strategy(&self.name);
+        }
+        intents
+    }
+
+    fn with_params(&self, params: &BTreeMap<String, f64>) -> Result<Box<dyn Strategy>> {
+        Ok(Box::new(Variant::new(self.name.clone(), self.inner.with_params(params)?)))
+    }
+}
+
+/// Parse a `base:param=value,...` variant spec into the base strategy's name and
+/// its parameter overrides
+pub fn parse_spec(spec: &str) -> Result<(String, BTreeMap<String, f64>)> {
+    let (base, overrides) = spec.split_once(':').unwrap_or((spec, ""));
+    let base = base.trim();
+    if base.is_empty() {
+        return Err(Error::invalid(format!("variant spec '{}' names no strategy", spec)));
+    }
+    let mut params = BTreeMap::new();
+    for entry in overrides.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+        let (key, value) = entry
+            .split_once('=')
+            .ok_or_else(|| Error::invalid(format!("variant parameter '{}' is not param=value", entry)))?;
+        let value: f64 = value
+            .trim()
+            .parse()
+            .map_err(|_| Error::invalid(format!("variant parameter '{}' has a non-numeric value", entry)))?;
+        params.insert(key.trim().to_string(), value);
+    }
+    Ok((base.to_string(), params))
+}
+
+/// `config` with the named fields replaced, for strategies whose tunables are a
+/// config struct. Only fields the struct already has can be overridden; whole
+/// numbers go in as integers so count fields take them.
+pub fn override_config<T: Serialize + DeserializeOwned>(config: &T, params: &BTreeMap<String, f64>) -> Result<T> {
+    let mut value = serde_json::to_value(config)?;
+    let fields = value
+        .as_object_mut()
+        .ok_or_else(|| Error::invalid("config is not a struct"))?;
+    for (key, &param) in params {
+        let field = fields
+            .get_mut(key)
+            .ok_or_else(|| Error::invalid(format!("no parameter '{}'", key)))?;
+        *field = match field {
+            serde_json::Value::Bool(_) => serde_json::Value::Bool(param != 0.0),
+            _ if param.fract() == 0.0 && field.is_u64() => serde_json::json!(param as u64),
+            _ => serde_json::json!(param),
+        };
+    }
+    Ok(serde_json::from_value(value)?)
+}
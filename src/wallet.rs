@@ -0,0 +1,63 @@
+use k256::ecdsa::SigningKey;
+use sha3::{Digest, Keccak256};
+
+use crate::error::ClobError;
+
+/// The signing wallet derived from PRIVATE_KEY
+#[derive(Debug, Clone)]
+pub struct Wallet {
+    /// EIP-55 checksummed address
+    pub address: String,
+    key: SigningKey,
+}
+
+impl Wallet {
+    /// Validate a hex private key (with or without 0x) and derive its address
+    pub fn from_private_key(private_key: &str) -> Result<Self, ClobError> {
+        let hex_key = private_key.trim().trim_start_matches("0x");
+        if hex_key.len() != 64 {
+            return Err(ClobError::auth(
+                "PRIVATE_KEY",
+                format!(
+                    "must be 32 bytes of hex (64 characters), got {} characters — is this an address or API key?",
+                    hex_key.len()
+                ),
+            ));
+        }
+        let bytes = hex::decode(hex_key).map_err(|_| ClobError::auth("PRIVATE_KEY", "not valid hex"))?;
+        let key =
+            SigningKey::from_slice(&bytes).map_err(|_| ClobError::auth("PRIVATE_KEY", "not a valid secp256k1 key"))?;
+
+        let public = key.verifying_key().to_encoded_point(false);
+        // Address = last 20 bytes of keccak256(uncompressed pubkey without the 0x04 prefix)
+        let hash = Keccak256::digest(&public.as_bytes()[1..]);
+        Ok(Self {
+            address: to_checksum_address(&hash[12..]),
+            key,
+        })
+    }
+
+    /// Sign a 32-byte digest, returning `r || s` and the recovery id
+    pub fn sign_hash(&self, hash: &[u8]) -> Result<([u8; 64], u8), ClobError> {
+        let (signature, recovery) = self
+            .key
+            .sign_prehash_recoverable(hash)
+            .map_err(|e| ClobError::Invalid(format!("Failed to sign digest: {}", e)))?;
+        Ok((signature.to_bytes().into(), recovery.to_byte()))
+    }
+}
+
+/// EIP-55 mixed-case checksum encoding
+pub fn to_checksum_address(address: &[u8]) -> String {
+    let lower = hex::encode(address);
+    let hash = Keccak256::digest(lower.as_bytes());
+    let checksummed: String = lower
+        .chars()
+        .enumerate()
+        .map(|(i, c)| {
+            let nibble = (hash[i / 2] >> if i % 2 == 0 { 4 } else { 0 }) & 0x0f;
+            if nibble >= 8 { c.to_ascii_uppercase() } else { c }
+        })
+        .collect();
+    format!("0x{}", checksummed)
+}
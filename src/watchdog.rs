@@ -0,0 +1,218 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+use tokio::task::JoinHandle;
+use tracing::{error, info, warn};
+
+use crate::config::WatchdogConfig;
+use crate::engine::risk::RiskManager;
+use crate::shutdown::Shutdown;
+
+/// A supervised run that lasted this long was healthy; its restart backoff starts over
+const STABLE_RUN: Duration = Duration::from_secs(300);
+
+/// Liveness signal from a supervised task.
+///
+/// Tasks that wait on a queue call `idle` before waiting and `beat` once work
+/// arrives, so only time spent on one item counts towards a stall. Feeds just
+/// `beat` on every message: a connection that goes quiet is the stall.
+#[derive(Debug, Clone)]
+pub struct Heartbeat {
+    epoch: Instant,
+    /// Milliseconds after `epoch` of the last beat
+    last: Arc<AtomicU64>,
+    idle: Arc<AtomicBool>,
+}
+
+impl Default for Heartbeat {
+    fn default() -> Self {
+        Self {
+            epoch: Instant::now(),
+            last: Arc::new(AtomicU64::new(0)),
+            idle: Arc::new(AtomicBool::new(false)),
+        }
+    }
+}
+
+impl Heartbeat {
+    pub fn beat(&self) {
+        self.last.store(self.epoch.elapsed().as_millis() as u64, Ordering::Relaxed);
+        self.idle.store(false, Ordering::Relaxed);
+    }
+
+    pub fn idle(&self) {
+        self.idle.store(true, Ordering::Relaxed);
+    }
+
+    fn silent_for(&self) -> Option<Duration> {
+        if self.idle.load(Ordering::Relaxed) {
+            return None;
+        }
+        let last = Duration::from_millis(self.last.load(Ordering::Relaxed));
+        Some(self.epoch.elapsed().saturating_sub(last))
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TaskStatus {
+    /// Trading halts if this task stays down
+    pub critical: bool,
+    pub running: bool,
+    pub restarts: u32,
+    /// Why the last run ended
+    pub last_exit: Option<String>,
+    pub down_since: Option<DateTime<Utc>>,
+}
+
+/// Supervised task name → its state
+pub type TaskStatuses = Arc<RwLock<BTreeMap<String, TaskStatus>>>;
+
+/// Supervisor for the long-running tasks.
+///
+/// Each task is built from a factory so it can be started again: one that
+/// returns, errors, panics or stops beating for longer than its stall timeout is
+/// aborted and rebuilt after a backoff that doubles up to `max_backoff_secs`.
+/// If a critical task is still down `halt_after_secs` after it went down, the
+/// risk manager halts trading; that stays in force once the task is back.
+/// Nothing is restarted once shutdown has begun.
+pub struct Watchdog {
+    tasks: TaskStatuses,
+    risk: RiskManager,
+    config: WatchdogConfig,
+    shutdown: Shutdown,
+}
+
+impl Watchdog {
+    pub fn new(risk: RiskManager, config: WatchdogConfig) -> Self {
+        Self {
+            tasks: TaskStatuses::default(),
+            risk,
+            config,
+            shutdown: Shutdown::default(),
+        }
+    }
+
+    pub fn with_shutdown(mut self, shutdown: Shutdown) -> Self {
+        self.shutdown = shutdown;
+        self
+    }
+
+    pub fn statuses(&self) -> TaskStatuses {
+        self.tasks.clone()
+    }
+
+    /// Start `factory`'s task under supervision. A zero `stall` never counts
+    /// silence as a stall. The returned handle finishes when the task ends after
+    /// shutdown has begun.
+    pub fn supervise<F, Fut, E>(&self, name: &str, critical: bool, stall: Duration, factory: F) -> JoinHandle<()>
+    where
+        F: Fn(Heartbeat) -> Fut + Send + 'static,
+        Fut: Future<Output = Result<(), E>> + Send + 'static,
+        E: std::fmt::Display + Send + 'static,
+    {
+        self.tasks.write().unwrap().insert(
+            name.to_string(),
+            TaskStatus {
+                critical,
+                running: false,
+                restarts: 0,
+                last_exit: None,
+                down_since: None,
+            },
+        );
+        let name = name.to_string();
+        let tasks = self.tasks.clone();
+        let check = Duration::from_secs(self.config.check_secs.max(1));
+        let initial_backoff = Duration::from_secs(self.config.backoff_secs.max(1));
+        let max_backoff = Duration::from_secs(self.config.max_backoff_secs).max(initial_backoff);
+        let shutdown = self.shutdown.clone();
+        tokio::spawn(async move {
+            let mut backoff = initial_backoff;
+            loop {
+                let heartbeat = Heartbeat::default();
+                heartbeat.beat();
+                let mut handle = tokio::spawn(factory(heartbeat.clone()));
+                let started = Instant::now();
+                update(&tasks, &name, |status| {
+                    status.running = true;
+                    status.down_since = None;
+                });
+
+                let exit = loop {
+                    tokio::select! {
+                        result = &mut handle => break match result {
+                            Ok(Ok(())) => "returned".to_string(),
+                            Ok(Err(e)) => format!("failed: {}", e),
+                            Err(e) if e.is_panic() => "panicked".to_string(),
+                            Err(e) => format!("aborted: {}", e),
+                        },
+                        _ = tokio::time::sleep(check) => {
+                            match heartbeat.silent_for() {
+                                Some(silent) if !stall.is_zero() && silent > stall => {
+                                    handle.abort();
+                                    break format!("stalled for {}s", silent.as_secs());
+                                }
+                                _ => {}
+                            }
+                        }
+                    }
+                };
+
+                if shutdown.is_triggered() {
+                    update(&tasks, &name, |status| status.running = false);
+                    return;
+                }
+                if started.elapsed() >= STABLE_RUN {
+                    backoff = initial_backoff;
+                }
+                error!("Task {} {}; restarting in {}s", name, exit, backoff.as_secs());
+                update(&tasks, &name, |status| {
+                    status.running = false;
+                    status.restarts += 1;
+                    status.last_exit = Some(exit);
+                    status.down_since = Some(Utc::now());
+                });
+                tokio::select! {
+                    _ = tokio::time::sleep(backoff) => {}
+                    _ = shutdown.wait() => return,
+                }
+                backoff = (backoff * 2).min(max_backoff);
+            }
+        })
+    }
+
+    /// Halt trading once a critical task has been down too long
+    pub async fn run(self) {
+        let halt_after = chrono::Duration::seconds(self.config.halt_after_secs as i64);
+        info!("Watchdog supervising {} tasks", self.tasks.read().unwrap().len());
+        let mut ticker = tokio::time::interval(Duration::from_secs(self.config.check_secs.max(1)));
+        loop {
+            tokio::select! {
+                _ = ticker.tick() => {}
+                _ = self.shutdown.wait() => return,
+            }
+            let down: Vec<String> = self
+                .tasks
+                .read()
+                .unwrap()
+                .iter()
+                .filter(|(_, s)| s.critical && s.down_since.is_some_and(|since| Utc::now() - since >= halt_after))
+                .map(|(name, _)| name.clone())
+                .collect();
+            if !down.is_empty() && self.risk.is_active() {
+                warn!("Critical tasks down: {}", down.join(", "));
+                self.risk.halt_for_tasks(&down);
+            }
+        }
+    }
+}
+
+fn update(tasks: &TaskStatuses, name: &str, f: impl FnOnce(&mut TaskStatus)) {
+    if let Some(status) = tasks.write().unwrap().get_mut(name) {
+        f(status);
+    }
+}